@@ -1,10 +1,10 @@
 //! An example of using logos with chumsky to parse sexprs
 //! Run it with the following command:
-//! cargo run --example logos
+//! cargo run --example logos --features logos
 
 use ariadne::{Color, Label, Report, ReportKind, Source};
 use chumsky::{
-    input::{Stream, ValueInput},
+    input::{LogosInput, ValueInput},
     prelude::*,
 };
 use logos::Logos;
@@ -126,23 +126,10 @@ const SRC: &str = r"
 ";
 
 fn main() {
-    // Create a logos lexer over the source code
-    let token_iter = Token::lexer(SRC)
-        .spanned()
-        // Convert logos errors into tokens. We want parsing to be recoverable and not fail at the lexing stage, so
-        // we have a dedicated `Token::Error` variant that represents a token error that was previously encountered
-        .map(|(tok, span)| match tok {
-            // Turn the `Range<usize>` spans logos gives us into chumsky's `SimpleSpan` via `Into`, because it's easier
-            // to work with
-            Ok(tok) => (tok, span.into()),
-            Err(()) => (Token::Error, span.into()),
-        });
-
-    // Turn the token iterator into a stream that chumsky can use for things like backtracking
-    let token_stream = Stream::from_iter(token_iter)
-        // Tell chumsky to split the (Token, SimpleSpan) stream into its parts so that it can handle the spans for us
-        // This involves giving chumsky an 'end of input' span: we just use a zero-width span at the end of the string
-        .spanned((SRC.len()..SRC.len()).into());
+    // Create a logos lexer over the source code and turn it into an input that chumsky can use for things like
+    // backtracking. We want parsing to be recoverable and not fail at the lexing stage, so lexer errors are converted
+    // into a dedicated `Token::Error` variant that represents a token error that was previously encountered
+    let token_stream = LogosInput::with_error_token(Token::lexer(SRC), |(), _| Token::Error);
 
     // Parse the token stream with our chumsky parser
     match parser().parse(token_stream).into_result() {
@@ -0,0 +1,265 @@
+//! Parsers for binary formats made up of bytes.
+//!
+//! *"Everything's got a moral, if only you can find it."*
+//!
+//! These parsers work on any input with `u8` tokens, such as `&[u8]` or a [`Stream`](crate::stream::Stream) of bytes.
+//! Fixed-size numbers are read with functions like [`u16_le`] or [`f64_be`], the suffix naming the byte order, while
+//! [`tag`] matches a fixed sequence of bytes (such as a magic number) and [`length_prefixed`] reads a count followed by
+//! that many items.
+//!
+//! For formats with fields that don't fall on byte boundaries, see the [`bits`](crate::bits) module.
+//!
+//! # Examples
+//!
+//! ```
+//! # use chumsky::{prelude::*, binary};
+//! // A chunk made up of a magic number, a version, and a list of big-endian points
+//! let point = binary::i16_be().then(binary::i16_be());
+//! let chunk = binary::tag::<_, extra::Err<Simple<u8>>>(b"PNT")
+//!     .ignore_then(binary::u8())
+//!     .then(binary::length_prefixed(binary::u8(), point).collect::<Vec<_>>());
+//!
+//! let bytes = [b'P', b'N', b'T', 1, 2, 0x00, 0x05, 0xFF, 0xFE, 0x01, 0x00, 0x00, 0x00];
+//! assert_eq!(
+//!     chunk.parse(&bytes[..]).into_result(),
+//!     Ok((1, vec![(5, -2), (256, 0)])),
+//! );
+//! ```
+
+use super::*;
+use crate::primitive::Just;
+use crate::private::{IPResult, IterParserSealed};
+
+fn take_array<'a, I, E, const N: usize>(
+    inp: &mut InputRef<'a, '_, I, E>,
+) -> Result<[u8; N], E::Error>
+where
+    I: ValueInput<'a, Token = u8>,
+    E: ParserExtra<'a, I>,
+{
+    let before = inp.cursor();
+    let mut bytes = [0; N];
+    for byte in bytes.iter_mut() {
+        match inp.next_inner() {
+            Some(b) => *byte = b,
+            None => {
+                let span = inp.span_since(&before);
+                return Err(Error::expected_found([], None, span));
+            }
+        }
+    }
+    Ok(bytes)
+}
+
+/// A parser that accepts a single byte.
+///
+/// This is equivalent to [`any`], but is provided for symmetry with the other number parsers.
+pub fn u8<'a, I, E>() -> impl Parser<'a, I, u8, E> + Copy
+where
+    I: ValueInput<'a, Token = u8>,
+    E: ParserExtra<'a, I>,
+{
+    any()
+}
+
+/// A parser that accepts a single byte, interpreted as a signed integer.
+pub fn i8<'a, I, E>() -> impl Parser<'a, I, i8, E> + Copy
+where
+    I: ValueInput<'a, Token = u8>,
+    E: ParserExtra<'a, I>,
+{
+    custom(|inp| take_array::<_, _, 1>(inp).map(i8::from_le_bytes))
+}
+
+macro_rules! number_parsers {
+    ($($ty:ident: $le:ident, $be:ident;)*) => {
+        $(
+            #[doc = concat!("A parser that accepts a little-endian [`", stringify!($ty), "`].")]
+            pub fn $le<'a, I, E>() -> impl Parser<'a, I, $ty, E> + Copy
+            where
+                I: ValueInput<'a, Token = u8>,
+                E: ParserExtra<'a, I>,
+            {
+                custom(|inp| take_array(inp).map($ty::from_le_bytes))
+            }
+
+            #[doc = concat!("A parser that accepts a big-endian [`", stringify!($ty), "`].")]
+            pub fn $be<'a, I, E>() -> impl Parser<'a, I, $ty, E> + Copy
+            where
+                I: ValueInput<'a, Token = u8>,
+                E: ParserExtra<'a, I>,
+            {
+                custom(|inp| take_array(inp).map($ty::from_be_bytes))
+            }
+        )*
+    };
+}
+
+number_parsers! {
+    u16: u16_le, u16_be;
+    u32: u32_le, u32_be;
+    u64: u64_le, u64_be;
+    u128: u128_le, u128_be;
+    i16: i16_le, i16_be;
+    i32: i32_le, i32_be;
+    i64: i64_le, i64_be;
+    i128: i128_le, i128_be;
+    f32: f32_le, f32_be;
+    f64: f64_le, f64_be;
+}
+
+/// A parser that accepts only the given sequence of bytes, such as a magic number at the start of a file.
+///
+/// The output of this parser is the sequence that was provided.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, binary};
+/// let png = binary::tag::<_, extra::Err<Simple<u8>>>(b"\x89PNG\r\n\x1a\n");
+///
+/// assert!(!png.parse(b"\x89PNG\r\n\x1a\n" as &[_]).has_errors());
+/// assert!(png.parse(b"GIF89a" as &[_]).has_errors());
+/// ```
+pub fn tag<'a, I, E>(tag: &'a [u8]) -> Just<&'a [u8], I, E>
+where
+    I: Input<'a, Token = u8>,
+    E: ParserExtra<'a, I>,
+{
+    just(tag)
+}
+
+/// See [`length_prefixed`].
+pub struct LengthPrefixed<L, P, OL> {
+    pub(crate) length: L,
+    pub(crate) item: P,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<OL>,
+}
+
+impl<L: Copy, P: Copy, OL> Copy for LengthPrefixed<L, P, OL> {}
+impl<L: Clone, P: Clone, OL> Clone for LengthPrefixed<L, P, OL> {
+    fn clone(&self) -> Self {
+        Self {
+            length: self.length.clone(),
+            item: self.item.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// An iterable parser that accepts a count (parsed by `length`), followed by exactly that many items (parsed by
+/// `item`).
+///
+/// If the count cannot be converted to a [`usize`], the parser fails with an error spanning the count.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, binary};
+/// let strings = binary::length_prefixed::<_, _, _, _, extra::Err<Simple<u8>>>(
+///     binary::u16_le(),
+///     binary::length_prefixed_bytes(binary::u8()),
+/// )
+/// .collect::<Vec<_>>();
+///
+/// assert_eq!(
+///     strings.parse(b"\x02\x00\x02hi\x03bye").into_result(),
+///     Ok(vec![&b"hi"[..], &b"bye"[..]]),
+/// );
+/// // The count says there should be 2 strings, but there is only one
+/// assert!(strings.parse(b"\x02\x00\x02hi").has_errors());
+/// ```
+pub const fn length_prefixed<'a, I, L, P, OL, E>(length: L, item: P) -> LengthPrefixed<L, P, OL>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    L: Parser<'a, I, OL, E>,
+    OL: TryInto<usize>,
+{
+    LengthPrefixed {
+        length,
+        item,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'a, I, E, L, P, OL, O> IterParserSealed<'a, I, O, E> for LengthPrefixed<L, P, OL>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    L: Parser<'a, I, OL, E>,
+    P: Parser<'a, I, O, E>,
+    OL: TryInto<usize>,
+{
+    type IterState<M: Mode> = usize;
+
+    #[inline(always)]
+    fn make_iter<M: Mode>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+    ) -> PResult<Emit, Self::IterState<M>> {
+        let before = inp.cursor();
+        // The count is needed to drive the iteration, even when the output is not
+        let length = self.length.go::<Emit>(inp)?;
+        match length.try_into() {
+            Ok(length) => Ok(length),
+            Err(_) => {
+                let span = inp.span_since(&before);
+                inp.add_alt(None, None, span);
+                Err(())
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn next<M: Mode>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        remaining: &mut Self::IterState<M>,
+    ) -> IPResult<M, O> {
+        if *remaining == 0 {
+            return Ok(None);
+        }
+        let item = self.item.go::<M>(inp)?;
+        *remaining -= 1;
+        Ok(Some(item))
+    }
+}
+
+/// A parser that accepts a count (parsed by `length`), followed by exactly that many bytes, producing them as a slice.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, binary};
+/// let name = binary::length_prefixed_bytes::<_, _, extra::Err<Simple<u8>>>(binary::u32_be());
+///
+/// assert_eq!(name.parse(b"\0\0\0\x05chums").into_result(), Ok(&b"chums"[..]));
+/// assert!(name.parse(b"\0\0\0\x05chum").has_errors());
+/// ```
+pub fn length_prefixed_bytes<'a, L, OL, E>(
+    length: L,
+) -> impl Parser<'a, &'a [u8], &'a [u8], E> + Clone
+where
+    L: Parser<'a, &'a [u8], OL, E> + Clone,
+    OL: TryInto<usize>,
+    E: ParserExtra<'a, &'a [u8]>,
+{
+    custom(move |inp| {
+        let before = inp.cursor();
+        let length = inp.parse(&length)?;
+        let span = inp.span_since(&before);
+        let length: usize = length
+            .try_into()
+            .map_err(|_| Error::expected_found([], None, span))?;
+        let start = inp.cursor();
+        for _ in 0..length {
+            if inp.next_inner().is_none() {
+                let span = inp.span_since(&start);
+                return Err(Error::expected_found([], None, span));
+            }
+        }
+        Ok(inp.slice_since(&start..))
+    })
+}
@@ -0,0 +1,223 @@
+//! Parsers and input for bit-level binary formats.
+//!
+//! *"The ships hung in the sky in much the same way that bricks don't."*
+//!
+//! [`Bits`] turns a byte slice into an input of individual bits (most significant bit of each byte first), so that
+//! bit-packed formats such as codecs and network headers can be parsed with the usual combinators. Spans and cursors
+//! are measured in bits.
+//!
+//! Fields that do not fall on byte boundaries can be parsed with [`take_bits`] and [`bit_flag`], while [`align`] and
+//! [`take_bytes`] deal with byte-aligned data.
+//!
+//! # Examples
+//!
+//! ```
+//! # use chumsky::{prelude::*, bits::{self, Bits}};
+//! // An IPv4 header starts with a 4-bit version, a 4-bit header length and an 8-bit type of service
+//! let header = bits::take_bits::<extra::Err<Simple<bool>>>(4)
+//!     .then(bits::take_bits(4))
+//!     .then(bits::take_bits(8));
+//!
+//! assert_eq!(header.parse(Bits::new(&[0x45, 0x00])).into_result(), Ok(((4, 5), 0)));
+//! ```
+
+use super::*;
+
+/// An input that produces the individual bits of a byte slice, most significant bit first.
+///
+/// Cursors and spans are measured in bits from the start of the slice. Slices taken from this input are the bytes
+/// that the bits fall within.
+#[derive(Copy, Clone, Debug)]
+pub struct Bits<'src> {
+    bytes: &'src [u8],
+}
+
+impl<'src> Bits<'src> {
+    /// Create a new bit input from a byte slice.
+    pub fn new(bytes: &'src [u8]) -> Self {
+        Self { bytes }
+    }
+}
+
+impl<'src> From<&'src [u8]> for Bits<'src> {
+    fn from(bytes: &'src [u8]) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl<'src> Input<'src> for Bits<'src> {
+    type Cursor = usize;
+    type Span = SimpleSpan<usize>;
+
+    type Token = bool;
+    type MaybeToken = bool;
+
+    type Cache = &'src [u8];
+
+    #[inline]
+    fn begin(self) -> (Self::Cursor, Self::Cache) {
+        (0, self.bytes)
+    }
+
+    #[inline]
+    fn cursor_location(cursor: &Self::Cursor) -> usize {
+        *cursor
+    }
+
+    #[inline(always)]
+    unsafe fn next_maybe(
+        this: &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<Self::MaybeToken> {
+        Self::next(this, cursor)
+    }
+
+    #[inline(always)]
+    unsafe fn span(_this: &mut Self::Cache, range: Range<&Self::Cursor>) -> Self::Span {
+        (*range.start..*range.end).into()
+    }
+}
+
+impl<'src> ExactSizeInput<'src> for Bits<'src> {
+    #[inline(always)]
+    unsafe fn span_from(this: &mut Self::Cache, range: RangeFrom<&Self::Cursor>) -> Self::Span {
+        (*range.start..this.len() * 8).into()
+    }
+}
+
+impl<'src> ValueInput<'src> for Bits<'src> {
+    #[inline(always)]
+    unsafe fn next(this: &mut Self::Cache, cursor: &mut Self::Cursor) -> Option<Self::Token> {
+        let byte = this.get(*cursor / 8)?;
+        let bit = byte & (0x80 >> (*cursor % 8)) != 0;
+        *cursor += 1;
+        Some(bit)
+    }
+}
+
+impl<'src> SliceInput<'src> for Bits<'src> {
+    type Slice = &'src [u8];
+
+    #[inline(always)]
+    fn full_slice(this: &mut Self::Cache) -> Self::Slice {
+        this
+    }
+
+    #[inline(always)]
+    unsafe fn slice(this: &mut Self::Cache, range: Range<&Self::Cursor>) -> Self::Slice {
+        // Include the byte that the last bit falls within, if it isn't on a byte boundary
+        let end = *range.end / 8 + (*range.end % 8 != 0) as usize;
+        &this[*range.start / 8..end]
+    }
+
+    #[inline(always)]
+    unsafe fn slice_from(this: &mut Self::Cache, from: RangeFrom<&Self::Cursor>) -> Self::Slice {
+        &this[*from.start / 8..]
+    }
+}
+
+/// A parser that accepts a single bit, producing `true` if it is set.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, bits::{self, Bits}};
+/// let flags = bits::bit_flag::<extra::Err<Simple<bool>>>()
+///     .repeated()
+///     .exactly(3)
+///     .collect::<Vec<_>>()
+///     .then_ignore(bits::align());
+///
+/// assert_eq!(flags.parse(Bits::new(&[0b1010_0000])).into_result(), Ok(vec![true, false, true]));
+/// ```
+pub fn bit_flag<'a, E: ParserExtra<'a, Bits<'a>>>() -> impl Parser<'a, Bits<'a>, bool, E> + Copy {
+    any()
+}
+
+/// A parser that accepts `n` bits, producing them as an unsigned integer with the first bit as the most significant.
+///
+/// The bits need not be aligned to a byte boundary.
+///
+/// # Panics
+///
+/// Panics if `n` is greater than 64.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, bits::{self, Bits}};
+/// let fields = bits::take_bits::<extra::Err<Simple<bool>>>(3)
+///     .then(bits::take_bits(9))
+///     .then_ignore(bits::align());
+///
+/// assert_eq!(fields.parse(Bits::new(&[0b101_00000, 0b1111_0000])).into_result(), Ok((5, 15)));
+/// assert!(fields.parse(Bits::new(&[0b101_00000])).has_errors());
+/// ```
+#[track_caller]
+pub fn take_bits<'a, E: ParserExtra<'a, Bits<'a>>>(
+    n: u32,
+) -> impl Parser<'a, Bits<'a>, u64, E> + Copy {
+    assert!(n <= 64, "cannot take more than 64 bits at once");
+    custom(move |inp| {
+        let before = inp.cursor();
+        let mut x = 0u64;
+        for _ in 0..n {
+            match inp.next_inner() {
+                Some(bit) => x = (x << 1) | bit as u64,
+                None => {
+                    let span = inp.span_since(&before);
+                    return Err(Error::expected_found([], None, span));
+                }
+            }
+        }
+        Ok(x)
+    })
+}
+
+/// A parser that skips any bits up to the next byte boundary.
+///
+/// This never fails: if the input is already aligned, nothing is skipped.
+pub fn align<'a, E: ParserExtra<'a, Bits<'a>>>() -> impl Parser<'a, Bits<'a>, (), E> + Copy {
+    custom(|inp| {
+        while *inp.cursor().inner() % 8 != 0 {
+            inp.next_inner();
+        }
+        Ok(())
+    })
+}
+
+/// A parser that accepts `n` whole bytes, producing them as a slice.
+///
+/// The input must be aligned to a byte boundary (see [`align`]), or the parser fails.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, bits::{self, Bits}};
+/// let packet = bits::take_bits::<extra::Err<Simple<bool>>>(8)
+///     .then(bits::take_bytes(2));
+///
+/// assert_eq!(packet.parse(Bits::new(&[1, 2, 3])).into_result(), Ok((1, &[2, 3][..])));
+///
+/// let unaligned = bits::bit_flag::<extra::Err<Simple<bool>>>().then(bits::take_bytes(1));
+/// assert!(unaligned.parse(Bits::new(&[1, 2])).has_errors());
+/// ```
+pub fn take_bytes<'a, E: ParserExtra<'a, Bits<'a>>>(
+    n: usize,
+) -> impl Parser<'a, Bits<'a>, &'a [u8], E> + Copy {
+    custom(move |inp| {
+        let before = inp.cursor();
+        if *before.inner() % 8 != 0 {
+            let found = inp.peek_maybe();
+            let span = inp.span_since(&before);
+            return Err(Error::expected_found([], found, span));
+        }
+        for _ in 0..n * 8 {
+            if inp.next_inner().is_none() {
+                let span = inp.span_since(&before);
+                return Err(Error::expected_found([], None, span));
+            }
+        }
+        Ok(inp.slice_since(&before..))
+    })
+}
@@ -31,6 +31,7 @@
 //! ```
 
 use super::*;
+use core::hash::BuildHasher;
 
 /// Implementing this trait allows you to cache parsers for use with inputs of different lifetimes, avoiding the
 /// need to recreate the parser for each input lifetime.
@@ -84,3 +85,132 @@ impl<C: Cached> Cache<C> {
         unsafe { &*(&self.parser as *const C::Parser<'_>).cast() }
     }
 }
+
+/// A store of the outputs of a rule that persists across parses. See [`Parser::cached_rule`].
+///
+/// Entries are keyed by the input that the rule covered, stored as an owned `K` (such as [`String`] for [`&str`]
+/// inputs, or [`Vec<u8>`] for [`&[u8]`] inputs), so an output is reused wherever the same fragment of input appears
+/// again: later in the same input, or in another input entirely (the cells of a spreadsheet, say).
+///
+/// Entries are never evicted automatically: use [`RuleCache::invalidate`] or [`RuleCache::clear`] when they may no
+/// longer be wanted.
+pub struct RuleCache<K, O> {
+    entries: RefCell<HashMap<K, O>>,
+}
+
+impl<K, O> RuleCache<K, O> {
+    /// Create a new, empty cache.
+    pub fn new() -> Self {
+        Self {
+            entries: RefCell::new(HashMap::default()),
+        }
+    }
+
+    /// The number of cached outputs.
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    /// Returns `true` if nothing has been cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().is_empty()
+    }
+
+    /// Remove the cached output for the given input, if there is one.
+    pub fn invalidate<Q: Hash + Eq + ?Sized>(&self, input: &Q)
+    where
+        K: Borrow<Q> + Hash + Eq,
+    {
+        self.entries.borrow_mut().remove(input);
+    }
+
+    /// Remove all cached outputs.
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear();
+    }
+}
+
+impl<K, O> Default for RuleCache<K, O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// See [`Parser::cached_rule`].
+pub struct CachedRule<'c, A, K, O> {
+    pub(crate) parser: A,
+    pub(crate) cache: &'c RuleCache<K, O>,
+}
+
+impl<A: Copy, K, O> Copy for CachedRule<'_, A, K, O> {}
+impl<A: Clone, K, O> Clone for CachedRule<'_, A, K, O> {
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
+            cache: self.cache,
+        }
+    }
+}
+
+impl<'a, 'c, I, O, E, A, K> ParserSealed<'a, I, O, E> for CachedRule<'c, A, K, O>
+where
+    I: SliceInput<'a>,
+    I::Slice: Hash,
+    E: ParserExtra<'a, I, Context = ()>,
+    A: Parser<'a, I, O, E>,
+    K: Hash + Eq + PartialEq<I::Slice> + From<I::Slice>,
+    O: Clone,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        // Without an output to reuse, there's nothing to gain from the cache
+        M::choose(
+            inp,
+            |inp| self.go_emit(inp),
+            |inp| self.parser.go::<Check>(inp),
+        )
+    }
+
+    go_extra!(O);
+}
+
+impl<'c, A, K, O> CachedRule<'c, A, K, O> {
+    fn go_emit<'a, I, E>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<Emit, O>
+    where
+        I: SliceInput<'a>,
+        I::Slice: Hash,
+        E: ParserExtra<'a, I, Context = ()>,
+        A: Parser<'a, I, O, E>,
+        K: Hash + Eq + PartialEq<I::Slice> + From<I::Slice>,
+        O: Clone,
+    {
+        let before = inp.save();
+
+        // Checking the input finds the fragment that the rule covers without producing an output, which is the
+        // expensive part that the cache avoids
+        self.parser.go::<Check>(inp)?;
+        if inp.errors.secondary.len() == before.err_count {
+            let fragment = inp.slice_since(before.cursor()..);
+            let entries = self.cache.entries.borrow();
+            let hash = entries.hasher().hash_one(&fragment);
+            if let Some((_, out)) = entries.raw_entry().from_hash(hash, |key| *key == fragment) {
+                return Ok(out.clone());
+            }
+        }
+        inp.rewind(before.clone());
+
+        let out = self.parser.go::<Emit>(inp)?;
+
+        // Outputs are only reusable if producing them didn't involve emitting errors
+        if inp.errors.secondary.len() == before.err_count {
+            let fragment = inp.slice_since(before.cursor()..);
+            let mut entries = self.cache.entries.borrow_mut();
+            let hash = entries.hasher().hash_one(&fragment);
+            entries
+                .raw_entry_mut()
+                .from_hash(hash, |key| *key == fragment)
+                .or_insert_with(|| (fragment.into(), out.clone()));
+        }
+        Ok(out)
+    }
+}
@@ -0,0 +1,113 @@
+//! Integration with [`clap`](::clap), for command-line arguments written in a small language of their own.
+//!
+//! *"Begin at the beginning," the King said, very gravely, "and go on till you come to the end: then stop."*
+//!
+//! [`value_parser`] turns a parser into a [`TypedValueParser`], which can be given to [`Arg::value_parser`]. When an
+//! argument fails to parse, the errors are turned into a clap error that shows the argument with the offending part
+//! of it highlighted.
+//!
+//! # Examples
+//!
+//! ```
+//! # use chumsky::{prelude::*, clap::value_parser};
+//! # use ::clap::{Arg, Command};
+//! // A range of line numbers, like `10..20`
+//! fn range<'a>() -> impl Parser<'a, &'a str, (u32, u32), extra::Err<Rich<'a, char>>> {
+//!     let num = text::int(10).from_str().unwrapped();
+//!     num.then_ignore(just("..")).then(num)
+//! }
+//!
+//! let cmd = Command::new("show").arg(
+//!     Arg::new("lines")
+//!         .long("lines")
+//!         .takes_value(true)
+//!         .value_parser(value_parser(|arg| range().parse(arg))),
+//! );
+//!
+//! let matches = cmd.clone().try_get_matches_from(["show", "--lines", "10..20"]).unwrap();
+//! assert_eq!(matches.get_one::<(u32, u32)>("lines"), Some(&(10, 20)));
+//!
+//! let err = cmd.try_get_matches_from(["show", "--lines", "10..x"]).unwrap_err();
+//! assert!(err.to_string().contains("10..x\n        ^"));
+//! ```
+
+use super::*;
+use ::clap::{builder::TypedValueParser, Arg, Command, Error as ClapError, ErrorKind};
+use std::ffi::OsStr;
+
+/// See [`value_parser`].
+pub struct ValueParser<F, O> {
+    parse: F,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<O>,
+}
+
+impl<F: Copy, O> Copy for ValueParser<F, O> {}
+impl<F: Clone, O> Clone for ValueParser<F, O> {
+    fn clone(&self) -> Self {
+        Self {
+            parse: self.parse.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// Turn a function that parses an argument into a clap [`TypedValueParser`].
+///
+/// The function is given the text of the argument, and will usually create a parser and [`Parser::parse`] the
+/// argument with it, as in `value_parser(|arg| my_parser().parse(arg))`. Creating the parser in the function (rather
+/// than passing a parser in) allows it to parse arguments of any lifetime.
+///
+/// Arguments that are not valid UTF-8 are rejected. If parsing fails, the first error is reported, along with the
+/// argument and a marker under the part of it that the error points to.
+pub fn value_parser<O, F>(parse: F) -> ValueParser<F, O>
+where
+    F: for<'a> Fn(&'a str) -> ParseResult<O, Rich<'a, char>>,
+{
+    ValueParser {
+        parse,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+// A clap error for an argument that failed to parse, showing where in the argument the error is
+fn arg_error(cmd: &Command, arg: Option<&Arg>, value: &str, err: &Rich<char>) -> ClapError {
+    let arg = arg.map_or_else(|| "...".to_string(), |arg| arg.to_string());
+    // Spans are byte offsets, but the marker is placed by character
+    let start = value
+        .get(..err.span().start)
+        .map_or(0, |s| s.chars().count());
+    let width = value
+        .get(err.span().start..err.span().end)
+        .map_or(1, |s| s.chars().count().max(1));
+    let message = format!(
+        "Invalid value '{value}' for '{arg}': {}\n\n    {value}\n    {}{}\n",
+        err.reason(),
+        " ".repeat(start),
+        "^".repeat(width),
+    );
+    ClapError::raw(ErrorKind::ValueValidation, message).format(&mut cmd.clone())
+}
+
+impl<O, F> TypedValueParser for ValueParser<F, O>
+where
+    O: 'static,
+    F: for<'a> Fn(&'a str) -> ParseResult<O, Rich<'a, char>> + Clone + Send + Sync + 'static,
+{
+    type Value = O;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, ClapError> {
+        let value = value.to_str().ok_or_else(|| {
+            ClapError::raw(ErrorKind::InvalidUtf8, "Invalid UTF-8 was detected\n")
+                .format(&mut cmd.clone())
+        })?;
+        (self.parse)(value)
+            .into_result()
+            .map_err(|errs| arg_error(cmd, arg, value, &errs[0]))
+    }
+}
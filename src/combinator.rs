@@ -5,7 +5,7 @@
 //! Although it's *sometimes* useful to be able to name their type, most of these parsers are much easier to work with
 //! when accessed through their respective methods on [`Parser`].
 
-use inspector::Inspector;
+use inspector::{Inspector, Strictness};
 
 use super::*;
 
@@ -254,7 +254,10 @@ where
                 Ok(M::bind(|| out))
             } else {
                 let err_span = inp.span_since(&before);
-                inp.add_alt(None, None, err_span);
+                let found = inp.token_at(&before);
+                // What could have continued the rejected output doesn't help to explain the rejection
+                inp.errors.follow = None;
+                inp.add_alt(None, found, err_span);
                 Err(())
             }
         })
@@ -332,6 +335,211 @@ where
     }
 }
 
+/// See [`Parser::inspect_rule`].
+#[derive(Copy, Clone)]
+pub struct InspectRule<A> {
+    pub(crate) parser: A,
+    pub(crate) name: &'static str,
+}
+
+impl<'a, I, O, E, A> ParserSealed<'a, I, O, E> for InspectRule<A>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let before = inp.cursor();
+        inp.state().on_enter_rule(self.name, &before);
+        let res = self.parser.go::<M>(inp);
+        let after = inp.cursor();
+        inp.state().on_exit_rule(self.name, &after, res.is_ok());
+        if res.is_ok() {
+            let span = inp.span_since(&before);
+            inp.state().on_label(&self.name, &span);
+        }
+        res
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::with_scope`].
+#[derive(Copy, Clone)]
+pub struct WithScope<A, F, G> {
+    pub(crate) parser: A,
+    pub(crate) enter: F,
+    pub(crate) exit: G,
+}
+
+impl<'a, I, O, E, A, F, G> ParserSealed<'a, I, O, E> for WithScope<A, F, G>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+    F: Fn(&mut E::State),
+    G: Fn(&mut E::State),
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        (self.enter)(inp.state());
+        let res = self.parser.go::<M>(inp);
+        // Exited whether or not the parser succeeded, so that scopes are always balanced
+        (self.exit)(inp.state());
+        res
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::with_fast_path`].
+#[derive(Copy, Clone)]
+pub struct WithFastPath<A, F> {
+    pub(crate) parser: A,
+    pub(crate) fast: F,
+}
+
+impl<'a, I, O, E, A, F> ParserSealed<'a, I, O, E> for WithFastPath<A, F>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+    F: Parser<'a, I, O, extra::Full<EmptyErr, E::State, E::Context>>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let before = inp.save();
+        match inp.with_errors(|inp| self.fast.go::<M>(inp)) {
+            (Ok(out), true) => Ok(out),
+            // Parse again to find out what went wrong
+            _ => {
+                inp.rewind(before);
+                self.parser.go::<M>(inp)
+            }
+        }
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::lenient`].
+#[derive(Copy, Clone)]
+pub struct Lenient<A, W> {
+    pub(crate) parser: A,
+    pub(crate) warning: W,
+}
+
+impl<'a, I, O, E, A, W> ParserSealed<'a, I, O, E> for Lenient<A, W>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    E::State: Strictness<'a, I>,
+    A: Parser<'a, I, O, E>,
+    W: ToString,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let before = inp.save();
+        if inp.is_strict() {
+            // Behave as if this parser does not exist
+            let found = inp.next_maybe_inner();
+            let span = inp.span_since(before.cursor());
+            inp.rewind(before);
+            inp.add_alt(None, found.map(|f| f.into()), span);
+            return Err(());
+        }
+        let out = self.parser.go::<M>(inp)?;
+        let span = inp.span_since(before.cursor());
+        inp.warn(E::Error::lenient_syntax(&self.warning.to_string(), span));
+        Ok(out)
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::strict`].
+#[derive(Copy, Clone)]
+pub struct Strict<A, W> {
+    pub(crate) parser: A,
+    pub(crate) warning: W,
+}
+
+impl<'a, I, O, E, A, W> ParserSealed<'a, I, Option<O>, E> for Strict<A, W>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    E::State: Strictness<'a, I>,
+    A: Parser<'a, I, O, E>,
+    W: ToString,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, Option<O>> {
+        if inp.is_strict() {
+            return self
+                .parser
+                .go::<M>(inp)
+                .map(|out| M::map::<O, _, _>(out, Some));
+        }
+        let before = inp.save();
+        Ok(match self.parser.go::<M>(inp) {
+            Ok(out) => M::map::<O, _, _>(out, Some),
+            Err(()) => {
+                inp.rewind(before);
+                let span = inp.span_since(&inp.cursor());
+                inp.warn(E::Error::lenient_syntax(&self.warning.to_string(), span));
+                M::bind::<Option<O>, _>(|| None)
+            }
+        })
+    }
+
+    go_extra!(Option<O>);
+}
+
+/// See [`Parser::map_once`].
+pub struct OneShot<A> {
+    pub(crate) parser: A,
+}
+
+impl<'a, I, O, E, A> OneShotParser<'a, I, O, E> for OneShot<A>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+{
+    fn parse_once_with_state(self, input: I, state: &mut E::State) -> ParseResult<O, E::Error>
+    where
+        E::Context: Default,
+    {
+        self.parser.parse_with_state(input, state)
+    }
+}
+
+/// See [`Parser::map_once`].
+pub struct MapOnce<A, OA, F> {
+    pub(crate) parser: A,
+    pub(crate) mapper: F,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<OA>,
+}
+
+impl<'a, I, O, E, A, OA, F> OneShotParser<'a, I, O, E> for MapOnce<A, OA, F>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: OneShotParser<'a, I, OA, E>,
+    F: FnOnce(OA) -> O,
+{
+    fn parse_once_with_state(self, input: I, state: &mut E::State) -> ParseResult<O, E::Error>
+    where
+        E::Context: Default,
+    {
+        self.parser
+            .parse_once_with_state(input, state)
+            .map_output(self.mapper)
+    }
+}
+
 /// See [`Parser::map_with`].
 pub struct MapWith<A, OA, F> {
     pub(crate) parser: A,
@@ -779,11 +987,48 @@ where
     go_extra!(O);
 }
 
+/// See [`Parser::identified`].
+#[derive(Copy, Clone)]
+pub struct Identified<A> {
+    pub(crate) parser: A,
+    pub(crate) id: ParserId,
+}
+
+impl<A> Identified<A> {
+    /// The identifier that was given to this parser when it was constructed.
+    pub fn id(&self) -> ParserId {
+        self.id
+    }
+}
+
+impl<'a, I, O, E, A> ParserSealed<'a, I, O, E> for Identified<A>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        self.parser.go::<M>(inp)
+    }
+
+    go_extra!(O);
+}
+
 /// See [`Parser::memoized`].
 #[cfg(feature = "memoization")]
 #[derive(Copy, Clone)]
 pub struct Memoized<A> {
     pub(crate) parser: A,
+    pub(crate) id: ParserId,
+}
+
+#[cfg(feature = "memoization")]
+impl<A> Memoized<A> {
+    /// The identifier of this parser, which its memoized results are stored under.
+    pub fn id(&self) -> ParserId {
+        self.id
+    }
 }
 
 #[cfg(feature = "memoization")]
@@ -797,11 +1042,7 @@ where
     #[inline(always)]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
         let before = inp.cursor();
-        // TODO: Don't use address, since this might not be constant?
-        let key = (
-            I::cursor_location(&before.inner),
-            &self.parser as *const _ as *const () as usize,
-        );
+        let key = (I::cursor_location(&before.inner), self.id.index());
 
         match inp.memos.entry(key) {
             hashbrown::hash_map::Entry::Occupied(o) => {
@@ -834,6 +1075,196 @@ where
     go_extra!(O);
 }
 
+#[cfg(feature = "memoization")]
+enum PackratEntry<C, O, Err> {
+    // Being parsed further up the stack, so this is left recursion
+    InProgress,
+    Failed {
+        alt: Located<C, Err>,
+        cut: bool,
+    },
+    Parsed {
+        end: C,
+        out: O,
+        errors: Vec<Located<C, Err>>,
+        warnings: Vec<Located<C, Err>>,
+    },
+}
+
+#[cfg(feature = "memoization")]
+struct PackratTable<C, O, Err> {
+    // The entries for each memoization scope, by position
+    scopes: HashMap<usize, HashMap<usize, PackratEntry<C, O, Err>>>,
+}
+
+/// Implemented by memo tables that can discard their results for a scope, once the parse that the scope belongs to is
+/// finished.
+#[cfg(feature = "memoization")]
+pub(crate) trait ClearScope {
+    fn clear_scope(&self, scope: usize);
+}
+
+#[cfg(feature = "memoization")]
+impl<C, O, Err> ClearScope for MemoCell<PackratTable<C, O, Err>> {
+    fn clear_scope(&self, scope: usize) {
+        lock_memo(self).scopes.remove(&scope);
+    }
+}
+
+#[cfg(all(feature = "memoization", feature = "sync"))]
+pub(crate) type MemoCell<T> = spin::Mutex<T>;
+#[cfg(all(feature = "memoization", not(feature = "sync")))]
+pub(crate) type MemoCell<T> = RefCell<T>;
+
+#[cfg(feature = "memoization")]
+pub(crate) fn lock_memo<T>(cell: &MemoCell<T>) -> impl core::ops::DerefMut<Target = T> + '_ {
+    #[cfg(feature = "sync")]
+    {
+        cell.lock()
+    }
+    #[cfg(not(feature = "sync"))]
+    {
+        cell.borrow_mut()
+    }
+}
+
+/// See [`Parser::packrat`].
+#[cfg(feature = "memoization")]
+pub struct Packrat<'a, A, I: Input<'a>, O, E: ParserExtra<'a, I>> {
+    pub(crate) parser: A,
+    id: ParserId,
+    // Shared between clones, since they parse the same way
+    table: RefC<MemoCell<PackratTable<I::Cursor, O, E::Error>>>,
+}
+
+#[cfg(feature = "memoization")]
+impl<'a, A, I: Input<'a>, O, E: ParserExtra<'a, I>> Packrat<'a, A, I, O, E> {
+    pub(crate) fn new(parser: A) -> Self {
+        Self {
+            parser,
+            id: ParserId::next(),
+            table: RefC::new(MemoCell::new(PackratTable {
+                scopes: HashMap::default(),
+            })),
+        }
+    }
+
+    /// The identifier of this parser. Clones of the parser share their identifier, along with their memoized results.
+    pub fn id(&self) -> ParserId {
+        self.id
+    }
+
+    fn table(
+        &self,
+    ) -> impl core::ops::DerefMut<Target = PackratTable<I::Cursor, O, E::Error>> + '_ {
+        lock_memo(&self.table)
+    }
+}
+
+#[cfg(feature = "memoization")]
+impl<'a, A: Clone, I: Input<'a>, O, E: ParserExtra<'a, I>> Clone for Packrat<'a, A, I, O, E> {
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
+            id: self.id,
+            table: self.table.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "memoization")]
+impl<'a, I, O, E, A> ParserSealed<'a, I, O, E> for Packrat<'a, A, I, O, E>
+where
+    I: Input<'a>,
+    I::Cursor: MaybeSync,
+    E: ParserExtra<'a, I>,
+    E::Error: Clone + MaybeSync,
+    A: Parser<'a, I, O, E>,
+    O: Clone + MaybeSync + 'a,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let before = inp.save();
+        let start = I::cursor_location(&before.cursor().inner);
+
+        let mut table = self.table();
+        let entries = match table.scopes.entry(inp.memo_scope) {
+            hashbrown::hash_map::Entry::Occupied(entries) => entries.into_mut(),
+            hashbrown::hash_map::Entry::Vacant(entries) => {
+                // The results for this scope are only valid until the end of the parse
+                let clear: RefC<DynClearScope<'a>> = self.table.clone();
+                inp.memo_scopes.register(clear, inp.memo_scope);
+                entries.insert(HashMap::default())
+            }
+        };
+        match entries.get(&start) {
+            Some(PackratEntry::InProgress) => {
+                drop(table);
+                let err_span = inp.span_since(before.cursor());
+                inp.add_alt(None, None, err_span);
+                return Err(());
+            }
+            Some(PackratEntry::Failed { alt, cut }) => {
+                let (alt, cut) = (alt.clone(), *cut);
+                drop(table);
+                inp.add_alt_err(&alt.pos, alt.err);
+                inp.errors.cut = cut;
+                return Err(());
+            }
+            Some(PackratEntry::Parsed {
+                end,
+                out,
+                errors,
+                warnings,
+            }) => {
+                let (end, out) = (end.clone(), out.clone());
+                inp.errors.secondary.extend(errors.iter().cloned());
+                inp.errors.warnings.extend(warnings.iter().cloned());
+                drop(table);
+                // SAFETY: the cursor was produced by advancing from this position in this input
+                unsafe { inp.set_cursor(end) };
+                return Ok(M::bind(|| out));
+            }
+            None => {
+                entries.insert(start, PackratEntry::InProgress);
+            }
+        }
+        drop(table);
+
+        // Outputs are always generated, so that they can be reused by later attempts that need them
+        let res = self.parser.go::<Emit>(inp);
+
+        let entry = match &res {
+            Ok(out) => PackratEntry::Parsed {
+                end: inp.cursor().inner,
+                out: out.clone(),
+                errors: inp.errors.secondary_errors_since(before.err_count).to_vec(),
+                warnings: inp
+                    .errors
+                    .warnings
+                    .get(before.warn_count..)
+                    .unwrap_or(&[])
+                    .to_vec(),
+            },
+            Err(()) => {
+                let alt = inp.take_alt();
+                inp.errors.alt = Some(alt.clone());
+                PackratEntry::Failed {
+                    alt,
+                    cut: inp.errors.cut,
+                }
+            }
+        };
+        if let Some(entries) = self.table().scopes.get_mut(&inp.memo_scope) {
+            entries.insert(start, entry);
+        }
+
+        res.map(|out| M::bind(|| out))
+    }
+
+    go_extra!(O);
+}
+
 /// See [`Parser::then`].
 pub struct Then<A, B, OA, OB, E> {
     pub(crate) parser_a: A,
@@ -870,16 +1301,16 @@ where
     go_extra!((OA, OB));
 }
 
-/// See [`Parser::ignore_then`].
-pub struct IgnoreThen<A, B, OA, E> {
+/// See [`Parser::glued`].
+pub struct Glued<A, B, OA, OB, E> {
     pub(crate) parser_a: A,
     pub(crate) parser_b: B,
     #[allow(dead_code)]
-    pub(crate) phantom: EmptyPhantom<(OA, E)>,
+    pub(crate) phantom: EmptyPhantom<(OA, OB, E)>,
 }
 
-impl<A: Copy, B: Copy, OA, E> Copy for IgnoreThen<A, B, OA, E> {}
-impl<A: Clone, B: Clone, OA, E> Clone for IgnoreThen<A, B, OA, E> {
+impl<A: Copy, B: Copy, OA, OB, E> Copy for Glued<A, B, OA, OB, E> {}
+impl<A: Clone, B: Clone, OA, OB, E> Clone for Glued<A, B, OA, OB, E> {
     fn clone(&self) -> Self {
         Self {
             parser_a: self.parser_a.clone(),
@@ -889,32 +1320,89 @@ impl<A: Clone, B: Clone, OA, E> Clone for IgnoreThen<A, B, OA, E> {
     }
 }
 
-impl<'a, I, E, A, B, OA, OB> ParserSealed<'a, I, OB, E> for IgnoreThen<A, B, OA, E>
+impl<'a, I, E, A, B, OA, OB> ParserSealed<'a, I, (OA, OB), E> for Glued<A, B, OA, OB, E>
 where
     I: Input<'a>,
     E: ParserExtra<'a, I>,
     A: Parser<'a, I, OA, E>,
     B: Parser<'a, I, OB, E>,
+    <I::Span as Span>::Offset: PartialOrd,
 {
-    #[inline(always)]
-    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, OB> {
-        self.parser_a.go::<Check>(inp)?;
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, (OA, OB)> {
+        let before_a = inp.cursor();
+        let a = self.parser_a.go::<M>(inp)?;
+        let a_span = inp.span_since(&before_a);
+
+        let before_b = inp.save();
         let b = self.parser_b.go::<M>(inp)?;
-        Ok(M::map(b, |b: OB| b))
+        let b_span = inp.span_since(before_b.cursor());
+
+        if a_span.end() != b_span.start() {
+            inp.rewind(before_b);
+            let found = inp.peek_maybe();
+            // Overlapping spans (such as those of synthesized tokens) have no gap between them, so clamp it
+            let gap_end = if b_span.start() < a_span.end() {
+                a_span.end()
+            } else {
+                b_span.start()
+            };
+            let gap = I::Span::new(a_span.context(), a_span.end()..gap_end);
+            inp.add_alt(None, found, gap);
+            return Err(());
+        }
+
+        Ok(M::combine(a, b, |a: OA, b: OB| (a, b)))
     }
 
-    go_extra!(OB);
+    go_extra!((OA, OB));
 }
 
-/// See [`Parser::then_ignore`].
-pub struct ThenIgnore<A, B, OB, E> {
+/// See [`Parser::ignore_then`].
+pub struct IgnoreThen<A, B, OA, E> {
     pub(crate) parser_a: A,
     pub(crate) parser_b: B,
     #[allow(dead_code)]
-    pub(crate) phantom: EmptyPhantom<(OB, E)>,
+    pub(crate) phantom: EmptyPhantom<(OA, E)>,
 }
 
-impl<A: Copy, B: Copy, OB, E> Copy for ThenIgnore<A, B, OB, E> {}
+impl<A: Copy, B: Copy, OA, E> Copy for IgnoreThen<A, B, OA, E> {}
+impl<A: Clone, B: Clone, OA, E> Clone for IgnoreThen<A, B, OA, E> {
+    fn clone(&self) -> Self {
+        Self {
+            parser_a: self.parser_a.clone(),
+            parser_b: self.parser_b.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<'a, I, E, A, B, OA, OB> ParserSealed<'a, I, OB, E> for IgnoreThen<A, B, OA, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, OA, E>,
+    B: Parser<'a, I, OB, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, OB> {
+        self.parser_a.go::<Check>(inp)?;
+        let b = self.parser_b.go::<M>(inp)?;
+        Ok(M::map(b, |b: OB| b))
+    }
+
+    go_extra!(OB);
+}
+
+/// See [`Parser::then_ignore`].
+pub struct ThenIgnore<A, B, OB, E> {
+    pub(crate) parser_a: A,
+    pub(crate) parser_b: B,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<(OB, E)>,
+}
+
+impl<A: Copy, B: Copy, OB, E> Copy for ThenIgnore<A, B, OB, E> {}
 impl<A: Clone, B: Clone, OB, E> Clone for ThenIgnore<A, B, OB, E> {
     fn clone(&self) -> Self {
         Self {
@@ -998,6 +1486,49 @@ where
     go_extra!(O);
 }
 
+/// See [`Parser::nested_in_region`].
+pub struct NestedInRegion<A, B, OB, E> {
+    pub(crate) parser_a: A,
+    pub(crate) parser_b: B,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<(OB, E)>,
+}
+
+impl<A: Copy, B: Copy, OB, E> Copy for NestedInRegion<A, B, OB, E> {}
+impl<A: Clone, B: Clone, OB, E> Clone for NestedInRegion<A, B, OB, E> {
+    fn clone(&self) -> Self {
+        Self {
+            parser_a: self.parser_a.clone(),
+            parser_b: self.parser_b.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<'a, I, E, A, B, O, OB> ParserSealed<'a, I, O, E> for NestedInRegion<A, B, OB, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+    B: Parser<'a, I, OB, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let before = inp.save();
+        self.parser_b.go::<Check>(inp)?;
+        let end = inp.cursor();
+        inp.rewind(before);
+
+        inp.with_region(&end.inner, |inp| {
+            (&self.parser_a)
+                .then_ignore(crate::primitive::end())
+                .go::<M>(inp)
+        })
+    }
+
+    go_extra!(O);
+}
+
 /// See [`Parser::ignore_with_ctx`].
 pub struct IgnoreWithCtx<A, B, OA, I, E> {
     pub(crate) parser: A,
@@ -1378,6 +1909,31 @@ where
         }
     }
 
+    /// Require that the pattern appear between a minimum and a maximum number of times (inclusive).
+    ///
+    /// This is equivalent to calling both [`Repeated::at_least`] and [`Repeated::at_most`].
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let hex_byte = any::<_, extra::Err<Simple<char>>>()
+    ///     .filter(char::is_ascii_hexdigit)
+    ///     .repeated()
+    ///     .between(2, 4)
+    ///     .to_slice();
+    ///
+    /// assert!(hex_byte.parse("f").has_errors());
+    /// assert_eq!(hex_byte.parse("ff").into_result(), Ok("ff"));
+    /// assert_eq!(hex_byte.parse("c0de").into_result(), Ok("c0de"));
+    /// assert!(hex_byte.parse("c0ffee").has_errors());
+    /// ```
+    pub fn between(self, at_least: usize, at_most: usize) -> Self {
+        Self {
+            at_least,
+            at_most: at_most as u64,
+            ..self
+        }
+    }
+
     /// Require that the pattern appear exactly the given number of times.
     ///
     /// ```
@@ -1424,6 +1980,75 @@ where
             ..self
         }
     }
+
+    /// Require that the pattern appear at most a maximum number of times, producing an error if it appears more often.
+    ///
+    /// Unlike [`Repeated::at_most`], which simply stops once the maximum is reached (leaving any further occurrences
+    /// for subsequent parsers to deal with), this fails with an error generated by the given function, which is given
+    /// the span of the first occurrence beyond the maximum. This is useful when parsing untrusted input, where
+    /// exceeding a limit should be reported rather than silently tolerated.
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let items = text::int::<_, _, extra::Err<Rich<char>>>(10)
+    ///     .separated_by(just(','))
+    ///     .collect::<Vec<_>>()
+    ///     .delimited_by(just('['), just(']'))
+    ///     .repeated()
+    ///     .at_most_or_err(2, |span| Rich::custom(span, "too many arrays (max 2)"))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(items.parse("[1][2,3]").into_result(), Ok(vec![vec!["1"], vec!["2", "3"]]));
+    /// assert_eq!(
+    ///     items.parse("[1][2,3][4]").into_errors(),
+    ///     vec![Rich::custom((8..11).into(), "too many arrays (max 2)")],
+    /// );
+    /// ```
+    pub fn at_most_or_err<F>(self, at_most: usize, make_err: F) -> RepeatedOrErr<A, OA, F, I, E>
+    where
+        F: Fn(I::Span) -> E::Error,
+    {
+        RepeatedOrErr {
+            repeated: self.at_most(at_most),
+            make_err,
+        }
+    }
+
+    /// Repeat the pattern lazily, stopping as soon as the given parser would match (like `*?` in a regex).
+    ///
+    /// Before each repetition (once the minimum number of repetitions has been reached), the `until` parser is tried
+    /// without consuming any input. If it succeeds, the repetition stops. This means that `until` is **not** consumed,
+    /// so it usually needs to be parsed again afterwards.
+    ///
+    /// This is useful for scanning over input until some terminator is found, even when the terminator could also be
+    /// parsed by the repeated pattern.
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let comment = any::<_, extra::Err<Simple<char>>>()
+    ///     .repeated()
+    ///     .lazy_until(just("*/"))
+    ///     .to_slice()
+    ///     .delimited_by(just("/*"), just("*/"));
+    /// let comments = comment.padded().repeated().collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     comments.parse("/* a */ /** b **/").into_result(),
+    ///     Ok(vec![" a ", "* b *"]),
+    /// );
+    /// // Without a terminator, the comment never ends
+    /// assert!(comments.parse("/* a").has_errors());
+    /// ```
+    pub fn lazy_until<B, OB>(self, until: B) -> RepeatedUntil<A, OA, B, OB, I, E>
+    where
+        B: Parser<'a, I, OB, E>,
+    {
+        RepeatedUntil {
+            repeated: self,
+            until,
+            phantom: EmptyPhantom::new(),
+        }
+    }
 }
 
 impl<'a, I, E, A, OA> ParserSealed<'a, I, (), E> for Repeated<A, OA, I, E>
@@ -1438,12 +2063,15 @@ where
         if self.at_most == !0 && self.at_least == 0 {
             loop {
                 let before = inp.save();
+                inp.errors.cut = false;
                 match self.parser.go::<Check>(inp) {
                     Ok(()) => {}
+                    Err(()) if inp.errors.cut => break Err(()),
                     Err(()) => {
-                        // TODO: Helper for this? Rewind does this? (seconds one may be bad for other cases)
-                        inp.errors.alt = None;
+                        // Only the error says what could have continued the repetition, which is worth reporting if
+                        // whatever follows the repetition fails at the same position
                         inp.rewind(before);
+                        inp.take_follow();
                         break Ok(M::bind(|| ()));
                     }
                 }
@@ -1507,18 +2135,20 @@ where
         }
 
         let before = inp.save();
+        inp.errors.cut = false;
         match self.parser.go::<M>(inp) {
             Ok(item) => {
                 *count += 1;
                 Ok(Some(item))
             }
+            Err(()) if inp.errors.cut => Err(()),
+            Err(()) if *count >= self.at_least => {
+                inp.rewind_follow(before);
+                Ok(None)
+            }
             Err(()) => {
                 inp.rewind(before);
-                if *count >= self.at_least {
-                    Ok(None)
-                } else {
-                    Err(())
-                }
+                Err(())
             }
         }
     }
@@ -1547,20 +2177,193 @@ where
         }
 
         let before = inp.save();
+        inp.errors.cut = false;
         match self.parser.go::<M>(inp) {
             Ok(item) => {
                 *count += 1;
                 Ok(Some(item))
             }
+            Err(()) if inp.errors.cut => Err(()),
+            Err(()) if *count >= at_least => {
+                inp.rewind_follow(before);
+                Ok(None)
+            }
             Err(()) => {
                 inp.rewind(before);
-                if *count >= at_least {
-                    Ok(None)
-                } else {
+                Err(())
+            }
+        }
+    }
+}
+
+/// See [`Repeated::at_most_or_err`].
+pub struct RepeatedOrErr<A, OA, F, I, E> {
+    pub(crate) repeated: Repeated<A, OA, I, E>,
+    pub(crate) make_err: F,
+}
+
+impl<A: Copy, OA, F: Copy, I, E> Copy for RepeatedOrErr<A, OA, F, I, E> {}
+impl<A: Clone, OA, F: Clone, I, E> Clone for RepeatedOrErr<A, OA, F, I, E> {
+    fn clone(&self) -> Self {
+        Self {
+            repeated: self.repeated.clone(),
+            make_err: self.make_err.clone(),
+        }
+    }
+}
+
+impl<'a, I, E, A, OA, F> ParserSealed<'a, I, (), E> for RepeatedOrErr<A, OA, F, I, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, OA, E>,
+    F: Fn(I::Span) -> E::Error,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, ()> {
+        let mut state = self.make_iter::<Check>(inp)?;
+        loop {
+            match self.next::<Check>(inp, &mut state) {
+                Ok(Some(())) => {}
+                Ok(None) => break Ok(M::bind(|| ())),
+                Err(()) => break Err(()),
+            }
+        }
+    }
+
+    go_extra!(());
+}
+
+impl<'a, A, O, F, I, E> IterParserSealed<'a, I, O, E> for RepeatedOrErr<A, O, F, I, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+    F: Fn(I::Span) -> E::Error,
+{
+    type IterState<M: Mode> = usize;
+
+    #[inline(always)]
+    fn make_iter<M: Mode>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+    ) -> PResult<Emit, Self::IterState<M>> {
+        self.repeated.make_iter::<M>(inp)
+    }
+
+    #[inline(always)]
+    fn next<M: Mode>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        count: &mut Self::IterState<M>,
+    ) -> IPResult<M, O> {
+        if *count as u64 >= self.repeated.at_most {
+            // Check whether the pattern would have appeared again had we let it
+            let before = inp.save();
+            let old_alt = inp.errors.alt.take();
+            let res = self.repeated.parser.go::<Check>(inp);
+            let span = inp.span_since(before.cursor());
+            let at = before.cursor().inner().clone();
+            inp.rewind(before);
+            inp.errors.alt = old_alt;
+            return match res {
+                Ok(()) => {
+                    inp.add_alt_err(&at, (self.make_err)(span));
                     Err(())
                 }
+                Err(()) => Ok(None),
+            };
+        }
+
+        self.repeated.next::<M>(inp, count)
+    }
+}
+
+/// See [`Repeated::lazy_until`].
+pub struct RepeatedUntil<A, OA, B, OB, I, E> {
+    pub(crate) repeated: Repeated<A, OA, I, E>,
+    pub(crate) until: B,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<OB>,
+}
+
+impl<A: Copy, OA, B: Copy, OB, I, E> Copy for RepeatedUntil<A, OA, B, OB, I, E> {}
+impl<A: Clone, OA, B: Clone, OB, I, E> Clone for RepeatedUntil<A, OA, B, OB, I, E> {
+    fn clone(&self) -> Self {
+        Self {
+            repeated: self.repeated.clone(),
+            until: self.until.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<'a, I, E, A, OA, B, OB> ParserSealed<'a, I, (), E> for RepeatedUntil<A, OA, B, OB, I, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, OA, E>,
+    B: Parser<'a, I, OB, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, ()> {
+        let mut state = self.make_iter::<Check>(inp)?;
+        loop {
+            #[cfg(debug_assertions)]
+            let before = inp.cursor();
+            match self.next::<Check>(inp, &mut state) {
+                Ok(Some(())) => {}
+                Ok(None) => break Ok(M::bind(|| ())),
+                Err(()) => break Err(()),
+            }
+            #[cfg(debug_assertions)]
+            debug_assert!(
+                before != inp.cursor(),
+                "found Repeated combinator making no progress at {}",
+                self.repeated.location,
+            );
+        }
+    }
+
+    go_extra!(());
+}
+
+impl<'a, A, O, B, OB, I, E> IterParserSealed<'a, I, O, E> for RepeatedUntil<A, O, B, OB, I, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+    B: Parser<'a, I, OB, E>,
+{
+    type IterState<M: Mode> = usize;
+
+    #[inline(always)]
+    fn make_iter<M: Mode>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+    ) -> PResult<Emit, Self::IterState<M>> {
+        self.repeated.make_iter::<M>(inp)
+    }
+
+    #[inline(always)]
+    fn next<M: Mode>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        count: &mut Self::IterState<M>,
+    ) -> IPResult<M, O> {
+        if *count >= self.repeated.at_least {
+            // Stop before the first place that the terminator appears, leaving it for the following parser
+            let before = inp.save();
+            let old_alt = inp.errors.alt.take();
+            let res = self.until.go::<Check>(inp);
+            inp.rewind(before);
+            inp.errors.alt = old_alt;
+            if res.is_ok() {
+                return Ok(None);
             }
         }
+
+        self.repeated.next::<M>(inp, count)
     }
 }
 
@@ -1731,6 +2534,231 @@ where
             ..self
         }
     }
+
+    /// Produce the separators along with the items, in the order that they appear in the input.
+    ///
+    /// Each separator is produced with its span. This is useful when separators carry meaning (such as the operators
+    /// in a chain of comparisons), or when they need to be preserved (such as by a code formatter).
+    ///
+    /// The output of this iterable parser is [`Separated<OA, OB, I::Span>`](Separated).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, combinator::Separated};
+    /// let args = text::int::<_, _, extra::Err<Simple<char>>>(10)
+    ///     .padded()
+    ///     .separated_by(one_of(",;"))
+    ///     .allow_trailing()
+    ///     .with_separators()
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     args.parse("1, 2; 3;").into_result(),
+    ///     Ok(vec![
+    ///         Separated::Item("1"),
+    ///         Separated::Separator(',', SimpleSpan::from(1..2)),
+    ///         Separated::Item("2"),
+    ///         Separated::Separator(';', SimpleSpan::from(4..5)),
+    ///         Separated::Item("3"),
+    ///         Separated::Separator(';', SimpleSpan::from(7..8)),
+    ///     ]),
+    /// );
+    /// ```
+    pub fn with_separators(self) -> WithSeparators<Self, OA> {
+        WithSeparators {
+            parser: self,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
+    /// Left-fold the items together, passing each separator (along with its span) to the fold function.
+    ///
+    /// The first item is the initial accumulator, and `f` combines the accumulator with each separator and the item
+    /// that follows it. At least one item is required. A leading or trailing separator (see
+    /// [`SeparatedBy::allow_leading`] and [`SeparatedBy::allow_trailing`]) is not passed to `f`.
+    ///
+    /// This is useful when the separators are operators, since the span of an operator can then be used to produce
+    /// errors that point to the operator itself.
+    ///
+    /// The output type of this parser is `OA`, the output of the items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// #[derive(Debug, PartialEq)]
+    /// enum Expr {
+    ///     Num(u64),
+    ///     Cmp(Box<Expr>, char, SimpleSpan, Box<Expr>),
+    /// }
+    ///
+    /// let cmp = text::int::<_, _, extra::Err<Simple<char>>>(10)
+    ///     .from_str()
+    ///     .unwrapped()
+    ///     .map(Expr::Num)
+    ///     .padded()
+    ///     .separated_by(one_of("<>"))
+    ///     .foldl_separators(|a, op, span, b| Expr::Cmp(Box::new(a), op, span, Box::new(b)));
+    ///
+    /// assert_eq!(cmp.parse("1").into_result(), Ok(Expr::Num(1)));
+    /// assert_eq!(
+    ///     cmp.parse("1 < 2 > 3").into_result(),
+    ///     Ok(Expr::Cmp(
+    ///         Box::new(Expr::Cmp(Box::new(Expr::Num(1)), '<', (2..3).into(), Box::new(Expr::Num(2)))),
+    ///         '>',
+    ///         (6..7).into(),
+    ///         Box::new(Expr::Num(3)),
+    ///     )),
+    /// );
+    /// assert!(cmp.parse("").has_errors());
+    /// ```
+    pub fn foldl_separators<F>(self, f: F) -> FoldlSeparators<F, Self, OA>
+    where
+        F: Fn(OA, OB, I::Span, OA) -> OA,
+    {
+        FoldlSeparators {
+            parser: Self {
+                at_least: self.at_least.max(1),
+                ..self
+            },
+            folder: f,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
+    /// Collect the items into a [`Vec`], pairing each item with the span of the separator that follows it (if any).
+    ///
+    /// This preserves exactly where the separators were placed, which is what code formatters need in order to
+    /// reproduce the input faithfully: whether a list has a trailing separator (the last item is paired with a span,
+    /// see [`SeparatedBy::allow_trailing`]), or whether separators are written at the start of lines rather than the
+    /// end, can be found from the spans without a separate concrete syntax tree.
+    ///
+    /// A leading separator (see [`SeparatedBy::allow_leading`]) does not follow an item, and so is not included. Use
+    /// [`SeparatedBy::with_separators`] if the outputs of the separators, or leading separators, are needed.
+    ///
+    /// The output type of this parser is `Vec<(OA, Option<I::Span>)>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let list = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+    ///     .padded()
+    ///     .separated_by(just(','))
+    ///     .allow_trailing()
+    ///     .collect_pairs();
+    ///
+    /// assert_eq!(
+    ///     list.parse("a, b,\n c,").into_result(),
+    ///     Ok(vec![
+    ///         ("a", Some(SimpleSpan::from(1..2))),
+    ///         ("b", Some(SimpleSpan::from(4..5))),
+    ///         ("c", Some(SimpleSpan::from(8..9))),
+    ///     ]),
+    /// );
+    /// // Without a trailing separator, the last item isn't paired with a span
+    /// assert_eq!(
+    ///     list.parse("a\n, b").into_result(),
+    ///     Ok(vec![("a", Some(SimpleSpan::from(2..3))), ("b", None)]),
+    /// );
+    /// ```
+    pub fn collect_pairs(self) -> CollectPairs<Self, OA> {
+        CollectPairs {
+            parser: self,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+// The separator before an item (if any, and only produced in mode `MB`) and the item itself (if any). A separator
+// without an item is a trailing separator.
+type SeparatedStep<M, MB, OA, OB> = Result<
+    (
+        Option<<MB as Mode>::Output<OB>>,
+        Option<<M as Mode>::Output<OA>>,
+    ),
+    (),
+>;
+
+impl<'a, A, B, OA, OB, I, E> SeparatedBy<A, B, OA, OB, I, E>
+where
+    A: Parser<'a, I, OA, E>,
+    B: Parser<'a, I, OB, E>,
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+{
+    #[inline(always)]
+    fn next_with_separator<M: Mode, MB: Mode>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        state: &mut usize,
+    ) -> SeparatedStep<M, MB, OA, (OB, I::Span)> {
+        if *state as u64 >= self.at_most {
+            return Ok((None, None));
+        }
+
+        let before_separator = inp.save();
+        inp.errors.cut = false;
+        let mut separator = None;
+        if *state == 0 && self.allow_leading {
+            match self.separator.go::<MB>(inp) {
+                Ok(out) => {
+                    let span = MB::bind(|| inp.span_since(before_separator.cursor()));
+                    separator = Some(MB::combine(out, span, |out, span| (out, span)));
+                }
+                Err(()) if inp.errors.cut => return Err(()),
+                Err(()) => inp.rewind_follow(before_separator.clone()),
+            }
+        } else if *state > 0 {
+            match self.separator.go::<MB>(inp) {
+                Ok(out) => {
+                    let span = MB::bind(|| inp.span_since(before_separator.cursor()));
+                    separator = Some(MB::combine(out, span, |out, span| (out, span)));
+                }
+                Err(()) if inp.errors.cut => return Err(()),
+                Err(()) if *state < self.at_least => {
+                    inp.rewind(before_separator);
+                    return Err(());
+                }
+                Err(()) => {
+                    inp.rewind_follow(before_separator);
+                    return Ok((None, None));
+                }
+            }
+        }
+
+        let before_item = inp.save();
+        inp.errors.cut = false;
+        match self.parser.go::<M>(inp) {
+            Ok(item) => {
+                *state += 1;
+                Ok((separator, Some(item)))
+            }
+            Err(()) if inp.errors.cut => Err(()),
+            Err(()) if *state < self.at_least => {
+                // We have errored before we have reached the count,
+                // and therefore should return this error, as we are
+                // still expecting items
+                inp.rewind(before_separator);
+                Err(())
+            }
+            Err(()) => {
+                // We are not expecting any more items, so it is okay
+                // for it to fail.
+
+                // though if we don't allow trailing, we shouldn't have
+                // consumed the separator, so we need to rewind it.
+                if self.allow_trailing {
+                    inp.rewind_follow(before_item);
+                    Ok((separator, None))
+                } else {
+                    inp.rewind_follow(before_separator);
+                    Ok((None, None))
+                }
+            }
+        }
+    }
 }
 
 impl<'a, I, E, A, B, OA, OB> IterParserSealed<'a, I, OA, E> for SeparatedBy<A, B, OA, OB, I, E>
@@ -1754,67 +2782,219 @@ where
     }
 
     #[inline(always)]
-    fn next<M: Mode>(
-        &self,
-        inp: &mut InputRef<'a, '_, I, E>,
-        state: &mut Self::IterState<M>,
-    ) -> IPResult<M, OA> {
-        if *state as u64 >= self.at_most {
-            return Ok(None);
-        }
-
-        let before_separator = inp.save();
-        if *state == 0 && self.allow_leading {
-            if self.separator.go::<Check>(inp).is_err() {
-                inp.rewind(before_separator.clone());
-            }
-        } else if *state > 0 {
-            match self.separator.go::<Check>(inp) {
-                Ok(()) => {
-                    // Do nothing
-                }
-                Err(()) if *state < self.at_least => {
-                    inp.rewind(before_separator);
-                    return Err(());
-                }
-                Err(()) => {
-                    inp.rewind(before_separator);
-                    return Ok(None);
+    fn next<M: Mode>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        state: &mut Self::IterState<M>,
+    ) -> IPResult<M, OA> {
+        self.next_with_separator::<M, Check>(inp, state)
+            .map(|(_, item)| item)
+    }
+}
+
+impl<'a, I, E, A, B, OA, OB> ParserSealed<'a, I, (), E> for SeparatedBy<A, B, OA, OB, I, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, OA, E>,
+    B: Parser<'a, I, OB, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, ()> {
+        let mut state = self.make_iter::<Check>(inp)?;
+        loop {
+            #[cfg(debug_assertions)]
+            let before = inp.cursor();
+            match self.next::<Check>(inp, &mut state) {
+                Ok(Some(())) => {}
+                Ok(None) => break Ok(M::bind(|| ())),
+                // TODO: Technically we should be rewinding here: as-is, this is invalid since errorring parsers
+                // are permitted to leave input state unspecified. Really, unwinding should occur *here* and not in
+                // `next`.
+                Err(()) => break Err(()),
+            }
+            #[cfg(debug_assertions)]
+            debug_assert!(
+                before != inp.cursor(),
+                "found SeparatedBy combinator making no progress at {}",
+                self.location,
+            );
+        }
+    }
+
+    go_extra!(());
+}
+
+/// An item or a separator, produced by [`SeparatedBy::with_separators`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Separated<T, P, S> {
+    /// An item.
+    Item(T),
+    /// A separator, along with its span.
+    Separator(P, S),
+}
+
+/// See [`SeparatedBy::with_separators`].
+pub struct WithSeparators<A, OA> {
+    pub(crate) parser: A,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<OA>,
+}
+
+impl<A: Copy, OA> Copy for WithSeparators<A, OA> {}
+impl<A: Clone, OA> Clone for WithSeparators<A, OA> {
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<'a, I, E, A, B, OA, OB> IterParserSealed<'a, I, Separated<OA, OB, I::Span>, E>
+    for WithSeparators<SeparatedBy<A, B, OA, OB, I, E>, OA>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, OA, E>,
+    B: Parser<'a, I, OB, E>,
+{
+    // Producing an item that was parsed along with its separator consumes no input
+    const NONCONSUMPTION_IS_OK: bool = true;
+
+    // The number of items so far, an item waiting to be produced after its separator, and whether the end was reached
+    type IterState<M: Mode>
+        = (usize, Option<M::Output<OA>>, bool)
+    where
+        I: 'a;
+
+    #[inline(always)]
+    fn make_iter<M: Mode>(
+        &self,
+        _inp: &mut InputRef<'a, '_, I, E>,
+    ) -> PResult<Emit, Self::IterState<M>> {
+        Ok((0, None, false))
+    }
+
+    #[inline(always)]
+    fn next<M: Mode>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        (count, pending, done): &mut Self::IterState<M>,
+    ) -> IPResult<M, Separated<OA, OB, I::Span>> {
+        if let Some(item) = pending.take() {
+            return Ok(Some(M::map(item, Separated::Item)));
+        } else if *done {
+            return Ok(None);
+        }
+
+        #[cfg(debug_assertions)]
+        let before = inp.cursor();
+        let (separator, item) = self.parser.next_with_separator::<M, M>(inp, count)?;
+        #[cfg(debug_assertions)]
+        debug_assert!(
+            *count < 2 || item.is_none() || before != inp.cursor(),
+            "found SeparatedBy combinator making no progress at {}",
+            self.parser.location,
+        );
+        *done = item.is_none();
+        match (separator, item) {
+            (Some(separator), item) => {
+                *pending = item;
+                Ok(Some(M::map(separator, |(out, span)| {
+                    Separated::Separator(out, span)
+                })))
+            }
+            (None, item) => Ok(item.map(|item| M::map(item, Separated::Item))),
+        }
+    }
+}
+
+/// See [`SeparatedBy::foldl_separators`].
+pub struct FoldlSeparators<F, A, OA> {
+    pub(crate) parser: A,
+    pub(crate) folder: F,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<OA>,
+}
+
+impl<F: Copy, A: Copy, OA> Copy for FoldlSeparators<F, A, OA> {}
+impl<F: Clone, A: Clone, OA> Clone for FoldlSeparators<F, A, OA> {
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
+            folder: self.folder.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<'a, I, E, F, A, B, OA, OB> ParserSealed<'a, I, OA, E>
+    for FoldlSeparators<F, SeparatedBy<A, B, OA, OB, I, E>, OA>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, OA, E>,
+    B: Parser<'a, I, OB, E>,
+    F: Fn(OA, OB, I::Span, OA) -> OA,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, OA> {
+        let before = inp.cursor();
+        let mut count = 0;
+        // At least one item is always required, so the first step only produces no item if `at_most(0)` was used
+        let (_, first) = self
+            .parser
+            .next_with_separator::<M, Check>(inp, &mut count)?;
+        let Some(mut acc) = first else {
+            let span = inp.span_since(&before);
+            inp.add_alt(None, None, span);
+            return Err(());
+        };
+        loop {
+            #[cfg(debug_assertions)]
+            let before = inp.cursor();
+            match self.parser.next_with_separator::<M, M>(inp, &mut count)? {
+                (Some(separator), Some(item)) => {
+                    acc = M::combine(
+                        acc,
+                        M::combine(separator, item, |sep, item| (sep, item)),
+                        |acc, ((sep, span), item)| (self.folder)(acc, sep, span, item),
+                    );
                 }
+                _ => break Ok(acc),
             }
+            #[cfg(debug_assertions)]
+            debug_assert!(
+                before != inp.cursor(),
+                "found SeparatedBy combinator making no progress at {}",
+                self.parser.location,
+            );
         }
+    }
 
-        let before_item = inp.save();
-        match self.parser.go::<M>(inp) {
-            Ok(item) => {
-                *state += 1;
-                Ok(Some(item))
-            }
-            Err(()) if *state < self.at_least => {
-                // We have errored before we have reached the count,
-                // and therefore should return this error, as we are
-                // still expecting items
-                inp.rewind(before_separator);
-                Err(())
-            }
-            Err(()) => {
-                // We are not expecting any more items, so it is okay
-                // for it to fail.
+    go_extra!(OA);
+}
 
-                // though if we don't allow trailing, we shouldn't have
-                // consumed the separator, so we need to rewind it.
-                if self.allow_trailing {
-                    inp.rewind(before_item);
-                } else {
-                    inp.rewind(before_separator);
-                }
-                Ok(None)
-            }
+/// See [`SeparatedBy::collect_pairs`].
+pub struct CollectPairs<A, OA> {
+    pub(crate) parser: A,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<OA>,
+}
+
+impl<A: Copy, OA> Copy for CollectPairs<A, OA> {}
+impl<A: Clone, OA> Clone for CollectPairs<A, OA> {
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
+            phantom: EmptyPhantom::new(),
         }
     }
 }
 
-impl<'a, I, E, A, B, OA, OB> ParserSealed<'a, I, (), E> for SeparatedBy<A, B, OA, OB, I, E>
+impl<'a, I, E, A, B, OA, OB> ParserSealed<'a, I, Vec<(OA, Option<I::Span>)>, E>
+    for CollectPairs<SeparatedBy<A, B, OA, OB, I, E>, OA>
 where
     I: Input<'a>,
     E: ParserExtra<'a, I>,
@@ -1822,29 +3002,44 @@ where
     B: Parser<'a, I, OB, E>,
 {
     #[inline(always)]
-    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, ()> {
-        let mut state = self.make_iter::<Check>(inp)?;
+    fn go<M: Mode>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+    ) -> PResult<M, Vec<(OA, Option<I::Span>)>> {
+        let mut count = 0;
+        let mut pairs = M::bind(Vec::new);
         loop {
             #[cfg(debug_assertions)]
             let before = inp.cursor();
-            match self.next::<Check>(inp, &mut state) {
-                Ok(Some(())) => {}
-                Ok(None) => break Ok(M::bind(|| ())),
-                // TODO: Technically we should be rewinding here: as-is, this is invalid since errorring parsers
-                // are permitted to leave input state unspecified. Really, unwinding should occur *here* and not in
-                // `next`.
-                Err(()) => break Err(()),
-            }
+            let (separator, item) = self.parser.next_with_separator::<M, M>(inp, &mut count)?;
             #[cfg(debug_assertions)]
             debug_assert!(
-                before != inp.cursor(),
+                count < 2 || item.is_none() || before != inp.cursor(),
                 "found SeparatedBy combinator making no progress at {}",
-                self.location,
+                self.parser.location,
             );
+            if let Some(separator) = separator {
+                // A leading separator has no item before it to be paired with
+                pairs = M::combine(pairs, separator, |mut pairs: Vec<_>, (_, span)| {
+                    if let Some((_, sep_span)) = pairs.last_mut() {
+                        *sep_span = Some(span);
+                    }
+                    pairs
+                });
+            }
+            match item {
+                Some(item) => {
+                    pairs = M::combine(pairs, item, |mut pairs, item| {
+                        pairs.push((item, None));
+                        pairs
+                    });
+                }
+                None => break Ok(pairs),
+            }
         }
     }
 
-    go_extra!(());
+    go_extra!(Vec<(OA, Option<I::Span>)>);
 }
 
 /// See [`IterParser::enumerate`].
@@ -2035,10 +3230,12 @@ where
     #[inline(always)]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, Option<O>> {
         let before = inp.save();
+        inp.errors.cut = false;
         Ok(match self.parser.go::<M>(inp) {
             Ok(out) => M::map::<O, _, _>(out, Some),
+            Err(()) if inp.errors.cut => return Err(()),
             Err(()) => {
-                inp.rewind(before);
+                inp.rewind_follow(before);
                 M::bind::<Option<O>, _>(|| None)
             }
         })
@@ -2074,13 +3271,15 @@ where
         }
 
         let before = inp.save();
+        inp.errors.cut = false;
         match self.parser.go::<M>(inp) {
             Ok(item) => {
                 *finished = true;
                 Ok(Some(item))
             }
+            Err(()) if inp.errors.cut => Err(()),
             Err(()) => {
-                inp.rewind(before);
+                inp.rewind_follow(before);
                 *finished = true;
                 Ok(None)
             }
@@ -2122,6 +3321,8 @@ where
         inp.rewind(before);
 
         inp.errors.alt = alt;
+        // A cut within a negated parser has no effect outside of it
+        inp.errors.cut = false;
 
         match result {
             Ok(()) => {
@@ -2572,6 +3773,98 @@ where
     go_extra!(O);
 }
 
+/// See [`Parser::commit`].
+#[derive(Copy, Clone)]
+pub struct Commit<A> {
+    pub(crate) parser: A,
+}
+
+impl<'a, I, O, E, A> ParserSealed<'a, I, O, E> for Commit<A>
+where
+    I: CommitInput<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let out = self.parser.go::<M>(inp)?;
+        inp.commit();
+        Ok(out)
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::cut`].
+#[derive(Copy, Clone)]
+pub struct Cut<A> {
+    pub(crate) parser: A,
+}
+
+impl<'a, I, O, E, A> ParserSealed<'a, I, O, E> for Cut<A>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let res = self.parser.go::<M>(inp);
+        if res.is_err() {
+            inp.errors.cut = true;
+        }
+        res
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::lookbehind`].
+#[derive(Copy, Clone)]
+pub struct Lookbehind<A> {
+    pub(crate) parser: A,
+    pub(crate) max_len: usize,
+}
+
+impl<'a, I, O, E, A> ParserSealed<'a, I, O, E> for Lookbehind<A>
+where
+    I: LookbehindInput<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let before = inp.save();
+        let target = I::cursor_location(&before.cursor().inner);
+        // Errors from attempts at earlier positions are meaningless at this one
+        let old_alt = inp.errors.alt.take();
+
+        // Try the nearest starting position first, such that the shortest match wins
+        for len in 1..=self.max_len {
+            inp.rewind(before.clone());
+            if !(0..len).all(|_| inp.step_back()) {
+                break;
+            }
+            if let Ok(out) = self.parser.go::<M>(inp) {
+                if I::cursor_location(&inp.cursor().inner) == target {
+                    inp.rewind(before);
+                    inp.errors.alt = old_alt;
+                    return Ok(out);
+                }
+            }
+        }
+
+        inp.rewind(before);
+        inp.errors.alt = old_alt;
+        let found = inp.peek_maybe();
+        let span = inp.span_since(&inp.cursor());
+        inp.add_alt(None, found, span);
+        Err(())
+    }
+
+    go_extra!(O);
+}
+
 /// See [`Parser::map_err`].
 #[derive(Copy, Clone)]
 pub struct MapErr<A, F> {
@@ -2605,6 +3898,112 @@ where
     go_extra!(O);
 }
 
+/// See [`Parser::found_slices`].
+#[derive(Copy, Clone)]
+pub struct FoundSlices<A> {
+    pub(crate) parser: A,
+    pub(crate) max_len: usize,
+}
+
+impl<'a, I, O, E, A> ParserSealed<'a, I, O, E> for FoundSlices<A>
+where
+    I: SliceInput<'a>,
+    I::Token: Char,
+    E: ParserExtra<'a, I>,
+    E::Error: FoundSliceError<'a, I>,
+    A: Parser<'a, I, O, E>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let before = inp.save();
+        let res = self.parser.go::<M>(inp);
+
+        if let Some(mut alt) = inp.errors.alt.take() {
+            if let Some(slice) = inp.found_run(&alt.pos, self.max_len) {
+                alt.err.set_found_slice(slice);
+            }
+            inp.errors.alt = Some(alt);
+        }
+
+        for i in before.err_count..inp.errors.secondary.len() {
+            let pos = inp.errors.secondary[i].pos.clone();
+            if let Some(slice) = inp.found_run(&pos, self.max_len) {
+                inp.errors.secondary[i].err.set_found_slice(slice);
+            }
+        }
+
+        res
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::with_lex_errors`].
+#[derive(Copy, Clone)]
+pub struct WithLexErrors<A, F> {
+    pub(crate) parser: A,
+    pub(crate) mapper: F,
+}
+
+impl<A, F> WithLexErrors<A, F> {
+    // Convert the input's error, if the input ended with one at the given location
+    fn lex_error_at<'a, I, E>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        loc: usize,
+    ) -> Option<Located<I::Cursor, E::Error>>
+    where
+        I: FallibleInput<'a>,
+        E: ParserExtra<'a, I>,
+        F: Fn(&I::LexError, I::Span) -> E::Error,
+    {
+        let (at, _) = I::lex_error(inp.cache).filter(|(at, _)| I::cursor_location(at) == loc)?;
+        let span = I::lex_error_span(inp.cache)?;
+        let (_, err) = I::lex_error(inp.cache)?;
+        Some(Located::at(at, (self.mapper)(err, span)))
+    }
+}
+
+impl<'a, I, O, E, A, F> ParserSealed<'a, I, O, E> for WithLexErrors<A, F>
+where
+    I: FallibleInput<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+    F: Fn(&I::LexError, I::Span) -> E::Error,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O>
+    where
+        Self: Sized,
+    {
+        let res = self.parser.go::<M>(inp);
+
+        // If the parser failed (or backtracked) where the input ended due to an error, that error is the real culprit
+        if let Some(loc) = inp
+            .errors
+            .alt
+            .as_ref()
+            .map(|alt| I::cursor_location(&alt.pos))
+        {
+            if let Some(err) = self.lex_error_at(inp, loc) {
+                inp.errors.alt = Some(err);
+            }
+        }
+
+        // The parser might also have succeeded, stopping just short of the error
+        if res.is_ok() && inp.peek_maybe().is_none() {
+            let loc = I::cursor_location(inp.cursor().inner());
+            if let Some(err) = self.lex_error_at(inp, loc) {
+                inp.emit(err.err);
+            }
+        }
+
+        res
+    }
+
+    go_extra!(O);
+}
+
 // /// See [`Parser::map_err_with_span`].
 // #[derive(Copy, Clone)]
 // pub struct MapErrWithSpan<A, F> {
@@ -2711,15 +4110,65 @@ where
 
         let mut emitter = Emitter::new();
         let out = (self.validator)(out, &mut MapExtra::new(&before, inp), &mut emitter);
-        for err in emitter.errors() {
+        let (errs, warnings) = emitter.errors();
+        for err in errs {
             inp.emit(err);
         }
+        for warning in warnings {
+            inp.warn(warning);
+        }
         Ok(M::bind(|| out))
     }
 
     go_extra!(U);
 }
 
+/// See [`Parser::or_else_with_span`].
+#[derive(Copy, Clone)]
+pub struct OrElseWithSpan<A, F> {
+    pub(crate) parser: A,
+    pub(crate) or_else: F,
+}
+
+impl<'a, I, O, E, A, F> ParserSealed<'a, I, O, E> for OrElseWithSpan<A, F>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+    F: Fn(E::Error, I::Span) -> Result<O, E::Error>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O>
+    where
+        Self: Sized,
+    {
+        let before = inp.save();
+        match self.parser.go::<M>(inp) {
+            Ok(out) => Ok(out),
+            Err(()) => {
+                let err = inp.take_alt();
+                // SAFETY: both cursors were generated by this input
+                let span = unsafe { I::span(inp.cache, &before.cursor().inner..&err.pos) };
+                match (self.or_else)(err.err, span) {
+                    Ok(out) => {
+                        inp.rewind(before);
+                        Ok(M::bind(|| out))
+                    }
+                    Err(new_err) => {
+                        inp.errors.alt = Some(Located {
+                            pos: err.pos,
+                            err: new_err,
+                        });
+                        Err(())
+                    }
+                }
+            }
+        }
+    }
+
+    go_extra!(O);
+}
+
 // /// See [`Parser::or_else`].
 // #[derive(Copy, Clone)]
 // pub struct OrElse<A, F> {
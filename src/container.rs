@@ -76,6 +76,32 @@ impl<T> Container<T> for Vec<T> {
     }
 }
 
+/// Short sequences (such as argument lists) are stored inline, without allocating.
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// use smallvec::SmallVec;
+///
+/// let args = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+///     .padded()
+///     .separated_by(just(','))
+///     .collect::<SmallVec<[_; 4]>>()
+///     .delimited_by(just('('), just(')'));
+///
+/// let out = args.parse("(a, b, c)").into_result().unwrap();
+/// assert_eq!(out.as_slice(), ["a", "b", "c"]);
+/// assert!(!out.spilled());
+/// ```
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> Container<A::Item> for smallvec::SmallVec<A> {
+    fn with_capacity(n: usize) -> Self {
+        Self::with_capacity(n)
+    }
+    fn push(&mut self, item: A::Item) {
+        (*self).push(item);
+    }
+}
+
 impl<T> Container<T> for LinkedList<T> {
     fn push(&mut self, item: T) {
         (*self).push_back(item);
@@ -0,0 +1,278 @@
+//! A [`serde`] deserializer for values parsed by a grammar, so that parsed data can be turned into any type that
+//! implements [`Deserialize`].
+//!
+//! *"Why, sometimes I've believed as many as six impossible things before breakfast."*
+//!
+//! A parser for a data format (such as a configuration language) can produce a [`Value`], a generic tree of data much
+//! like a JSON value. [`from_value`] then deserializes the tree into a type of your choice, which usually derives
+//! [`Deserialize`]. This means that a grammar written with chumsky gets support for `#[derive(Deserialize)]` without
+//! needing to implement serde's traits by hand.
+//!
+//! Values are converted to the types that serde asks for as follows:
+//!
+//! - Options are [`Value::Null`] for `None`, or any other value for `Some`.
+//! - Structs and maps are [`Value::Map`]s. Structs may also be [`Value::Seq`]s of their fields, in order.
+//! - Enum variants are either a [`Value::Str`] naming a unit variant, or a [`Value::Map`] with a single entry, the key
+//!   naming the variant and the value being its contents.
+//!
+//! # Examples
+//!
+//! ```
+//! # use chumsky::{prelude::*, de::{self, Value}};
+//! # use serde::Deserialize;
+//! #[derive(Debug, PartialEq, Deserialize)]
+//! struct Config {
+//!     name: String,
+//!     port: u16,
+//!     verbose: Option<bool>,
+//! }
+//!
+//! // A format made up of `key = value` lines
+//! let value = choice((
+//!     text::int::<_, _, extra::Err<Rich<char>>>(10).from_str().unwrapped().map(Value::Int),
+//!     text::keyword("true").to(Value::Bool(true)),
+//!     text::keyword("false").to(Value::Bool(false)),
+//!     none_of('"').repeated().to_slice().map(Value::from).delimited_by(just('"'), just('"')),
+//! ));
+//! let entry = text::ident().map(Value::from).then_ignore(just('=').padded()).then(value);
+//! let config = entry.padded().repeated().collect().map(Value::Map);
+//!
+//! let src = r#"
+//!     name = "chumsky"
+//!     port = 8080
+//! "#;
+//! let value = config.parse(src).into_result().unwrap();
+//! assert_eq!(
+//!     de::from_value(value),
+//!     Ok(Config { name: "chumsky".to_string(), port: 8080, verbose: None }),
+//! );
+//!
+//! let value = config.parse("name = 42").into_result().unwrap();
+//! assert_eq!(
+//!     de::from_value::<Config>(value).unwrap_err().to_string(),
+//!     "invalid type: integer `42`, expected a string",
+//! );
+//! ```
+
+use super::*;
+use alloc::borrow::Cow;
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Unexpected};
+
+/// A generic tree of data, produced by a parser and deserialized with [`from_value`].
+///
+/// Strings may borrow from the input, so deserializing a `&str` from a value doesn't need to copy it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value<'a> {
+    /// A missing value, such as `null`.
+    Null,
+    /// A boolean.
+    Bool(bool),
+    /// An integer.
+    Int(i64),
+    /// A floating-point number.
+    Float(f64),
+    /// A string.
+    Str(Cow<'a, str>),
+    /// A sequence of values, such as a list.
+    Seq(Vec<Value<'a>>),
+    /// A sequence of key-value pairs, such as an object or a table.
+    Map(Vec<(Value<'a>, Value<'a>)>),
+}
+
+impl<'a> From<&'a str> for Value<'a> {
+    fn from(s: &'a str) -> Self {
+        Self::Str(Cow::Borrowed(s))
+    }
+}
+
+impl From<String> for Value<'_> {
+    fn from(s: String) -> Self {
+        Self::Str(Cow::Owned(s))
+    }
+}
+
+impl Value<'_> {
+    fn unexpected(&self) -> Unexpected<'_> {
+        match self {
+            Self::Null => Unexpected::Unit,
+            Self::Bool(b) => Unexpected::Bool(*b),
+            Self::Int(i) => Unexpected::Signed(*i),
+            Self::Float(f) => Unexpected::Float(*f),
+            Self::Str(s) => Unexpected::Str(s),
+            Self::Seq(_) => Unexpected::Seq,
+            Self::Map(_) => Unexpected::Map,
+        }
+    }
+}
+
+/// An error produced when a [`Value`] cannot be deserialized into the requested type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl de::StdError for Error {}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+/// Deserialize a [`Value`] into any type that implements [`Deserialize`].
+pub fn from_value<'a, T: Deserialize<'a>>(value: Value<'a>) -> Result<T, Error> {
+    T::deserialize(value)
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Value<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for Value<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Self::Null => visitor.visit_unit(),
+            Self::Bool(b) => visitor.visit_bool(b),
+            Self::Int(i) => visitor.visit_i64(i),
+            Self::Float(f) => visitor.visit_f64(f),
+            Self::Str(Cow::Borrowed(s)) => visitor.visit_borrowed_str(s),
+            Self::Str(Cow::Owned(s)) => visitor.visit_string(s),
+            Self::Seq(items) => {
+                let mut seq = de::value::SeqDeserializer::new(items.into_iter());
+                let out = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(out)
+            }
+            Self::Map(entries) => {
+                let mut map = de::value::MapDeserializer::new(entries.into_iter());
+                let out = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(out)
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Self::Null => visitor.visit_none(),
+            value => visitor.visit_some(value),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self {
+            Self::Str(_) => visitor.visit_enum(Enum {
+                variant: self,
+                content: None,
+            }),
+            Self::Map(entries) if entries.len() == 1 => {
+                let (variant, content) = entries.into_iter().next().unwrap();
+                visitor.visit_enum(Enum {
+                    variant,
+                    content: Some(content),
+                })
+            }
+            value => Err(de::Error::invalid_type(
+                value.unexpected(),
+                &"a string or a map with a single entry",
+            )),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+// The variant of an enum, along with its contents (if it isn't a unit variant)
+struct Enum<'de> {
+    variant: Value<'de>,
+    content: Option<Value<'de>>,
+}
+
+impl<'de> de::EnumAccess<'de> for Enum<'de> {
+    type Error = Error;
+    type Variant = Variant<'de>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Error> {
+        Ok((seed.deserialize(self.variant)?, Variant(self.content)))
+    }
+}
+
+// The contents of an enum variant
+struct Variant<'de>(Option<Value<'de>>);
+
+impl<'de> de::VariantAccess<'de> for Variant<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.0 {
+            None | Some(Value::Null) => Ok(()),
+            Some(value) => Err(de::Error::invalid_type(
+                value.unexpected(),
+                &"a unit variant",
+            )),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        match self.0 {
+            Some(value) => seed.deserialize(value),
+            None => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"a newtype variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Some(value) => value.deserialize_seq(visitor),
+            None => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"a tuple variant",
+            )),
+        }
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.0 {
+            Some(value) => value.deserialize_any(visitor),
+            None => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"a struct variant",
+            )),
+        }
+    }
+}
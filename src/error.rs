@@ -79,6 +79,44 @@ pub trait Error<'a, I: Input<'a>>: Sized {
         span: I::Span,
     ) -> Self;
 
+    /// Create a new error describing a non-associative operator that was chained onto another operator of the same
+    /// precedence, such as the second `<` in `a < b < c` (see `pratt::non_assoc`).
+    ///
+    /// By default, this is an error that found the operator when nothing in particular was expected.
+    #[inline(always)]
+    fn not_associative(found: Option<MaybeRef<'a, I::Token>>, span: I::Span) -> Self {
+        Self::expected_found(None, found, span)
+    }
+
+    /// Create a new error describing input that is nested more deeply than the parse allows (see
+    /// [`ParseOptions::recursion_limit`](crate::extra::ParseOptions::recursion_limit)), found where the limit was
+    /// reached.
+    ///
+    /// By default, this is an error that found the next token when nothing in particular was expected.
+    #[inline(always)]
+    fn nested_too_deep(found: Option<MaybeRef<'a, I::Token>>, span: I::Span) -> Self {
+        Self::expected_found(None, found, span)
+    }
+
+    /// Create a new error describing a parser that backtracked to before a point that it had committed to (see
+    /// [`Parser::commit`]), by which time the input may have discarded the tokens it needed.
+    ///
+    /// By default, this is an error that found the end of input when nothing in particular was expected.
+    #[inline(always)]
+    fn input_discarded(span: I::Span) -> Self {
+        Self::expected_found(None, None, span)
+    }
+
+    /// Create a new warning describing lenient syntax that was accepted, with the message given to
+    /// [`Parser::lenient`] or [`Parser::strict`].
+    ///
+    /// By default, this is an error that found nothing in particular when nothing in particular was expected.
+    #[inline(always)]
+    fn lenient_syntax(msg: &str, span: I::Span) -> Self {
+        #![allow(unused_variables)]
+        Self::expected_found(None, None, span)
+    }
+
     /// Merge two errors that point to the same input together, combining their information.
     #[inline(always)]
     fn merge(self, other: Self) -> Self {
@@ -109,8 +147,77 @@ pub trait Error<'a, I: Input<'a>>: Sized {
     }
 }
 
+/// A trait implemented by [`Error`]s that can refer to secondary source locations in addition to their primary span.
+///
+/// Secondary labels are useful for diagnostics that involve more than one place in the input, such as "previous
+/// definition here" for a duplicate item or "opening delimiter here" for an unclosed delimiter. Error types that have
+/// nowhere to store this information are free to ignore it.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let def = text::ident::<_, _, extra::Err<Rich<char>>>()
+///     .map_with(|name, e| (name, e.span()))
+///     .padded()
+///     .repeated()
+///     .collect::<Vec<_>>()
+///     .validate(|defs, _, emitter| {
+///         for (i, (name, span)) in defs.iter().enumerate() {
+///             if let Some((_, prev)) = defs[..i].iter().find(|(prev, _)| prev == name) {
+///                 emitter.emit(
+///                     Rich::custom(*span, format!("'{name}' is defined twice"))
+///                         .with_secondary(*prev, "previous definition here"),
+///                 );
+///             }
+///         }
+///         defs
+///     });
+///
+/// let errs = def.parse("foo bar foo").into_errors();
+/// assert_eq!(errs.len(), 1);
+/// assert_eq!(errs[0].span(), &SimpleSpan::from(8..11));
+/// assert_eq!(
+///     errs[0].secondary().collect::<Vec<_>>(),
+///     vec![(&SimpleSpan::from(0..3), "previous definition here")],
+/// );
+/// assert_eq!(errs[0].to_string(), "'foo' is defined twice; previous definition here at 0..3");
+/// ```
+pub trait SecondaryLabelError<'a, I: Input<'a>>: Error<'a, I> {
+    /// Attach a secondary span to this error, along with a message explaining why it is relevant.
+    fn add_secondary<M: ToString>(&mut self, span: I::Span, msg: M);
+
+    /// Like [`SecondaryLabelError::add_secondary`], but takes and returns the error by value for convenient chaining.
+    #[inline]
+    fn with_secondary<M: ToString>(mut self, span: I::Span, msg: M) -> Self {
+        self.add_secondary(span, msg);
+        self
+    }
+}
+
+/// A trait implemented by [`Error`]s that can describe what was found at the error site with a slice of the input,
+/// rather than a single token.
+///
+/// An error reporting that it found `'f'` is much less helpful than one that reports finding `foobar`. Errors are always
+/// created with a single token, but [`Parser::found_slices`] will later offer them the run of input that begins at that
+/// token (such as the whole word or operator) via this trait. Error types that have nowhere to store this information
+/// are free to ignore it.
+pub trait FoundSliceError<'a, I: SliceInput<'a>>: Error<'a, I> {
+    /// Record the run of input that was found at the location of this error.
+    fn set_found_slice(&mut self, slice: I::Slice);
+}
+
 /// A ZST error type that tracks only whether a parse error occurred at all. This type is for when
 /// you want maximum parse speed, at the cost of all error reporting.
+///
+/// An `EmptyErr` has no span, found token, or expected patterns, and all `EmptyErr`s are equal. Because it is
+/// zero-sized, parsers skip building errors altogether when it is used, rather than building errors and then
+/// discarding them: a parse that fails produces a single `EmptyErr` (plus one for each error emitted during error
+/// recovery), which displays as `error`.
+///
+/// To use this type only in some builds (such as release builds), keeping a more detailed error type for
+/// development, see [`extra::DebugErr`]. To parse with this type and only build detailed errors when parsing fails, see
+/// [`Parser::with_fast_path`].
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Copy, Clone, Default)]
 pub struct EmptyErr(());
@@ -126,6 +233,16 @@ impl<'a, I: Input<'a>> Error<'a, I> for EmptyErr {
     }
 }
 
+impl<'a, I: Input<'a>> SecondaryLabelError<'a, I> for EmptyErr {
+    #[inline(always)]
+    fn add_secondary<M: ToString>(&mut self, _: I::Span, _: M) {}
+}
+
+impl<'a, I: SliceInput<'a>> FoundSliceError<'a, I> for EmptyErr {
+    #[inline(always)]
+    fn set_found_slice(&mut self, _: I::Slice) {}
+}
+
 impl fmt::Display for EmptyErr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "error")
@@ -158,6 +275,16 @@ impl<'a, I: Input<'a>> Error<'a, I> for Cheap<I::Span> {
     }
 }
 
+impl<'a, I: Input<'a>> SecondaryLabelError<'a, I> for Cheap<I::Span> {
+    #[inline(always)]
+    fn add_secondary<M: ToString>(&mut self, _: I::Span, _: M) {}
+}
+
+impl<'a, I: SliceInput<'a>> FoundSliceError<'a, I> for Cheap<I::Span> {
+    #[inline(always)]
+    fn set_found_slice(&mut self, _: I::Slice) {}
+}
+
 impl<S> fmt::Debug for Cheap<S>
 where
     S: fmt::Debug,
@@ -225,6 +352,16 @@ impl<'a, I: Input<'a>> Error<'a, I> for Simple<'a, I::Token, I::Span> {
     }
 }
 
+impl<'a, I: Input<'a>> SecondaryLabelError<'a, I> for Simple<'a, I::Token, I::Span> {
+    #[inline(always)]
+    fn add_secondary<M: ToString>(&mut self, _: I::Span, _: M) {}
+}
+
+impl<'a, I: SliceInput<'a>> FoundSliceError<'a, I> for Simple<'a, I::Token, I::Span> {
+    #[inline(always)]
+    fn set_found_slice(&mut self, _: I::Slice) {}
+}
+
 impl<T, S> fmt::Debug for Simple<'_, T, S>
 where
     T: fmt::Debug,
@@ -293,13 +430,15 @@ impl<'a, T, L> RichPattern<'a, T, L> {
         f: &mut fmt::Formatter,
         mut fmt_token: impl FnMut(&T, &mut fmt::Formatter<'_>) -> fmt::Result,
         mut fmt_label: impl FnMut(&L, &mut fmt::Formatter<'_>) -> fmt::Result,
+        quote_tokens: bool,
     ) -> fmt::Result {
         match self {
-            Self::Token(tok) => {
+            Self::Token(tok) if quote_tokens => {
                 write!(f, "'")?;
                 fmt_token(tok, f)?;
                 write!(f, "'")
             }
+            Self::Token(tok) => fmt_token(tok, f),
             Self::Label(label) => fmt_label(label, f),
             Self::EndOfInput => write!(f, "end of input"),
         }
@@ -336,6 +475,24 @@ where
 
 // TODO: Maybe should make ExpectedFound encapsulated a bit more
 /// The reason for a [`Rich`] error.
+///
+/// Use [`Rich::reason`] to decide what to do about an error without inspecting its [`Display`](fmt::Display) output.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, error::RichReason};
+/// let digit = one_of::<_, _, extra::Err<Rich<char>>>("0123456789").try_map(|c, span| match c {
+///     '0' => Err(Rich::custom(span, "leading zero")),
+///     c => Ok(c),
+/// });
+///
+/// let err = &digit.parse("x").into_errors()[0];
+/// assert!(matches!(err.reason(), RichReason::ExpectedFound { found: Some(c), .. } if **c == 'x'));
+///
+/// let err = &digit.parse("0").into_errors()[0];
+/// assert_eq!(err.reason(), &RichReason::Custom("leading zero".to_string()));
+/// ```
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum RichReason<'a, T, L = &'static str> {
@@ -381,6 +538,25 @@ impl<'a, T, L> RichReason<'a, T, L> {
         }
     }
 
+    /// Iterate over the individual reasons that make up this one, in order.
+    ///
+    /// [`RichReason::Many`] is flattened into the reasons it contains, so every reason produced is either
+    /// [`RichReason::ExpectedFound`] or [`RichReason::Custom`].
+    pub fn reasons(&self) -> impl Iterator<Item = &Self> {
+        fn push_reasons<'a, 'b, T, L>(
+            reason: &'b RichReason<'a, T, L>,
+            v: &mut Vec<&'b RichReason<'a, T, L>>,
+        ) {
+            match reason {
+                RichReason::Many(many) => many.iter().for_each(|r| push_reasons(r, v)),
+                reason => v.push(reason),
+            }
+        }
+        let mut v = Vec::new();
+        push_reasons(self, &mut v);
+        v.into_iter()
+    }
+
     #[cfg(feature = "label")]
     fn take_found(&mut self) -> Option<MaybeRef<'a, T>> {
         match self {
@@ -420,19 +596,25 @@ impl<'a, T, L> RichReason<'a, T, L> {
         map_token_inner(self, &mut f)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn inner_fmt<S>(
         &self,
         f: &mut fmt::Formatter<'_>,
         mut fmt_token: impl FnMut(&T, &mut fmt::Formatter<'_>) -> fmt::Result,
         mut fmt_span: impl FnMut(&S, &mut fmt::Formatter<'_>) -> fmt::Result,
         mut fmt_label: impl FnMut(&L, &mut fmt::Formatter<'_>) -> fmt::Result,
+        quote_tokens: bool,
         span: Option<&S>,
+        found_slice: Option<&str>,
         #[cfg(feature = "label")] context: &[(L, S)],
     ) -> fmt::Result {
         match self {
             RichReason::ExpectedFound { expected, found } => {
                 write!(f, "found ")?;
-                write_token(f, &mut fmt_token, found.as_deref())?;
+                match (found_slice, found) {
+                    (Some(slice), Some(_)) => write!(f, "{slice}")?,
+                    _ => write_token(f, &mut fmt_token, found.as_deref())?,
+                }
                 if let Some(span) = span {
                     write!(f, " at ")?;
                     fmt_span(span, f)?;
@@ -440,17 +622,21 @@ impl<'a, T, L> RichReason<'a, T, L> {
                 write!(f, " expected ")?;
                 match &expected[..] {
                     [] => write!(f, "something else")?,
-                    [expected] => expected.write(f, &mut fmt_token, &mut fmt_label)?,
+                    [expected] => {
+                        expected.write(f, &mut fmt_token, &mut fmt_label, quote_tokens)?
+                    }
                     _ => {
                         for expected in &expected[..expected.len() - 1] {
-                            expected.write(f, &mut fmt_token, &mut fmt_label)?;
+                            expected.write(f, &mut fmt_token, &mut fmt_label, quote_tokens)?;
                             write!(f, ", ")?;
                         }
                         write!(f, "or ")?;
-                        expected
-                            .last()
-                            .unwrap()
-                            .write(f, &mut fmt_token, &mut fmt_label)?;
+                        expected.last().unwrap().write(
+                            f,
+                            &mut fmt_token,
+                            &mut fmt_label,
+                            quote_tokens,
+                        )?;
                     }
                 }
             }
@@ -540,6 +726,8 @@ where
             T::fmt,
             |_: &(), _| Ok(()),
             L::fmt,
+            true,
+            None,
             None,
             #[cfg(feature = "label")]
             &[],
@@ -557,6 +745,8 @@ pub struct Rich<'a, T, S = SimpleSpan<usize>, L = &'static str> {
     reason: Box<RichReason<'a, T, L>>,
     #[cfg(feature = "label")]
     context: Vec<(L, S)>,
+    secondary: Vec<(S, String)>,
+    found_slice: Option<String>,
 }
 
 impl<T, S, L> Rich<'_, T, S, L> {
@@ -564,19 +754,27 @@ impl<T, S, L> Rich<'_, T, S, L> {
         &self,
         f: &mut fmt::Formatter<'_>,
         fmt_token: impl FnMut(&T, &mut fmt::Formatter<'_>) -> fmt::Result,
-        fmt_span: impl FnMut(&S, &mut fmt::Formatter<'_>) -> fmt::Result,
+        mut fmt_span: impl FnMut(&S, &mut fmt::Formatter<'_>) -> fmt::Result,
         fmt_label: impl FnMut(&L, &mut fmt::Formatter<'_>) -> fmt::Result,
+        quote_tokens: bool,
         with_spans: bool,
     ) -> fmt::Result {
         self.reason.inner_fmt(
             f,
             fmt_token,
-            fmt_span,
+            &mut fmt_span,
             fmt_label,
+            quote_tokens,
             if with_spans { Some(&self.span) } else { None },
+            self.found_slice.as_deref(),
             #[cfg(feature = "label")]
             &self.context,
-        )
+        )?;
+        for (span, msg) in &self.secondary {
+            write!(f, "; {msg} at ")?;
+            fmt_span(span, f)?;
+        }
+        Ok(())
     }
 }
 
@@ -589,6 +787,8 @@ impl<'a, T, S, L> Rich<'a, T, S, L> {
             reason: Box::new(RichReason::Custom(msg.to_string())),
             #[cfg(feature = "label")]
             context: Vec::new(),
+            secondary: Vec::new(),
+            found_slice: None,
         }
     }
 
@@ -612,6 +812,13 @@ impl<'a, T, S, L> Rich<'a, T, S, L> {
         self.reason.found()
     }
 
+    /// Get the run of input found by this error, if one was captured.
+    ///
+    /// When present, this is displayed in place of [`Rich::found`]. See [`Parser::found_slices`].
+    pub fn found_slice(&self) -> Option<&str> {
+        self.found_slice.as_deref()
+    }
+
     /// Return an iterator over the labelled contexts of this error, from least general to most.
     ///
     /// 'Context' here means parser patterns that the parser was in the process of parsing when the error occurred. To
@@ -621,6 +828,22 @@ impl<'a, T, S, L> Rich<'a, T, S, L> {
         self.context.iter().map(|(l, s)| (l, s))
     }
 
+    /// Attach a secondary span to this error, along with a message explaining why it is relevant.
+    ///
+    /// This is the inherent equivalent of [`SecondaryLabelError::with_secondary`], which is more convenient when the
+    /// input type cannot be inferred (such as inside [`Parser::validate`]).
+    pub fn with_secondary<M: ToString>(mut self, span: S, msg: M) -> Self {
+        self.secondary.push((span, msg.to_string()));
+        self
+    }
+
+    /// Return an iterator over the secondary spans of this error, along with the messages that explain them.
+    ///
+    /// To add secondary spans, see [`Rich::with_secondary`] or [`SecondaryLabelError`].
+    pub fn secondary(&self) -> impl ExactSizeIterator<Item = (&S, &str)> {
+        self.secondary.iter().map(|(s, msg)| (s, msg.as_str()))
+    }
+
     /// Convert this error into an owned version of itself by cloning any borrowed internal tokens, if necessary.
     pub fn into_owned<'b>(self) -> Rich<'b, T, S, L>
     where
@@ -649,6 +872,66 @@ impl<'a, T, S, L> Rich<'a, T, S, L> {
         v.into_iter()
     }
 
+    /// Remove each expected token that is subsumed by one of the expected labels, according to the given function.
+    ///
+    /// When a labelled parser fails alongside another parser that starts with the same token (for example, a labelled
+    /// expression and a parenthesised expression), both the label and the token are expected, which is confusing to
+    /// users. Since a label is a summary of the tokens it may start with, it's usually better to show only the label.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, error::RichPattern};
+    /// let expr = recursive(|expr| {
+    ///     text::int::<_, _, extra::Err<Rich<char>>>(10)
+    ///         .labelled("expression")
+    ///         .or(expr.delimited_by(just('('), just(')')))
+    /// });
+    ///
+    /// let mut err = expr.parse("x").into_errors().remove(0);
+    /// assert_eq!(err.expected().count(), 2);
+    ///
+    /// err.subsume_expected(|label, tok| *label == "expression" && *tok == '(');
+    /// assert_eq!(err.expected().collect::<Vec<_>>(), vec![&RichPattern::Label("expression")]);
+    /// ```
+    #[cfg(feature = "label")]
+    pub fn subsume_expected(&mut self, subsumes: impl Fn(&L, &T) -> bool) {
+        fn patterns<'b, 'a, T, L>(
+            reason: &'b RichReason<'a, T, L>,
+            v: &mut Vec<&'b RichPattern<'a, T, L>>,
+        ) {
+            match reason {
+                RichReason::ExpectedFound { expected, .. } => v.extend(expected.iter()),
+                RichReason::Custom(_) => {}
+                RichReason::Many(many) => many.iter().for_each(|r| patterns(r, v)),
+            }
+        }
+
+        fn retain<T, L>(reason: &mut RichReason<'_, T, L>, keep: &mut impl Iterator<Item = bool>) {
+            match reason {
+                RichReason::ExpectedFound { expected, .. } => {
+                    expected.retain(|_| keep.next().unwrap_or(true))
+                }
+                RichReason::Custom(_) => {}
+                RichReason::Many(many) => many.iter_mut().for_each(|r| retain(r, keep)),
+            }
+        }
+
+        // Labels anywhere in the error may subsume tokens anywhere else in it, since they are all expected here
+        let mut pats = Vec::new();
+        patterns(&self.reason, &mut pats);
+        let keep = pats
+            .iter()
+            .map(|pat| match pat {
+                RichPattern::Token(tok) => !pats
+                    .iter()
+                    .any(|l| matches!(l, RichPattern::Label(l) if subsumes(l, tok))),
+                _ => true,
+            })
+            .collect::<Vec<_>>();
+        retain(&mut self.reason, &mut keep.into_iter());
+    }
+
     /// Transform this error's tokens using the given function.
     ///
     /// This is useful when you wish to combine errors from multiple compilation passes (lexing and parsing, say) where
@@ -662,6 +945,8 @@ impl<'a, T, S, L> Rich<'a, T, S, L> {
             reason: Box::new(self.reason.map_token(f)),
             #[cfg(feature = "label")]
             context: self.context,
+            secondary: self.secondary,
+            found_slice: self.found_slice,
         }
     }
 }
@@ -691,17 +976,45 @@ where
             }),
             #[cfg(feature = "label")]
             context: Vec::new(),
+            secondary: Vec::new(),
+            found_slice: None,
         }
     }
 
     #[inline]
-    fn merge(self, other: Self) -> Self {
+    fn not_associative(_: Option<MaybeRef<'a, I::Token>>, span: I::Span) -> Self {
+        Self::custom(span, "operator is not associative")
+    }
+
+    #[inline]
+    fn nested_too_deep(_: Option<MaybeRef<'a, I::Token>>, span: I::Span) -> Self {
+        Self::custom(span, "nesting too deep")
+    }
+
+    #[inline]
+    fn lenient_syntax(msg: &str, span: I::Span) -> Self {
+        Self::custom(span, msg)
+    }
+
+    #[inline]
+    fn input_discarded(span: I::Span) -> Self {
+        Self::custom(
+            span,
+            "attempted to backtrack into input that has already been discarded",
+        )
+    }
+
+    #[inline]
+    fn merge(mut self, other: Self) -> Self {
         let new_reason = self.reason.flat_merge(*other.reason);
+        self.secondary.extend(other.secondary);
         Self {
             span: self.span,
             reason: Box::new(new_reason),
             #[cfg(feature = "label")]
             context: self.context, // TOOD: Merge contexts
+            secondary: self.secondary,
+            found_slice: self.found_slice.or(other.found_slice),
         }
     }
 
@@ -786,10 +1099,35 @@ where
         }
         #[cfg(feature = "label")]
         self.context.clear();
+        self.secondary.clear();
+        self.found_slice = None;
         self
     }
 }
 
+impl<'a, I: Input<'a>, L> SecondaryLabelError<'a, I> for Rich<'a, I::Token, I::Span, L>
+where
+    I::Token: PartialEq,
+    L: PartialEq,
+{
+    #[inline]
+    fn add_secondary<M: ToString>(&mut self, span: I::Span, msg: M) {
+        self.secondary.push((span, msg.to_string()));
+    }
+}
+
+impl<'a, I: SliceInput<'a>, L> FoundSliceError<'a, I> for Rich<'a, I::Token, I::Span, L>
+where
+    I::Token: PartialEq,
+    I::Slice: ToString,
+    L: PartialEq,
+{
+    #[inline]
+    fn set_found_slice(&mut self, slice: I::Slice) {
+        self.found_slice = Some(slice.to_string());
+    }
+}
+
 #[cfg(feature = "label")]
 impl<'a, I: Input<'a>, L> LabelError<'a, I, L> for Rich<'a, I::Token, I::Span, L>
 where
@@ -819,6 +1157,48 @@ where
             self.context.push((label, span));
         }
     }
+
+    fn simplify_expected(&mut self, f: &dyn Fn(&I::Token) -> Option<L>) {
+        fn simplify<'a, T, L: PartialEq>(
+            reason: &mut RichReason<'a, T, L>,
+            f: &dyn Fn(&T) -> Option<L>,
+        ) {
+            match reason {
+                RichReason::ExpectedFound { expected, .. } => {
+                    let mut simplified = Vec::with_capacity(expected.len());
+                    for pat in expected.drain(..) {
+                        let pat = match pat {
+                            RichPattern::Token(tok) => match f(&tok) {
+                                Some(label) => RichPattern::Label(label),
+                                None => RichPattern::Token(tok),
+                            },
+                            pat => pat,
+                        };
+                        // Many tokens may be summarized by the same label, but it only needs to appear once
+                        let is_dup = match &pat {
+                            RichPattern::Label(label) => simplified
+                                .iter()
+                                .any(|p| matches!(p, RichPattern::Label(l) if l == label)),
+                            _ => false,
+                        };
+                        if !is_dup {
+                            simplified.push(pat);
+                        }
+                    }
+                    *expected = simplified;
+                }
+                RichReason::Custom(_) => {}
+                RichReason::Many(reasons) => reasons.iter_mut().for_each(|r| simplify(r, f)),
+            }
+        }
+
+        simplify(&mut self.reason, f);
+    }
+
+    #[inline]
+    fn subsume_expected(&mut self, subsumes: &dyn Fn(&L, &I::Token) -> bool) {
+        Rich::subsume_expected(self, subsumes)
+    }
 }
 
 impl<T, S, L> fmt::Debug for Rich<'_, T, S, L>
@@ -828,7 +1208,7 @@ where
     L: fmt::Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.inner_fmt(f, T::fmt, S::fmt, L::fmt, true)
+        self.inner_fmt(f, T::fmt, S::fmt, L::fmt, true, true)
     }
 }
 
@@ -839,7 +1219,285 @@ where
     L: fmt::Display,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.inner_fmt(f, T::fmt, S::fmt, L::fmt, false)
+        self.inner_fmt(f, T::fmt, S::fmt, L::fmt, true, false)
+    }
+}
+
+/// A self-contained summary of a [`Rich`] error, intended for exporting diagnostics to tooling that is not written in
+/// Rust (such as editors and web UIs). See [`Rich::to_report`].
+///
+/// Tokens and labels are converted to text, so the report does not depend on the token or label types of the parser.
+/// With the `serde` feature enabled, the report can be serialized. In JSON, the schema is:
+///
+/// ```json
+/// {
+///     "message": "found x expected 'a', expression, or end of input",
+///     "span": { "start": 3, "end": 4, "context": null },
+///     "expected": [
+///         { "kind": "token", "value": "a" },
+///         { "kind": "label", "value": "expression" },
+///         { "kind": "end_of_input" }
+///     ],
+///     "found": "x",
+///     "custom": [],
+///     "contexts": [{ "message": "statement", "span": { "start": 0, "end": 3, "context": null } }],
+///     "notes": [{ "message": "first defined here", "span": { "start": 10, "end": 11, "context": null } }]
+/// }
+/// ```
+///
+/// - `message` is the human-readable description of the error (its [`Display`](fmt::Display) output without spans).
+/// - `span` is the location of the error, serialized as the span type would be (shown here for [`SimpleSpan`]).
+/// - `expected` lists the patterns that would have been accepted.
+/// - `found` is the token that was found, or `null` if the end of input was found (or the error is custom).
+/// - `custom` lists any custom messages (see [`Rich::custom`]).
+/// - `contexts` lists the labelled contexts the error occurred in, from least general to most. It is always empty
+///   unless the `label` feature is enabled.
+/// - `notes` lists the secondary spans of the error (see [`Rich::secondary`]).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RichReport<S> {
+    /// The human-readable description of the error.
+    pub message: String,
+    /// The location of the error.
+    pub span: S,
+    /// The patterns that were expected.
+    pub expected: Vec<ReportPattern>,
+    /// The token that was found, or `None` for the end of input.
+    pub found: Option<String>,
+    /// Custom messages attached to the error.
+    pub custom: Vec<String>,
+    /// The labelled contexts the error occurred in, from least general to most.
+    pub contexts: Vec<ReportLabel<S>>,
+    /// Secondary spans related to the error.
+    pub notes: Vec<ReportLabel<S>>,
+}
+
+/// A pattern that was expected, as it appears in a [`RichReport`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(tag = "kind", content = "value", rename_all = "snake_case")
+)]
+pub enum ReportPattern {
+    /// A specific token was expected.
+    Token(String),
+    /// A labelled pattern was expected.
+    Label(String),
+    /// The end of input was expected.
+    EndOfInput,
+}
+
+/// A span with an accompanying message, as it appears in a [`RichReport`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ReportLabel<S> {
+    /// The message, such as the name of a context.
+    pub message: String,
+    /// The span the message refers to.
+    pub span: S,
+}
+
+impl<T, S, L> Rich<'_, T, S, L>
+where
+    T: fmt::Display,
+    S: Clone,
+    L: fmt::Display,
+{
+    /// Summarise this error as a [`RichReport`], suitable for exporting to other tools.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, error::{ReportPattern, RichReport}};
+    /// let errs = just::<_, _, extra::Err<Rich<char>>>('a').parse("x").into_errors();
+    /// let report = errs[0].to_report();
+    ///
+    /// assert_eq!(report.message, "found x expected 'a'");
+    /// assert_eq!(report.span, (0..1).into());
+    /// assert_eq!(report.expected, vec![ReportPattern::Token("a".to_string())]);
+    /// assert_eq!(report.found.as_deref(), Some("x"));
+    /// ```
+    pub fn to_report(&self) -> RichReport<S> {
+        fn collect<T: fmt::Display, L: fmt::Display, S>(
+            reason: &RichReason<'_, T, L>,
+            report: &mut RichReport<S>,
+        ) {
+            match reason {
+                RichReason::ExpectedFound { expected, found } => {
+                    report.expected.extend(expected.iter().map(|pat| match pat {
+                        RichPattern::Token(tok) => ReportPattern::Token(tok.to_string()),
+                        RichPattern::Label(label) => ReportPattern::Label(label.to_string()),
+                        RichPattern::EndOfInput => ReportPattern::EndOfInput,
+                    }));
+                    if report.found.is_none() {
+                        report.found = found.as_ref().map(|tok| tok.to_string());
+                    }
+                }
+                RichReason::Custom(msg) => report.custom.push(msg.clone()),
+                RichReason::Many(many) => many.iter().for_each(|r| collect(r, report)),
+            }
+        }
+
+        // The message is the reason alone, but with the found slice (if any) in place of the found token
+        struct Message<'b, 'a, T, L>(&'b RichReason<'a, T, L>, Option<&'b str>);
+        impl<T: fmt::Display, L: fmt::Display> fmt::Display for Message<'_, '_, T, L> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.inner_fmt(
+                    f,
+                    T::fmt,
+                    |_: &(), _| Ok(()),
+                    L::fmt,
+                    true,
+                    None,
+                    self.1,
+                    #[cfg(feature = "label")]
+                    &[],
+                )
+            }
+        }
+
+        let mut report = RichReport {
+            message: Message(&self.reason, self.found_slice.as_deref()).to_string(),
+            span: self.span.clone(),
+            expected: Vec::new(),
+            found: None,
+            custom: Vec::new(),
+            #[cfg(feature = "label")]
+            contexts: self
+                .context
+                .iter()
+                .map(|(label, span)| ReportLabel {
+                    message: label.to_string(),
+                    span: span.clone(),
+                })
+                .collect(),
+            #[cfg(not(feature = "label"))]
+            contexts: Vec::new(),
+            notes: self
+                .secondary
+                .iter()
+                .map(|(span, msg)| ReportLabel {
+                    message: msg.clone(),
+                    span: span.clone(),
+                })
+                .collect(),
+        };
+        collect(&self.reason, &mut report);
+        if report.found.is_some() {
+            if let Some(slice) = &self.found_slice {
+                report.found = Some(slice.clone());
+            }
+        }
+        report
+    }
+}
+
+/// A trait for tokens that have a human-readable name, for use when displaying errors.
+///
+/// Token types are often enums that are extracted with [`select!`], and that derive [`Debug`](fmt::Debug) but not
+/// [`Display`](fmt::Display). Rather than showing the [`Debug`](fmt::Debug) representation of tokens (like
+/// `Ident("x")`) in errors, or implementing [`Display`](fmt::Display) for the token type just for this purpose, this
+/// trait can be implemented to give each token a name (like `identifier` or `'('`). The names are used when displaying
+/// errors with [`Rich::display_named`] and [`RichPattern::display_named`], both for the token that was found and for
+/// the tokens that were expected.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, error::TokenName};
+/// # use std::fmt;
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum Token<'a> {
+///     Ident(&'a str),
+///     Num(u64),
+///     LParen,
+///     RParen,
+/// }
+///
+/// impl TokenName for Token<'_> {
+///     fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         match self {
+///             Token::Ident(name) => write!(f, "identifier `{name}`"),
+///             Token::Num(_) => write!(f, "number"),
+///             Token::LParen => write!(f, "'('"),
+///             Token::RParen => write!(f, "')'"),
+///         }
+///     }
+/// }
+///
+/// fn call<'a>() -> impl Parser<'a, &'a [Token<'a>], (&'a str, u64), extra::Err<Rich<'a, Token<'a>>>> {
+///     select! { Token::Ident(name) => name }
+///         .then_ignore(just(Token::LParen))
+///         .then(select! { Token::Num(x) => x })
+///         .then_ignore(just(Token::RParen))
+/// }
+///
+/// let errs = call().parse(&[Token::Ident("f"), Token::Num(42)]).into_errors();
+/// assert_eq!(errs[0].display_named().to_string(), "found number expected '('");
+/// assert_eq!(errs[0].expected().next().unwrap().display_named().to_string(), "'('");
+/// ```
+pub trait TokenName {
+    /// Write the human-readable name of this token.
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+impl TokenName for char {
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{self}'")
+    }
+}
+
+impl<T: TokenName + ?Sized> TokenName for &T {
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt_name(self, f)
+    }
+}
+
+/// Displays an error or pattern using the [names](TokenName) of its tokens. See [`Rich::display_named`].
+pub struct DisplayNamed<'b, T>(&'b T);
+
+impl<T, S, L> Rich<'_, T, S, L> {
+    /// Display this error using the [names](TokenName) of its tokens, rather than their [`Display`](fmt::Display)
+    /// implementation.
+    ///
+    /// Unlike tokens displayed with [`Display`](fmt::Display), names are not wrapped in quotes, so that names like
+    /// `identifier` read naturally. See [`TokenName`] for an example.
+    pub fn display_named(&self) -> DisplayNamed<'_, Self> {
+        DisplayNamed(self)
+    }
+}
+
+impl<T, L> RichPattern<'_, T, L> {
+    /// Display this pattern using the [name](TokenName) of its token, if it has one. See [`Rich::display_named`].
+    pub fn display_named(&self) -> DisplayNamed<'_, Self> {
+        DisplayNamed(self)
+    }
+}
+
+impl<T, S, L> fmt::Display for DisplayNamed<'_, Rich<'_, T, S, L>>
+where
+    T: TokenName,
+    S: fmt::Display,
+    L: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0
+            .inner_fmt(f, T::fmt_name, S::fmt, L::fmt, false, false)
+    }
+}
+
+impl<T, L> fmt::Display for DisplayNamed<'_, RichPattern<'_, T, L>>
+where
+    T: TokenName,
+    L: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            RichPattern::Token(tok) => tok.fmt_name(f),
+            RichPattern::Label(label) => write!(f, "{label}"),
+            RichPattern::EndOfInput => write!(f, "end of input"),
+        }
     }
 }
 
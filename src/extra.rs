@@ -50,12 +50,67 @@ pub type Err<E> = Full<E, DefaultState, DefaultCtx>;
 /// Use `State<S>` or `Full<E, S, C>` as the `Extra` type parameter of a parser to use a custom state type.
 /// You can then use `parser().parse_with_state(&mut S)` to parse with a custom state.
 ///
+/// A tuple of state values may be used to give different parts of a grammar their own state. See
+/// [`Has`](crate::inspector::Has).
+///
 /// See [`Parser::map_with`] for examples.
 pub type State<S> = Full<DefaultErr, S, DefaultCtx>;
 
 /// Use specified context type, but default other types. See [`ParserExtra`] for more details.
 pub type Context<C> = Full<DefaultErr, DefaultState, C>;
 
+/// Use the specified error type if `DETAILED` is `true`, and [`EmptyErr`] otherwise, with default other types. See
+/// [`ParserExtra`] for more details.
+///
+/// `EmptyErr` is zero-sized, so with it the parser skips building errors altogether: only whether parsing failed is
+/// recorded, with no expected tokens or spans. This gives the best throughput where inputs are trusted (or are
+/// checked again elsewhere), while keeping detailed errors for development and testing.
+///
+/// The choice is left to you, since it changes the error type: a common one is `{ cfg!(debug_assertions) }`, giving
+/// detailed errors in debug builds only, but a feature of your own crate works just as well. Code that needs to
+/// construct or inspect errors (such as a [`Parser::try_map`] closure calling [`Rich::custom`]) can only be used when
+/// `DETAILED` is `true`, or must be generic over the error type.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// // Detailed errors in debug builds, and the fastest possible parser in release builds
+/// type Extra<'a> = extra::DebugErr<Rich<'a, char>, { cfg!(debug_assertions) }>;
+///
+/// let num = text::int::<_, _, Extra>(10).then_ignore(end());
+///
+/// assert_eq!(num.parse("42").into_result(), Ok("42"));
+/// let errs = num.parse("4x").into_errors();
+/// assert_eq!(errs.len(), 1);
+/// if cfg!(debug_assertions) {
+///     assert_eq!(errs[0].to_string(), "found x expected end of input");
+/// } else {
+///     assert_eq!(errs[0].to_string(), "error");
+/// }
+/// ```
+pub struct DebugErr<E, const DETAILED: bool>(PhantomData<E>);
+
+impl<E, const DETAILED: bool> Sealed for DebugErr<E, DETAILED> {}
+impl<'a, I, E> ParserExtra<'a, I> for DebugErr<E, true>
+where
+    I: Input<'a>,
+    E: Error<'a, I> + 'a,
+{
+    type Error = E;
+    type State = DefaultState;
+    type Context = DefaultCtx;
+}
+impl<'a, I, E> ParserExtra<'a, I> for DebugErr<E, false>
+where
+    I: Input<'a>,
+    E: Error<'a, I> + 'a,
+{
+    type Error = EmptyErr;
+    type State = DefaultState;
+    type Context = DefaultCtx;
+}
+
 /// Specify all extra types. See [`ParserExtra`] for more details.
 pub struct Full<E, S, C>(PhantomData<(E, S, C)>);
 
@@ -71,3 +126,126 @@ where
     type State = S;
     type Context = C;
 }
+
+/// Options that control how a parse is run, rather than what it accepts. See [`Parser::parse_with_options`].
+///
+/// Options are set with a builder: start from [`ParseOptions::new`] and add the limits that you need. By default, no
+/// limits apply. When a limit is reached, the parse is stopped early and [`ParseResult::halted`] reports why.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, extra::{Halt, ParseOptions}};
+/// let digits = text::digits::<_, _, extra::Err<Simple<char>>>(10).to_slice();
+///
+/// let options = ParseOptions::new().token_budget(4);
+/// assert_eq!(digits.parse_with_options("123", &mut (), &options).into_result(), Ok("123"));
+///
+/// let res = digits.parse_with_options("123456", &mut (), &options);
+/// assert!(res.has_errors() && !res.has_output());
+/// assert_eq!(res.halted(), Some(Halt::TokenBudget));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ParseOptions {
+    pub(crate) max_errors: Option<usize>,
+    pub(crate) recursion_limit: Option<usize>,
+    pub(crate) token_budget: Option<usize>,
+    pub(crate) cancellation: Option<Cancellation>,
+    pub(crate) strict: Option<bool>,
+}
+
+impl ParseOptions {
+    /// Create a new set of options, with no limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop parsing once this many errors have been emitted during error recovery. A limit of `0` is treated as `1`.
+    ///
+    /// Parsers with error recovery can produce a flood of errors from badly malformed input, most of which are noise.
+    pub fn max_errors(self, max_errors: usize) -> Self {
+        Self {
+            max_errors: Some(max_errors.max(1)),
+            ..self
+        }
+    }
+
+    /// Stop parsing if [`recursive`] parsers are nested more deeply than this.
+    ///
+    /// This guards against deeply nested input (such as `[[[[...]]]]`) overflowing the stack. When the limit is
+    /// reached, the parse fails with an [`Error::nested_too_deep`](crate::error::Error::nested_too_deep) error (for
+    /// [`Rich`](crate::error::Rich), "nesting too deep") at the point where it was reached.
+    pub fn recursion_limit(self, recursion_limit: usize) -> Self {
+        Self {
+            recursion_limit: Some(recursion_limit),
+            ..self
+        }
+    }
+
+    /// Stop parsing after this many tokens have been read from the input.
+    ///
+    /// Tokens that are read again after backtracking count again, so this limits the total work done by a parse
+    /// rather than the length of the input.
+    pub fn token_budget(self, token_budget: usize) -> Self {
+        Self {
+            token_budget: Some(token_budget),
+            ..self
+        }
+    }
+
+    /// Stop parsing when the given [`Cancellation`] is cancelled.
+    pub fn cancellation(self, cancellation: Cancellation) -> Self {
+        Self {
+            cancellation: Some(cancellation),
+            ..self
+        }
+    }
+
+    /// Decide whether the parse is strict, overriding the parser state. See [`Parser::lenient`] and
+    /// [`Parser::strict`].
+    pub fn strict(self, strict: bool) -> Self {
+        Self {
+            strict: Some(strict),
+            ..self
+        }
+    }
+}
+
+/// A handle that can be used to stop a parse from elsewhere, such as another thread. See
+/// [`ParseOptions::cancellation`].
+///
+/// Clones of a handle share the same flag, so cancelling one cancels all of them.
+#[derive(Clone, Debug, Default)]
+pub struct Cancellation(alloc::sync::Arc<core::sync::atomic::AtomicBool>);
+
+impl Cancellation {
+    /// Create a new handle, not yet cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel any parses that use this handle. Parsing stops when the next token is read.
+    pub fn cancel(&self) {
+        self.0.store(true, core::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Whether this handle has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(core::sync::atomic::Ordering::Relaxed)
+    }
+}
+
+/// The reason that a parse was stopped early. See [`ParseResult::halted`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Halt {
+    /// The limit set by [`ParseOptions::max_errors`] was reached.
+    MaxErrors,
+    /// The limit set by [`ParseOptions::recursion_limit`] was reached.
+    RecursionLimit,
+    /// The limit set by [`ParseOptions::token_budget`] was reached.
+    TokenBudget,
+    /// The [`Cancellation`] given to [`ParseOptions::cancellation`] was cancelled.
+    Cancelled,
+    /// The parser backtracked to before a point that it had committed to with [`Parser::commit`].
+    Discarded,
+}
@@ -0,0 +1,210 @@
+//! Parsers and input for two-dimensional, grid-like inputs.
+//!
+//! *"It is a mistake to think you can solve any major problems just with potatoes."*
+//!
+//! [`Grid`] presents a sequence of rows as an input, for things like puzzle maps or fixed-layout tables. Tokens are read
+//! from one row after another, but spans are made up of [`Pos`]itions (a row and a column) so that errors point to
+//! the right place in the grid. Use [`end_of_row`] and [`next_row`] to parse the rows of a grid separately, and
+//! [`position`] to find out where a parser is.
+//!
+//! # Examples
+//!
+//! ```
+//! # use chumsky::{prelude::*, grid::{self, Grid, Pos}};
+//! let cell = one_of::<_, _, extra::Err<Rich<u8, SimpleSpan<Pos>>>>(b".#");
+//! let row = cell
+//!     .and_is(grid::end_of_row().not())
+//!     .repeated()
+//!     .collect::<Vec<_>>();
+//! let map = row.separated_by(grid::next_row()).collect::<Vec<_>>();
+//!
+//! let rows = map.parse(Grid::lines("#..\n.#.\n..#")).into_result().unwrap();
+//! assert_eq!(rows[1], b".#.");
+//!
+//! let errs = map.parse(Grid::lines("#..\n.x.")).into_errors();
+//! assert_eq!(errs[0].span().start, Pos { row: 1, col: 1 });
+//! ```
+
+use super::*;
+
+/// A position in a [`Grid`]: a row and a column, both starting from zero.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Pos {
+    /// The row.
+    pub row: usize,
+    /// The column within the row.
+    pub col: usize,
+}
+
+impl fmt::Display for Pos {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.row, self.col)
+    }
+}
+
+/// An input made up of rows of tokens, with spans measured in [`Pos`]itions.
+///
+/// Tokens are produced from each row in turn, so parsers that don't care about rows can treat the grid as a flat
+/// sequence of tokens. Rows may differ in length.
+#[derive(Clone, Debug)]
+pub struct Grid<'src, T> {
+    rows: Vec<&'src [T]>,
+}
+
+impl<'src, T> Grid<'src, T> {
+    /// Create a new grid from the given rows.
+    pub fn new<R: IntoIterator<Item = &'src [T]>>(rows: R) -> Self {
+        Self {
+            rows: rows.into_iter().collect(),
+        }
+    }
+}
+
+impl<'src> Grid<'src, u8> {
+    /// Create a new grid of bytes from the lines of a string, without their line endings.
+    pub fn lines(s: &'src str) -> Self {
+        Self::new(s.lines().map(str::as_bytes))
+    }
+}
+
+// The position of a cursor, moved forward past the ends of rows (if possible) so that it refers to a cell
+fn normalize<T>(rows: &[&[T]], mut pos: Pos) -> Pos {
+    while pos.row + 1 < rows.len() && pos.col >= rows[pos.row].len() {
+        pos.row += 1;
+        pos.col = 0;
+    }
+    pos
+}
+
+impl<'src, T> Input<'src> for Grid<'src, T> {
+    /// The position of the cursor, along with the number of tokens before it.
+    type Cursor = (Pos, usize);
+    type Span = SimpleSpan<Pos>;
+
+    type Token = T;
+    type MaybeToken = &'src T;
+
+    type Cache = Vec<&'src [T]>;
+
+    #[inline]
+    fn begin(self) -> (Self::Cursor, Self::Cache) {
+        ((Pos::default(), 0), self.rows)
+    }
+
+    #[inline]
+    fn cursor_location(cursor: &Self::Cursor) -> usize {
+        cursor.1
+    }
+
+    #[inline]
+    unsafe fn next_maybe(
+        rows: &mut Self::Cache,
+        (pos, offset): &mut Self::Cursor,
+    ) -> Option<Self::MaybeToken> {
+        *pos = normalize(rows, *pos);
+        let tok = rows.get(pos.row)?.get(pos.col)?;
+        pos.col += 1;
+        *offset += 1;
+        Some(tok)
+    }
+
+    #[inline]
+    unsafe fn span(rows: &mut Self::Cache, range: Range<&Self::Cursor>) -> Self::Span {
+        let (start, end) = (range.start, range.end);
+        // Spans that cover tokens start at the first of them, rather than at the end of the previous row
+        let start = if start.1 < end.1 {
+            normalize(rows, start.0)
+        } else {
+            start.0
+        };
+        (start..end.0).into()
+    }
+}
+
+impl<'src, T> ExactSizeInput<'src> for Grid<'src, T> {
+    #[inline]
+    unsafe fn span_from(rows: &mut Self::Cache, range: RangeFrom<&Self::Cursor>) -> Self::Span {
+        let end = match rows.last() {
+            Some(last) => Pos {
+                row: rows.len() - 1,
+                col: last.len(),
+            },
+            None => Pos::default(),
+        };
+        (range.start.0..end.max(range.start.0)).into()
+    }
+}
+
+impl<'src, T: Clone> ValueInput<'src> for Grid<'src, T> {
+    #[inline]
+    unsafe fn next(this: &mut Self::Cache, cursor: &mut Self::Cursor) -> Option<Self::Token> {
+        Self::next_maybe(this, cursor).cloned()
+    }
+}
+
+impl<'src, T> BorrowInput<'src> for Grid<'src, T> {
+    #[inline]
+    unsafe fn next_ref(this: &mut Self::Cache, cursor: &mut Self::Cursor) -> Option<&'src T> {
+        Self::next_maybe(this, cursor)
+    }
+}
+
+/// A parser that succeeds without consuming any input if there are no more tokens in the current row.
+///
+/// The last row of the grid also ends at the end of the input.
+pub fn end_of_row<'src, T, E>() -> impl Parser<'src, Grid<'src, T>, (), E> + Copy
+where
+    T: 'src,
+    E: ParserExtra<'src, Grid<'src, T>>,
+{
+    custom(|inp: &mut InputRef<'src, '_, Grid<'src, T>, E>| {
+        let before = inp.cursor();
+        let pos = before.inner().0;
+        let row_len = inp.cache.get(pos.row).map_or(0, |row| row.len());
+        if pos.col >= row_len {
+            Ok(())
+        } else {
+            let found = inp.next_maybe_inner().map(|tok| tok.into());
+            let span = inp.span_since(&before);
+            Err(Error::expected_found([], found, span))
+        }
+    })
+}
+
+/// A parser that skips any tokens remaining in the current row and moves to the start of the next row.
+///
+/// This fails if the current row is the last row of the grid.
+pub fn next_row<'src, T, E>() -> impl Parser<'src, Grid<'src, T>, (), E> + Copy
+where
+    T: 'src,
+    E: ParserExtra<'src, Grid<'src, T>>,
+{
+    custom(|inp: &mut InputRef<'src, '_, Grid<'src, T>, E>| {
+        let before = inp.cursor();
+        let (pos, offset) = *before.inner();
+        if pos.row + 1 >= inp.cache.len() {
+            let span = inp.span_since(&before);
+            return Err(Error::expected_found([], None, span));
+        }
+        let skipped = inp.cache[pos.row].len().saturating_sub(pos.col);
+        let next = Pos {
+            row: pos.row + 1,
+            col: 0,
+        };
+        // SAFETY: the new cursor is the start of the next row, after the tokens remaining in this row
+        unsafe { inp.set_cursor((next, offset + skipped)) };
+        Ok(())
+    })
+}
+
+/// A parser that consumes no input and produces the current [`Pos`]ition in the grid.
+///
+/// At the end of a row, this is the position after its last token, rather than the start of the next row.
+pub fn position<'src, T, E>() -> impl Parser<'src, Grid<'src, T>, Pos, E> + Copy
+where
+    T: 'src,
+    E: ParserExtra<'src, Grid<'src, T>>,
+{
+    custom(|inp: &mut InputRef<'src, '_, Grid<'src, T>, E>| Ok(inp.cursor().inner().0))
+}
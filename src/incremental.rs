@@ -0,0 +1,352 @@
+//! Utilities for incrementally reparsing text after it has been edited.
+//!
+//! Language servers and editors tend to reparse a document after every keystroke, but most edits only affect a tiny
+//! part of it. [`Incremental`] remembers the top-level items of a previous parse along with their spans. When the
+//! text changes, it reparses only the items that were touched by the [`Edit`], stopping as soon as it finds itself
+//! back at the start of an item it already knows about. Any items after that point are kept, and their spans are
+//! shifted to account for the change in length.
+//!
+//! This approach is conservative: it never reuses anything smaller than a whole top-level item, so it works with any
+//! grammar that can be split into a sequence of independent items (functions, statements, JSON lines, etc.).
+//!
+//! # Example
+//!
+//! ```
+//! # use chumsky::{prelude::*, incremental::{Edit, Incremental}};
+//! // Each item is a `name = value;` binding, including any whitespace that precedes it
+//! fn binding<'a>() -> impl Parser<'a, &'a str, (String, u32)> {
+//!     text::ident()
+//!         .then_ignore(just('=').padded())
+//!         .then(text::int(10).from_str().unwrapped())
+//!         .then_ignore(just(';'))
+//!         .padded()
+//!         .map(|(name, value): (&str, u32)| (name.to_string(), value))
+//! }
+//!
+//! let src = "a = 1; b = 2; c = 3;";
+//! let mut doc = Incremental::parse(&binding(), src).unwrap();
+//!
+//! // Change the value of `b`
+//! let edit = Edit::new(11..12, "42");
+//! let new_src = edit.apply(src);
+//! assert!(doc.reparse(&binding(), &new_src, &edit, |_, _| {}).is_empty());
+//!
+//! let outputs = doc.items().iter().map(|(out, _)| out.clone()).collect::<Vec<_>>();
+//! assert_eq!(outputs, [("a".to_string(), 1), ("b".to_string(), 42), ("c".to_string(), 3)]);
+//! // The span of the final item was shifted rather than reparsed
+//! assert_eq!(doc.items()[2].1, SimpleSpan::from(15..21));
+//! ```
+
+use super::*;
+
+/// A change to a piece of source text: the bytes in `range` get replaced with `replacement`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Edit {
+    /// The byte range of the original text that was replaced.
+    pub range: Range<usize>,
+    /// The text that was inserted in place of the original range.
+    pub replacement: String,
+}
+
+impl Edit {
+    /// Create a new edit that replaces the given byte range with some text.
+    ///
+    /// Insertions can be represented by an empty range, and deletions by an empty replacement.
+    pub fn new(range: Range<usize>, replacement: impl Into<String>) -> Self {
+        Self {
+            range,
+            replacement: replacement.into(),
+        }
+    }
+
+    /// Apply this edit to the original text, producing the edited text.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range of the edit is out of bounds or does not lie on [`char`] boundaries.
+    pub fn apply(&self, src: &str) -> String {
+        let mut new_src = String::with_capacity(src.len() + self.replacement.len());
+        new_src.push_str(&src[..self.range.start]);
+        new_src.push_str(&self.replacement);
+        new_src.push_str(&src[self.range.end..]);
+        new_src
+    }
+
+    /// The change in length of the text caused by this edit, in bytes.
+    pub fn delta(&self) -> isize {
+        self.replacement.len() as isize - self.range.len() as isize
+    }
+
+    /// Translate an offset into the original text to the equivalent offset in the edited text.
+    ///
+    /// Offsets before the edit are unchanged, and offsets within the replaced range are moved to the end of the
+    /// replacement.
+    pub fn shift(&self, offset: usize) -> usize {
+        if offset < self.range.start {
+            offset
+        } else if offset < self.range.end {
+            self.range.start + self.replacement.len()
+        } else {
+            (offset as isize + self.delta()) as usize
+        }
+    }
+}
+
+/// The retained result of parsing text as a sequence of top-level items, which can be updated after an [`Edit`].
+///
+/// See the [module-level documentation](self) for more information.
+///
+/// Items are parsed one at a time, starting from the beginning of the input, until the end of the input is reached.
+/// The item parser is expected to consume any trivia (whitespace, comments, etc.) around the item too, such that the
+/// spans of consecutive items are adjacent. An item parser that succeeds without consuming any input stops parsing,
+/// as does an item parser that fails: the remaining text is left unparsed and gets revisited by every subsequent
+/// reparse.
+///
+/// Outputs that are kept across a reparse must not borrow from the input, so they will usually be owned types.
+#[derive(Clone, Debug)]
+pub struct Incremental<O> {
+    items: Vec<(O, SimpleSpan)>,
+    parsed_to: usize,
+    len: usize,
+}
+
+impl<O> Incremental<O> {
+    /// Parse the given text as a sequence of items, retaining the outputs so that they can later be updated with
+    /// [`Incremental::reparse`].
+    ///
+    /// The output of the result is always present, even if some of the text could not be parsed.
+    pub fn parse<'src, P, E>(parser: &P, src: &'src str) -> ParseResult<Self, E::Error>
+    where
+        P: Parser<'src, &'src str, O, E>,
+        E: ParserExtra<'src, &'src str>,
+        E::State: Default,
+        E::Context: Default,
+    {
+        let mut errs = Vec::new();
+        let (items, parsed_to) = parse_items(parser, src, 0, &mut errs, |_| false);
+        let this = Self {
+            items,
+            parsed_to,
+            len: src.len(),
+        };
+        ParseResult::new(Some(this), errs)
+    }
+
+    /// Update the retained items to reflect an edit made to the text, reparsing only the items touched by the edit.
+    ///
+    /// `new_src` must be the result of applying `edit` to the text that was last parsed. Items that appear after the
+    /// edit and did not need reparsing are kept, and `shift` is called on each of their outputs with the number of
+    /// bytes they moved by so that any spans stored inside them can be updated.
+    ///
+    /// Only errors produced while reparsing are returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `new_src` is inconsistent with the edit.
+    pub fn reparse<'src, P, E, F>(
+        &mut self,
+        parser: &P,
+        new_src: &'src str,
+        edit: &Edit,
+        mut shift: F,
+    ) -> Vec<E::Error>
+    where
+        P: Parser<'src, &'src str, O, E>,
+        E: ParserExtra<'src, &'src str>,
+        E::State: Default,
+        E::Context: Default,
+        F: FnMut(&mut O, isize),
+    {
+        assert_eq!(
+            self.len as isize + edit.delta(),
+            new_src.len() as isize,
+            "edited text does not match the length implied by the edit",
+        );
+
+        let delta = edit.delta();
+        // Items that end before the edit are unaffected, as are those that start after it. Items that merely touch
+        // the edit still get reparsed, since the edit might extend the tokens at their edges.
+        let first = self
+            .items
+            .partition_point(|(_, span)| span.end < edit.range.start);
+        let after = self
+            .items
+            .partition_point(|(_, span)| span.start <= edit.range.end);
+        let start = self
+            .items
+            .get(first)
+            .map_or(self.parsed_to, |(_, span)| span.start);
+        assert!(
+            new_src.is_char_boundary(start),
+            "edited text does not match the previously parsed text",
+        );
+
+        let mut tail = self.items.split_off(after);
+        self.items.truncate(first);
+        let mut tail_starts = tail
+            .iter()
+            .map(|(_, span)| (span.start as isize + delta) as usize)
+            .peekable();
+
+        // Keep parsing until we end up back at the start of a known item
+        let mut skipped = 0;
+        let mut errs = Vec::new();
+        let (items, parsed_to) = parse_items(parser, new_src, start, &mut errs, |pos| {
+            while tail_starts.next_if(|start| *start < pos).is_some() {
+                skipped += 1;
+            }
+            tail_starts.peek() == Some(&pos)
+        });
+        self.items.extend(items);
+
+        if tail_starts.peek() == Some(&parsed_to) {
+            self.items
+                .extend(tail.drain(skipped..).map(|(mut out, span)| {
+                    shift(&mut out, delta);
+                    let span = (span.start as isize + delta) as usize
+                        ..(span.end as isize + delta) as usize;
+                    (out, span.into())
+                }));
+            self.parsed_to = (self.parsed_to as isize + delta) as usize;
+        } else {
+            self.parsed_to = parsed_to;
+        }
+        self.len = new_src.len();
+
+        errs
+    }
+
+    /// Get the items that are currently known, along with their spans.
+    pub fn items(&self) -> &[(O, SimpleSpan)] {
+        &self.items
+    }
+
+    /// Convert this into the items that are currently known, along with their spans.
+    pub fn into_items(self) -> Vec<(O, SimpleSpan)> {
+        self.items
+    }
+
+    /// Whether the entire text was successfully parsed into items.
+    pub fn is_complete(&self) -> bool {
+        self.parsed_to == self.len
+    }
+}
+
+// Parse items from `start` until the end of the input, until an item fails to parse, or until `stop` returns `true`
+// for the position of the next item. Returns the items alongside the position parsing stopped at.
+fn parse_items<'src, P, O, E>(
+    parser: &P,
+    src: &'src str,
+    start: usize,
+    errs: &mut Vec<E::Error>,
+    mut stop: impl FnMut(usize) -> bool,
+) -> (Vec<(O, SimpleSpan)>, usize)
+where
+    P: Parser<'src, &'src str, O, E>,
+    E: ParserExtra<'src, &'src str>,
+    E::State: Default,
+    E::Context: Default,
+{
+    let mut items = Vec::new();
+    let mut pos = start;
+    while pos < src.len() && !stop(pos) {
+        let mut own = InputOwn::<&'src str, E>::new(src);
+        own.start = pos;
+        let mut inp = own.as_ref_start();
+        let res = parser.go::<Emit>(&mut inp);
+        let end = *inp.cursor().inner();
+        let alt = inp.take_alt();
+        errs.extend(own.into_errs());
+        match res {
+            Ok(out) if end > pos => {
+                items.push((out, (pos..end).into()));
+                pos = end;
+            }
+            Ok(_) => break,
+            Err(()) => {
+                errs.push(alt.err);
+                break;
+            }
+        }
+    }
+    (items, pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word<'a>() -> impl Parser<'a, &'a str, String> {
+        text::ident().padded().map(ToString::to_string)
+    }
+
+    fn words(doc: &Incremental<String>) -> Vec<(&str, Range<usize>)> {
+        doc.items()
+            .iter()
+            .map(|(w, span)| (w.as_str(), span.into_range()))
+            .collect()
+    }
+
+    fn check(src: &str, edit: Edit) {
+        let mut doc = Incremental::parse(&word(), src).unwrap();
+        let new_src = edit.apply(src);
+        let errs = doc.reparse(&word(), &new_src, &edit, |_, _| {});
+        let expected = Incremental::parse(&word(), &new_src);
+        assert_eq!(errs.is_empty(), !expected.has_errors());
+        let expected = expected.into_output().unwrap();
+        assert_eq!(words(&doc), words(&expected), "{src:?} -> {new_src:?}");
+        assert_eq!(doc.is_complete(), expected.is_complete());
+    }
+
+    #[test]
+    fn reparse_matches_full_parse() {
+        let src = "foo bar baz qux";
+        check(src, Edit::new(4..7, "hello"));
+        check(src, Edit::new(3..3, "d"));
+        check(src, Edit::new(4..4, "x"));
+        check(src, Edit::new(3..4, ""));
+        check(src, Edit::new(0..0, "  "));
+        check(src, Edit::new(15..15, " end"));
+        check(src, Edit::new(5..13, "y z w"));
+        check(src, Edit::new(0..15, ""));
+        check(src, Edit::new(7..8, "+"));
+    }
+
+    #[test]
+    fn reparse_recovers_tail() {
+        let src = "foo + bar baz";
+        let mut doc = Incremental::parse(&word(), src).into_output().unwrap();
+        assert!(!doc.is_complete());
+        assert_eq!(words(&doc), [("foo", 0..4)]);
+
+        let edit = Edit::new(4..5, "");
+        let new_src = edit.apply(src);
+        assert!(doc.reparse(&word(), &new_src, &edit, |_, _| {}).is_empty());
+        assert!(doc.is_complete());
+        assert_eq!(words(&doc), [("foo", 0..5), ("bar", 5..9), ("baz", 9..12)]);
+    }
+
+    #[test]
+    fn reparse_shifts_retained() {
+        let src = "a b c d";
+        let mut doc = Incremental::parse(&word(), src).unwrap();
+        let edit = Edit::new(0..1, "abc");
+        let new_src = edit.apply(src);
+        let mut shifted = Vec::new();
+        doc.reparse(&word(), &new_src, &edit, |w, delta| {
+            shifted.push((w.clone(), delta))
+        });
+        assert_eq!(
+            shifted,
+            [
+                ("b".to_string(), 2),
+                ("c".to_string(), 2),
+                ("d".to_string(), 2)
+            ]
+        );
+        assert_eq!(
+            words(&doc),
+            [("abc", 0..4), ("b", 4..6), ("c", 6..8), ("d", 8..9)]
+        );
+    }
+}
@@ -5,11 +5,16 @@
 //! [`Input`] is the primary trait used to feed input data into a chumsky parser. You can create them in a number of
 //! ways: from strings, slices, arrays, etc.
 
-use inspector::Inspector;
+use extra::{Halt, ParseOptions};
+use inspector::{Inspector, Strictness};
+use recovery::RecoveryEvent;
 
-pub use crate::stream::{BoxedExactSizeStream, BoxedStream, IterInput, Stream};
+pub use crate::stream::{
+    BoundedStream, BoxedExactSizeStream, BoxedStream, FallibleStream, IterInput, Stream,
+};
 
 use super::*;
+use alloc::borrow::Cow;
 #[cfg(feature = "std")]
 use std::io::{BufReader, Read, Seek};
 
@@ -84,6 +89,10 @@ pub trait Input<'src>: 'src {
     /// However, what you choose for this span is up to you: but consider that the context, start, and end of the span
     /// will be recombined to create new spans as required by the parser.
     ///
+    /// A zero-width span (such as that of an error that occurs before any tokens are consumed) is placed at the start
+    /// of the next token, or is the EoI span if there are no tokens left. Tokens are often not contiguous (they may be
+    /// separated by whitespace, for example), so this is not necessarily the end of the previous token.
+    ///
     /// Although `Spanned` does implement [`BorrowInput`], please be aware that, as you might anticipate, the slices
     /// will be those of the original input (usually `&[(T, S)]`) and not `&[T]` so as to avoid the need to copy
     /// around sections of the input.
@@ -132,6 +141,40 @@ pub trait Input<'src>: 'src {
             phantom: PhantomData,
         }
     }
+
+    /// Normalize the tokens of this input with the given function before they are matched against.
+    ///
+    /// This is useful for languages that are insensitive to case, character width, or similar, such as SQL. Only the
+    /// tokens seen by parsers are normalized: spans and slices still refer to the original input, so the output of
+    /// [`Parser::to_slice`] is the text as it was written. Parsers that match text, like [`just`] and
+    /// [`text::ascii::keyword`], match the normalized tokens but also output the text as it was written. See
+    /// [`text::fold_case`] and [`text::fold_width`] for some common normalizations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let select = text::ascii::keyword::<_, _, _, extra::Err<Simple<char>>>("select").padded();
+    ///
+    /// let normalize = |c| text::fold_case(text::fold_width(c));
+    ///
+    /// assert_eq!(select.parse("SeLeCt".normalize(normalize)).into_result(), Ok("SeLeCt"));
+    /// // Full-width characters, as are often found in CJK text, can be matched as their half-width forms
+    /// assert_eq!(
+    ///     select.parse("ＳＥＬＥＣＴ".normalize(normalize)).into_result(),
+    ///     Ok("ＳＥＬＥＣＴ"),
+    /// );
+    /// ```
+    fn normalize<F>(self, normalize: F) -> Normalized<Self, F>
+    where
+        Self: ValueInput<'src> + Sized,
+        F: Fn(Self::Token) -> Self::Token,
+    {
+        Normalized {
+            input: self,
+            normalize,
+        }
+    }
 }
 
 /// Implement by inputs that have a known size (including spans)
@@ -189,6 +232,37 @@ pub trait ValueInput<'src>: Input<'src> {
     unsafe fn next(cache: &mut Self::Cache, cursor: &mut Self::Cursor) -> Option<Self::Token>;
 }
 
+/// Implemented by inputs that may fail while producing tokens, such as a lexer that encounters invalid text.
+///
+/// An input of this kind ends at the first error it encounters, retaining the error. See [`Parser::with_lex_errors`].
+pub trait FallibleInput<'src>: Input<'src> {
+    /// The type of error produced when the input fails to produce a token.
+    type LexError;
+
+    /// Get the error that ended the input, if one has been encountered so far, along with the cursor at which it
+    /// occurred.
+    fn lex_error(cache: &Self::Cache) -> Option<(Self::Cursor, &Self::LexError)>;
+
+    /// Get the span of the error that ended the input, if one has been encountered so far.
+    ///
+    /// By default, this is a zero-width span at the location of the error. Inputs that know the extent of the text
+    /// that could not be turned into a token (such as [`LogosInput`]) report that instead.
+    fn lex_error_span(cache: &mut Self::Cache) -> Option<Self::Span> {
+        let (at, _) = Self::lex_error(cache)?;
+        // SAFETY: The cursor was generated by the input
+        Some(unsafe { Self::span(cache, &at..&at) })
+    }
+}
+
+/// Implemented by inputs that can discard tokens which will never be needed again, such as a [`BoundedStream`].
+///
+/// See [`Parser::commit`].
+pub trait CommitInput<'src>: Input<'src> {
+    /// Indicate that the parser will never backtrack to before the given cursor, allowing the input to discard the
+    /// tokens that precede it.
+    fn commit(cache: &mut Self::Cache, cursor: &Self::Cursor);
+}
+
 /// Implemented by inputs that can have tokens borrowed from them.
 pub trait BorrowInput<'src>: Input<'src> {
     /// Borrowed version of [`ValueInput::next`] with the same safety requirements.
@@ -202,6 +276,21 @@ pub trait BorrowInput<'src>: Input<'src> {
     ) -> Option<&'src Self::Token>;
 }
 
+/// Implemented by inputs that can be traversed backwards, allowing tokens that have already been consumed to be
+/// inspected. See [`InputRef::peek_back`] and [`Parser::lookbehind`].
+pub trait LookbehindInput<'src>: Input<'src> {
+    /// Move the cursor back by one token, returning the token that was stepped over, or `None` if the cursor is at the
+    /// start of the input.
+    ///
+    /// # Safety
+    ///
+    /// As with functions on [`Input`], the cursors provided must be generated by this input.
+    unsafe fn prev_maybe(
+        cache: &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<Self::MaybeToken>;
+}
+
 impl<'src> Input<'src> for &'src str {
     type Cursor = usize;
     type Span = SimpleSpan<usize>;
@@ -261,6 +350,19 @@ impl<'src> ValueInput<'src> for &'src str {
     }
 }
 
+impl<'src> LookbehindInput<'src> for &'src str {
+    #[inline(always)]
+    unsafe fn prev_maybe(
+        this: &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<Self::MaybeToken> {
+        // SAFETY: We only ever return cursors that are at a character boundary
+        let c = this.get_unchecked(..*cursor).chars().next_back()?;
+        *cursor -= c.len_utf8();
+        Some(c)
+    }
+}
+
 impl Sealed for &str {}
 impl<'src> StrInput<'src, char> for &'src str {}
 
@@ -367,6 +469,17 @@ impl<'src, T> BorrowInput<'src> for &'src [T] {
     }
 }
 
+impl<'src, T> LookbehindInput<'src> for &'src [T] {
+    #[inline(always)]
+    unsafe fn prev_maybe(
+        this: &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<Self::MaybeToken> {
+        *cursor = cursor.checked_sub(1)?;
+        this.get(*cursor)
+    }
+}
+
 impl<'src, T: 'src, const N: usize> Input<'src> for &'src [T; N] {
     type Cursor = usize;
     type Span = SimpleSpan<usize>;
@@ -451,6 +564,17 @@ impl<'src, T: 'src, const N: usize> BorrowInput<'src> for &'src [T; N] {
     }
 }
 
+impl<'src, T: 'src, const N: usize> LookbehindInput<'src> for &'src [T; N] {
+    #[inline(always)]
+    unsafe fn prev_maybe(
+        this: &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<Self::MaybeToken> {
+        *cursor = cursor.checked_sub(1)?;
+        this.get(*cursor)
+    }
+}
+
 /// A wrapper around an input that splits an input into spans and tokens. See [`Input::spanned`].
 #[derive(Copy, Clone)]
 pub struct SpannedInput<T, S, I> {
@@ -497,11 +621,17 @@ where
 
     #[inline]
     unsafe fn span((cache, eoi): &mut Self::Cache, range: Range<&Self::Cursor>) -> Self::Span {
-        let start = I::next_maybe(cache, &mut range.start.0.clone())
-            .map(|tok| tok.borrow().1.start())
-            // .or_else(|| self.input.next_maybe(self.input.start()).1)
-            .unwrap_or_else(|| eoi.start());
-        let end = range.end.1.clone().unwrap_or_else(|| eoi.end());
+        let Some(start) =
+            I::next_maybe(cache, &mut range.start.0.clone()).map(|tok| tok.borrow().1.start())
+        else {
+            // An empty range at the end of the input
+            return eoi.clone();
+        };
+        let end = if I::cursor_location(&range.start.0) == I::cursor_location(&range.end.0) {
+            start.clone()
+        } else {
+            range.end.1.clone().unwrap_or_else(|| eoi.end())
+        };
         S::new(eoi.context(), start..end)
     }
 }
@@ -558,6 +688,24 @@ where
     }
 }
 
+impl<'src, T, S, I> LookbehindInput<'src> for SpannedInput<T, S, I>
+where
+    I: LookbehindInput<'src, Token = (T, S)>,
+    T: 'src,
+    S: Span + Clone + 'src,
+{
+    #[inline]
+    unsafe fn prev_maybe(
+        (cache, _): &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<Self::MaybeToken> {
+        let tok = I::prev_maybe(cache, &mut cursor.0)?;
+        // The cursor now sits after the token before the one we stepped over
+        cursor.1 = I::prev_maybe(cache, &mut cursor.0.clone()).map(|tok| tok.borrow().1.end());
+        Some(tok.map_maybe(|(tok, _)| tok, |(tok, _)| tok))
+    }
+}
+
 impl<'src, T, S, I> SliceInput<'src> for SpannedInput<T, S, I>
 where
     I: Input<'src> + SliceInput<'src, Token = (T, S)>,
@@ -585,6 +733,35 @@ where
     }
 }
 
+/// Implemented by token types that form a tree, in which some tokens are *groups* containing further tokens between a
+/// pair of delimiters (as with `proc_macro2::TokenTree`).
+///
+/// Such tokens can be parsed from a [`TokenTreeInput`], descending into groups with
+/// [`group_of`](crate::primitive::group_of) and [`Parser::nested_in`].
+pub trait TokenTree<S>: Sized {
+    /// The kind of delimiter that encloses a group, such as an enum of parentheses, brackets and braces.
+    type Delimiter: PartialEq;
+
+    /// If this token is a group, get its delimiter, its contents, and the span of its closing delimiter.
+    fn as_group(&self) -> Option<TreeGroup<'_, Self, S>>;
+}
+
+/// The contents of a group token. See [`TokenTree::as_group`].
+pub struct TreeGroup<'a, T: TokenTree<S>, S> {
+    /// The delimiter that encloses the group.
+    pub delimiter: &'a T::Delimiter,
+    /// The tokens within the group, and their spans.
+    pub tokens: &'a [(T, S)],
+    /// The span of the closing delimiter.
+    ///
+    /// This becomes the end of input span of the group's contents, so errors such as 'unexpected end of input' within
+    /// the group point at the closing delimiter.
+    pub close: S,
+}
+
+/// An input over a slice of token trees and their spans. See [`TokenTree`].
+pub type TokenTreeInput<'src, T, S> = SpannedInput<T, S, &'src [(T, S)]>;
+
 /// An input wrapper that returns a custom span, with the user-defined context
 /// contained in the Span::Context. See [`Input::with_context`].
 #[derive(Copy, Clone)]
@@ -684,6 +861,21 @@ where
     }
 }
 
+impl<'src, S, I: LookbehindInput<'src>> LookbehindInput<'src> for WithContext<S, I>
+where
+    S: Span + Clone + 'src,
+    S::Context: Clone + 'src,
+    S::Offset: From<<I::Span as Span>::Offset>,
+{
+    #[inline(always)]
+    unsafe fn prev_maybe(
+        (cache, _): &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<Self::MaybeToken> {
+        I::prev_maybe(cache, cursor)
+    }
+}
+
 impl<'src, S, I: SliceInput<'src>> SliceInput<'src> for WithContext<S, I>
 where
     S: Span + Clone + 'src,
@@ -826,6 +1018,22 @@ where
     }
 }
 
+impl<'src, S, I: LookbehindInput<'src>, F: 'src> LookbehindInput<'src> for MappedSpan<S, I, F>
+where
+    S: Span + Clone + 'src,
+    S::Context: Clone + 'src,
+    S::Offset: From<<I::Span as Span>::Offset>,
+    F: Fn(I::Span) -> S,
+{
+    #[inline(always)]
+    unsafe fn prev_maybe(
+        (cache, _): &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<Self::MaybeToken> {
+        I::prev_maybe(cache, cursor)
+    }
+}
+
 impl<'src, S, I: SliceInput<'src>, F: 'src> SliceInput<'src> for MappedSpan<S, I, F>
 where
     S: Span + Clone + 'src,
@@ -874,148 +1082,1438 @@ where
 {
 }
 
-/// Input type which supports seekable readers. Uses a [`BufReader`] internally to buffer input and
-/// avoid unnecessary IO calls.
-///
-/// Only available with the `std` feature
-#[cfg(feature = "std")]
-pub struct IoInput<R> {
-    reader: BufReader<R>,
-    last_cursor: usize,
-}
-
-#[cfg(feature = "std")]
-impl<R: Read + Seek> IoInput<R> {
-    /// Create a new `IoReader` from a seekable reader.
-    pub fn new(reader: R) -> IoInput<R> {
-        IoInput {
-            reader: BufReader::new(reader),
-            last_cursor: 0,
-        }
-    }
+/// An input wrapper that normalizes the tokens of an input, while keeping its spans and slices. See
+/// [`Input::normalize`].
+#[derive(Copy, Clone)]
+pub struct Normalized<I, F> {
+    input: I,
+    normalize: F,
 }
 
-#[cfg(feature = "std")]
-impl<'src, R: Read + Seek + 'src> Input<'src> for IoInput<R> {
-    type Cursor = usize;
-    type Span = SimpleSpan;
+impl<'src, I, F> Input<'src> for Normalized<I, F>
+where
+    I: ValueInput<'src>,
+    F: Fn(I::Token) -> I::Token + 'src,
+{
+    type Cursor = I::Cursor;
+    type Span = I::Span;
 
-    type Token = u8;
-    type MaybeToken = u8;
+    type Token = I::Token;
+    type MaybeToken = I::Token;
 
-    type Cache = Self;
+    type Cache = (I::Cache, F);
 
+    #[inline(always)]
     fn begin(self) -> (Self::Cursor, Self::Cache) {
-        (0, self)
+        let (cursor, cache) = self.input.begin();
+        (cursor, (cache, self.normalize))
     }
 
-    #[inline(always)]
+    #[inline]
     fn cursor_location(cursor: &Self::Cursor) -> usize {
-        *cursor
+        I::cursor_location(cursor)
     }
 
     #[inline(always)]
     unsafe fn next_maybe(
-        this: &mut Self::Cache,
+        (cache, normalize): &mut Self::Cache,
         cursor: &mut Self::Cursor,
     ) -> Option<Self::MaybeToken> {
-        Self::next(this, cursor)
+        I::next(cache, cursor).map(normalize)
     }
 
-    #[inline]
-    unsafe fn span(_this: &mut Self::Cache, range: Range<&Self::Cursor>) -> Self::Span {
-        (*range.start..*range.end).into()
+    #[inline(always)]
+    unsafe fn span((cache, _): &mut Self::Cache, range: Range<&Self::Cursor>) -> Self::Span {
+        I::span(cache, range)
     }
 }
 
-#[cfg(feature = "std")]
-impl<'src, R: Read + Seek + 'src> ValueInput<'src> for IoInput<R> {
-    unsafe fn next(this: &mut Self::Cache, cursor: &mut Self::Cursor) -> Option<Self::Token> {
-        if *cursor != this.last_cursor {
-            let seek = *cursor as i64 - this.last_cursor as i64;
-
-            this.reader.seek_relative(seek).unwrap();
-
-            this.last_cursor = *cursor;
-        }
-
-        let mut out = 0;
-
-        let r = this.reader.read_exact(std::slice::from_mut(&mut out));
+impl<'src, I, F> ExactSizeInput<'src> for Normalized<I, F>
+where
+    I: ValueInput<'src> + ExactSizeInput<'src>,
+    F: Fn(I::Token) -> I::Token + 'src,
+{
+    #[inline(always)]
+    unsafe fn span_from(
+        (cache, _): &mut Self::Cache,
+        range: RangeFrom<&Self::Cursor>,
+    ) -> Self::Span {
+        I::span_from(cache, range)
+    }
+}
 
-        match r {
-            Ok(()) => {
-                this.last_cursor += 1;
-                *cursor += 1;
-                Some(out)
-            }
-            Err(_) => None,
-        }
+impl<'src, I, F> ValueInput<'src> for Normalized<I, F>
+where
+    I: ValueInput<'src>,
+    F: Fn(I::Token) -> I::Token + 'src,
+{
+    #[inline(always)]
+    unsafe fn next(cache: &mut Self::Cache, cursor: &mut Self::Cursor) -> Option<Self::Token> {
+        Self::next_maybe(cache, cursor)
     }
 }
 
-/// Represents a location in an input that can be rewound to.
-///
-/// Checkpoints can be created with [`InputRef::save`] and rewound to with [`InputRef::rewind`].
-pub struct Checkpoint<'src, 'parse, I: Input<'src>, C> {
-    cursor: Cursor<'src, 'parse, I>,
-    pub(crate) err_count: usize,
-    pub(crate) inspector: C,
-    phantom: PhantomData<fn(&'parse ()) -> &'parse ()>, // Invariance
+impl<'src, I, F> LookbehindInput<'src> for Normalized<I, F>
+where
+    I: ValueInput<'src> + LookbehindInput<'src>,
+    F: Fn(I::Token) -> I::Token + 'src,
+{
+    #[inline(always)]
+    unsafe fn prev_maybe(
+        (cache, normalize): &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<Self::MaybeToken> {
+        I::prev_maybe(cache, cursor)?;
+        // Step forward over the token again to get it by value
+        I::next(cache, &mut cursor.clone()).map(normalize)
+    }
 }
 
-impl<'src, 'parse, I: Input<'src>, C> Checkpoint<'src, 'parse, I, C> {
-    /// Get the [`Cursor`] that this checkpoint corresponds to.
-    pub fn cursor(&self) -> &Cursor<'src, 'parse, I> {
-        &self.cursor
+impl<'src, I, F> SliceInput<'src> for Normalized<I, F>
+where
+    I: ValueInput<'src> + SliceInput<'src>,
+    F: Fn(I::Token) -> I::Token + 'src,
+{
+    type Slice = I::Slice;
+
+    #[inline(always)]
+    fn full_slice((cache, _): &mut Self::Cache) -> Self::Slice {
+        I::full_slice(cache)
     }
 
-    /// Get the [`Checkpoint`][Inspector::Checkpoint] that this marker corresponds to.
-    pub fn inspector(&self) -> &C {
-        &self.inspector
+    #[inline(always)]
+    unsafe fn slice((cache, _): &mut Self::Cache, range: Range<&Self::Cursor>) -> Self::Slice {
+        I::slice(cache, range)
     }
-}
 
-impl<'src, I: Input<'src>, C: Clone> Clone for Checkpoint<'src, '_, I, C> {
     #[inline(always)]
-    fn clone(&self) -> Self {
-        Self {
-            cursor: self.cursor.clone(),
-            err_count: self.err_count,
-            inspector: self.inspector.clone(),
-            phantom: PhantomData,
-        }
+    unsafe fn slice_from(
+        (cache, _): &mut Self::Cache,
+        from: RangeFrom<&Self::Cursor>,
+    ) -> Self::Slice {
+        I::slice_from(cache, from)
     }
 }
 
-/// Represents a location in an input.
-///
-/// If you to rewind to an old input location, see [`Checkpoint`].
-#[repr(transparent)]
-pub struct Cursor<'src, 'parse, I: Input<'src>> {
-    pub(crate) inner: I::Cursor,
-    phantom: PhantomData<fn(&'parse ()) -> &'parse ()>, // Invariance
+impl<'src, I, F> Sealed for Normalized<I, F>
+where
+    I: ValueInput<'src>,
+    F: Fn(I::Token) -> I::Token + 'src,
+{
+}
+impl<'src, C, I, F> StrInput<'src, C> for Normalized<I, F>
+where
+    I: StrInput<'src, C>,
+    F: Fn(I::Token) -> I::Token + 'src,
+    C: Char,
+{
 }
 
-impl<'src, I: Input<'src>> Cursor<'src, '_, I> {
-    /// Get the input's internal cursor.
-    pub fn inner(&self) -> &I::Cursor {
-        &self.inner
-    }
+/// An input made up of several inputs chained end-to-end, such as a prelude followed by a user's file, or a file with
+/// `#include`s expanded in place.
+///
+/// Each section is given a context (a file name, path, ID, etc.) that is attached to the spans produced from it, so
+/// that errors can point to the section they originated in. Spans are of type [`SimpleSpan`], with offsets relative to
+/// the start of their section. A span that would cross from one section into another is truncated at the end of the
+/// section it starts in.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, input::Concat, span::Span};
+/// let src = Concat::new([("prelude", "let a;"), ("main", " let b; let c")]);
+///
+/// let ident = any::<_, extra::Err<Rich<_, _>>>().filter(|c: &char| c.is_alphabetic());
+/// let stmt = just("let").padded().ignore_then(ident).then_ignore(just(';'));
+/// let errs = stmt.repeated().collect::<Vec<_>>().parse(src).into_errors();
+///
+/// // The missing semicolon is reported at the end of the `main` section.
+/// assert_eq!(errs[0].span().context(), "main");
+/// assert_eq!(errs[0].span().start, 13);
+/// ```
+pub struct Concat<C, I> {
+    sections: Vec<(C, I)>,
 }
 
-impl<'src, I: Input<'src>> Clone for Cursor<'src, '_, I> {
-    #[inline(always)]
-    fn clone(&self) -> Self {
+impl<C, I> Concat<C, I> {
+    /// Create a new input from a sequence of `(context, input)` sections, to be parsed in order.
+    pub fn new<S: IntoIterator<Item = (C, I)>>(sections: S) -> Self {
         Self {
-            inner: self.inner.clone(),
-            phantom: PhantomData,
+            sections: sections.into_iter().collect(),
         }
     }
 }
 
-impl<'src, I: Input<'src>> Eq for Cursor<'src, '_, I> {}
-impl<'src, I: Input<'src>> PartialEq for Cursor<'src, '_, I> {
+impl<'src, C, I> Concat<C, I>
+where
+    C: Clone + 'src,
+    I: ExactSizeInput<'src>,
+{
+    /// Move a cursor sitting at the end of a section to the start of the next one, if there is one.
+    ///
+    /// # Safety
+    ///
+    /// As with functions on [`Input`], the cursor must be generated by this input: its inner cursor must have been
+    /// generated by the section that it refers to.
+    unsafe fn normalize(
+        cache: &mut <Self as Input<'src>>::Cache,
+        (section, inner, base): &mut <Self as Input<'src>>::Cursor,
+    ) {
+        while *section + 1 < cache.len() {
+            let (_, inner_cache, _) = &mut cache[*section];
+            if I::next_maybe(inner_cache, &mut inner.clone()).is_some() {
+                break;
+            }
+            *base += I::cursor_location(inner);
+            *section += 1;
+            *inner = cache[*section].2.clone();
+        }
+    }
+}
+
+impl<'src, C, I> Input<'src> for Concat<C, I>
+where
+    C: Clone + 'src,
+    I: ExactSizeInput<'src>,
+{
+    type Cursor = (usize, I::Cursor, usize);
+    type Span = SimpleSpan<<I::Span as Span>::Offset, C>;
+
+    type Token = I::Token;
+    type MaybeToken = I::MaybeToken;
+
+    type Cache = Vec<(C, I::Cache, I::Cursor)>;
+
+    #[inline]
+    fn begin(self) -> (Self::Cursor, Self::Cache) {
+        let cache: Vec<_> = self
+            .sections
+            .into_iter()
+            .map(|(ctx, input)| {
+                let (cursor, cache) = input.begin();
+                (ctx, cache, cursor)
+            })
+            .collect();
+        assert!(
+            !cache.is_empty(),
+            "a concatenated input must have at least one section"
+        );
+        let start = cache[0].2.clone();
+        ((0, start, 0), cache)
+    }
+
+    #[inline]
+    fn cursor_location((_, inner, base): &Self::Cursor) -> usize {
+        base + I::cursor_location(inner)
+    }
+
+    #[inline]
+    unsafe fn next_maybe(
+        cache: &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<Self::MaybeToken> {
+        Self::normalize(cache, cursor);
+        let (section, inner, _) = cursor;
+        I::next_maybe(&mut cache[*section].1, inner)
+    }
+
+    #[inline]
+    unsafe fn span(cache: &mut Self::Cache, range: Range<&Self::Cursor>) -> Self::Span {
+        let mut start = range.start.clone();
+        Self::normalize(cache, &mut start);
+        let (section, inner, _) = &start;
+        let (ctx, inner_cache, _) = &mut cache[*section];
+        let inner_span = match range.end {
+            (end_section, end, _) if end_section == section => I::span(inner_cache, inner..end),
+            (end_section, _, _) if end_section < section => I::span(inner_cache, inner..inner),
+            _ => I::span_from(inner_cache, inner..),
+        };
+        Span::new(ctx.clone(), inner_span.start()..inner_span.end())
+    }
+}
+
+impl<'src, C, I> ExactSizeInput<'src> for Concat<C, I>
+where
+    C: Clone + 'src,
+    I: ExactSizeInput<'src>,
+{
+    #[inline]
+    unsafe fn span_from(cache: &mut Self::Cache, range: RangeFrom<&Self::Cursor>) -> Self::Span {
+        let mut start = range.start.clone();
+        Self::normalize(cache, &mut start);
+        let (section, inner, _) = &start;
+        let (ctx, inner_cache, _) = &mut cache[*section];
+        let inner_span = I::span_from(inner_cache, inner..);
+        Span::new(ctx.clone(), inner_span.start()..inner_span.end())
+    }
+}
+
+impl<'src, C, I> ValueInput<'src> for Concat<C, I>
+where
+    C: Clone + 'src,
+    I: ExactSizeInput<'src> + ValueInput<'src>,
+{
+    #[inline]
+    unsafe fn next(cache: &mut Self::Cache, cursor: &mut Self::Cursor) -> Option<Self::Token> {
+        Self::normalize(cache, cursor);
+        let (section, inner, _) = cursor;
+        I::next(&mut cache[*section].1, inner)
+    }
+}
+
+impl<'src, C, I> BorrowInput<'src> for Concat<C, I>
+where
+    C: Clone + 'src,
+    I: ExactSizeInput<'src> + BorrowInput<'src>,
+{
+    #[inline]
+    unsafe fn next_ref(
+        cache: &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<&'src Self::Token> {
+        Self::normalize(cache, cursor);
+        let (section, inner, _) = cursor;
+        I::next_ref(&mut cache[*section].1, inner)
+    }
+}
+
+/// An input with its concrete type erased, so that the same parser can be run over different kinds of input chosen
+/// at runtime without being compiled once for each of them.
+///
+/// Any [`ValueInput`] with the right token and span types can be erased, such as a `&str` (for `char` tokens) or a
+/// [`Stream`] of tokens produced by a lexer. The cost is a dynamic call for each token that is
+/// read.
+///
+/// Cursors of this input count the tokens that have been read from the start of the input, while spans are those of
+/// the original input.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, input::{DynInput, Stream}};
+/// type Input<'a> = DynInput<'a, char, SimpleSpan>;
+///
+/// // The parser is only compiled for `DynInput`
+/// let word = any::<Input, extra::Err<Rich<char>>>()
+///     .filter(|c: &char| c.is_alphabetic())
+///     .repeated()
+///     .at_least(1)
+///     .map_with(|_, e| e.span())
+///     .separated_by(just(' '))
+///     .collect::<Vec<_>>();
+///
+/// let from_str = DynInput::new("foo bar");
+/// let from_iter = DynInput::new(Stream::from_iter("foo bar".chars()));
+///
+/// let expected = vec![SimpleSpan::from(0..3), SimpleSpan::from(4..7)];
+/// assert_eq!(word.parse(from_str).into_result(), Ok(expected.clone()));
+/// assert_eq!(word.parse(from_iter).into_result(), Ok(expected));
+/// ```
+pub struct DynInput<'src, T, S> {
+    input: Box<dyn ErasedInput<'src, T, S> + 'src>,
+}
+
+impl<'src, T, S> DynInput<'src, T, S> {
+    /// Erase the type of the given input.
+    pub fn new<I>(input: I) -> Self
+    where
+        I: ValueInput<'src, Token = T, Span = S>,
+    {
+        Self {
+            input: Box::new(input),
+        }
+    }
+}
+
+// Object-safe version of `Input::begin`
+trait ErasedInput<'src, T, S> {
+    fn begin(self: Box<Self>) -> Box<dyn ErasedCache<T, S> + 'src>;
+}
+
+impl<'src, I: ValueInput<'src>> ErasedInput<'src, I::Token, I::Span> for I {
+    fn begin(self: Box<Self>) -> Box<dyn ErasedCache<I::Token, I::Span> + 'src> {
+        let (cursor, cache) = (*self).begin();
+        Box::new(Erased::<I> {
+            cache,
+            cursors: vec![cursor],
+        })
+    }
+}
+
+// Object-safe versions of the other methods of `Input`, using token counts as cursors
+#[doc(hidden)]
+pub trait ErasedCache<T, S> {
+    fn next(&mut self, cursor: &mut usize) -> Option<T>;
+    fn span(&mut self, range: Range<&usize>) -> S;
+}
+
+struct Erased<'src, I: Input<'src>> {
+    cache: I::Cache,
+    // The cursor of the inner input after each number of tokens, up to the furthest point read so far
+    cursors: Vec<I::Cursor>,
+}
+
+impl<'src, I: ValueInput<'src>> ErasedCache<I::Token, I::Span> for Erased<'src, I> {
+    #[inline]
+    fn next(&mut self, cursor: &mut usize) -> Option<I::Token> {
+        let mut inner = self.cursors[*cursor].clone();
+        // SAFETY: all cursors were generated by the inner input
+        let tok = unsafe { I::next(&mut self.cache, &mut inner) }?;
+        *cursor += 1;
+        if *cursor == self.cursors.len() {
+            self.cursors.push(inner);
+        }
+        Some(tok)
+    }
+
+    #[inline]
+    fn span(&mut self, range: Range<&usize>) -> I::Span {
+        // SAFETY: all cursors were generated by the inner input
+        unsafe {
+            I::span(
+                &mut self.cache,
+                &self.cursors[*range.start]..&self.cursors[*range.end],
+            )
+        }
+    }
+}
+
+impl<'src, T: 'src, S: Span + 'src> Input<'src> for DynInput<'src, T, S> {
+    type Cursor = usize;
+    type Span = S;
+
+    type Token = T;
+    type MaybeToken = T;
+
+    type Cache = Box<dyn ErasedCache<T, S> + 'src>;
+
+    #[inline]
+    fn begin(self) -> (Self::Cursor, Self::Cache) {
+        (0, self.input.begin())
+    }
+
+    #[inline]
+    fn cursor_location(cursor: &Self::Cursor) -> usize {
+        *cursor
+    }
+
+    #[inline(always)]
+    unsafe fn next_maybe(
+        cache: &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<Self::MaybeToken> {
+        cache.next(cursor)
+    }
+
+    #[inline(always)]
+    unsafe fn span(cache: &mut Self::Cache, range: Range<&Self::Cursor>) -> Self::Span {
+        cache.span(range)
+    }
+}
+
+impl<'src, T: 'src, S: Span + 'src> ValueInput<'src> for DynInput<'src, T, S> {
+    #[inline(always)]
+    unsafe fn next(cache: &mut Self::Cache, cursor: &mut Self::Cursor) -> Option<Self::Token> {
+        cache.next(cursor)
+    }
+}
+
+/// Input type which supports seekable readers. Uses a [`BufReader`] internally to buffer input and
+/// avoid unnecessary IO calls.
+///
+/// Only available with the `std` feature
+#[cfg(feature = "std")]
+pub struct IoInput<R> {
+    reader: BufReader<R>,
+    last_cursor: usize,
+}
+
+#[cfg(feature = "std")]
+impl<R: Read + Seek> IoInput<R> {
+    /// Create a new `IoReader` from a seekable reader.
+    pub fn new(reader: R) -> IoInput<R> {
+        IoInput {
+            reader: BufReader::new(reader),
+            last_cursor: 0,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'src, R: Read + Seek + 'src> Input<'src> for IoInput<R> {
+    type Cursor = usize;
+    type Span = SimpleSpan;
+
+    type Token = u8;
+    type MaybeToken = u8;
+
+    type Cache = Self;
+
+    fn begin(self) -> (Self::Cursor, Self::Cache) {
+        (0, self)
+    }
+
+    #[inline(always)]
+    fn cursor_location(cursor: &Self::Cursor) -> usize {
+        *cursor
+    }
+
+    #[inline(always)]
+    unsafe fn next_maybe(
+        this: &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<Self::MaybeToken> {
+        Self::next(this, cursor)
+    }
+
+    #[inline]
+    unsafe fn span(_this: &mut Self::Cache, range: Range<&Self::Cursor>) -> Self::Span {
+        (*range.start..*range.end).into()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'src, R: Read + Seek + 'src> ValueInput<'src> for IoInput<R> {
+    unsafe fn next(this: &mut Self::Cache, cursor: &mut Self::Cursor) -> Option<Self::Token> {
+        if *cursor != this.last_cursor {
+            let seek = *cursor as i64 - this.last_cursor as i64;
+
+            this.reader.seek_relative(seek).unwrap();
+
+            this.last_cursor = *cursor;
+        }
+
+        let mut out = 0;
+
+        let r = this.reader.read_exact(std::slice::from_mut(&mut out));
+
+        match r {
+            Ok(()) => {
+                this.last_cursor += 1;
+                *cursor += 1;
+                Some(out)
+            }
+            Err(_) => None,
+        }
+    }
+}
+
+/// Input type which lazily pulls bytes from any reader, without requiring it to be seekable.
+///
+/// Unlike [`IoInput`], only a bounded window of recently read bytes is kept in memory, so large files and pipes can be
+/// parsed without reading them in full first. The window always covers at least the given number of bytes behind the
+/// furthest point that has been read so far: this limits how far the parser may backtrack.
+///
+/// Spans are byte offsets from the start of the reader.
+///
+/// Only available with the `std` feature
+///
+/// # Errors
+///
+/// If the reader fails, or the parser attempts to backtrack further than the retained window, the input ends at that
+/// point and retains a [`ReadError`]: use [`Parser::with_lex_errors`] to report it. If the window is too small for
+/// the grammar, increase it with [`ReadInput::with_window`] or reduce the amount of lookahead in the grammar.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, input::ReadInput};
+/// let reader: &[u8] = b"12,345,6";
+///
+/// let num = any::<_, extra::Err<Rich<u8>>>()
+///     .filter(u8::is_ascii_digit)
+///     .repeated()
+///     .at_least(1)
+///     .collect::<Vec<_>>();
+/// let nums = num
+///     .separated_by(just(b','))
+///     .collect::<Vec<_>>()
+///     .then_ignore(end())
+///     .with_lex_errors(|err, span| Rich::custom(span, err));
+///
+/// assert_eq!(
+///     nums.parse(ReadInput::new(reader).with_window(4)).into_result(),
+///     Ok(vec![b"12".to_vec(), b"345".to_vec(), b"6".to_vec()]),
+/// );
+/// ```
+#[cfg(feature = "std")]
+pub struct ReadInput<R> {
+    reader: R,
+    buf: Vec<u8>,
+    // The offset of the first byte in `buf`
+    base: usize,
+    window: usize,
+    error: Option<(usize, ReadError)>,
+}
+
+/// The error retained by a [`ReadInput`] when it cannot produce the next byte.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum ReadError {
+    /// The reader failed.
+    Io(std::io::Error),
+    /// A parser attempted to backtrack to a byte that has fallen out of the retained window.
+    Discarded,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read input: {err}"),
+            Self::Discarded => write!(
+                f,
+                "attempted to backtrack further than the retained window of input"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ReadError {}
+
+#[cfg(feature = "std")]
+impl<R: Read> ReadInput<R> {
+    /// Create a new `ReadInput` from a reader, retaining a window of 64 KiB.
+    pub fn new(reader: R) -> ReadInput<R> {
+        ReadInput {
+            reader,
+            buf: Vec::new(),
+            base: 0,
+            window: 64 * 1024,
+            error: None,
+        }
+    }
+
+    /// Set the number of bytes behind the furthest read position that are kept available for backtracking.
+    pub fn with_window(self, window: usize) -> Self {
+        Self { window, ..self }
+    }
+
+    // Ensure that the byte at `offset` is buffered, returning `false` if the reader is exhausted or has failed
+    fn fill_to(&mut self, offset: usize) -> bool {
+        const CHUNK: usize = 8 * 1024;
+
+        while offset >= self.base + self.buf.len() {
+            // Nothing more can be read after a failure
+            if matches!(self.error, Some((_, ReadError::Io(_)))) {
+                return false;
+            }
+
+            // Drop bytes that have fallen out of the window, but only in bulk to avoid shuffling on every read
+            let excess = self.buf.len().saturating_sub(self.window);
+            if excess >= CHUNK.max(self.window) {
+                self.buf.drain(..excess);
+                self.base += excess;
+            }
+
+            let len = self.buf.len();
+            self.buf.resize(len + CHUNK, 0);
+            let res = loop {
+                match self.reader.read(&mut self.buf[len..]) {
+                    Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+                    res => break res,
+                }
+            };
+            let n = *res.as_ref().unwrap_or(&0);
+            self.buf.truncate(len + n);
+            if let Err(err) = res {
+                self.error = Some((self.base + len, ReadError::Io(err)));
+                return false;
+            } else if n == 0 {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'src, R: Read + 'src> Input<'src> for ReadInput<R> {
+    type Cursor = usize;
+    type Span = SimpleSpan;
+
+    type Token = u8;
+    type MaybeToken = u8;
+
+    type Cache = Self;
+
+    fn begin(self) -> (Self::Cursor, Self::Cache) {
+        (0, self)
+    }
+
+    #[inline(always)]
+    fn cursor_location(cursor: &Self::Cursor) -> usize {
+        *cursor
+    }
+
+    #[inline(always)]
+    unsafe fn next_maybe(
+        this: &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<Self::MaybeToken> {
+        Self::next(this, cursor)
+    }
+
+    #[inline]
+    unsafe fn span(_this: &mut Self::Cache, range: Range<&Self::Cursor>) -> Self::Span {
+        (*range.start..*range.end).into()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'src, R: Read + 'src> ValueInput<'src> for ReadInput<R> {
+    unsafe fn next(this: &mut Self::Cache, cursor: &mut Self::Cursor) -> Option<Self::Token> {
+        if *cursor < this.base {
+            // Only the first failure is interesting, later ones are likely a consequence of it
+            this.error.get_or_insert((*cursor, ReadError::Discarded));
+            return None;
+        }
+        if this.fill_to(*cursor) {
+            let tok = this.buf[*cursor - this.base];
+            *cursor += 1;
+            Some(tok)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'src, R: Read + 'src> FallibleInput<'src> for ReadInput<R> {
+    type LexError = ReadError;
+
+    #[inline]
+    fn lex_error(this: &Self::Cache) -> Option<(Self::Cursor, &Self::LexError)> {
+        this.error.as_ref().map(|(at, err)| (*at, err))
+    }
+}
+
+/// An input that pulls tokens from a [`logos::Lexer`], using the byte ranges that logos reports as token spans.
+///
+/// Tokens are pulled lazily, so the lexer does not need to be run to completion before parsing starts. By default, the
+/// input ends at the first error the lexer produces and retains the error, along with the span of the text that could
+/// not be lexed: use [`Parser::with_lex_errors`] to report it. Alternatively, [`LogosInput::with_error_token`] turns
+/// lexer errors into tokens so that parsing (and error recovery) can continue past them.
+///
+/// Only available with the `logos` feature
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, input::LogosInput};
+/// use logos::Logos;
+///
+/// #[derive(Logos, Clone, Debug, PartialEq)]
+/// #[logos(skip r"\s+")]
+/// enum Token {
+///     #[regex("[0-9]+", |lex| lex.slice().parse().ok())]
+///     Num(u32),
+///     #[token("+")]
+///     Plus,
+/// }
+///
+/// let num = select! { Token::Num(x) => x };
+/// let sum = num
+///     .foldl(just::<_, _, extra::Err<Rich<_>>>(Token::Plus).ignore_then(num).repeated(), |a, b| a + b)
+///     .then_ignore(end())
+///     .with_lex_errors(|_, span| Rich::custom(span, "unknown token"));
+///
+/// assert_eq!(sum.parse(LogosInput::new(Token::lexer("1 + 2 + 3"))).into_result(), Ok(6));
+///
+/// let errs = sum.parse(LogosInput::new(Token::lexer("1 + 2 ? 3"))).into_errors();
+/// assert_eq!(errs, vec![Rich::custom((6..7).into(), "unknown token")]);
+/// ```
+#[cfg(feature = "logos")]
+pub struct LogosInput<'src, T: ::logos::Logos<'src>> {
+    lexer: ::logos::Lexer<'src, T>,
+    tokens: Vec<(T, SimpleSpan)>,
+    error: Option<(T::Error, SimpleSpan)>,
+    on_error: Option<Box<dyn Fn(T::Error, SimpleSpan) -> T + 'src>>,
+    eoi: usize,
+}
+
+#[cfg(feature = "logos")]
+impl<'src, T: ::logos::Logos<'src>> LogosInput<'src, T> {
+    /// Create a new input from a lexer that ends at the first error the lexer produces.
+    pub fn new(lexer: ::logos::Lexer<'src, T>) -> Self {
+        use ::logos::Source;
+
+        Self {
+            eoi: lexer.source().len(),
+            lexer,
+            tokens: Vec::new(),
+            error: None,
+            on_error: None,
+        }
+    }
+
+    /// Create a new input from a lexer, converting errors produced by the lexer into tokens with the given function.
+    ///
+    /// This is useful when lexer errors should not prevent the rest of the input from being parsed. The parser can
+    /// then report the error token as unexpected, or recover from it like any other token.
+    pub fn with_error_token<F: Fn(T::Error, SimpleSpan) -> T + 'src>(
+        lexer: ::logos::Lexer<'src, T>,
+        f: F,
+    ) -> Self {
+        Self {
+            on_error: Some(Box::new(f)),
+            ..Self::new(lexer)
+        }
+    }
+
+    // Pull tokens from the lexer until the token at the given cursor is available or the lexer stops
+    fn pull(&mut self, cursor: usize) {
+        while self.tokens.len() <= cursor && self.error.is_none() {
+            let tok = match self.lexer.next() {
+                Some(tok) => tok,
+                None => break,
+            };
+            let span = SimpleSpan::from(self.lexer.span());
+            match (tok, &self.on_error) {
+                (Ok(tok), _) => self.tokens.push((tok, span)),
+                (Err(err), Some(on_error)) => self.tokens.push((on_error(err, span), span)),
+                (Err(err), None) => self.error = Some((err, span)),
+            }
+        }
+    }
+
+    // The offset at which the token at the given cursor starts
+    fn offset(&mut self, cursor: usize) -> usize {
+        self.pull(cursor);
+        match (self.tokens.get(cursor), &self.error) {
+            (Some((_, span)), _) => span.start,
+            (None, Some((_, span))) => span.start,
+            (None, None) => self.eoi,
+        }
+    }
+}
+
+#[cfg(feature = "logos")]
+impl<'src, T: ::logos::Logos<'src> + Clone + 'src> Input<'src> for LogosInput<'src, T> {
+    type Span = SimpleSpan;
+
+    type Token = T;
+    type MaybeToken = T;
+
+    type Cursor = usize;
+
+    type Cache = Self;
+
+    #[inline(always)]
+    fn begin(self) -> (Self::Cursor, Self::Cache) {
+        (0, self)
+    }
+
+    #[inline]
+    fn cursor_location(cursor: &Self::Cursor) -> usize {
+        *cursor
+    }
+
+    #[inline(always)]
+    unsafe fn next_maybe(
+        this: &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<Self::MaybeToken> {
+        Self::next(this, cursor)
+    }
+
+    #[inline]
+    unsafe fn span(this: &mut Self::Cache, range: Range<&Self::Cursor>) -> Self::Span {
+        let start = this.offset(*range.start);
+        let end = match range.end.checked_sub(1) {
+            Some(last) if *range.end > *range.start => this.tokens[last].1.end,
+            _ => start,
+        };
+        (start..end).into()
+    }
+}
+
+#[cfg(feature = "logos")]
+impl<'src, T: ::logos::Logos<'src> + Clone + 'src> ValueInput<'src> for LogosInput<'src, T> {
+    #[inline]
+    unsafe fn next(this: &mut Self::Cache, cursor: &mut Self::Cursor) -> Option<Self::Token> {
+        this.pull(*cursor);
+        this.tokens.get(*cursor).map(|(tok, _)| {
+            *cursor += 1;
+            tok.clone()
+        })
+    }
+}
+
+#[cfg(feature = "logos")]
+impl<'src, T: ::logos::Logos<'src> + Clone + 'src> FallibleInput<'src> for LogosInput<'src, T> {
+    type LexError = T::Error;
+
+    #[inline]
+    fn lex_error(this: &Self::Cache) -> Option<(Self::Cursor, &Self::LexError)> {
+        this.error.as_ref().map(|(err, _)| (this.tokens.len(), err))
+    }
+
+    /// The span of the text that could not be lexed, as reported by the lexer.
+    #[inline]
+    fn lex_error_span(this: &mut Self::Cache) -> Option<Self::Span> {
+        this.error.as_ref().map(|(_, span)| *span)
+    }
+}
+
+/// An input made up of several chunks of text, such as the pieces of a rope, that are parsed as if they were one
+/// contiguous string without first joining them together.
+///
+/// Cursors and spans are byte offsets from the start of the first chunk. Consecutive tokens usually come from the
+/// same chunk, so finding the chunk that contains a cursor is cheap in the common case.
+///
+/// Rope types that can produce their chunks as string slices (such as `ropey`'s `Rope::chunks`) can be parsed by
+/// collecting those slices first, which only copies pointers rather than the text itself. Taking a slice that crosses
+/// the boundary between two chunks allocates a new [`String`]: slices that fall within a single chunk are borrowed.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, input::ChunkedStr};
+/// let chunks = ["hel", "lo wo", "rld"];
+///
+/// let word = any::<_, extra::Err<Simple<char>>>()
+///     .filter(|c: &char| c.is_alphabetic())
+///     .repeated()
+///     .at_least(1)
+///     .to_slice();
+/// let words = word.separated_by(just(' ')).collect::<Vec<_>>();
+///
+/// assert_eq!(
+///     words.parse(ChunkedStr::new(&chunks)).into_result(),
+///     Ok(vec!["hello".into(), "world".into()]),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct ChunkedStr<'src> {
+    chunks: &'src [&'src str],
+    // The byte offset at which each chunk starts, followed by the total length
+    starts: Vec<usize>,
+    // The index of the chunk that was last read from
+    last_chunk: usize,
+}
+
+impl<'src> ChunkedStr<'src> {
+    /// Create a new input from a series of chunks of text.
+    pub fn new(chunks: &'src [&'src str]) -> Self {
+        let starts = core::iter::once(0)
+            .chain(chunks.iter().scan(0, |start, chunk| {
+                *start += chunk.len();
+                Some(*start)
+            }))
+            .collect();
+        Self {
+            chunks,
+            starts,
+            last_chunk: 0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        *self.starts.last().unwrap()
+    }
+
+    // Find the chunk that contains the given offset, assuming that the offset is not at the end of the input
+    fn chunk_at(&mut self, offset: usize) -> usize {
+        let contains = |idx: usize| self.starts[idx] <= offset && offset < self.starts[idx + 1];
+        let idx = if contains(self.last_chunk) {
+            self.last_chunk
+        } else if self.last_chunk + 1 < self.chunks.len() && contains(self.last_chunk + 1) {
+            self.last_chunk + 1
+        } else {
+            // Empty chunks share their start with the following chunk, so find the last chunk that starts at or before
+            // the offset
+            self.starts.partition_point(|start| *start <= offset) - 1
+        };
+        self.last_chunk = idx;
+        idx
+    }
+
+    fn slice_range(&mut self, range: Range<usize>) -> Cow<'src, str> {
+        if range.is_empty() {
+            return Cow::Borrowed("");
+        }
+        let first = self.chunk_at(range.start);
+        let chunk_start = self.starts[first];
+        if range.end <= self.starts[first + 1] {
+            Cow::Borrowed(&self.chunks[first][range.start - chunk_start..range.end - chunk_start])
+        } else {
+            let mut s = String::with_capacity(range.len());
+            let mut idx = first;
+            while self.starts[idx] < range.end {
+                let (start, end) = (self.starts[idx], self.starts[idx + 1]);
+                s.push_str(
+                    &self.chunks[idx][range.start.max(start) - start..range.end.min(end) - start],
+                );
+                idx += 1;
+            }
+            Cow::Owned(s)
+        }
+    }
+}
+
+impl<'src> Input<'src> for ChunkedStr<'src> {
+    type Cursor = usize;
+    type Span = SimpleSpan<usize>;
+
+    type Token = char;
+    type MaybeToken = char;
+
+    type Cache = Self;
+
+    #[inline]
+    fn begin(self) -> (Self::Cursor, Self::Cache) {
+        (0, self)
+    }
+
+    #[inline]
+    fn cursor_location(cursor: &Self::Cursor) -> usize {
+        *cursor
+    }
+
+    #[inline(always)]
+    unsafe fn next_maybe(
+        this: &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<Self::MaybeToken> {
+        Self::next(this, cursor)
+    }
+
+    #[inline(always)]
+    unsafe fn span(_this: &mut Self::Cache, range: Range<&Self::Cursor>) -> Self::Span {
+        (*range.start..*range.end).into()
+    }
+}
+
+impl<'src> ExactSizeInput<'src> for ChunkedStr<'src> {
+    #[inline(always)]
+    unsafe fn span_from(this: &mut Self::Cache, range: RangeFrom<&Self::Cursor>) -> Self::Span {
+        (*range.start..this.len()).into()
+    }
+}
+
+impl<'src> ValueInput<'src> for ChunkedStr<'src> {
+    #[inline]
+    unsafe fn next(this: &mut Self::Cache, cursor: &mut Self::Cursor) -> Option<Self::Token> {
+        if *cursor < this.len() {
+            let idx = this.chunk_at(*cursor);
+            let c = this.chunks[idx][*cursor - this.starts[idx]..]
+                .chars()
+                .next()
+                .unwrap();
+            *cursor += c.len_utf8();
+            Some(c)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'src> SliceInput<'src> for ChunkedStr<'src> {
+    type Slice = Cow<'src, str>;
+
+    #[inline]
+    fn full_slice(this: &mut Self::Cache) -> Self::Slice {
+        let len = this.len();
+        this.slice_range(0..len)
+    }
+
+    #[inline]
+    unsafe fn slice(this: &mut Self::Cache, range: Range<&Self::Cursor>) -> Self::Slice {
+        this.slice_range(*range.start..*range.end)
+    }
+
+    #[inline]
+    unsafe fn slice_from(this: &mut Self::Cache, from: RangeFrom<&Self::Cursor>) -> Self::Slice {
+        let len = this.len();
+        this.slice_range(*from.start..len)
+    }
+}
+
+/// An input over bytes that are expected to be UTF-8 text, validated lazily as they are read.
+///
+/// This allows very large inputs, such as memory-mapped files (see the `memmap2` feature), to be parsed as text without
+/// first validating (or even reading) all of the bytes. The input behaves like a [`&str`], producing [`char`]s and
+/// [`&str`] slices, with cursors and spans measured in bytes.
+///
+/// If invalid UTF-8 is encountered, the input ends at that point and retains an [`InvalidUtf8`] error: use
+/// [`Parser::with_lex_errors`] to report it. Alternatively, [`Utf8Input::lossy`] replaces invalid bytes instead.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, input::Utf8Input};
+/// let words = text::ident::<_, _, extra::Err<Rich<char>>>()
+///     .padded()
+///     .repeated()
+///     .collect::<Vec<_>>()
+///     .then_ignore(end())
+///     .with_lex_errors(|err, span| Rich::custom(span, err));
+///
+/// assert_eq!(
+///     words.parse(Utf8Input::new("héllo wörld".as_bytes())).into_result(),
+///     Ok(vec!["héllo", "wörld"]),
+/// );
+///
+/// let errs = words.parse(Utf8Input::new(b"hello \xFFworld")).into_errors();
+/// assert_eq!(errs, vec![Rich::custom((6..6).into(), "invalid UTF-8 at byte offset 6")]);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Utf8Input<'src> {
+    bytes: &'src [u8],
+    lossy: bool,
+}
+
+impl<'src> Utf8Input<'src> {
+    /// Create a new input from bytes that are expected to be UTF-8 text.
+    pub fn new(bytes: &'src [u8]) -> Self {
+        Self {
+            bytes,
+            lossy: false,
+        }
+    }
+
+    /// Create a new input from bytes that are expected to be UTF-8 text, producing
+    /// [`char::REPLACEMENT_CHARACTER`] for each byte that is not valid UTF-8 instead of ending the input.
+    ///
+    /// Cursors and spans remain relative to the original bytes. Since a [`&str`] cannot contain invalid UTF-8, slices
+    /// of the input are cut short at the first invalid byte they cover.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, input::Utf8Input};
+    /// let chars = any::<_, extra::Err<Rich<char>>>()
+    ///     .map_with(|c, e| (c, e.span()))
+    ///     .repeated()
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     chars.parse(Utf8Input::lossy(b"a\xFF\xC3\xA9")).into_result(),
+    ///     Ok(vec![('a', (0..1).into()), ('\u{FFFD}', (1..2).into()), ('é', (2..4).into())]),
+    /// );
+    /// ```
+    pub fn lossy(bytes: &'src [u8]) -> Self {
+        Self { bytes, lossy: true }
+    }
+}
+
+#[cfg(feature = "memmap2")]
+impl<'src> From<&'src memmap2::Mmap> for Utf8Input<'src> {
+    fn from(mmap: &'src memmap2::Mmap) -> Self {
+        Self::new(mmap)
+    }
+}
+
+/// The error retained by a [`Utf8Input`] when it encounters bytes that are not valid UTF-8.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct InvalidUtf8 {
+    /// The byte offset of the first invalid byte.
+    pub offset: usize,
+}
+
+impl fmt::Display for InvalidUtf8 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid UTF-8 at byte offset {}", self.offset)
+    }
+}
+
+impl<'src> Input<'src> for Utf8Input<'src> {
+    type Cursor = usize;
+    type Span = SimpleSpan<usize>;
+
+    type Token = char;
+    type MaybeToken = char;
+
+    type Cache = (&'src [u8], Option<InvalidUtf8>, bool);
+
+    #[inline]
+    fn begin(self) -> (Self::Cursor, Self::Cache) {
+        (0, (self.bytes, None, self.lossy))
+    }
+
+    #[inline]
+    fn cursor_location(cursor: &Self::Cursor) -> usize {
+        *cursor
+    }
+
+    #[inline(always)]
+    unsafe fn next_maybe(
+        this: &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<Self::MaybeToken> {
+        Self::next(this, cursor)
+    }
+
+    #[inline(always)]
+    unsafe fn span(_this: &mut Self::Cache, range: Range<&Self::Cursor>) -> Self::Span {
+        (*range.start..*range.end).into()
+    }
+}
+
+impl<'src> ExactSizeInput<'src> for Utf8Input<'src> {
+    #[inline(always)]
+    unsafe fn span_from(
+        (bytes, _, _): &mut Self::Cache,
+        range: RangeFrom<&Self::Cursor>,
+    ) -> Self::Span {
+        (*range.start..bytes.len()).into()
+    }
+}
+
+impl<'src> ValueInput<'src> for Utf8Input<'src> {
+    #[inline]
+    unsafe fn next(
+        (bytes, error, lossy): &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<Self::Token> {
+        let first = *bytes.get(*cursor)?;
+        if first < 0x80 {
+            *cursor += 1;
+            return Some(first as char);
+        }
+
+        // Validate just the bytes of this character
+        let width = match first {
+            0xC2..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF4 => 4,
+            _ => 0,
+        };
+        match bytes
+            .get(*cursor..*cursor + width)
+            .and_then(|c| core::str::from_utf8(c).ok())
+            .and_then(|c| c.chars().next())
+        {
+            Some(c) if width > 0 => {
+                *cursor += width;
+                Some(c)
+            }
+            _ if *lossy => {
+                *cursor += 1;
+                Some(char::REPLACEMENT_CHARACTER)
+            }
+            _ => {
+                *error = Some(InvalidUtf8 { offset: *cursor });
+                None
+            }
+        }
+    }
+}
+
+impl<'src> FallibleInput<'src> for Utf8Input<'src> {
+    type LexError = InvalidUtf8;
+
+    #[inline]
+    fn lex_error((_, error, _): &Self::Cache) -> Option<(Self::Cursor, &Self::LexError)> {
+        error.as_ref().map(|err| (err.offset, err))
+    }
+}
+
+impl<'src> SliceInput<'src> for Utf8Input<'src> {
+    type Slice = &'src str;
+
+    /// Only the valid prefix of the input is produced, which requires validating the whole input.
+    #[inline]
+    fn full_slice((bytes, _, _): &mut Self::Cache) -> Self::Slice {
+        valid_prefix(bytes)
+    }
+
+    #[inline(always)]
+    unsafe fn slice(
+        (bytes, _, lossy): &mut Self::Cache,
+        range: Range<&Self::Cursor>,
+    ) -> Self::Slice {
+        let bytes = &bytes[*range.start..*range.end];
+        if *lossy {
+            valid_prefix(bytes)
+        } else {
+            // SAFETY: Unless the input is lossy, cursors only ever advance over bytes that have been validated
+            core::str::from_utf8_unchecked(bytes)
+        }
+    }
+
+    #[inline]
+    unsafe fn slice_from(
+        (bytes, _, _): &mut Self::Cache,
+        from: RangeFrom<&Self::Cursor>,
+    ) -> Self::Slice {
+        // Only the rest of the input needs validating, not everything before it too
+        valid_prefix(&bytes[(*from.start).min(bytes.len())..])
+    }
+}
+
+impl Sealed for Utf8Input<'_> {}
+impl<'src> StrInput<'src, char> for Utf8Input<'src> {}
+
+// The longest prefix of the given bytes that is valid UTF-8
+fn valid_prefix(bytes: &[u8]) -> &str {
+    match core::str::from_utf8(bytes) {
+        Ok(s) => s,
+        // SAFETY: `valid_up_to` is the length of the valid prefix
+        Err(e) => unsafe { core::str::from_utf8_unchecked(&bytes[..e.valid_up_to()]) },
+    }
+}
+
+/// A file that has been memory-mapped, so that it can be parsed without first reading it into memory.
+///
+/// The file can be parsed as bytes with [`MappedFile::bytes`], or as text with [`MappedFile::text`] (which reports
+/// invalid UTF-8 as an error) or [`MappedFile::text_lossy`] (which replaces it). In every case, spans are byte offsets
+/// into the file.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, input::MappedFile};
+/// # let path = std::env::temp_dir().join("chumsky_mapped_file_doctest.txt");
+/// # std::fs::write(&path, "hello world").unwrap();
+/// let words = text::ident::<_, _, extra::Err<Rich<char>>>()
+///     .map_with(|word, e| (word, e.span()))
+///     .padded()
+///     .repeated()
+///     .collect::<Vec<_>>()
+///     .with_lex_errors(|err, span| Rich::custom(span, err));
+///
+/// // SAFETY: nothing else modifies the file while it is mapped
+/// let file = unsafe { MappedFile::open(&path) }.unwrap();
+/// assert_eq!(
+///     words.parse(file.text()).into_result(),
+///     Ok(vec![("hello", (0..5).into()), ("world", (6..11).into())]),
+/// );
+/// # drop(file);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[cfg(feature = "memmap2")]
+#[derive(Debug)]
+pub struct MappedFile {
+    mmap: memmap2::Mmap,
+}
+
+#[cfg(feature = "memmap2")]
+impl MappedFile {
+    /// Open and memory-map the file at the given path.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated (by this or any other process) while it is mapped, since the
+    /// contents of the mapping would change underneath the parser. See [`memmap2::Mmap::map`].
+    pub unsafe fn open<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        Ok(Self {
+            mmap: memmap2::Mmap::map(&file)?,
+        })
+    }
+
+    /// Get an input over the raw bytes of the file.
+    pub fn bytes(&self) -> &[u8] {
+        &self.mmap
+    }
+
+    /// Get an input over the file as UTF-8 text. The input ends at the first invalid byte, which can be reported with
+    /// [`Parser::with_lex_errors`]. See [`Utf8Input::new`].
+    pub fn text(&self) -> Utf8Input<'_> {
+        Utf8Input::new(&self.mmap)
+    }
+
+    /// Get an input over the file as UTF-8 text, replacing any invalid bytes with [`char::REPLACEMENT_CHARACTER`]. See
+    /// [`Utf8Input::lossy`].
+    pub fn text_lossy(&self) -> Utf8Input<'_> {
+        Utf8Input::lossy(&self.mmap)
+    }
+}
+
+#[cfg(feature = "memmap2")]
+impl From<memmap2::Mmap> for MappedFile {
+    fn from(mmap: memmap2::Mmap) -> Self {
+        Self { mmap }
+    }
+}
+
+/// Represents a location in an input that can be rewound to.
+///
+/// Checkpoints can be created with [`InputRef::save`] and rewound to with [`InputRef::rewind`].
+pub struct Checkpoint<'src, 'parse, I: Input<'src>, C> {
+    cursor: Cursor<'src, 'parse, I>,
+    pub(crate) err_count: usize,
+    pub(crate) warn_count: usize,
+    pub(crate) note_count: usize,
+    pub(crate) recovery_count: usize,
+    follow_count: usize,
+    pub(crate) inspector: C,
+    phantom: PhantomData<fn(&'parse ()) -> &'parse ()>, // Invariance
+}
+
+impl<'src, 'parse, I: Input<'src>, C> Checkpoint<'src, 'parse, I, C> {
+    /// Get the [`Cursor`] that this checkpoint corresponds to.
+    pub fn cursor(&self) -> &Cursor<'src, 'parse, I> {
+        &self.cursor
+    }
+
+    /// Get the [`Checkpoint`][Inspector::Checkpoint] that this marker corresponds to.
+    pub fn inspector(&self) -> &C {
+        &self.inspector
+    }
+
+    // The same checkpoint, but with a different inspector checkpoint
+    pub(crate) fn with_inspector<D>(&self, inspector: D) -> Checkpoint<'src, 'parse, I, D> {
+        Checkpoint {
+            cursor: self.cursor.clone(),
+            err_count: self.err_count,
+            warn_count: self.warn_count,
+            note_count: self.note_count,
+            recovery_count: self.recovery_count,
+            follow_count: self.follow_count,
+            inspector,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'src, I: Input<'src>, C: Clone> Clone for Checkpoint<'src, '_, I, C> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self {
+            cursor: self.cursor.clone(),
+            err_count: self.err_count,
+            warn_count: self.warn_count,
+            note_count: self.note_count,
+            recovery_count: self.recovery_count,
+            follow_count: self.follow_count,
+            inspector: self.inspector.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// Represents a location in an input.
+///
+/// If you to rewind to an old input location, see [`Checkpoint`].
+#[repr(transparent)]
+pub struct Cursor<'src, 'parse, I: Input<'src>> {
+    pub(crate) inner: I::Cursor,
+    phantom: PhantomData<fn(&'parse ()) -> &'parse ()>, // Invariance
+}
+
+impl<'src, I: Input<'src>> Cursor<'src, '_, I> {
+    /// Get the input's internal cursor.
+    pub fn inner(&self) -> &I::Cursor {
+        &self.inner
+    }
+}
+
+impl<'src, I: Input<'src>> Clone for Cursor<'src, '_, I> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'src, I: Input<'src>> Eq for Cursor<'src, '_, I> {}
+impl<'src, I: Input<'src>> PartialEq for Cursor<'src, '_, I> {
     fn eq(&self, other: &Self) -> bool {
         I::cursor_location(&self.inner)
             .cmp(&I::cursor_location(&other.inner))
@@ -1025,7 +2523,7 @@ impl<'src, I: Input<'src>> PartialEq for Cursor<'src, '_, I> {
 
 impl<'src, I: Input<'src>> PartialOrd for Cursor<'src, '_, I> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(I::cursor_location(&self.inner).cmp(&I::cursor_location(&other.inner)))
+        Some(self.cmp(other))
     }
 }
 
@@ -1035,38 +2533,212 @@ impl<'src, I: Input<'src>> Ord for Cursor<'src, '_, I> {
     }
 }
 
-pub(crate) struct Errors<T, E> {
-    pub(crate) alt: Option<Located<T, E>>,
-    pub(crate) secondary: Vec<Located<T, E>>,
-}
+// The secondary errors, warnings, notes and recoveries of a finished parse
+pub(crate) type Diagnostics<E> = (Vec<E>, Vec<E>, Vec<E>, Vec<RecoveryEvent>);
+
+pub(crate) struct Errors<T, E> {
+    pub(crate) alt: Option<Located<T, E>>,
+    /// The error of the last attempt to continue a repetition, which describes what else could have followed it. This
+    /// is merged into the next alt error if that error occurs at the same position.
+    pub(crate) follow: Option<Located<T, E>>,
+    /// The number of follow errors that have been kept, so that rewinding can tell whether the current one was kept by
+    /// a parser that is being backtracked over.
+    pub(crate) follow_count: usize,
+    pub(crate) secondary: Vec<Located<T, E>>,
+    /// Errors that were downgraded to warnings, and so don't cause the parse to fail. See [`Emitter::warn`].
+    pub(crate) warnings: Vec<Located<T, E>>,
+    /// Informational diagnostics, which don't cause the parse to fail. See [`MapExtra::emit_note`].
+    pub(crate) notes: Vec<Located<T, E>>,
+    /// The recoveries made by [`Parser::recover_with`]. See [`ParseResult::recoveries`].
+    pub(crate) recoveries: Vec<RecoveryEvent>,
+    /// Whether the last parser to fail did so after passing a cut, and so must not be backtracked out of.
+    /// See [`Parser::cut`].
+    pub(crate) cut: bool,
+}
+
+impl<T, E> Errors<T, E> {
+    /// Returns a slice of the secondary errors (if any) have been emitted since the given checkpoint was created.
+    #[inline]
+    pub(crate) fn secondary_errors_since(&mut self, err_count: usize) -> &mut [Located<T, E>] {
+        self.secondary.get_mut(err_count..).unwrap_or(&mut [])
+    }
+}
+
+impl<T, E> Default for Errors<T, E> {
+    fn default() -> Self {
+        Self {
+            alt: None,
+            follow: None,
+            follow_count: 0,
+            secondary: Vec::new(),
+            warnings: Vec::new(),
+            notes: Vec::new(),
+            recoveries: Vec::new(),
+            cut: false,
+        }
+    }
+}
+
+/// The [`ParseOptions`] of a parse, along with the progress made towards their limits.
+pub(crate) struct Limits<T> {
+    pub(crate) options: ParseOptions,
+    /// Whether tokens must be counted or checked for cancellation as they are read.
+    active: bool,
+    tokens: usize,
+    depth: usize,
+    /// The location of the end of the region that parsing is confined to, if any. See [`Parser::nested_in_region`].
+    end: Option<usize>,
+    /// The location that the parser last committed to, if any. See [`Parser::commit`].
+    committed: Option<usize>,
+    /// Why (and where) parsing was stopped, if it has been.
+    pub(crate) halt: Option<(Halt, T)>,
+}
+
+impl<T> Limits<T> {
+    pub(crate) fn new(options: ParseOptions) -> Self {
+        Self {
+            active: options.token_budget.is_some() || options.cancellation.is_some(),
+            options,
+            tokens: 0,
+            depth: 0,
+            end: None,
+            committed: None,
+            halt: None,
+        }
+    }
+
+    fn set_end(&mut self, end: Option<usize>) -> Option<usize> {
+        let old = core::mem::replace(&mut self.end, end);
+        self.active = self.options.token_budget.is_some()
+            || self.options.cancellation.is_some()
+            || self.end.is_some()
+            || self.halt.is_some();
+        old
+    }
+
+    fn halt(&mut self, reason: Halt, at: T) {
+        if self.halt.is_none() {
+            self.halt = Some((reason, at));
+            self.active = true;
+        }
+    }
+}
+
+/// Buffers used while parsing that can be reused from one parse to the next. See [`Parser::parse_with_scratch`].
+///
+/// Parsing allocates space to keep track of errors, warnings, notes and recoveries as it goes. When many small inputs
+/// are parsed one after another, holding on to a `ParseScratch` and passing it to each parse allows this space to be
+/// reused rather than allocated afresh each time. Nothing else carries over between parses: the buffers are empty
+/// whenever they're not in use.
+///
+/// The buffers don't refer to the type of input or error that they were used with, so the same `ParseScratch` can be
+/// used with inputs that don't live as long as it does.
+#[derive(Default)]
+pub struct ParseScratch {
+    secondary: RawBuf,
+    warnings: RawBuf,
+    notes: RawBuf,
+    recoveries: Vec<RecoveryEvent>,
+}
+
+impl ParseScratch {
+    /// Create a new set of buffers. No space is allocated until they are first used.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+// The allocation of an empty `Vec`, kept without its element type so that it can be reused by parses whose errors
+// borrow from different inputs. It is only reused for elements of the same size and alignment.
+#[derive(Default)]
+struct RawBuf {
+    alloc: Option<(core::ptr::NonNull<u8>, usize, core::alloc::Layout)>,
+}
+
+// SAFETY: A `RawBuf` owns its allocation, which holds no values
+unsafe impl Send for RawBuf {}
+// SAFETY: As above
+unsafe impl Sync for RawBuf {}
+
+impl RawBuf {
+    fn take<T>(&mut self) -> Vec<T> {
+        match self.alloc {
+            Some((ptr, cap, layout)) if layout == core::alloc::Layout::new::<T>() => {
+                self.alloc = None;
+                // SAFETY: The allocation was made by a `Vec` with this capacity, of elements with the same size and
+                // alignment as `T`, and holds no elements
+                unsafe { Vec::from_raw_parts(ptr.as_ptr().cast(), 0, cap) }
+            }
+            _ => Vec::new(),
+        }
+    }
 
-impl<T, E> Errors<T, E> {
-    /// Returns a slice of the secondary errors (if any) have been emitted since the given checkpoint was created.
-    #[inline]
-    pub(crate) fn secondary_errors_since(&mut self, err_count: usize) -> &mut [Located<T, E>] {
-        self.secondary.get_mut(err_count..).unwrap_or(&mut [])
+    fn put<T>(&mut self, mut buf: Vec<T>) {
+        buf.clear();
+        if core::mem::size_of::<T>() == 0 || buf.capacity() == 0 {
+            return;
+        }
+        let mut buf = core::mem::ManuallyDrop::new(buf);
+        *self = Self {
+            alloc: core::ptr::NonNull::new(buf.as_mut_ptr().cast())
+                .map(|ptr| (ptr, buf.capacity(), core::alloc::Layout::new::<T>())),
+        };
     }
 }
 
-impl<T, E> Default for Errors<T, E> {
-    fn default() -> Self {
-        Self {
-            alt: None,
-            secondary: Vec::new(),
+impl Drop for RawBuf {
+    fn drop(&mut self) {
+        if let Some((ptr, cap, layout)) = self.alloc {
+            // SAFETY: The allocation was made by a `Vec` with this capacity, so this is the layout it was made with
+            unsafe {
+                alloc::alloc::dealloc(
+                    ptr.as_ptr(),
+                    core::alloc::Layout::from_size_align_unchecked(
+                        layout.size() * cap,
+                        layout.align(),
+                    ),
+                )
+            }
         }
     }
 }
 
-/// Internal type representing the owned parts of an input - used at the top level by a call to
-/// `parse`.
-pub(crate) struct InputOwn<'src, 's, I: Input<'src>, E: ParserExtra<'src, I>> {
+/// The owned parts of an input (its cache, errors, state and context), as used at the top level by a call to
+/// [`Parser::parse`].
+///
+/// Most users never need to touch this type. It exists for benchmark harnesses, test doubles and other tooling that
+/// want to run an individual parser against a prepared [`InputRef`] (perhaps from a specific position in the input)
+/// and inspect the result, including the error it would report, without performing a full end-to-end parse.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, input::InputOwn};
+/// let int = text::int::<_, _, extra::Err<Rich<char>>>(10);
+///
+/// let mut own: InputOwn<_, extra::Err<Rich<char>>> = InputOwn::new("x = 42;");
+/// // SAFETY: byte offset 4 of the input is a character boundary
+/// let mut inp = unsafe { own.as_ref_at(4) };
+///
+/// assert_eq!(inp.parse(&int), Ok("42"));
+/// assert_eq!(*inp.cursor().inner(), 6);
+/// // The error that would have been reported, had this been a full parse
+/// let err = inp.parse(&int).unwrap_err();
+/// assert_eq!(err.span(), &SimpleSpan::from(6..7));
+/// ```
+pub struct InputOwn<'src, 's, I: Input<'src>, E: ParserExtra<'src, I>> {
     pub(crate) start: I::Cursor,
     pub(crate) cache: I::Cache,
     pub(crate) errors: Errors<I::Cursor, E::Error>,
+    pub(crate) limits: Limits<I::Cursor>,
     pub(crate) state: MaybeMut<'s, E::State>,
     pub(crate) ctx: E::Context,
     #[cfg(feature = "memoization")]
     pub(crate) memos: HashMap<(usize, usize), Option<Located<I::Cursor, E::Error>>>,
+    #[cfg(feature = "memoization")]
+    pub(crate) memo_scope: usize,
+    #[cfg(feature = "memoization")]
+    pub(crate) memo_scopes: MemoScopes<'src>,
 }
 
 impl<'src, 's, I, E> InputOwn<'src, 's, I, E>
@@ -1074,8 +2746,8 @@ where
     I: Input<'src>,
     E: ParserExtra<'src, I>,
 {
-    #[cfg_attr(not(test), allow(dead_code))]
-    pub(crate) fn new(input: I) -> InputOwn<'src, 's, I, E>
+    /// Prepare an input for parsing, with default state and context.
+    pub fn new(input: I) -> InputOwn<'src, 's, I, E>
     where
         E::State: Default,
         E::Context: Default,
@@ -1085,14 +2757,20 @@ where
             start,
             cache,
             errors: Errors::default(),
+            limits: Limits::new(ParseOptions::default()),
             state: MaybeMut::Val(E::State::default()),
             ctx: E::Context::default(),
             #[cfg(feature = "memoization")]
             memos: HashMap::default(),
+            #[cfg(feature = "memoization")]
+            memo_scope: next_memo_scope(),
+            #[cfg(feature = "memoization")]
+            memo_scopes: MemoScopes::default(),
         }
     }
 
-    pub(crate) fn new_state(input: I, state: &'s mut E::State) -> InputOwn<'src, 's, I, E>
+    /// Prepare an input for parsing, with the given state and default context.
+    pub fn new_state(input: I, state: &'s mut E::State) -> InputOwn<'src, 's, I, E>
     where
         E::Context: Default,
     {
@@ -1101,32 +2779,167 @@ where
             start,
             cache,
             errors: Errors::default(),
+            limits: Limits::new(ParseOptions::default()),
             state: MaybeMut::Ref(state),
             ctx: E::Context::default(),
             #[cfg(feature = "memoization")]
             memos: HashMap::default(),
+            #[cfg(feature = "memoization")]
+            memo_scope: next_memo_scope(),
+            #[cfg(feature = "memoization")]
+            memo_scopes: MemoScopes::default(),
+        }
+    }
+
+    /// Apply the given [`ParseOptions`] to parses of this input.
+    pub fn with_options(self, options: ParseOptions) -> Self {
+        Self {
+            limits: Limits::new(options),
+            ..self
         }
     }
 
-    pub(crate) fn as_ref_start<'parse>(&'parse mut self) -> InputRef<'src, 'parse, I, E> {
+    /// Get an [`InputRef`] positioned at the start of the input.
+    pub fn as_ref_start<'parse>(&'parse mut self) -> InputRef<'src, 'parse, I, E> {
         InputRef {
             cursor: self.start.clone(),
             cache: &mut self.cache,
             errors: &mut self.errors,
+            limits: &mut self.limits,
+            state: &mut self.state,
+            ctx: &self.ctx,
+            #[cfg(feature = "memoization")]
+            memos: &mut self.memos,
+            #[cfg(feature = "memoization")]
+            memo_scope: self.memo_scope,
+            #[cfg(feature = "memoization")]
+            memo_scopes: &mut self.memo_scopes,
+        }
+    }
+
+    /// Get an [`InputRef`] positioned at the given cursor.
+    ///
+    /// # Safety
+    ///
+    /// The cursor must be valid for this input, as if it had been generated by it. For example, for [`&str`] the
+    /// cursor is a byte offset, which must lie on a character boundary and be no greater than the length of the input.
+    pub unsafe fn as_ref_at<'parse>(
+        &'parse mut self,
+        cursor: I::Cursor,
+    ) -> InputRef<'src, 'parse, I, E> {
+        InputRef {
+            cursor,
+            cache: &mut self.cache,
+            errors: &mut self.errors,
+            limits: &mut self.limits,
             state: &mut self.state,
             ctx: &self.ctx,
             #[cfg(feature = "memoization")]
             memos: &mut self.memos,
+            #[cfg(feature = "memoization")]
+            memo_scope: self.memo_scope,
+            #[cfg(feature = "memoization")]
+            memo_scopes: &mut self.memo_scopes,
         }
     }
 
-    pub(crate) fn into_errs(self) -> Vec<E::Error> {
+    /// Take the secondary errors that were emitted while parsing, such as those generated during error recovery.
+    ///
+    /// The primary error of a failed parse is not included: it is returned by [`InputRef::parse`].
+    pub fn into_errs(self) -> Vec<E::Error> {
         self.errors
             .secondary
             .into_iter()
             .map(|err| err.err)
             .collect()
     }
+
+    // The secondary errors, warnings and notes that were emitted while parsing, along with the recoveries that were
+    // made
+    pub(crate) fn into_diagnostics(self) -> Diagnostics<E::Error> {
+        let errs = self.errors.secondary.into_iter().map(|err| err.err);
+        let warnings = self.errors.warnings.into_iter().map(|err| err.err);
+        let notes = self.errors.notes.into_iter().map(|err| err.err);
+        (
+            errs.collect(),
+            warnings.collect(),
+            notes.collect(),
+            self.errors.recoveries,
+        )
+    }
+
+    // Parse using the buffers of the given scratch space, rather than allocating new ones
+    pub(crate) fn with_scratch(mut self, scratch: &mut ParseScratch) -> Self {
+        self.errors.secondary = scratch.secondary.take();
+        self.errors.warnings = scratch.warnings.take();
+        self.errors.notes = scratch.notes.take();
+        self.errors.recoveries = core::mem::take(&mut scratch.recoveries);
+        self
+    }
+
+    // As with `into_diagnostics`, but handing the buffers back to the scratch space for reuse. A buffer that holds
+    // diagnostics becomes the output instead, since the scratch space would otherwise need to allocate a new one.
+    pub(crate) fn into_diagnostics_with_scratch(
+        self,
+        scratch: &mut ParseScratch,
+    ) -> Diagnostics<E::Error> {
+        fn reuse<T, E>(raw: &mut RawBuf, buf: Vec<Located<T, E>>) -> Vec<E> {
+            if buf.is_empty() {
+                raw.put(buf);
+                Vec::new()
+            } else {
+                buf.into_iter().map(|err| err.err).collect()
+            }
+        }
+
+        let errors = self.errors;
+        let recoveries = if errors.recoveries.is_empty() {
+            scratch.recoveries = errors.recoveries;
+            Vec::new()
+        } else {
+            errors.recoveries
+        };
+        (
+            reuse(&mut scratch.secondary, errors.secondary),
+            reuse(&mut scratch.warnings, errors.warnings),
+            reuse(&mut scratch.notes, errors.notes),
+            recoveries,
+        )
+    }
+}
+
+/// Produce an identifier for a new memoization scope, distinct from all others. See [`InputRef::memo_scope`].
+#[cfg(feature = "memoization")]
+pub(crate) fn next_memo_scope() -> usize {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    NEXT.fetch_add(1, Ordering::Relaxed)
+}
+
+/// The scopes of a parse that memo tables hold results for, so that the results can be discarded once the parse is
+/// finished. See [`Parser::packrat`].
+#[cfg(feature = "memoization")]
+#[derive(Default)]
+pub(crate) struct MemoScopes<'src> {
+    tables: Vec<(RefC<DynClearScope<'src>>, usize)>,
+}
+
+#[cfg(feature = "memoization")]
+impl<'src> MemoScopes<'src> {
+    /// Record that the given table holds results for the given scope.
+    pub(crate) fn register(&mut self, table: RefC<DynClearScope<'src>>, scope: usize) {
+        self.tables.push((table, scope));
+    }
+}
+
+#[cfg(feature = "memoization")]
+impl Drop for MemoScopes<'_> {
+    fn drop(&mut self) {
+        for (table, scope) in self.tables.drain(..) {
+            table.clear_scope(scope);
+        }
+    }
 }
 
 /// Internal type representing an input as well as all the necessary context for parsing.
@@ -1134,10 +2947,17 @@ pub struct InputRef<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> {
     cursor: I::Cursor,
     pub(crate) cache: &'parse mut I::Cache,
     pub(crate) errors: &'parse mut Errors<I::Cursor, E::Error>,
+    pub(crate) limits: &'parse mut Limits<I::Cursor>,
     pub(crate) state: &'parse mut E::State,
     pub(crate) ctx: &'parse E::Context,
     #[cfg(feature = "memoization")]
     pub(crate) memos: &'parse mut HashMap<(usize, usize), Option<Located<I::Cursor, E::Error>>>,
+    /// Identifies the input and context of this parse, so that packrat parsers only reuse results that were produced
+    /// for the same input and context. See [`Parser::packrat`].
+    #[cfg(feature = "memoization")]
+    pub(crate) memo_scope: usize,
+    #[cfg(feature = "memoization")]
+    pub(crate) memo_scopes: &'parse mut MemoScopes<'src>,
 }
 
 impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'parse, I, E> {
@@ -1157,14 +2977,55 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
             state: self.state,
             ctx: new_ctx,
             errors: self.errors,
+            limits: self.limits,
             #[cfg(feature = "memoization")]
             memos: self.memos,
+            #[cfg(feature = "memoization")]
+            memo_scope: next_memo_scope(),
+            #[cfg(feature = "memoization")]
+            memo_scopes: &mut *self.memo_scopes,
         };
         let res = f(&mut new_inp);
         self.cursor = new_inp.cursor;
         res
     }
 
+    // Run a sub-parse with a different error type. Along with the result, this returns whether the sub-parse was
+    // clean: that is, whether it emitted no errors, warnings or notes and made no recoveries (these are discarded).
+    #[inline]
+    pub(crate) fn with_errors<EM, O>(
+        &mut self,
+        f: impl FnOnce(&mut InputRef<'src, '_, I, EM>) -> O,
+    ) -> (O, bool)
+    where
+        EM: ParserExtra<'src, I, State = E::State, Context = E::Context>,
+    {
+        let mut errors = Errors::default();
+        #[cfg(feature = "memoization")]
+        let mut memos = HashMap::default();
+        let mut new_inp = InputRef {
+            cursor: self.cursor.clone(),
+            cache: self.cache,
+            state: self.state,
+            ctx: self.ctx,
+            errors: &mut errors,
+            limits: self.limits,
+            #[cfg(feature = "memoization")]
+            memos: &mut memos,
+            #[cfg(feature = "memoization")]
+            memo_scope: next_memo_scope(),
+            #[cfg(feature = "memoization")]
+            memo_scopes: &mut *self.memo_scopes,
+        };
+        let res = f(&mut new_inp);
+        self.cursor = new_inp.cursor;
+        let clean = errors.secondary.is_empty()
+            && errors.warnings.is_empty()
+            && errors.notes.is_empty()
+            && errors.recoveries.is_empty();
+        (res, clean)
+    }
+
     #[inline]
     pub(crate) fn with_state<'sub_parse, S, O>(
         &'sub_parse mut self,
@@ -1181,8 +3042,13 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
             state: new_state,
             ctx: self.ctx,
             errors: self.errors,
+            limits: self.limits,
             #[cfg(feature = "memoization")]
             memos: self.memos,
+            #[cfg(feature = "memoization")]
+            memo_scope: next_memo_scope(),
+            #[cfg(feature = "memoization")]
+            memo_scopes: &mut *self.memo_scopes,
         };
         let res = f(&mut new_inp);
         self.cursor = new_inp.cursor;
@@ -1209,12 +3075,48 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
             state: self.state,
             ctx: self.ctx,
             errors: self.errors,
+            limits: self.limits,
             #[cfg(feature = "memoization")]
             memos,
+            #[cfg(feature = "memoization")]
+            memo_scope: next_memo_scope(),
+            #[cfg(feature = "memoization")]
+            memo_scopes: &mut *self.memo_scopes,
         };
         f(&mut new_inp)
     }
 
+    /// Confine parsing to the part of the input before `end`, which is treated as if it were the end of the input.
+    #[inline]
+    pub(crate) fn with_region<O>(
+        &mut self,
+        end: &I::Cursor,
+        f: impl FnOnce(&mut InputRef<'src, '_, I, E>) -> O,
+    ) -> O {
+        let outer_end = self.limits.set_end(Some(I::cursor_location(end)));
+        // Results from within the region may not hold outside of it, so they're kept apart
+        #[cfg(feature = "memoization")]
+        let mut memos = HashMap::default();
+        let mut new_inp = InputRef {
+            cursor: self.cursor.clone(),
+            cache: &mut *self.cache,
+            state: &mut *self.state,
+            ctx: self.ctx,
+            errors: &mut *self.errors,
+            limits: &mut *self.limits,
+            #[cfg(feature = "memoization")]
+            memos: &mut memos,
+            #[cfg(feature = "memoization")]
+            memo_scope: next_memo_scope(),
+            #[cfg(feature = "memoization")]
+            memo_scopes: &mut *self.memo_scopes,
+        };
+        let res = f(&mut new_inp);
+        self.cursor = new_inp.cursor;
+        self.limits.set_end(outer_end);
+        res
+    }
+
     /// Get the internal cursor of the input at this moment in time.
     ///
     /// Can be used for generating spans or slices. See [`InputRef::span_from`] and [`InputRef::slice`].
@@ -1239,6 +3141,10 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
         Checkpoint {
             cursor,
             err_count: self.errors.secondary.len(),
+            warn_count: self.errors.warnings.len(),
+            note_count: self.errors.notes.len(),
+            recovery_count: self.errors.recoveries.len(),
+            follow_count: self.errors.follow_count,
             inspector,
             phantom: PhantomData,
         }
@@ -1253,10 +3159,48 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
         checkpoint: Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
     ) {
         self.errors.secondary.truncate(checkpoint.err_count);
+        self.errors.warnings.truncate(checkpoint.warn_count);
+        self.errors.notes.truncate(checkpoint.note_count);
+        self.errors.recoveries.truncate(checkpoint.recovery_count);
+        if matches!(self.limits.committed, Some(committed) if I::cursor_location(&checkpoint.cursor.inner) < committed)
+        {
+            // The input may no longer have the tokens that the parser is about to read again
+            self.limits
+                .halt(Halt::Discarded, checkpoint.cursor.inner.clone());
+        }
+        // Rewinding means that the failure being rewound over has been backtracked from, so a cut within it no longer
+        // applies to the parsers that enclose it. A halt can't be backtracked from, though.
+        self.errors.cut = self.limits.halt.is_some();
+        // What could have continued a repetition within the input being rewound over is no longer relevant
+        if self.errors.follow_count != checkpoint.follow_count {
+            self.errors.follow = None;
+        }
         self.state.on_rewind(&checkpoint);
         self.cursor = checkpoint.cursor.inner;
     }
 
+    // Rewind after a failed attempt to continue a repetition (or to parse something optional), which then ends where
+    // the attempt began. If the attempt failed right there, its error says what else could have come next, and is kept
+    // as the follow error (see `Errors::follow`). An error from further into the input is left to be reported as usual.
+    #[inline]
+    pub(crate) fn rewind_follow(
+        &mut self,
+        checkpoint: Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+    ) {
+        self.rewind(checkpoint);
+        if matches!(&self.errors.alt, Some(alt) if I::cursor_location(&alt.pos) == I::cursor_location(&self.cursor))
+        {
+            self.take_follow();
+        }
+    }
+
+    // Keep the alt error as the follow error, to be merged into whatever fails at the same position next.
+    #[inline]
+    pub(crate) fn take_follow(&mut self) {
+        self.errors.follow = self.errors.alt.take();
+        self.errors.follow_count += 1;
+    }
+
     /// Get a mutable reference to the state associated with the current parse.
     #[inline(always)]
     pub fn state(&mut self) -> &mut E::State {
@@ -1278,7 +3222,7 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
     where
         I: Input<'src>,
     {
-        loop {
+        while self.within_limits() {
             let mut cursor = self.cursor.clone();
             // SAFETY: cursor was generated by previous call to `Input::next`
             let token = unsafe { I::next_maybe(self.cache, &mut cursor) };
@@ -1286,6 +3230,7 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
                 break;
             } else {
                 if let Some(t) = &token {
+                    self.count_token();
                     self.state.on_token(t.borrow());
                 }
                 self.cursor = cursor;
@@ -1293,14 +3238,97 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
         }
     }
 
+    /// Whether another token may be read without going beyond the limits of the parse, halting it if not.
+    #[inline(always)]
+    fn within_limits(&mut self) -> bool {
+        !self.limits.active || self.check_limits()
+    }
+
+    /// Count a token that has been read, if the parse has a token budget to keep to.
+    #[inline(always)]
+    fn count_token(&mut self) {
+        if self.limits.active {
+            self.limits.tokens += 1;
+        }
+    }
+
+    /// Whether the cursor is at the end of the region that parsing is confined to. See [`InputRef::with_region`].
+    #[inline(always)]
+    fn at_region_end(&self) -> bool {
+        matches!(self.limits.end, Some(end) if I::cursor_location(&self.cursor) >= end)
+    }
+
+    #[cold]
+    fn check_limits(&mut self) -> bool {
+        if self.at_region_end() {
+            return false;
+        }
+        let limits = &mut *self.limits;
+        if matches!(&limits.options.cancellation, Some(c) if c.is_cancelled()) {
+            limits.halt(Halt::Cancelled, self.cursor.clone());
+        } else if matches!(limits.options.token_budget, Some(budget) if limits.tokens >= budget) {
+            limits.halt(Halt::TokenBudget, self.cursor.clone());
+        }
+        if limits.halt.is_some() {
+            // Unlike the end of the input (or of a region), a halt must not be backtracked from: a parser that fails
+            // here failed because the parse was halted, not because the input has run out
+            self.errors.cut = true;
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Run a recursive parser, failing instead if doing so would go beyond the recursion limit of the parse.
+    #[inline(always)]
+    pub(crate) fn recurse<O>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<O, ()>,
+    ) -> Result<O, ()> {
+        if let Some(limit) = self.limits.options.recursion_limit {
+            if self.limits.depth >= limit {
+                self.limits.halt(Halt::RecursionLimit, self.cursor.clone());
+                self.errors.cut = true;
+                let span = self.span_since(&self.cursor());
+                let found = self.peek_maybe();
+                // This error explains why parsing stopped, so it takes the place of any other
+                self.errors.alt = Some(Located::at(
+                    self.cursor.clone(),
+                    E::Error::nested_too_deep(found, span),
+                ));
+                return Err(());
+            }
+        }
+        self.limits.depth += 1;
+        let res = f(self);
+        self.limits.depth -= 1;
+        res
+    }
+
+    /// Whether the parse is strict, according to its [`ParseOptions`] or else its state.
+    #[inline]
+    pub(crate) fn is_strict(&self) -> bool
+    where
+        E::State: Strictness<'src, I>,
+    {
+        self.limits
+            .options
+            .strict
+            .unwrap_or_else(|| self.state.is_strict())
+    }
+
     #[inline(always)]
     pub(crate) fn next_inner(&mut self) -> Option<I::Token>
     where
         I: ValueInput<'src>,
     {
+        if !self.within_limits() {
+            return None;
+        }
         // SAFETY: cursor was generated by previous call to `Input::next`
         let token = unsafe { I::next(self.cache, &mut self.cursor) };
         if let Some(t) = &token {
+            self.count_token();
             self.state.on_token(t);
         }
         token
@@ -1308,9 +3336,13 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
 
     #[inline(always)]
     pub(crate) fn next_maybe_inner(&mut self) -> Option<I::MaybeToken> {
+        if !self.within_limits() {
+            return None;
+        }
         // SAFETY: cursor was generated by previous call to `Input::next`
         let token = unsafe { I::next_maybe(self.cache, &mut self.cursor) };
         if let Some(t) = &token {
+            self.count_token();
             self.state.on_token(t.borrow());
         }
         token
@@ -1321,9 +3353,13 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
     where
         I: BorrowInput<'src>,
     {
+        if !self.within_limits() {
+            return None;
+        }
         // SAFETY: cursor was generated by previous call to `Input::next`
         let token = unsafe { I::next_ref(self.cache, &mut self.cursor) };
         if let Some(t) = &token {
+            self.count_token();
             self.state.on_token(t);
         }
         token
@@ -1399,11 +3435,65 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
         self.next_ref_inner()
     }
 
+    #[inline]
+    pub(crate) fn commit(&mut self)
+    where
+        I: CommitInput<'src>,
+    {
+        let loc = I::cursor_location(&self.cursor);
+        self.limits.committed = Some(self.limits.committed.map_or(loc, |c| c.max(loc)));
+        I::commit(self.cache, &self.cursor);
+    }
+
+    // Move the cursor back by one token, returning `false` at the start of the input.
+    #[inline]
+    pub(crate) fn step_back(&mut self) -> bool
+    where
+        I: LookbehindInput<'src>,
+    {
+        // SAFETY: cursor was generated by previous call to `Input::next` or `LookbehindInput::prev_maybe`
+        unsafe { I::prev_maybe(self.cache, &mut self.cursor) }.is_some()
+    }
+
+    /// Peek at a token that has already been consumed, without moving the cursor.
+    ///
+    /// `n = 1` gives the token immediately before the cursor, `n = 2` the one before that, and so on. `n = 0` is
+    /// equivalent to [`InputRef::peek_maybe`]. Returns `None` if fewer than `n` tokens precede the cursor.
+    #[inline]
+    pub fn peek_back(&mut self, n: usize) -> Option<MaybeRef<'src, I::Token>>
+    where
+        I: LookbehindInput<'src>,
+    {
+        if n == 0 {
+            return self.peek_maybe();
+        }
+        let mut cursor = self.cursor.clone();
+        let mut tok = None;
+        for _ in 0..n {
+            // SAFETY: cursor was generated by previous call to `Input::next` or `LookbehindInput::prev_maybe`
+            tok = Some(unsafe { I::prev_maybe(self.cache, &mut cursor) }?);
+        }
+        tok.map(Into::into)
+    }
+
+    // The token that follows the given cursor, without moving to it
+    #[inline(always)]
+    pub(crate) fn token_at(
+        &mut self,
+        at: &Cursor<'src, 'parse, I>,
+    ) -> Option<MaybeRef<'src, I::Token>> {
+        // SAFETY: cursor was generated by previous call to `Input::next`
+        unsafe { I::next_maybe(self.cache, &mut at.inner.clone()).map(Into::into) }
+    }
+
     /// Peek the next token in the input. Returns `None` if the end of the input has been reached.
     ///
     /// See [`InputRef::next_maybe`] for more information about what this function guarantees.
     #[inline(always)]
     pub fn peek_maybe(&mut self) -> Option<MaybeRef<'src, I::Token>> {
+        if self.at_region_end() {
+            return None;
+        }
         // SAFETY: cursor was generated by previous call to `Input::next`
         unsafe { I::next_maybe(self.cache, &mut self.cursor.clone()).map(Into::into) }
     }
@@ -1414,8 +3504,11 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
     where
         I: ValueInput<'src>,
     {
+        if self.at_region_end() {
+            return None;
+        }
         // SAFETY: cursor was generated by previous call to `Input::next`
-        unsafe { I::next(self.cache, &mut self.cursor.clone()).map(Into::into) }
+        unsafe { I::next(self.cache, &mut self.cursor.clone()) }
     }
 
     /// Peek the next token in the input. Returns `None` if the end of the input has been reached.
@@ -1424,8 +3517,11 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
     where
         I: BorrowInput<'src>,
     {
+        if self.at_region_end() {
+            return None;
+        }
         // SAFETY: cursor was generated by previous call to `Input::next`
-        unsafe { I::next_ref(self.cache, &mut self.cursor.clone()).map(Into::into) }
+        unsafe { I::next_ref(self.cache, &mut self.cursor.clone()) }
     }
 
     /// Skip the next token in the input.
@@ -1486,6 +3582,37 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
         unsafe { I::slice_from(self.cache, &self.cursor..) }
     }
 
+    /// Get the run of similar tokens (a word, an operator, or a single whitespace character) that starts at the given
+    /// cursor, no longer than `max` tokens. Returns `None` at the end of the input.
+    pub(crate) fn found_run(&mut self, at: &I::Cursor, max: usize) -> Option<I::Slice>
+    where
+        I: SliceInput<'src>,
+        I::Token: Char,
+    {
+        let mut cursor = at.clone();
+        // SAFETY: cursor was generated by previous call to `Input::next`
+        let first = unsafe { I::next_maybe(self.cache, &mut cursor) }?;
+        let first = first.borrow();
+        if !first.is_whitespace() {
+            let is_word = first.is_ident_continue();
+            for _ in 1..max {
+                let before = cursor.clone();
+                // SAFETY: cursor was generated by previous call to `Input::next`
+                match unsafe { I::next_maybe(self.cache, &mut cursor) } {
+                    Some(tok)
+                        if !tok.borrow().is_whitespace()
+                            && tok.borrow().is_ident_continue() == is_word => {}
+                    _ => {
+                        cursor = before;
+                        break;
+                    }
+                }
+            }
+        }
+        // SAFETY: cursors were generated by previous calls to `Input::next`
+        Some(unsafe { I::slice(self.cache, at..&cursor) })
+    }
+
     // /// Get a span over the input that covers the given cursor range.
     // #[inline(always)]
     // pub fn span(&self, range: Range<&Cursor<'src, 'parse, I>>) -> I::Span {
@@ -1494,6 +3621,14 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
     //     unsafe { I::span(self.cache, &range.start.inner..&range.end.inner) }
     // }
 
+    // Get a span over the input that covers the given cursor range
+    #[inline(always)]
+    pub(crate) fn span_between(&mut self, range: Range<&Cursor<'src, 'parse, I>>) -> I::Span {
+        // SAFETY: `Cursor` is invariant over 'parse, so we know that this cursor came from the same input
+        // See `https://plv.mpi-sws.org/rustbelt/ghostcell/`
+        unsafe { I::span(self.cache, &range.start.inner..&range.end.inner) }
+    }
+
     /// Get a span over the input that goes from the given cursor to the end of the input.
     // TODO: Unify with `InputRef::span`
     #[inline(always)]
@@ -1514,6 +3649,25 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
         unsafe { I::span(self.cache, &before.inner..&self.cursor) }
     }
 
+    // Whether the tokens since `before` are the characters of `s`, according to `eq`. Unlike comparing the slice of the
+    // input, this sees the tokens as the input produces them (after `Input::normalize`, for example).
+    #[inline]
+    pub(crate) fn tokens_eq_since<C: Char>(
+        &mut self,
+        before: &Cursor<'src, 'parse, I>,
+        s: &C::Str,
+        mut eq: impl FnMut(C, C) -> bool,
+    ) -> bool
+    where
+        I: StrInput<'src, C>,
+    {
+        let mut cursor = before.inner;
+        C::str_to_chars(s).all(|c| {
+            // SAFETY: The cursor came from the same input, and only moves by reading from it
+            matches!(unsafe { I::next(self.cache, &mut cursor) }, Some(tok) if eq(tok, c))
+        }) && cursor == self.cursor
+    }
+
     /// SAFETY: Previous cursor + skip must not exceed length
     #[inline(always)]
     #[cfg(any(feature = "regex", feature = "lexical-numbers"))]
@@ -1524,11 +3678,51 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
         self.cursor += skip;
     }
 
+    /// SAFETY: The cursor must be one that could have been produced by advancing the current cursor
+    #[inline(always)]
+    pub(crate) unsafe fn set_cursor(&mut self, cursor: I::Cursor) {
+        self.cursor = cursor;
+    }
+
     #[inline]
     pub(crate) fn emit(&mut self, error: E::Error) {
+        if self.limits.halt.is_some() {
+            return;
+        }
         self.errors
             .secondary
             .push(Located::at(self.cursor.clone(), error));
+        if matches!(self.limits.options.max_errors, Some(max) if self.errors.secondary.len() >= max)
+        {
+            self.limits.halt(Halt::MaxErrors, self.cursor.clone());
+        }
+    }
+
+    #[inline]
+    pub(crate) fn warn(&mut self, warning: E::Error) {
+        self.errors
+            .warnings
+            .push(Located::at(self.cursor.clone(), warning));
+    }
+
+    // Record that a strategy recovered, consuming the input between `start` and the current cursor
+    pub(crate) fn record_recovery(&mut self, strategy: &'static str, start: &I::Cursor) {
+        let end = I::cursor_location(&self.cursor);
+        // Recoveries are rare, so the skipped tokens are counted again rather than tracking them as they are read
+        let mut cursor = start.clone();
+        let mut tokens = 0;
+        while I::cursor_location(&cursor) < end {
+            // SAFETY: cursor was generated by previous call to `Input::next`
+            if unsafe { I::next_maybe(self.cache, &mut cursor) }.is_none() {
+                break;
+            }
+            tokens += 1;
+        }
+        self.errors.recoveries.push(RecoveryEvent {
+            strategy,
+            skipped: I::cursor_location(start)..end,
+            tokens,
+        });
     }
 
     #[inline]
@@ -1558,6 +3752,7 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
             },
             None => Located::at(at.clone(), Error::expected_found(expected, found, span)),
         });
+        self.merge_follow(at);
     }
 
     #[inline]
@@ -1575,6 +3770,18 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
             },
             None => Located::at(at.clone(), err),
         });
+        self.merge_follow(at);
+    }
+
+    // If a repetition ended where an error just occurred, what could have continued the repetition is also expected
+    // there. Otherwise, it's no longer relevant.
+    #[inline]
+    fn merge_follow(&mut self, at: &I::Cursor) {
+        if let Some(follow) = self.errors.follow.take() {
+            if I::cursor_location(&follow.pos) == I::cursor_location(at) {
+                self.add_alt_err(&follow.pos, follow.err);
+            }
+        }
     }
 
     // Take the alt error. If one doesn't exist, generate a fake one.
@@ -1587,11 +3794,49 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
             )
         })
     }
+
+    /// Finish a top-level parse with the given result, producing its output (if any), its primary error (if any), and
+    /// the reason that parsing was halted (if it was).
+    ///
+    /// The output of a halted parse is discarded, since it may have been produced from only part of the input.
+    pub(crate) fn finish<O>(
+        &mut self,
+        res: Result<O, ()>,
+    ) -> (Option<O>, Option<E::Error>, Option<Halt>) {
+        let alt = self.take_alt();
+        match (self.limits.halt.take(), res) {
+            (None, Ok(out)) => (Some(out), None, None),
+            (None, Err(())) => (None, Some(alt.err), None),
+            // The errors that were emitted are enough to explain why there is no output
+            (Some((Halt::MaxErrors, _)), _) => (None, None, Some(Halt::MaxErrors)),
+            // Whatever happened after backtracking is a consequence of the tokens that were missing
+            (Some((Halt::Discarded, at)), _) => {
+                // SAFETY: the cursor was generated by this input
+                let span = unsafe { I::span(self.cache, &at..&at) };
+                (
+                    None,
+                    Some(E::Error::input_discarded(span)),
+                    Some(Halt::Discarded),
+                )
+            }
+            (Some((reason, _)), Err(())) => (None, Some(alt.err), Some(reason)),
+            (Some((reason, at)), Ok(_)) => {
+                // SAFETY: the cursor was generated by this input
+                let span = unsafe { I::span(self.cache, &at..&at) };
+                (
+                    None,
+                    Some(E::Error::expected_found([], None, span)),
+                    Some(reason),
+                )
+            }
+        }
+    }
 }
 
 /// Struct used in [`Parser::validate`] to collect user-emitted errors
 pub struct Emitter<E> {
     emitted: Vec<E>,
+    warnings: Vec<E>,
 }
 
 impl<E> Emitter<E> {
@@ -1599,12 +3844,13 @@ impl<E> Emitter<E> {
     pub(crate) fn new() -> Emitter<E> {
         Emitter {
             emitted: Vec::new(),
+            warnings: Vec::new(),
         }
     }
 
     #[inline]
-    pub(crate) fn errors(self) -> Vec<E> {
-        self.emitted
+    pub(crate) fn errors(self) -> (Vec<E>, Vec<E>) {
+        (self.emitted, self.warnings)
     }
 
     /// Emit a non-fatal error
@@ -1612,6 +3858,15 @@ impl<E> Emitter<E> {
     pub fn emit(&mut self, err: E) {
         self.emitted.push(err)
     }
+
+    /// Emit an error as a warning.
+    ///
+    /// Unlike errors emitted with [`Emitter::emit`], warnings do not count towards the errors of a parse, so a parse
+    /// that produces only warnings still succeeds. They can be found with [`ParseResult::warnings`].
+    #[inline]
+    pub fn warn(&mut self, warning: E) {
+        self.warnings.push(warning)
+    }
 }
 
 /// See [`Parser::map_with`].
@@ -1619,6 +3874,7 @@ pub struct MapExtra<'src, 'b, I: Input<'src>, E: ParserExtra<'src, I>> {
     before: &'b I::Cursor,
     after: &'b I::Cursor,
     cache: &'b mut I::Cache,
+    errors: &'b mut Errors<I::Cursor, E::Error>,
     state: &'b mut E::State,
     ctx: &'b E::Context,
 }
@@ -1633,6 +3889,7 @@ impl<'src, 'b, I: Input<'src>, E: ParserExtra<'src, I>> MapExtra<'src, 'b, I, E>
             before: &before.inner,
             after: &inp.cursor,
             cache: inp.cache,
+            errors: inp.errors,
             ctx: inp.ctx,
             state: inp.state,
         }
@@ -1668,4 +3925,54 @@ impl<'src, 'b, I: Input<'src>, E: ParserExtra<'src, I>> MapExtra<'src, 'b, I, E>
     pub fn ctx(&self) -> &E::Context {
         self.ctx
     }
+
+    /// Record a warning, which is reported by [`ParseResult::warnings`] but does not cause the parse to fail.
+    ///
+    /// As with [`Emitter::warn`], warnings recorded by a parser that is later backtracked over are discarded. Note that
+    /// [`Parser::map_with`] closures don't run when the output is not needed (such as with [`Parser::check`]), so
+    /// use [`Parser::validate`] for diagnostics that must always be recorded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// // Lint against redundant parentheses while parsing
+    /// let expr = recursive(|expr| {
+    ///     let parens = expr
+    ///         .delimited_by(just('('), just(')'))
+    ///         .map_with(|inner: (bool, u32), e| {
+    ///             if inner.0 {
+    ///                 let span = e.span();
+    ///                 e.emit_warning(Rich::custom(span, "redundant parentheses"));
+    ///             }
+    ///             (true, inner.1)
+    ///         });
+    ///     text::int::<_, _, extra::Err<Rich<char>>>(10)
+    ///         .from_str()
+    ///         .unwrapped()
+    ///         .map(|n| (false, n))
+    ///         .or(parens)
+    /// });
+    ///
+    /// let res = expr.parse("((1))");
+    /// assert_eq!(res.output(), Some(&(true, 1)));
+    /// assert_eq!(res.warnings().map(|w| *w.span()).collect::<Vec<_>>(), vec![(0..5).into()]);
+    /// ```
+    #[inline]
+    pub fn emit_warning(&mut self, warning: E::Error) {
+        self.errors
+            .warnings
+            .push(Located::at(self.after.clone(), warning));
+    }
+
+    /// Record a note: an informational diagnostic that is reported by [`ParseResult::notes`] but does not cause the
+    /// parse to fail, and is not a warning.
+    ///
+    /// Notes recorded by a parser that is later backtracked over are discarded.
+    #[inline]
+    pub fn emit_note(&mut self, note: E::Error) {
+        self.errors
+            .notes
+            .push(Located::at(self.after.clone(), note));
+    }
 }
@@ -8,14 +8,72 @@ use crate::{
     input::{Checkpoint, Cursor},
     Input,
 };
-use core::ops::{Deref, DerefMut};
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    any::Any,
+    cell::Cell,
+    fmt,
+    hash::{BuildHasher, Hash, Hasher},
+    marker::PhantomData,
+    ops::{Deref, DerefMut, Index},
+};
+use hashbrown::{hash_map::DefaultHashBuilder, HashMap};
 
 #[allow(unused)] // for intra-doc links
 use crate::Parser;
 
 /// A type that receives event hooks when certain parsing actions occur.
 ///
+/// The parser state (see [`extra::State`](crate::extra::State)) is always an inspector, and it is told about every
+/// token that is consumed, every time a combinator saves the parse position or rewinds to it, and every time a rule
+/// marked with [`Parser::inspect_rule`] is entered or exited. This allows tools such as debuggers, tracers, coverage
+/// tools, and incremental parsing engines to observe a parse as it happens. The default state, `()`, ignores all of
+/// these hooks, as does [`SimpleState`].
+///
 /// If you don't need to receive event hooks, use [`SimpleState`].
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, inspector::Inspector, input::{Checkpoint, Cursor}};
+/// // Records the rules that were entered, and whether they succeeded
+/// #[derive(Default)]
+/// struct Tracer {
+///     depth: usize,
+///     log: Vec<String>,
+/// }
+///
+/// impl<'src> Inspector<'src, &'src str> for Tracer {
+///     type Checkpoint = usize;
+///     fn on_token(&mut self, _: &char) {}
+///     fn on_save<'parse>(&self, _: &Cursor<'src, 'parse, &'src str>) -> usize {
+///         self.depth
+///     }
+///     fn on_rewind<'parse>(&mut self, marker: &Checkpoint<'src, 'parse, &'src str, usize>) {
+///         self.depth = *marker.inspector();
+///     }
+///     fn on_enter_rule<'parse>(&mut self, name: &'static str, _: &Cursor<'src, 'parse, &'src str>) {
+///         self.log.push(format!("{}{name}", "  ".repeat(self.depth)));
+///         self.depth += 1;
+///     }
+///     fn on_exit_rule<'parse>(&mut self, _: &'static str, _: &Cursor<'src, 'parse, &'src str>, ok: bool) {
+///         self.depth -= 1;
+///         if !ok {
+///             self.log.last_mut().unwrap().push_str(" (failed)");
+///         }
+///     }
+/// }
+///
+/// let int = text::int::<_, _, extra::State<Tracer>>(10).inspect_rule("int");
+/// let ident = text::ascii::ident().inspect_rule("ident");
+/// let call = ident
+///     .then(int.or(ident).delimited_by(just('('), just(')')))
+///     .inspect_rule("call");
+///
+/// let mut tracer = Tracer::default();
+/// assert!(!call.parse_with_state("f(x)", &mut tracer).has_errors());
+/// assert_eq!(tracer.log, vec!["call", "  ident", "  int (failed)", "  ident"]);
+/// ```
 pub trait Inspector<'src, I: Input<'src>> {
     /// A type the Inspector can use to revert to a previous state.
     ///
@@ -32,6 +90,45 @@ pub trait Inspector<'src, I: Input<'src>> {
     /// You can use [`Checkpoint::inspector`] to get back the [`Checkpoint`][Self::Checkpoint]
     /// you originally created in [`on_save`][Self::on_save].
     fn on_rewind<'parse>(&mut self, marker: &Checkpoint<'src, 'parse, I, Self::Checkpoint>);
+
+    /// This function is called when a rule marked with [`Parser::inspect_rule`] is about to be parsed, with the cursor
+    /// at which it begins.
+    ///
+    /// By default, this does nothing.
+    #[inline(always)]
+    fn on_enter_rule<'parse>(&mut self, name: &'static str, cursor: &Cursor<'src, 'parse, I>) {
+        let _ = (name, cursor);
+    }
+
+    /// This function is called when a rule marked with [`Parser::inspect_rule`] has been parsed, with the cursor at
+    /// which it ended and whether it succeeded.
+    ///
+    /// If the rule failed, the cursor is where the parser gave up, and the input will be rewound by whichever
+    /// combinator handles the failure.
+    ///
+    /// By default, this does nothing.
+    #[inline(always)]
+    fn on_exit_rule<'parse>(
+        &mut self,
+        name: &'static str,
+        cursor: &Cursor<'src, 'parse, I>,
+        succeeded: bool,
+    ) {
+        let _ = (name, cursor, succeeded);
+    }
+
+    /// This function is called when a parser marked with [`Parser::labelled`] or [`Parser::inspect_rule`] succeeds,
+    /// with its label (or, for a rule, its name) and the span of the input that it parsed.
+    ///
+    /// Since different parsers may be labelled with different types, the label is given as [`Any`]: downcast it to
+    /// the type of label you're interested in. The names of rules are `&'static str`s. See [`Highlights`] for an
+    /// example.
+    ///
+    /// By default, this does nothing.
+    #[inline(always)]
+    fn on_label(&mut self, label: &dyn Any, span: &I::Span) {
+        let _ = (label, span);
+    }
 }
 
 impl<'src, I: Input<'src>> Inspector<'src, I> for () {
@@ -44,9 +141,135 @@ impl<'src, I: Input<'src>> Inspector<'src, I> for () {
     fn on_rewind<'parse>(&mut self, _: &Checkpoint<'src, 'parse, I, Self>) {}
 }
 
+/// A state type made up of several independent state values, one of which can be accessed by its type.
+///
+/// A tuple of inspectors is itself an inspector, passing every event hook on to each of its elements. This allows
+/// different parts of a grammar to each declare the state that they need (such as an interner, a stack of
+/// indentation levels, or a set of enabled language features) with a `Has<T, _>` bound, rather than sharing one
+/// struct that has to know about all of them. Use [`Has::get`] or [`Has::get_mut`] to access a particular element.
+///
+/// The second type parameter is a [`Slot`] giving the position of `T` within the tuple. It is almost always inferred,
+/// so it can be written as `_` or left as a type parameter of a generic function. If a tuple contains the same type
+/// more than once, the position must be given explicitly.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, inspector::{Has, HashCons, Inspector, Interned, SimpleState}};
+/// // The identifier layer only knows that the state contains an interner
+/// fn ident<'a, S, N>() -> impl Parser<'a, &'a str, Interned<String>, extra::State<S>> + Clone
+/// where
+///     S: Inspector<'a, &'a str> + Has<HashCons<String>, N> + 'a,
+/// {
+///     text::ascii::ident::<_, _, extra::State<S>>()
+///         .map_with(|name, e| e.state().get_mut().intern(name.to_string()))
+/// }
+///
+/// type Features = SimpleState<Vec<&'static str>>;
+/// type State = (HashCons<String>, Features);
+/// // `unsafe` is only allowed when the `unsafe` feature is enabled
+/// let item = text::ascii::keyword::<_, _, _, extra::State<State>>("unsafe")
+///     .validate(|_, e, emitter| {
+///         let features: &Features = e.state().get();
+///         if !features.contains(&"unsafe") {
+///             emitter.emit(EmptyErr::default());
+///         }
+///     })
+///     .padded()
+///     .or_not()
+///     .then(ident())
+///     .padded()
+///     .repeated()
+///     .collect::<Vec<_>>();
+///
+/// let mut state = (HashCons::new(), SimpleState(vec!["unsafe"]));
+/// let items = item.parse_with_state("unsafe a b a", &mut state).into_result().unwrap();
+/// assert_eq!(items[0].1, items[2].1);
+/// assert_eq!(state.0.len(), 2);
+///
+/// let mut state = (HashCons::new(), SimpleState(vec![]));
+/// assert!(item.parse_with_state("unsafe a", &mut state).has_errors());
+/// ```
+pub trait Has<T, N> {
+    /// Get a reference to the element of type `T`.
+    fn get(&self) -> &T;
+    /// Get a mutable reference to the element of type `T`.
+    fn get_mut(&mut self) -> &mut T;
+}
+
+/// The position of an element within a tuple of state values. See [`Has`].
+pub enum Slot<const N: usize> {}
+
+macro_rules! impl_has_for_tuple {
+    ($tuple:tt; $($X:ident $idx:tt)*) => {
+        $(impl_has_for_tuple!(~ $tuple $X $idx);)*
+    };
+    (~ ($($G:ident),*) $X:ident $idx:tt) => {
+        impl<$($G),*> Has<$X, Slot<$idx>> for ($($G,)*) {
+            #[inline(always)]
+            fn get(&self) -> &$X {
+                &self.$idx
+            }
+            #[inline(always)]
+            fn get_mut(&mut self) -> &mut $X {
+                &mut self.$idx
+            }
+        }
+    };
+}
+
+macro_rules! impl_inspector_for_tuple {
+    ($($X:ident $idx:tt)*) => {
+        impl<'src, I: Input<'src>, $($X: Inspector<'src, I>),*> Inspector<'src, I> for ($($X,)*) {
+            type Checkpoint = ($($X::Checkpoint,)*);
+            #[inline(always)]
+            fn on_token(&mut self, token: &<I as Input<'src>>::Token) {
+                $(self.$idx.on_token(token);)*
+            }
+            #[inline(always)]
+            fn on_save<'parse>(&self, cursor: &Cursor<'src, 'parse, I>) -> Self::Checkpoint {
+                ($(self.$idx.on_save(cursor),)*)
+            }
+            #[inline(always)]
+            fn on_rewind<'parse>(&mut self, marker: &Checkpoint<'src, 'parse, I, Self::Checkpoint>) {
+                $(self.$idx.on_rewind(&marker.with_inspector(marker.inspector().$idx));)*
+            }
+            #[inline(always)]
+            fn on_enter_rule<'parse>(&mut self, name: &'static str, cursor: &Cursor<'src, 'parse, I>) {
+                $(self.$idx.on_enter_rule(name, cursor);)*
+            }
+            #[inline(always)]
+            fn on_exit_rule<'parse>(
+                &mut self,
+                name: &'static str,
+                cursor: &Cursor<'src, 'parse, I>,
+                succeeded: bool,
+            ) {
+                $(self.$idx.on_exit_rule(name, cursor, succeeded);)*
+            }
+            #[inline(always)]
+            fn on_label(&mut self, label: &dyn Any, span: &I::Span) {
+                $(self.$idx.on_label(label, span);)*
+            }
+        }
+
+        impl_has_for_tuple!(($($X),*); $($X $idx)*);
+    };
+}
+
+impl_inspector_for_tuple!(A 0);
+impl_inspector_for_tuple!(A 0 B 1);
+impl_inspector_for_tuple!(A 0 B 1 C 2);
+impl_inspector_for_tuple!(A 0 B 1 C 2 D 3);
+impl_inspector_for_tuple!(A 0 B 1 C 2 D 3 E 4);
+impl_inspector_for_tuple!(A 0 B 1 C 2 D 3 E 4 F 5);
+impl_inspector_for_tuple!(A 0 B 1 C 2 D 3 E 4 F 5 G 6);
+impl_inspector_for_tuple!(A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7);
+
 /// A state type that should be accessible directly from `parser.state()` and has no special behavior.
 ///
 /// This wrapper implements the [`Inspector`] trait for you so you don't have to.
+#[derive(Copy, Clone, Debug, Default)]
 pub struct SimpleState<T>(pub T);
 impl<'src, T, I: Input<'src>> Inspector<'src, I> for SimpleState<T> {
     type Checkpoint = ();
@@ -77,3 +300,509 @@ impl<T> From<T> for SimpleState<T> {
         Self(value)
     }
 }
+
+/// A state type that undoes changes made to it by a branch of the parser that is later backtracked over.
+///
+/// Parsers that change the state (such as with [`Parser::validate`] or [`Parser::map_with`]) normally keep their
+/// changes even if the parser that made them ends up failing and another alternative is tried instead. For a
+/// context-sensitive grammar, this means that a failed speculative branch can leave behind symbols that were never
+/// really declared. A [`Rollback`] avoids this: when the parser rewinds, the value is restored to what it was at the
+/// point being rewound to.
+///
+/// The value is accessed through [`Deref`] and [`DerefMut`]. The first mutable access after each save point clones
+/// the value so that it can be restored later, so this works best for values that are cheap to clone (such as
+/// persistent data structures or small collections). Old values are kept until the [`Rollback`] is dropped or
+/// converted with [`Rollback::into_inner`].
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, inspector::Rollback};
+/// type Extra<'a> = extra::Full<EmptyErr, Rollback<Vec<&'a str>>, ()>;
+///
+/// // `a, b = 1` declares `a` and `b`, but `a, b` is just a list of names
+/// let declare = text::ascii::ident::<_, _, Extra>().validate(|name, e, _| {
+///     e.state().push(name);
+///     name
+/// });
+/// let assign = declare
+///     .separated_by(just(','))
+///     .then_ignore(just('='))
+///     .then_ignore(text::int(10));
+/// let stmt = assign.or(text::ascii::ident().separated_by(just(',')));
+///
+/// let mut declared = Rollback::new(Vec::new());
+/// stmt.parse_with_state("a,b", &mut declared).into_result().unwrap();
+/// assert!(declared.is_empty());
+///
+/// stmt.parse_with_state("a,b=1", &mut declared).into_result().unwrap();
+/// assert_eq!(*declared, ["a", "b"]);
+/// ```
+pub struct Rollback<T> {
+    value: T,
+    // Values from before the first mutation after a save point, oldest first
+    undo: Vec<T>,
+    // Whether `value` has been mutated since the last save point
+    dirty: Cell<bool>,
+}
+
+impl<T> Rollback<T> {
+    /// Create a new [`Rollback`] with the given initial value.
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            undo: Vec::new(),
+            dirty: Cell::new(false),
+        }
+    }
+
+    /// Convert this into the current value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: Default> Default for Rollback<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> From<T> for Rollback<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Rollback<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Rollback").field(&self.value).finish()
+    }
+}
+
+impl<T> Deref for Rollback<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T: Clone> DerefMut for Rollback<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        if !self.dirty.replace(true) {
+            self.undo.push(self.value.clone());
+        }
+        &mut self.value
+    }
+}
+
+impl<'src, T, I: Input<'src>> Inspector<'src, I> for Rollback<T> {
+    type Checkpoint = usize;
+    #[inline(always)]
+    fn on_token(&mut self, _: &<I as Input<'src>>::Token) {}
+    #[inline(always)]
+    fn on_save<'parse>(&self, _: &Cursor<'src, 'parse, I>) -> Self::Checkpoint {
+        self.dirty.set(false);
+        self.undo.len()
+    }
+    #[inline]
+    fn on_rewind<'parse>(&mut self, marker: &Checkpoint<'src, 'parse, I, Self::Checkpoint>) {
+        let len = *marker.inspector();
+        if len < self.undo.len() {
+            // The oldest value after the save point is the value at the save point
+            self.undo.truncate(len + 1);
+            self.value = self.undo.pop().unwrap();
+        }
+        self.dirty.set(false);
+    }
+}
+
+/// A handle to a value interned in a [`HashCons`].
+///
+/// Handles are cheap to copy and compare: two handles from the same [`HashCons`] are equal if and only if the values
+/// they refer to are equal.
+pub struct Interned<T> {
+    idx: usize,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Interned<T> {
+    /// The index of the value within its [`HashCons`], in order of first appearance.
+    pub fn index(&self) -> usize {
+        self.idx
+    }
+}
+
+impl<T> Copy for Interned<T> {}
+impl<T> Clone for Interned<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> PartialEq for Interned<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.idx == other.idx
+    }
+}
+impl<T> Eq for Interned<T> {}
+impl<T> Hash for Interned<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.idx.hash(state)
+    }
+}
+impl<T> fmt::Debug for Interned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Interned({})", self.idx)
+    }
+}
+
+/// A state type that de-duplicates (hash-conses) parser outputs, such that structurally equal values are stored only
+/// once.
+///
+/// Use [`HashCons::intern`] from within [`Parser::map_with`] (via `e.state()`) to turn a node into an [`Interned`]
+/// handle. Nodes built from handles are themselves cheap to hash and compare, so deeply repetitive input (such as
+/// machine-generated JSON or SQL) produces a compact graph rather than a tree of copies.
+///
+/// Values interned by a branch that is later backtracked over are kept: this is harmless, since interning has no
+/// effect other than to make a value available.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, inspector::{HashCons, Interned}};
+/// #[derive(PartialEq, Eq, Hash, Debug)]
+/// enum Tree {
+///     Leaf(u32),
+///     Node(Vec<Interned<Tree>>),
+/// }
+///
+/// let tree = recursive(|tree| {
+///     text::int::<_, _, extra::Full<EmptyErr, HashCons<Tree>, ()>>(10)
+///         .from_str()
+///         .unwrapped()
+///         .map(Tree::Leaf)
+///         .or(tree
+///             .separated_by(just(','))
+///             .collect()
+///             .delimited_by(just('['), just(']'))
+///             .map(Tree::Node))
+///         .map_with(|tree, e| e.state().intern(tree))
+/// });
+///
+/// let mut arena = HashCons::new();
+/// let root = tree
+///     .parse_with_state("[[1,2],[1,2],[1,[1,2]]]", &mut arena)
+///     .into_result()
+///     .unwrap();
+///
+/// // Only 5 distinct trees exist: `1`, `2`, `[1,2]`, `[1,[1,2]]` and the root
+/// assert_eq!(arena.len(), 5);
+/// let Tree::Node(children) = &arena[root] else { panic!() };
+/// assert_eq!(children[0], children[1]);
+/// ```
+pub struct HashCons<T> {
+    items: Vec<T>,
+    // Keys are indices into `items`, hashed by the value they refer to
+    index: HashMap<usize, (), ()>,
+    hasher: DefaultHashBuilder,
+}
+
+impl<T> HashCons<T> {
+    /// Create a new, empty [`HashCons`].
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            index: HashMap::with_hasher(()),
+            hasher: DefaultHashBuilder::default(),
+        }
+    }
+
+    /// Intern a value, returning a handle to it. If an equal value was already interned, its handle is returned and
+    /// `value` is dropped.
+    pub fn intern(&mut self, value: T) -> Interned<T>
+    where
+        T: Hash + Eq,
+    {
+        let Self {
+            items,
+            index,
+            hasher,
+        } = self;
+        let hash = hasher.hash_one(&value);
+        let idx = match index
+            .raw_entry_mut()
+            .from_hash(hash, |&idx| items[idx] == value)
+        {
+            hashbrown::hash_map::RawEntryMut::Occupied(o) => *o.key(),
+            hashbrown::hash_map::RawEntryMut::Vacant(v) => {
+                let idx = items.len();
+                items.push(value);
+                v.insert_with_hasher(hash, idx, (), |&idx| hasher.hash_one(&items[idx]));
+                idx
+            }
+        };
+        Interned {
+            idx,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Get the value that a handle refers to.
+    pub fn get(&self, handle: Interned<T>) -> &T {
+        &self.items[handle.idx]
+    }
+
+    /// The number of distinct values that have been interned.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if no values have been interned.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Convert this [`HashCons`] into the interned values, ordered such that each value's index is that of its
+    /// [`Interned`] handle.
+    pub fn into_vec(self) -> Vec<T> {
+        self.items
+    }
+}
+
+impl<T> Default for HashCons<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Index<Interned<T>> for HashCons<T> {
+    type Output = T;
+
+    fn index(&self, handle: Interned<T>) -> &Self::Output {
+        self.get(handle)
+    }
+}
+
+impl<'src, T, I: Input<'src>> Inspector<'src, I> for HashCons<T> {
+    type Checkpoint = ();
+    #[inline(always)]
+    fn on_token(&mut self, _: &<I as Input<'src>>::Token) {}
+    #[inline(always)]
+    fn on_save<'parse>(&self, _: &Cursor<'src, 'parse, I>) -> Self::Checkpoint {}
+    #[inline(always)]
+    fn on_rewind<'parse>(&mut self, _: &Checkpoint<'src, 'parse, I, Self::Checkpoint>) {}
+}
+
+// A `lasso` interner can be used as the state directly, or as an element of a tuple of state values. Strings interned
+// by a branch that is later backtracked over stay in the interner, which is harmless.
+#[cfg(feature = "lasso")]
+impl<'src, K, H, I: Input<'src>> Inspector<'src, I> for lasso::Rodeo<K, H> {
+    type Checkpoint = ();
+    #[inline(always)]
+    fn on_token(&mut self, _: &<I as Input<'src>>::Token) {}
+    #[inline(always)]
+    fn on_save<'parse>(&self, _: &Cursor<'src, 'parse, I>) -> Self::Checkpoint {}
+    #[inline(always)]
+    fn on_rewind<'parse>(&mut self, _: &Checkpoint<'src, 'parse, I, Self::Checkpoint>) {}
+}
+
+#[cfg(feature = "lasso")]
+impl<K, H> Has<lasso::Rodeo<K, H>, Slot<0>> for lasso::Rodeo<K, H> {
+    #[inline(always)]
+    fn get(&self) -> &Self {
+        self
+    }
+    #[inline(always)]
+    fn get_mut(&mut self) -> &mut Self {
+        self
+    }
+}
+
+/// A state type that collects `(span, kind)` pairs for use in semantic syntax highlighting, deriving them from the
+/// labels of a grammar's parsers.
+///
+/// Each time a parser marked with [`Parser::labelled`] or [`Parser::inspect_rule`] succeeds, its label (or, for a rule,
+/// its name) is given to the mapping that this state was created with. If the mapping produces a kind, the span of the
+/// input that the parser covered is recorded with that kind. This allows editors to reuse a grammar for semantic
+/// highlighting without annotating it separately. Labels of a type other than `L` are ignored.
+///
+/// The kind type `K` is up to you: it might be an enum of token classes (keyword, identifier, string, etc.) or the
+/// name of a scope in your editor's theme. Highlights recorded by a branch that is later backtracked over are
+/// discarded, so only highlights from the successful parse remain.
+///
+/// Highlights are recorded in the order that their parsers finish, meaning that a nested highlight appears before
+/// the highlight that encloses it. Use [`Highlights::into_sorted`] to order them by position instead.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, inspector::Highlights};
+/// #[derive(Copy, Clone, Debug, PartialEq)]
+/// enum Kind { Keyword, Ident, Number }
+///
+/// type Extra<'a> = extra::State<Highlights<SimpleSpan, &'static str, Kind>>;
+///
+/// let keyword = |kw| text::ascii::keyword::<_, _, _, Extra>(kw).inspect_rule("keyword");
+/// let stmt = keyword("let")
+///     .ignore_then(text::ascii::ident().inspect_rule("ident").padded())
+///     .then_ignore(just('='))
+///     .then(text::int(10).inspect_rule("number").padded())
+///     .inspect_rule("statement");
+///
+/// let mut highlights = Highlights::new(|name: &&str| match *name {
+///     "keyword" => Some(Kind::Keyword),
+///     "ident" => Some(Kind::Ident),
+///     "number" => Some(Kind::Number),
+///     // Rules that don't correspond to a kind of syntax aren't highlighted
+///     _ => None,
+/// });
+/// stmt.parse_with_state("let x = 42", &mut highlights).into_result().unwrap();
+/// assert_eq!(highlights.into_sorted(), vec![
+///     ((0..3).into(), Kind::Keyword),
+///     ((4..5).into(), Kind::Ident),
+///     ((8..10).into(), Kind::Number),
+/// ]);
+/// ```
+pub struct Highlights<S, L, K> {
+    map: Box<dyn Fn(&L) -> Option<K>>,
+    spans: Vec<(S, K)>,
+}
+
+impl<S, L, K> Highlights<S, L, K> {
+    /// Create a new [`Highlights`] with no highlights recorded, which uses the given function to decide the kind of
+    /// highlight, if any, for each label.
+    pub fn new(map: impl Fn(&L) -> Option<K> + 'static) -> Self {
+        Self {
+            map: Box::new(map),
+            spans: Vec::new(),
+        }
+    }
+
+    /// Iterate over the recorded highlights, in the order they were recorded.
+    pub fn iter(&self) -> impl Iterator<Item = &(S, K)> + '_ {
+        self.spans.iter()
+    }
+
+    /// Convert this into the recorded highlights, in the order they were recorded.
+    pub fn into_vec(self) -> Vec<(S, K)> {
+        self.spans
+    }
+
+    /// Convert this into the recorded highlights, ordered by the start of their spans. Where highlights start at the
+    /// same position, enclosing highlights come before those nested within them.
+    pub fn into_sorted(mut self) -> Vec<(S, K)>
+    where
+        S: crate::span::Span,
+        S::Offset: Ord,
+    {
+        self.spans
+            .sort_by(|(a, _), (b, _)| a.start().cmp(&b.start()).then(b.end().cmp(&a.end())));
+        self.spans
+    }
+}
+
+impl<'src, L: 'static, K, I: Input<'src>> Inspector<'src, I> for Highlights<I::Span, L, K>
+where
+    I::Span: Clone,
+{
+    type Checkpoint = usize;
+    #[inline(always)]
+    fn on_token(&mut self, _: &<I as Input<'src>>::Token) {}
+    #[inline(always)]
+    fn on_save<'parse>(&self, _: &Cursor<'src, 'parse, I>) -> Self::Checkpoint {
+        self.spans.len()
+    }
+    #[inline(always)]
+    fn on_rewind<'parse>(&mut self, marker: &Checkpoint<'src, 'parse, I, Self::Checkpoint>) {
+        self.spans.truncate(*marker.inspector());
+    }
+    #[inline]
+    fn on_label(&mut self, label: &dyn Any, span: &I::Span) {
+        if let Some(kind) = label.downcast_ref().and_then(&self.map) {
+            self.spans.push((span.clone(), kind));
+        }
+    }
+}
+
+/// A state type that decides whether a parse is strict. See [`Parser::lenient`] and [`Parser::strict`].
+///
+/// This is implemented by [`Leniency`]. If your parser needs other state too, implement it for your own state type
+/// by forwarding to a [`Leniency`] within it.
+pub trait Strictness<'src, I: Input<'src>>: Inspector<'src, I> {
+    /// Whether the parse should enforce strict syntax.
+    fn is_strict(&self) -> bool;
+}
+
+/// A state type that selects between strict and lenient parsing, for use with [`Parser::lenient`] and
+/// [`Parser::strict`].
+///
+/// This allows the same grammar to enforce strict syntax in one place (such as in CI) while accepting legacy or
+/// sloppy forms elsewhere, in which case a warning is recorded for each of them (see [`ParseResult::warnings`] and
+/// [`Error::lenient_syntax`]). Warnings recorded by a branch that is later backtracked over are discarded.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, inspector::Leniency};
+/// type Extra<'a> = extra::Full<Rich<'a, char>, Leniency, ()>;
+///
+/// // Statements must end with `;`, and `<>` is a legacy spelling of `!=`
+/// let op = just::<_, _, Extra>("!=").or(just("<>").to("!=").lenient("`<>` is deprecated, use `!=`"));
+/// let stmt = text::ident()
+///     .then(op.padded())
+///     .then(text::ident())
+///     .then_ignore(just(';').strict("missing `;`"));
+///
+/// let mut strict = Leniency::strict();
+/// assert!(stmt.parse_with_state("a != b;", &mut strict).into_result().is_ok());
+/// assert!(stmt.parse_with_state("a <> b", &mut strict).has_errors());
+///
+/// let res = stmt.parse_with_state("a <> b", &mut Leniency::lenient());
+/// assert!(!res.has_errors());
+/// assert_eq!(
+///     res.warnings().map(|w| (*w.span(), w.to_string())).collect::<Vec<_>>(),
+///     vec![
+///         ((2..4).into(), "`<>` is deprecated, use `!=`".to_string()),
+///         ((6..6).into(), "missing `;`".to_string()),
+///     ],
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Leniency {
+    strict: bool,
+}
+
+impl Leniency {
+    /// Create a state that enforces strict syntax.
+    pub fn strict() -> Self {
+        Self { strict: true }
+    }
+
+    /// Create a state that accepts lenient syntax, recording warnings.
+    pub fn lenient() -> Self {
+        Self { strict: false }
+    }
+
+    /// Whether this state enforces strict syntax.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+}
+
+impl<'src, I: Input<'src>> Inspector<'src, I> for Leniency {
+    type Checkpoint = ();
+    #[inline(always)]
+    fn on_token(&mut self, _: &<I as Input<'src>>::Token) {}
+    #[inline(always)]
+    fn on_save<'parse>(&self, _: &Cursor<'src, 'parse, I>) -> Self::Checkpoint {}
+    #[inline(always)]
+    fn on_rewind<'parse>(&mut self, _: &Checkpoint<'src, 'parse, I, Self::Checkpoint>) {}
+}
+
+impl<'src, I: Input<'src>> Strictness<'src, I> for Leniency {
+    #[inline]
+    fn is_strict(&self) -> bool {
+        self.strict
+    }
+}
@@ -1,6 +1,8 @@
 //! Items related to parser labelling.
 
 use super::*;
+use core::{cell::RefCell, panic::Location};
+use inspector::Inspector;
 
 /// A trait implemented by [`Error`]s that can originate from labelled parsers. See [`Parser::labelled`].
 pub trait LabelError<'a, I: Input<'a>, L>: Error<'a, I> {
@@ -16,6 +18,25 @@ pub trait LabelError<'a, I: Input<'a>, L>: Error<'a, I> {
     ///
     /// In practice, this usually means adding the context to a context 'stack', similar to a backtrace.
     fn in_context(&mut self, label: L, span: I::Span);
+
+    /// Summarize the expected tokens within this error, replacing each token for which the given function produces a
+    /// label with that label. See [`Parser::simplify_expected`].
+    ///
+    /// By default, this does nothing.
+    fn simplify_expected(&mut self, f: &dyn Fn(&I::Token) -> Option<L>) {
+        let _ = f;
+    }
+
+    /// Remove each expected token that is subsumed by one of the expected labels, according to the given function.
+    ///
+    /// This is useful when a label and a token that starts the labelled pattern are both expected (such as
+    /// "expression" and `(`), which is confusing to users. It is usually called when rendering errors, since the
+    /// labels that end up in an error are only known once parsing has finished. See [`Rich::subsume_expected`].
+    ///
+    /// By default, this does nothing.
+    fn subsume_expected(&mut self, subsumes: &dyn Fn(&L, &I::Token) -> bool) {
+        let _ = subsumes;
+    }
 }
 
 /// See [`Parser::labelled`].
@@ -43,7 +64,7 @@ where
     I: Input<'a>,
     E: ParserExtra<'a, I>,
     A: Parser<'a, I, O, E>,
-    L: Clone,
+    L: Clone + 'static,
     E::Error: LabelError<'a, I, L>,
 {
     #[inline]
@@ -77,8 +98,167 @@ where
             }
         }
 
+        if res.is_ok() {
+            let span = inp.span_since(before.cursor());
+            inp.state().on_label(&self.label, &span);
+        }
+
         res
     }
 
     go_extra!(O);
 }
+
+/// A labelled rule recorded in a [`RuleRegistry`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rule<L> {
+    /// The label of the rule.
+    pub label: L,
+    /// The location in the source code where the rule was labelled.
+    pub location: &'static Location<'static>,
+}
+
+/// A registry of the labelled rules that make up a grammar, along with where each was defined. See
+/// [`Parser::labelled_in`].
+///
+/// Registering rules allows tooling to discover the rules of a grammar without parsing anything (for example, to
+/// measure which rules a test suite covers, to visualize the grammar, or to check that error filters refer to rules
+/// that exist). The registry also flags rules that are given the same label in different places, which usually
+/// indicates a copy-paste mistake: registering the same label from the same location more than once (such as when a
+/// function that builds a parser is called repeatedly) is not considered a duplicate.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, label::RuleRegistry};
+/// let rules = RuleRegistry::new();
+///
+/// let int = text::int::<&str, _, extra::Err<Rich<char>>>(10).labelled_in(&rules, "number");
+/// let ident = text::ascii::ident().labelled_in(&rules, "identifier");
+/// let float = int.then(just('.').then(int)).to_slice().labelled_in(&rules, "number");
+/// let atom = float.or(int).or(ident);
+/// assert!(!atom.parse("4.2").has_errors());
+///
+/// assert_eq!(
+///     rules.rules().iter().map(|r| r.label).collect::<Vec<_>>(),
+///     vec!["number", "identifier", "number"],
+/// );
+/// assert!(rules.get(&"identifier").is_some());
+///
+/// // `number` was defined twice
+/// let dups = rules.duplicates();
+/// assert_eq!(dups.len(), 1);
+/// assert_eq!(dups[0].0.label, "number");
+/// ```
+pub struct RuleRegistry<L> {
+    rules: RefCell<Vec<Rule<L>>>,
+    duplicates: RefCell<Vec<(Rule<L>, Rule<L>)>>,
+}
+
+impl<L> RuleRegistry<L> {
+    /// Create a new, empty registry.
+    pub fn new() -> Self {
+        Self {
+            rules: RefCell::new(Vec::new()),
+            duplicates: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Record a rule with the given label, defined at the given location.
+    ///
+    /// This is called by [`Parser::labelled_in`], but may also be used to register rules that are not labelled.
+    pub fn register(&self, label: L, location: &'static Location<'static>)
+    where
+        L: Clone + PartialEq,
+    {
+        let rule = Rule { label, location };
+        let mut rules = self.rules.borrow_mut();
+        match rules.iter().find(|r| r.label == rule.label) {
+            Some(prev) if prev.location == rule.location => return,
+            Some(prev) => self
+                .duplicates
+                .borrow_mut()
+                .push((rule.clone(), prev.clone())),
+            None => {}
+        }
+        rules.push(rule);
+    }
+
+    /// Get the rules that have been registered, in the order they were registered.
+    pub fn rules(&self) -> Vec<Rule<L>>
+    where
+        L: Clone,
+    {
+        self.rules.borrow().clone()
+    }
+
+    /// Get the location of the first rule registered with the given label, if any.
+    pub fn get(&self, label: &L) -> Option<&'static Location<'static>>
+    where
+        L: PartialEq,
+    {
+        self.rules
+            .borrow()
+            .iter()
+            .find(|r| &r.label == label)
+            .map(|r| r.location)
+    }
+
+    /// Get the pairs of rules that share a label but were defined in different places. The first rule of each pair is
+    /// the later definition, and the second is the rule it duplicates.
+    pub fn duplicates(&self) -> Vec<(Rule<L>, Rule<L>)>
+    where
+        L: Clone,
+    {
+        self.duplicates.borrow().clone()
+    }
+}
+
+impl<L> Default for RuleRegistry<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// See [`Parser::simplify_expected`].
+#[derive(Copy, Clone)]
+pub struct SimplifyExpected<A, F> {
+    pub(crate) parser: A,
+    pub(crate) summarize: F,
+}
+
+impl<'a, I, O, E, A, F, L> ParserSealed<'a, I, O, E> for SimplifyExpected<A, F>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+    F: Fn(&I::Token) -> Option<L>,
+    E::Error: LabelError<'a, I, L>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let old_alt = inp.errors.alt.take();
+        let before = inp.save();
+        let res = self.parser.go::<M>(inp);
+
+        let new_alt = inp.errors.alt.take();
+        inp.errors.alt = old_alt;
+
+        if let Some(mut new_alt) = new_alt {
+            new_alt.err.simplify_expected(&self.summarize);
+            inp.add_alt_err(&new_alt.pos, new_alt.err);
+        }
+
+        for err in inp.errors.secondary_errors_since(before.err_count) {
+            err.err.simplify_expected(&self.summarize);
+        }
+
+        res
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::or_recover_to_end_of`].
+pub type OrRecoverToEndOf<A, L, T, U, D, F, const N: usize> =
+    RecoverWith<Labelled<A, L>, recovery::SyncTo<Ignored<T, U>, D, F, N>>;
@@ -54,25 +54,35 @@ macro_rules! go_cfg_extra {
     };
 }
 
+pub mod binary;
+pub mod bits;
 mod blanket;
 #[cfg(feature = "unstable")]
 pub mod cache;
+#[cfg(feature = "clap")]
+pub mod clap;
 pub mod combinator;
 pub mod container;
+#[cfg(feature = "serde")]
+pub mod de;
 #[cfg(feature = "either")]
 mod either;
 pub mod error;
 #[cfg(feature = "extension")]
 pub mod extension;
 pub mod extra;
+pub mod grid;
 #[cfg(docsrs)]
 pub mod guide;
+pub mod incremental;
 pub mod input;
 pub mod inspector;
 #[cfg(feature = "label")]
 pub mod label;
 #[cfg(feature = "lexical-numbers")]
 pub mod number;
+#[cfg(feature = "os_str")]
+pub mod os_str;
 #[cfg(feature = "pratt")]
 pub mod pratt;
 pub mod primitive;
@@ -83,7 +93,11 @@ pub mod recursive;
 pub mod regex;
 pub mod span;
 mod stream;
+#[cfg(feature = "test-utils")]
+pub mod test_suite;
 pub mod text;
+#[cfg(feature = "proc-macro2")]
+pub mod token_stream;
 pub mod util;
 
 /// Commonly used functions, traits and types.
@@ -100,17 +114,24 @@ pub mod prelude {
         extra,
         input::Input,
         primitive::{
-            any, any_ref, choice, custom, empty, end, group, just, map_ctx, none_of, one_of, todo,
+            any, any_ref, at_most_one_of, choice, custom, empty, end, exactly_one_of, group, just,
+            just_ref, map_ctx, none_of, none_of_ref, one_of, one_of_ref, permutation, todo,
+        },
+        recovery::{
+            insert_missing, nested_delimiters, recover_as_error_node, skip_balanced,
+            skip_then_retry_until, skip_unterminated, skip_until, sync_to, via_custom, via_parser,
         },
-        recovery::{nested_delimiters, skip_then_retry_until, skip_until, via_parser},
         recursive::{recursive, Recursive},
         span::{SimpleSpan, Span as _},
-        text, Boxed, ConfigIterParser, ConfigParser, IterParser, ParseResult, Parser,
+        text, Boxed, ConfigIterParser, ConfigParser, IterParser, OneShotParser, ParseResult,
+        Parser,
     };
-    pub use crate::{select, select_ref};
+    pub use crate::{peg, recursive_group, select, select_ref};
 }
 
 use crate::input::InputOwn;
+#[cfg(not(feature = "std"))]
+use alloc::string::ToString;
 use alloc::{boxed::Box, string::String, vec, vec::Vec};
 #[cfg(feature = "nightly")]
 use core::marker::Tuple;
@@ -130,24 +151,26 @@ use hashbrown::HashMap;
 #[cfg(feature = "serde")]
 use serde::{de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
 
+#[cfg(feature = "pratt")]
+use self::inspector::Inspector;
 #[cfg(feature = "label")]
-use self::label::{LabelError, Labelled};
+use self::label::{LabelError, Labelled, SimplifyExpected};
 use self::{
     combinator::*,
     container::*,
-    error::Error,
+    error::{Error, FoundSliceError},
     extra::ParserExtra,
     input::{
-        BorrowInput, Emitter, ExactSizeInput, InputRef, MapExtra, SliceInput, StrInput, ValueInput,
+        BorrowInput, CommitInput, Emitter, ExactSizeInput, FallibleInput, InputRef,
+        LookbehindInput, MapExtra, SliceInput, StrInput, ValueInput,
     },
-    inspector::Inspector,
     prelude::*,
     primitive::Any,
     private::{
         Check, ConfigIterParserSealed, ConfigParserSealed, Emit, IPResult, IterParserSealed,
         Located, MaybeUninitExt, Mode, PResult, ParserSealed, Sealed,
     },
-    recovery::{RecoverWith, Strategy},
+    recovery::{RecoverWith, ResetState, Strategy},
     span::Span,
     text::*,
     util::{IntoMaybe, MaybeMut, MaybeRef},
@@ -186,6 +209,8 @@ mod sync {
     pub(crate) type RefC<T> = alloc::sync::Arc<T>;
     pub(crate) type RefW<T> = alloc::sync::Weak<T>;
     pub(crate) type DynParser<'a, 'b, I, O, E> = dyn Parser<'a, I, O, E> + Send + Sync + 'b;
+    #[cfg(feature = "memoization")]
+    pub(crate) type DynClearScope<'b> = dyn combinator::ClearScope + Send + Sync + 'b;
     #[cfg(feature = "pratt")]
     pub(crate) type DynOperator<'a, 'b, I, O, E> =
         dyn pratt::Operator<'a, I, O, E> + Send + Sync + 'b;
@@ -203,6 +228,8 @@ mod sync {
     pub(crate) type RefC<T> = alloc::rc::Rc<T>;
     pub(crate) type RefW<T> = alloc::rc::Weak<T>;
     pub(crate) type DynParser<'a, 'b, I, O, E> = dyn Parser<'a, I, O, E> + 'b;
+    #[cfg(feature = "memoization")]
+    pub(crate) type DynClearScope<'b> = dyn combinator::ClearScope + 'b;
     #[cfg(feature = "pratt")]
     pub(crate) type DynOperator<'a, 'b, I, O, E> = dyn pratt::Operator<'a, I, O, E> + 'b;
 
@@ -212,6 +239,8 @@ mod sync {
     impl<T> MaybeSync for T {}
 }
 
+#[cfg(feature = "memoization")]
+use sync::DynClearScope;
 use sync::{DynParser, MaybeSync, RefC, RefW};
 
 /// The result of performing a parse on an input with [`Parser`].
@@ -226,11 +255,50 @@ use sync::{DynParser, MaybeSync, RefC, RefW};
 pub struct ParseResult<T, E> {
     output: Option<T>,
     errs: Vec<E>,
+    warnings: Vec<E>,
+    notes: Vec<E>,
+    recoveries: Vec<recovery::RecoveryEvent>,
+    halt: Option<extra::Halt>,
 }
 
 impl<T, E> ParseResult<T, E> {
     pub(crate) fn new(output: Option<T>, errs: Vec<E>) -> ParseResult<T, E> {
-        ParseResult { output, errs }
+        ParseResult {
+            output,
+            errs,
+            warnings: Vec::new(),
+            notes: Vec::new(),
+            recoveries: Vec::new(),
+            halt: None,
+        }
+    }
+
+    // Map the output, keeping everything else about the result
+    pub(crate) fn map_output<U>(self, f: impl FnOnce(T) -> U) -> ParseResult<U, E> {
+        ParseResult {
+            output: self.output.map(f),
+            errs: self.errs,
+            warnings: self.warnings,
+            notes: self.notes,
+            recoveries: self.recoveries,
+            halt: self.halt,
+        }
+    }
+
+    pub(crate) fn with_halt(self, halt: Option<extra::Halt>) -> Self {
+        Self { halt, ..self }
+    }
+
+    pub(crate) fn with_warnings(self, warnings: Vec<E>) -> Self {
+        Self { warnings, ..self }
+    }
+
+    pub(crate) fn with_notes(self, notes: Vec<E>) -> Self {
+        Self { notes, ..self }
+    }
+
+    pub(crate) fn with_recoveries(self, recoveries: Vec<recovery::RecoveryEvent>) -> Self {
+        Self { recoveries, ..self }
     }
 
     /// Whether this result contains output
@@ -243,6 +311,14 @@ impl<T, E> ParseResult<T, E> {
         !self.errs.is_empty()
     }
 
+    /// If parsing was stopped early by one of the limits in its [`ParseOptions`](extra::ParseOptions), the reason
+    /// that it was stopped. See [`Parser::parse_with_options`].
+    ///
+    /// A halted parse never has output, since it may have been produced from only part of the input.
+    pub fn halted(&self) -> Option<extra::Halt> {
+        self.halt
+    }
+
     /// Get a reference to the output of this result, if it exists
     pub fn output(&self) -> Option<&T> {
         self.output.as_ref()
@@ -254,6 +330,57 @@ impl<T, E> ParseResult<T, E> {
         self.errs.iter()
     }
 
+    /// Get an iterator over the warnings for this result, such as those emitted by [`Parser::validate`] with
+    /// [`Emitter::warn`] or by [`MapExtra::emit_warning`](input::MapExtra::emit_warning). Warnings are not errors: a
+    /// result with only warnings still has output.
+    pub fn warnings(&self) -> impl ExactSizeIterator<Item = &E> + DoubleEndedIterator {
+        self.warnings.iter()
+    }
+
+    /// Convert this `ParseResult` into a vector containing any warnings. See [`ParseResult::warnings`].
+    pub fn into_warnings(self) -> Vec<E> {
+        self.warnings
+    }
+
+    /// Get an iterator over the notes for this result, emitted with [`MapExtra::emit_note`](input::MapExtra::emit_note).
+    /// Like warnings, notes do not cause the parse to fail.
+    pub fn notes(&self) -> impl ExactSizeIterator<Item = &E> + DoubleEndedIterator {
+        self.notes.iter()
+    }
+
+    /// Convert this `ParseResult` into a vector containing any notes. See [`ParseResult::notes`].
+    pub fn into_notes(self) -> Vec<E> {
+        self.notes
+    }
+
+    /// Get an iterator over the recoveries made by [`Parser::recover_with`] during the parse, in the order that they
+    /// were made. Each [`RecoveryEvent`](recovery::RecoveryEvent) records which strategy recovered and the input that
+    /// it skipped.
+    ///
+    /// Recoveries that were later backtracked out of (such as within an alternative of [`Parser::or`] that went on to
+    /// fail) are not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let item = text::int::<_, _, extra::Err<Simple<char>>>(10)
+    ///     .recover_with(via_parser(none_of(',').repeated().at_least(1).to("?")));
+    /// let items = item.separated_by(just(',')).collect::<Vec<_>>();
+    ///
+    /// let res = items.parse("1,x y,3");
+    /// assert_eq!(res.output(), Some(&vec!["1", "?", "3"]));
+    /// let recovery = res.recoveries().next().unwrap();
+    /// assert_eq!(recovery.strategy, "via_parser");
+    /// assert_eq!(recovery.skipped, 2..5);
+    /// assert_eq!(recovery.tokens, 3);
+    /// ```
+    pub fn recoveries(
+        &self,
+    ) -> impl ExactSizeIterator<Item = &recovery::RecoveryEvent> + DoubleEndedIterator {
+        self.recoveries.iter()
+    }
+
     /// Convert this `ParseResult` into an option containing the output, if any exists
     pub fn into_output(self) -> Option<T> {
         self.output
@@ -301,6 +428,37 @@ impl<T, E> ParseResult<T, E> {
     }
 }
 
+/// An identifier for a parser, assigned when the parser is constructed. See [`Parser::identified`].
+///
+/// Identifiers are handed out in the order that parsers are constructed in, so a program that builds its parsers in
+/// the same order every time it runs (such as on a single thread, before starting to parse) gives each parser the same
+/// identifier on every run. This makes them suitable for referring to parsers from memoization tables, coverage
+/// reports, or visualizations. Unlike the address of a parser, a parser's identifier does not change when it is moved,
+/// and clones of a parser share its identifier.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ParserId(usize);
+
+impl ParserId {
+    pub(crate) fn next() -> Self {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Get the identifier as a number. Parsers constructed later have larger numbers.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+impl fmt::Display for ParserId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
 /// A trait implemented by parsers.
 ///
 /// Parsers take inputs of type `I`, which will implement [`Input`]. Refer to the documentation on [`Input`] for examples
@@ -371,19 +529,99 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         I: Input<'a>,
         E::Context: Default,
     {
-        let mut own = InputOwn::new_state(input, state);
+        self.parse_with_options(input, state, &extra::ParseOptions::default())
+    }
+
+    /// Parse a stream of tokens with the given state, as with [`Parser::parse_with_state`], applying the given
+    /// [`ParseOptions`](extra::ParseOptions).
+    ///
+    /// Options limit how a parse runs (for example, how many errors it may produce, or how many tokens it may read)
+    /// without changing the grammar. If a limit is reached, parsing stops early: the result has no output, and
+    /// [`ParseResult::halted`] gives the reason.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, extra::{Halt, ParseOptions}};
+    /// let list = recursive(|list| {
+    ///     list.delimited_by(just('['), just(']'))
+    ///         .or(just::<_, _, extra::Err<Rich<char>>>('x').to(()))
+    /// });
+    ///
+    /// let options = ParseOptions::new().recursion_limit(3);
+    /// assert!(list.parse_with_options("[[x]]", &mut (), &options).into_result().is_ok());
+    ///
+    /// let res = list.parse_with_options("[[[[x]]]]", &mut (), &options);
+    /// assert_eq!(res.halted(), Some(Halt::RecursionLimit));
+    /// assert_eq!(res.errors().map(|e| e.to_string()).collect::<Vec<_>>(), vec!["nesting too deep"]);
+    /// ```
+    fn parse_with_options(
+        &self,
+        input: I,
+        state: &mut E::State,
+        options: &extra::ParseOptions,
+    ) -> ParseResult<O, E::Error>
+    where
+        I: Input<'a>,
+        E::Context: Default,
+    {
+        let mut own = InputOwn::new_state(input, state).with_options(options.clone());
         let mut inp = own.as_ref_start();
         let res = self.then_ignore(end()).go::<Emit>(&mut inp);
-        let alt = inp.take_alt();
-        let mut errs = own.into_errs();
-        let out = match res {
-            Ok(out) => Some(out),
-            Err(()) => {
-                errs.push(alt.err);
-                None
-            }
-        };
+        let (out, err, halt) = inp.finish(res);
+        let (mut errs, warnings, notes, recoveries) = own.into_diagnostics();
+        errs.extend(err);
+        ParseResult::new(out, errs)
+            .with_halt(halt)
+            .with_warnings(warnings)
+            .with_notes(notes)
+            .with_recoveries(recoveries)
+    }
+
+    /// Parse a stream of tokens, as with [`Parser::parse`], reusing the buffers held by the given
+    /// [`ParseScratch`](input::ParseScratch) rather than allocating new ones.
+    ///
+    /// This is useful when parsing a great many small inputs, such as the messages handled by a service, where the
+    /// allocations made by each parse can be a significant part of its cost.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, input::ParseScratch};
+    /// fn point<'src>() -> impl Parser<'src, &'src str, Vec<&'src str>, extra::Err<Rich<'src, char>>> {
+    ///     text::int(10).separated_by(just(',')).exactly(2).collect()
+    /// }
+    ///
+    /// let mut scratch = ParseScratch::new();
+    /// for i in 0..3 {
+    ///     // Each message only lives for one iteration, but the scratch space outlives them all
+    ///     let msg = if i < 2 { format!("{i},{i}") } else { format!("{i}") };
+    ///     let res = point().parse_with_scratch(msg.as_str(), &mut scratch);
+    ///     assert_eq!(res.has_errors(), i == 2);
+    /// }
+    /// ```
+    fn parse_with_scratch(
+        &self,
+        input: I,
+        scratch: &mut input::ParseScratch,
+    ) -> ParseResult<O, E::Error>
+    where
+        Self: Sized,
+        I: Input<'a>,
+        E::State: Default,
+        E::Context: Default,
+    {
+        let mut own = InputOwn::new(input).with_scratch(scratch);
+        let mut inp = own.as_ref_start();
+        let res = self.then_ignore(end()).go::<Emit>(&mut inp);
+        let (out, err, halt) = inp.finish(res);
+        let (mut errs, warnings, notes, recoveries) = own.into_diagnostics_with_scratch(scratch);
+        errs.extend(err);
         ParseResult::new(out, errs)
+            .with_halt(halt)
+            .with_warnings(warnings)
+            .with_notes(notes)
+            .with_recoveries(recoveries)
     }
 
     /// Parse a stream of tokens, ignoring any output, and returning any errors encountered along the way.
@@ -416,19 +654,86 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         I: Input<'a>,
         E::Context: Default,
     {
-        let mut own = InputOwn::new_state(input, state);
+        self.check_with_options(input, state, &extra::ParseOptions::default())
+    }
+
+    /// Parse a stream of tokens with the given state, ignoring any output, as with [`Parser::check_with_state`],
+    /// applying the given [`ParseOptions`](extra::ParseOptions). See [`Parser::parse_with_options`].
+    fn check_with_options(
+        &self,
+        input: I,
+        state: &mut E::State,
+        options: &extra::ParseOptions,
+    ) -> ParseResult<(), E::Error>
+    where
+        Self: Sized,
+        I: Input<'a>,
+        E::Context: Default,
+    {
+        let mut own = InputOwn::new_state(input, state).with_options(options.clone());
         let mut inp = own.as_ref_start();
         let res = self.then_ignore(end()).go::<Check>(&mut inp);
-        let alt = inp.take_alt();
-        let mut errs = own.into_errs();
-        let out = match res {
-            Ok(()) => Some(()),
-            Err(()) => {
-                errs.push(alt.err);
-                None
-            }
-        };
+        let (out, err, halt) = inp.finish(res);
+        let (mut errs, warnings, notes, recoveries) = own.into_diagnostics();
+        errs.extend(err);
         ParseResult::new(out, errs)
+            .with_halt(halt)
+            .with_warnings(warnings)
+            .with_notes(notes)
+            .with_recoveries(recoveries)
+    }
+
+    /// Parse a stream of items with this parser, one after another, with access to the given state in between them.
+    ///
+    /// This is useful for inputs made up of many independent records, where outside code needs to look at or update
+    /// the state as each record arrives (such as adding definitions to a schema that later records refer to).
+    ///
+    /// The returned [`StateIter`] produces a [`ParseResult`] for each item, and stops at the end of the input. When an
+    /// item fails to parse, the iterator is left at the start of it (and any changes to the state made while
+    /// parsing it are rewound, as for any other backtracking): use [`StateIter::skip`] to move past it and resume
+    /// iteration, or else iteration stops there.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, extra::SimpleState};
+    /// // Each record is a name followed by the number of times the name has been seen so far
+    /// let record = text::ascii::ident::<_, _, extra::Full<Simple<char>, SimpleState<Vec<&str>>, ()>>()
+    ///     .then_ignore(just(';'));
+    ///
+    /// let mut seen = SimpleState(Vec::new());
+    /// let mut records = record.iter_with_state("foo;bar;!!;foo;", &mut seen);
+    ///
+    /// let mut out = Vec::new();
+    /// while let Some(res) = records.next() {
+    ///     match res.into_output() {
+    ///         Some(name) => {
+    ///             let count = records.state().iter().filter(|n| **n == name).count();
+    ///             records.state().push(name);
+    ///             out.push((name, count));
+    ///         }
+    ///         // Skip the broken record and carry on with the next one
+    ///         None => assert!(records.skip_with(none_of(';').repeated().then(just(';')))),
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(out, [("foo", 0), ("bar", 0), ("foo", 1)]);
+    /// ```
+    fn iter_with_state<'parse>(
+        self,
+        input: I,
+        state: &'parse mut E::State,
+    ) -> StateIter<'a, 'parse, Self, I, O, E>
+    where
+        Self: Sized,
+        E::Context: Default,
+    {
+        StateIter {
+            parser: self,
+            own: InputOwn::new_state(input, state),
+            stalled: false,
+            phantom: EmptyPhantom::new(),
+        }
     }
 
     /// Convert the output of this parser into a slice of the input, based on the current parser's
@@ -447,6 +752,10 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     ///
     /// The output type of this parser is `I`, the input that was found.
     ///
+    /// If the output doesn't match the predicate, this parser fails (allowing alternatives to be tried) with an error
+    /// that spans the rejected input and reports its first token as the one found. With the `label` feature, use
+    /// `labelled` to say what was expected instead.
+    ///
     /// # Examples
     ///
     /// ```
@@ -460,6 +769,22 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     /// assert_eq!(lowercase.parse("hello").into_result(), Ok("hello".to_string()));
     /// assert!(lowercase.parse("Hello").has_errors());
     /// ```
+    ///
+    /// Rejecting reserved words that would otherwise be matched as identifiers:
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let ident = text::ascii::ident::<_, _, extra::Err<Rich<char>>>()
+    ///     .filter(|name: &&str| !["let", "fn"].contains(name));
+    /// let decl = text::ascii::keyword("let").padded().ignore_then(ident);
+    ///
+    /// assert_eq!(decl.parse("let x").into_result(), Ok("x"));
+    /// assert_eq!(decl.parse("let lettuce").into_result(), Ok("lettuce"));
+    ///
+    /// let errs = decl.parse("let fn").into_errors();
+    /// assert_eq!(errs[0].span(), &SimpleSpan::from(4..6));
+    /// assert_eq!(errs[0].found(), Some(&'f'));
+    /// ```
     fn filter<F: Fn(&O) -> bool>(self, f: F) -> Filter<Self, F>
     where
         Self: Sized,
@@ -509,6 +834,43 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         }
     }
 
+    /// Map the final output of this parser to another value with a function that is called at most once, producing a
+    /// [`OneShotParser`].
+    ///
+    /// Unlike [`Parser::map`], the function may be [`FnOnce`], so it can move values that it captures (such as a large
+    /// buffer, or a resource that cannot be cloned) into the output. The function is only called if parsing produces
+    /// an output, after parsing has finished. Because of this, the resulting parser cannot be combined with other
+    /// parsers: it can only be run with [`OneShotParser::parse_once`], or mapped further.
+    ///
+    /// The output type of this parser is `U`, the same as the function's output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// // A resource that cannot be cloned
+    /// struct Buffer(Vec<u8>);
+    ///
+    /// let buffer = Buffer(vec![1, 2, 3]);
+    /// let parser = text::int::<_, _, extra::Err<Simple<char>>>(10)
+    ///     .from_str::<usize>()
+    ///     .unwrapped()
+    ///     .map_once(move |index| (buffer, index))
+    ///     .map_once(|(buffer, index)| buffer.0[index]);
+    ///
+    /// assert_eq!(parser.parse_once("2").into_result(), Ok(3));
+    /// ```
+    fn map_once<U, F: FnOnce(O) -> U>(self, f: F) -> MapOnce<OneShot<Self>, O, F>
+    where
+        Self: Sized,
+    {
+        MapOnce {
+            parser: OneShot { parser: self },
+            mapper: f,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
     /// Map the output of this parser to another value, with the opportunity to get extra metadata.
     ///
     /// The output type of this parser is `U`, the same as the function's output.
@@ -604,6 +966,174 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         }
     }
 
+    /// Accept this parser's input only when parsing leniently, recording a warning when it is used.
+    ///
+    /// This is intended for legacy or sloppy forms of syntax that should be rejected when parsing strictly (for
+    /// example, in CI) but accepted elsewhere. Whether the parse is strict is decided by the parser state, which must
+    /// implement [`Strictness`](inspector::Strictness) (such as [`Leniency`](inspector::Leniency)). When parsing
+    /// strictly, this parser fails without consuming any input, as if it was not part of the grammar at all.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// See [`Leniency`](inspector::Leniency).
+    fn lenient<W: ToString>(self, warning: W) -> Lenient<Self, W>
+    where
+        Self: Sized,
+    {
+        Lenient {
+            parser: self,
+            warning,
+        }
+    }
+
+    /// Require this parser only when parsing strictly. When parsing leniently, this parser becomes optional, and a
+    /// warning is recorded if it is missing.
+    ///
+    /// This is intended for syntax that was not always required, such as a trailing delimiter. Whether the parse is
+    /// strict is decided by the parser state, which must implement [`Strictness`](inspector::Strictness) (such as
+    /// [`Leniency`](inspector::Leniency)).
+    ///
+    /// The output type of this parser is `Option<O>`, which is `None` only if the input was missing in a lenient parse.
+    ///
+    /// # Examples
+    ///
+    /// See [`Leniency`](inspector::Leniency).
+    fn strict<W: ToString>(self, warning: W) -> Strict<Self, W>
+    where
+        Self: Sized,
+    {
+        Strict {
+            parser: self,
+            warning,
+        }
+    }
+
+    /// Mark this parser as a named rule, telling the parser state when it is entered and exited.
+    ///
+    /// Each time the parser is run, [`on_enter_rule`](inspector::Inspector::on_enter_rule) and
+    /// [`on_exit_rule`](inspector::Inspector::on_exit_rule) are called on the parser state with the given name. This
+    /// allows a parse to be observed at the level of a grammar's rules (for tracing, debugging, or measuring coverage)
+    /// without changing what is parsed. When the parser succeeds, [`on_label`](inspector::Inspector::on_label) is also
+    /// called with the name and the span it parsed, such as to derive [`Highlights`](inspector::Highlights) from it.
+    /// With the default state, this does nothing.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// See [`Inspector`](inspector::Inspector).
+    fn inspect_rule(self, name: &'static str) -> InspectRule<Self>
+    where
+        Self: Sized,
+    {
+        InspectRule { parser: self, name }
+    }
+
+    /// Call `enter` on the parser state before this parser runs, and `exit` once it has finished.
+    ///
+    /// `exit` is called whether the parser succeeds or fails, so every call to `enter` is balanced by a call to `exit`,
+    /// even if the input is then backtracked or recovered. This makes it a reliable way to push and pop a scope in the
+    /// state, such as a level of a symbol table or of an indentation stack, around a piece of syntax: doing so with
+    /// [`Parser::map_with`] would skip the pop whenever the parser fails part of the way through.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, extra::SimpleState};
+    /// // Variables are declared with `let x;`, and may only be used in the block that declares them (or a block within
+    /// // it)
+    /// type Scopes<'a> = SimpleState<Vec<Vec<&'a str>>>;
+    /// let block = recursive(|block| {
+    ///     let ident = text::ascii::ident::<_, _, extra::Full<Rich<char>, Scopes, ()>>().padded();
+    ///     // `validate` is used rather than `map_with` because it runs even when the output isn't needed
+    ///     let decl = text::ascii::keyword("let")
+    ///         .ignore_then(ident)
+    ///         .validate(|name, e, _| e.state().last_mut().unwrap().push(name));
+    ///     let var = ident.validate(|name, e, emitter| {
+    ///         if !e.state().iter().flatten().any(|n| *n == name) {
+    ///             emitter.emit(Rich::custom(e.span(), format!("`{name}` is not declared")));
+    ///         }
+    ///     });
+    ///     choice((decl, var.ignored(), block))
+    ///         .then_ignore(just(';').padded())
+    ///         .repeated()
+    ///         .delimited_by(just('{').padded(), just('}').padded())
+    ///         .with_scope(|s: &mut Scopes| s.push(Vec::new()), |s: &mut Scopes| drop(s.pop()))
+    /// });
+    ///
+    /// let mut scopes = SimpleState(Vec::new());
+    /// let src = "{ let x; { let y; x; y; }; x; }";
+    /// assert!(!block.parse_with_state(src, &mut scopes).has_errors());
+    ///
+    /// let src = "{ let x; { let y; }; y; }";
+    /// let errs = block.parse_with_state(src, &mut scopes).into_errors();
+    /// assert_eq!(errs[0].to_string(), "`y` is not declared");
+    ///
+    /// // The scopes are balanced, even after a failed parse
+    /// assert!(block.parse_with_state("{ let x; { let y;", &mut scopes).has_errors());
+    /// assert!(scopes.is_empty());
+    /// ```
+    fn with_scope<F, G>(self, enter: F, exit: G) -> WithScope<Self, F, G>
+    where
+        Self: Sized,
+        F: Fn(&mut E::State),
+        G: Fn(&mut E::State),
+    {
+        WithScope {
+            parser: self,
+            enter,
+            exit,
+        }
+    }
+
+    /// Parse with `fast`, a version of this parser that produces [`EmptyErr`]s, and only if that fails, parse again
+    /// with this parser to find out what went wrong.
+    ///
+    /// Errors are expensive to build, and most of them are thrown away again as alternatives are tried. Since
+    /// [`EmptyErr`] is zero-sized, `fast` builds no errors at all, so parsing valid input takes the cheapest path
+    /// possible. When the input is invalid, this parser is run over the same input again, producing detailed errors,
+    /// so there is no loss in error quality. If `fast` succeeds but emits errors or warnings (or recovers from an
+    /// error), these are also produced by parsing again.
+    ///
+    /// This works best for a part of a grammar that is usually valid and parsed often, such as the items of a large
+    /// data file. The easiest way to get both versions of a parser is to write a function that is generic over the
+    /// [`ParserExtra`] type, and call it twice.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, extra::ParserExtra};
+    /// fn record<'a, E: ParserExtra<'a, &'a str>>() -> impl Parser<'a, &'a str, Vec<&'a str>, E> + Clone {
+    ///     text::int(10).separated_by(just(',')).collect().then_ignore(just('\n'))
+    /// }
+    ///
+    /// let records = record::<extra::Err<Rich<char>>>()
+    ///     .with_fast_path(record())
+    ///     .repeated()
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     records.parse("1,2\n3\n").into_result(),
+    ///     Ok(vec![vec!["1", "2"], vec!["3"]]),
+    /// );
+    /// let errs = records.then_ignore(end()).parse("1,2\n3,x\n").into_errors();
+    /// // The error is as detailed as if there were no fast path
+    /// assert_eq!((errs[0].found(), errs[0].span()), (Some(&'x'), &SimpleSpan::from(6..7)));
+    /// ```
+    fn with_fast_path<F>(self, fast: F) -> WithFastPath<Self, F>
+    where
+        Self: Sized,
+        F: Parser<'a, I, O, extra::Full<EmptyErr, E::State, E::Context>>,
+    {
+        WithFastPath { parser: self, fast }
+    }
+
     /// Map the output of this parser to another value.
     /// If the output of this parser isn't a tuple, use [`Parser::map`].
     ///
@@ -794,6 +1324,69 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         }
     }
 
+    /// Cache the outputs of this parser in the given [`RuleCache`](cache::RuleCache), such that they can be reused
+    /// by later parses of the same input, even across calls to [`Parser::parse`].
+    ///
+    /// This is useful for workloads that parse many small, similar inputs, such as the formulas in a spreadsheet. See
+    /// [`RuleCache`](cache::RuleCache) for how entries are keyed and invalidated.
+    ///
+    /// To find which fragment of the input the parser covers, it is first run without producing an output (as with
+    /// [`Parser::check`]). If an output was cached for that fragment, it is reused, and any work the parser does to
+    /// produce its output (such as in [`Parser::map`]) does not happen. Otherwise, the parser is run again to produce
+    /// its output. Outputs are only cached if the parser succeeded without emitting any errors.
+    ///
+    /// Since outputs are keyed by the input alone, the parser must not depend on a context (its context must be
+    /// `()`), and its output should only depend on the input it covers.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, cache::RuleCache};
+    /// use std::cell::Cell;
+    ///
+    /// let runs = Cell::new(0);
+    /// let cache = RuleCache::<String, u64>::new();
+    /// let sum = text::int::<_, _, extra::Err<Simple<char>>>(10)
+    ///     .from_str::<u64>()
+    ///     .unwrapped()
+    ///     .separated_by(just('+'))
+    ///     .collect::<Vec<_>>()
+    ///     .map(|xs| { runs.set(runs.get() + 1); xs.iter().sum() })
+    ///     .cached_rule(&cache);
+    ///
+    /// assert_eq!(sum.parse("1+2+3").into_result(), Ok(6));
+    /// assert_eq!(sum.parse("1+2+3").into_result(), Ok(6));
+    /// assert_eq!(runs.get(), 1);
+    ///
+    /// cache.invalidate("1+2+3");
+    /// assert_eq!(sum.parse("1+2+3").into_result(), Ok(6));
+    /// assert_eq!(runs.get(), 2);
+    ///
+    /// // Checking the input doesn't produce an output, so there is nothing to cache
+    /// assert!(!sum.check("4+5").has_errors());
+    /// assert_eq!(cache.len(), 1);
+    ///
+    /// // Fragments that repeat within an input are reused too
+    /// let sums = sum.separated_by(just(',')).collect::<Vec<_>>();
+    /// assert_eq!(sums.parse("4+5,4+5,4+5").into_result(), Ok(vec![9; 3]));
+    /// assert_eq!(runs.get(), 3);
+    /// assert_eq!(cache.len(), 2);
+    /// ```
+    #[cfg(feature = "unstable")]
+    fn cached_rule<K>(self, cache: &cache::RuleCache<K, O>) -> cache::CachedRule<'_, Self, K, O>
+    where
+        Self: Sized,
+        E: ParserExtra<'a, I, Context = ()>,
+        O: Clone,
+    {
+        cache::CachedRule {
+            parser: self,
+            cache,
+        }
+    }
+
     /// Memoize the parser such that later attempts to parse the same input 'remember' the attempt and exit early.
     ///
     /// If you're finding that certain inputs produce exponential behavior in your parser, strategically applying
@@ -809,8 +1402,91 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     where
         Self: Sized,
     {
-        Memoized { parser: self }
-    }
+        Memoized {
+            parser: self,
+            id: ParserId::next(),
+        }
+    }
+
+    /// Memoize both the successes and failures of this parser, such that later attempts to parse from the same
+    /// position reuse the earlier result (including its output) instead of parsing again. This is 'packrat' parsing.
+    ///
+    /// Where [`Parser::memoized`] only remembers failures, this also remembers outputs, so a rule that is tried by
+    /// many alternatives in turn (such as the left operand of every binary operator) is only parsed once at each
+    /// position. Applying this to every rule of a backtracking grammar makes it parse in linear time. Like
+    /// [`Parser::memoized`], this also permits [left recursion](https://en.wikipedia.org/wiki/Left_recursion).
+    ///
+    /// Outputs are always generated (even when only checking the input), so that they can be reused. Errors emitted
+    /// during recovery, and warnings, are reused too. Results are only reused for the same input and context: parsers
+    /// within [`Parser::nested_in`], or that see a different context (such as via [`Parser::then_with_ctx`]), start
+    /// afresh. Results are discarded once the parse is finished.
+    ///
+    /// Changes made to the parser state are *not* repeated when a result is reused, so this should not be applied to
+    /// parsers that update the state (such as via [`Parser::map_with`]) unless those updates only need to happen once.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// # use std::cell::Cell;
+    /// let attempts = Cell::new(0);
+    /// let term = text::int::<_, _, extra::Err<Simple<char>>>(10)
+    ///     .map(|n| {
+    ///         attempts.set(attempts.get() + 1);
+    ///         n
+    ///     })
+    ///     .packrat();
+    /// // Each alternative starts by parsing a term again
+    /// let expr = choice((
+    ///     term.clone().then_ignore(just('+')).then(term.clone()),
+    ///     term.clone().then_ignore(just('-')).then(term.clone()),
+    ///     term.clone().then_ignore(just('*')).then(term.clone()),
+    /// ));
+    ///
+    /// assert_eq!(expr.parse("12*34").into_result(), Ok(("12", "34")));
+    /// // The first term was only parsed once
+    /// assert_eq!(attempts.get(), 2);
+    /// ```
+    #[cfg(feature = "memoization")]
+    fn packrat(self) -> Packrat<'a, Self, I, O, E>
+    where
+        Self: Sized,
+    {
+        Packrat::new(self)
+    }
+
+    /// Give this parser a [`ParserId`], so that it can be referred to by tooling such as coverage reports or
+    /// visualizations.
+    ///
+    /// The identifier is assigned when this method is called, and can be retrieved with [`Identified::id`]. The
+    /// parser itself behaves exactly like the inner parser.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let digits = text::digits::<_, _, extra::Err<Simple<char>>>(10).to_slice().identified();
+    /// let letters = text::ascii::ident::<&str, _, extra::Err<Simple<char>>>().identified();
+    ///
+    /// // Parsers constructed later get larger identifiers, and clones share their identifier
+    /// assert!(digits.id() < letters.id());
+    /// assert_eq!(digits.clone().id(), digits.id());
+    ///
+    /// assert_eq!(digits.parse("123").into_result(), Ok("123"));
+    /// ```
+    fn identified(self) -> Identified<Self>
+    where
+        Self: Sized,
+    {
+        Identified {
+            parser: self,
+            id: ParserId::next(),
+        }
+    }
 
     /// Transform all outputs of this parser to a predetermined value.
     ///
@@ -847,6 +1523,10 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     /// Labelling a parser makes all errors generated by the parser refer to the label rather than any sub-elements
     /// within the parser. For example, labelling a parser for an expression would yield "expected expression" errors
     /// rather than "expected integer, string, binary op, etc." errors.
+    ///
+    /// When the parser succeeds, the label and the span it parsed are given to the parser state (see
+    /// [`on_label`](inspector::Inspector::on_label)), such as to derive [`Highlights`](inspector::Highlights) from the
+    /// labels of a grammar.
     // TODO: Example
     #[cfg(feature = "label")]
     fn labelled<L>(self, label: L) -> Labelled<Self, L>
@@ -861,6 +1541,102 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         }
     }
 
+    /// Label this parser with the given label, like [`Parser::labelled`], and record the label in a grammar-wide
+    /// [`RuleRegistry`](label::RuleRegistry) along with the location of this call.
+    ///
+    /// Labels that are registered from more than one place are flagged by
+    /// [`RuleRegistry::duplicates`](label::RuleRegistry::duplicates).
+    ///
+    /// # Examples
+    ///
+    /// See [`RuleRegistry`](label::RuleRegistry).
+    #[cfg(feature = "label")]
+    #[track_caller]
+    fn labelled_in<L>(self, registry: &label::RuleRegistry<L>, label: L) -> Labelled<Self, L>
+    where
+        Self: Sized,
+        L: Clone + PartialEq,
+        E::Error: LabelError<'a, I, L>,
+    {
+        registry.register(label.clone(), core::panic::Location::caller());
+        self.labelled(label)
+    }
+
+    /// Summarize the tokens that this parser expects when reporting errors, replacing each expected token for which the
+    /// given function produces a label with that label.
+    ///
+    /// This sits between [`Parser::labelled`], which replaces everything the parser expects with a single label, and
+    /// the full set of expected tokens. For example, a parser for binary operators might summarize all of its operator
+    /// tokens as "operator", while still reporting the other tokens it expects (such as a closing delimiter)
+    /// individually.
+    ///
+    /// Unlike [`Parser::labelled`], this applies to all errors produced within the parser, not only those at its start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, error::{RichPattern, RichReason}};
+    /// let op = one_of::<_, _, extra::Err<Rich<char>>>("+-*/");
+    /// let expr = text::int(10)
+    ///     .separated_by(op)
+    ///     .at_least(1)
+    ///     .then_ignore(just(';'))
+    ///     .simplify_expected(|c: &char| "+-*/".contains(*c).then_some("operator"));
+    ///
+    /// let err = &expr.parse("1+2").into_errors()[0];
+    /// let RichReason::ExpectedFound { expected, .. } = err.reason() else { panic!() };
+    /// assert_eq!(expected, &vec![RichPattern::Label("operator"), RichPattern::Token(';'.into())]);
+    /// ```
+    #[cfg(feature = "label")]
+    fn simplify_expected<L, F>(self, f: F) -> SimplifyExpected<Self, F>
+    where
+        Self: Sized,
+        F: Fn(&I::Token) -> Option<L>,
+        E::Error: LabelError<'a, I, L>,
+    {
+        SimplifyExpected {
+            parser: self,
+            summarize: f,
+        }
+    }
+
+    /// Report the run of input found at the site of each error produced by this parser (such as a whole word or
+    /// operator), rather than just its first token.
+    ///
+    /// A run is a sequence of identifier characters, a sequence of other non-whitespace characters, or a single
+    /// whitespace character, and is cut off after `max_len` tokens. It is passed to the error through
+    /// [`FoundSliceError`](error::FoundSliceError): [`Rich`] displays it in place of the token that was found, while the
+    /// other built-in error types ignore it.
+    ///
+    /// This is usually applied once, to the top-level parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let call = text::ident::<_, _, extra::Err<Rich<char>>>()
+    ///     .then(text::int(10).delimited_by(just('('), just(')')));
+    ///
+    /// let err = &call.parse("foo(bar)").into_errors()[0];
+    /// assert_eq!(err.to_string(), "found b expected '0'");
+    ///
+    /// let err = &call.found_slices(16).parse("foo(bar)").into_errors()[0];
+    /// assert_eq!(err.found_slice(), Some("bar"));
+    /// assert_eq!(err.to_string(), "found bar expected '0'");
+    /// ```
+    fn found_slices(self, max_len: usize) -> FoundSlices<Self>
+    where
+        Self: Sized,
+        I: SliceInput<'a>,
+        I::Token: Char,
+        E::Error: FoundSliceError<'a, I>,
+    {
+        FoundSlices {
+            parser: self,
+            max_len,
+        }
+    }
+
     /// Parse one thing and then another thing, yielding a tuple of the two outputs.
     ///
     /// The output type of this parser is `(O, U)`, a combination of the outputs of both parsers.
@@ -893,6 +1669,46 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         }
     }
 
+    /// Like [`Parser::then`], but additionally requires that the two patterns are adjacent in the input, with nothing
+    /// (such as whitespace or comments) between them.
+    ///
+    /// Adjacency is determined by comparing the end of the first pattern's span with the start of the second's, so
+    /// this is most useful for token inputs whose spans record where each token appeared in the source (for example,
+    /// distinguishing `1..2` from `1 .. 2`, or `<=` from `< =`). When parsing text directly, any trivia-skipping should
+    /// be applied around the glued patterns rather than within them.
+    ///
+    /// The output type of this parser is `(O, U)`, a combination of the outputs of both parsers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// #[derive(Clone, PartialEq, Debug)]
+    /// enum Token { Lt, Eq }
+    ///
+    /// let le = just::<_, _, extra::Err<Rich<_>>>(Token::Lt).glued(just(Token::Eq)).to("<=");
+    ///
+    /// // `<=`
+    /// let adjacent = [(Token::Lt, (0..1).into()), (Token::Eq, (1..2).into())];
+    /// assert_eq!(le.parse(adjacent.as_slice().spanned((2..2).into())).into_result(), Ok("<="));
+    ///
+    /// // `< =`
+    /// let apart = [(Token::Lt, (0..1).into()), (Token::Eq, (2..3).into())];
+    /// let errs = le.parse(apart.as_slice().spanned((3..3).into())).into_errors();
+    /// assert_eq!(errs[0].span(), &SimpleSpan::from(1..2));
+    /// ```
+    fn glued<U, B: Parser<'a, I, U, E>>(self, other: B) -> Glued<Self, B, O, U, E>
+    where
+        Self: Sized,
+        <I::Span as Span>::Offset: PartialOrd,
+    {
+        Glued {
+            parser_a: self,
+            parser_b: other,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
     /// Parse one thing and then another thing, yielding only the output of the latter.
     ///
     /// The output type of this parser is `U`, the same as the second parser.
@@ -984,6 +1800,9 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     /// `Stream<Iterator<Item = T>>`, `P` will be run first against that input, and is expected to
     /// output a new `Stream<Iterator<Item = T>>` which the original parser will be run against.
     ///
+    /// Spans produced while parsing the new input are relative to it. To parse part of the current input (such as the
+    /// contents of a string literal) with spans that refer to the original source, use [`Parser::nested_in_region`].
+    ///
     /// The output of this parser is `O`, the output of the parser it is called on.
     ///
     /// # Examples
@@ -1038,6 +1857,67 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         }
     }
 
+    /// Parse the part of the input matched by another parser, as if it were the whole input.
+    ///
+    /// `other` is run first to find the extent of the region, such as the inside of a string literal or the body of a
+    /// doc comment. The parser this was called on is then run over that region of the *same* input, and is expected to
+    /// consume all of it. Unlike [`Parser::nested_in`], no new input is created, so spans and slices produced within the
+    /// region refer to the original input, and errors point to the right place in the source. The output of `other`
+    /// is discarded.
+    ///
+    /// The output type of this parser is `O`, the output of the parser it is called on.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// #[derive(Debug, PartialEq)]
+    /// enum Part<'a> {
+    ///     Text(&'a str),
+    ///     Var(&'a str, SimpleSpan),
+    /// }
+    ///
+    /// // A string literal with interpolated variables, like `"Hello, {name}!"`
+    /// let part = choice((
+    ///     text::ascii::ident::<_, _, extra::Err<Rich<char>>>()
+    ///         .map_with(|name, e| Part::Var(name, e.span()))
+    ///         .delimited_by(just('{'), just('}')),
+    ///     none_of('{').repeated().at_least(1).to_slice().map(Part::Text),
+    /// ));
+    /// let string = part
+    ///     .repeated()
+    ///     .collect::<Vec<_>>()
+    ///     .nested_in_region(none_of('"').repeated())
+    ///     .delimited_by(just('"'), just('"'));
+    /// let assign = text::ascii::ident().padded().then_ignore(just('=')).then(string.padded());
+    ///
+    /// assert_eq!(
+    ///     assign.parse("greeting = \"Hello, {name}!\"").into_result(),
+    ///     Ok(("greeting", vec![
+    ///         Part::Text("Hello, "),
+    ///         Part::Var("name", (20..24).into()),
+    ///         Part::Text("!"),
+    ///     ])),
+    /// );
+    ///
+    /// // The unclosed interpolation is reported at the end of the string's contents, not the end of the input
+    /// let errs = assign.parse("greeting = \"Hello, {name\"").into_errors();
+    /// assert_eq!(errs[0].span(), &SimpleSpan::from(24..24));
+    /// ```
+    fn nested_in_region<OB, B: Parser<'a, I, OB, E>>(
+        self,
+        other: B,
+    ) -> NestedInRegion<Self, B, OB, E>
+    where
+        Self: Sized,
+    {
+        NestedInRegion {
+            parser_a: self,
+            parser_b: other,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
     /// Parse one thing and then another thing, creating the second parser from the result of
     /// the first. If you do need the context in the output, use [`Parser::then_with_ctx`].
     ///
@@ -1061,6 +1941,35 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     /// assert_eq!(successive_letters.parse(b"ab").into_result(), Ok(b'b')); // 'b' follows 'a'
     /// assert!(successive_letters.parse(b"ac").has_errors()); // 'c' does not follow 'a'
     /// ```
+    ///
+    /// Because the output of the first parser only exists as context, it can be used to pick how the rest of the
+    /// input gets parsed without cluttering the final output type. Here, a radix prefix decides which digits are
+    /// valid:
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let radix = just::<_, _, extra::Err<Rich<char>>>("0x").to(16)
+    ///     .or(just("0b").to(2))
+    ///     .or(empty().to(10));
+    ///
+    /// let digits = any::<&str, extra::Full<Rich<char>, (), u32>>()
+    ///     .try_map_with(|c: char, e| match c.to_digit(*e.ctx()) {
+    ///         Some(_) => Ok(c),
+    ///         None => Err(Rich::custom(e.span(), format!("'{c}' is not a digit in radix {}", e.ctx()))),
+    ///     })
+    ///     .repeated()
+    ///     .at_least(1)
+    ///     .to_slice()
+    ///     .map_with(|digits, e| u32::from_str_radix(digits, *e.ctx()).unwrap());
+    ///
+    /// // The output is just the number: the radix never shows up in it
+    /// let number = radix.ignore_with_ctx(digits);
+    ///
+    /// assert_eq!(number.parse("0x2A").into_result(), Ok(42));
+    /// assert_eq!(number.parse("0b101").into_result(), Ok(5));
+    /// assert_eq!(number.parse("17").into_result(), Ok(17));
+    /// assert!(number.parse("0b12").has_errors()); // '2' is not a binary digit
+    /// ```
     fn ignore_with_ctx<U, P>(
         self,
         then: P,
@@ -1420,6 +2329,12 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     /// The output type of this parser is, by default, `()`. If you want to collect the items into a [`Container`]
     /// (such as a [`Vec`]), use [`IterParser::collect`].
     ///
+    /// If the pattern fails right where another repetition would have begun (rather than partway through an item),
+    /// and whatever follows the repetition then fails at that same position, the error also includes what the pattern
+    /// expected there, since the pattern could have been repeated again instead. A repetition that stops because it
+    /// reached [`Repeated::at_most`] doesn't try the pattern again, so has nothing to add. The same goes for
+    /// [`Parser::separated_by`] and [`Parser::or_not`].
+    ///
     /// # Examples
     ///
     /// ```
@@ -1534,6 +2449,9 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     ///
     /// The output of the original parser must be of type `(A, impl IntoIterator<Item = B>)`.
     ///
+    /// The fold function is also given a [`MapExtra`], which provides access to the parser's state and to the span of
+    /// the input folded so far: from the start of the first item up to the end of the item being folded in.
+    ///
     /// The output type of this parser is `A`, the left-hand component of the original parser's output.
     ///
     /// # Examples
@@ -1603,6 +2521,38 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     ///     panic!("Not an Expr::Add");
     /// }
     /// ```
+    ///
+    /// ## Spans
+    ///
+    /// Each node of a left-nested AST can be given a span covering all of its operands.
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// #[derive(Debug, PartialEq)]
+    /// enum Expr {
+    ///     Var(char, SimpleSpan),
+    ///     Call(Box<Expr>, SimpleSpan),
+    /// }
+    ///
+    /// let var = any::<_, extra::Err<Simple<char>>>()
+    ///     .filter(char::is_ascii_alphabetic)
+    ///     .map_with(|c, e| Expr::Var(c, e.span()));
+    ///
+    /// let call = var.foldl_with(just("()").repeated(), |f, _, e| {
+    ///     Expr::Call(Box::new(f), e.span())
+    /// });
+    ///
+    /// assert_eq!(
+    ///     call.parse("f()()").into_result(),
+    ///     Ok(Expr::Call(
+    ///         Box::new(Expr::Call(
+    ///             Box::new(Expr::Var('f', (0..1).into())),
+    ///             (0..3).into(),
+    ///         )),
+    ///         (0..5).into(),
+    ///     )),
+    /// );
+    /// ```
     #[cfg_attr(debug_assertions, track_caller)]
     fn foldl_with<B, F, OB>(self, other: B, f: F) -> FoldlWith<F, Self, B, OB, E>
     where
@@ -1649,6 +2599,126 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         Rewind { parser: self }
     }
 
+    /// Once this parser succeeds, allow the input to discard everything that precedes the current position.
+    ///
+    /// This makes it possible to parse unbounded streams in bounded memory with an input such as
+    /// [`BoundedStream`](input::BoundedStream), provided the parser commits regularly (for example, after each record).
+    ///
+    /// After committing, the parser can no longer backtrack to before this point. Grammars should only commit once
+    /// they are certain that no enclosing combinator (such as [`Parser::or`]) will need to try an alternative. If one
+    /// does, parsing halts with [`Error::input_discarded`] (see [`Halt::Discarded`](extra::Halt::Discarded)).
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, extra::Halt, input::BoundedStream};
+    /// let record = text::digits::<_, _, extra::Err<Rich<_>>>(10).then_ignore(just(';'));
+    ///
+    /// // Committing after each record is fine...
+    /// let records = record.commit().repeated().count();
+    /// assert_eq!(records.parse(BoundedStream::from_iter("1;2;3;".chars())).into_result(), Ok(3));
+    ///
+    /// // ...but committing and then backtracking past the commit point is an error
+    /// let bad = record
+    ///     .commit()
+    ///     .then(just('!'))
+    ///     .ignored()
+    ///     .or(record.ignored().then_ignore(just('?')));
+    ///
+    /// let res = bad.parse(BoundedStream::from_iter("1;?".chars()));
+    /// assert_eq!(res.halted(), Some(Halt::Discarded));
+    /// assert_eq!(res.errors().next().unwrap().to_string(), "attempted to backtrack into input that has already been discarded");
+    /// ```
+    fn commit(self) -> Commit<Self>
+    where
+        Self: Sized,
+        I: CommitInput<'a>,
+    {
+        Commit { parser: self }
+    }
+
+    /// If this parser fails, prevent enclosing parsers from backtracking to try alternatives, so that the error
+    /// generated by this parser is reported.
+    ///
+    /// This is useful at points in a grammar after which the input can only be one thing. For example, after the
+    /// keyword `let`, the input must be a `let` statement: if the rest of it fails to parse, there is no point trying
+    /// to parse the input as an expression instead. Cutting makes the error more accurate, and avoids the cost of
+    /// trying alternatives that can only fail.
+    ///
+    /// A cut affects alternatives tried by [`Parser::or`], [`choice`], [`Parser::or_not`], [`Parser::repeated`],
+    /// [`Parser::separated_by`] and similar, all the way up to the nearest error recovery strategy (see
+    /// [`Parser::recover_with`]), which may still recover from the error.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let ident = text::ascii::ident::<_, _, extra::Err<Rich<char>>>().padded();
+    /// let let_stmt = text::ascii::keyword("let")
+    ///     .ignore_then(ident.then_ignore(just('=')).then(ident).cut())
+    ///     .map(|(lhs, rhs)| format!("{lhs} = {rhs}"));
+    /// let stmts = let_stmt
+    ///     .or(ident.map(str::to_string))
+    ///     .repeated()
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(stmts.parse("let a = b c").into_result(), Ok(vec!["a = b".to_string(), "c".to_string()]));
+    ///
+    /// // Without the cut, this would be parsed as the identifiers `let`, `a` and `b`
+    /// let errs = stmts.parse("let a b").into_errors();
+    /// assert_eq!(errs[0].to_string(), "found b expected '='");
+    /// ```
+    fn cut(self) -> Cut<Self>
+    where
+        Self: Sized,
+    {
+        Cut { parser: self }
+    }
+
+    /// Succeed if this parser matches the input *immediately before* the current position, without consuming
+    /// anything.
+    ///
+    /// Starting positions up to `max_len` tokens back are tried, nearest first. The first attempt that finishes exactly
+    /// at the current position determines the output. Because the pattern is parsed forwards, the input must support
+    /// stepping backwards (see [`LookbehindInput`](input::LookbehindInput)). To inspect individual tokens directly,
+    /// [`InputRef::peek_back`] can be used instead.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// // A newline terminates a statement unless it is preceded by a backslash
+    /// let terminator = just::<_, _, extra::Err<Simple<char>>>('\\')
+    ///     .lookbehind(1)
+    ///     .not()
+    ///     .ignore_then(just('\n'));
+    ///
+    /// let stmt = any()
+    ///     .and_is(terminator.not())
+    ///     .repeated()
+    ///     .to_slice()
+    ///     .then_ignore(terminator);
+    ///
+    /// assert_eq!(stmt.parse("a + b\n").into_result(), Ok("a + b"));
+    /// assert_eq!(stmt.parse("a + \\\nb\n").into_result(), Ok("a + \\\nb"));
+    /// ```
+    fn lookbehind(self, max_len: usize) -> Lookbehind<Self>
+    where
+        Self: Sized,
+        I: LookbehindInput<'a>,
+    {
+        Lookbehind {
+            parser: self,
+            max_len,
+        }
+    }
+
     /// Make the parser lazy, such that it parses as much as it validly can and then finished successfully, leaving
     /// trailing input untouched.
     ///
@@ -1724,6 +2794,10 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     /// run into cases where valid syntax fails to parse without errors, this might be happening: consider removing
     /// error recovery or switching to a more specific error recovery strategy.
     ///
+    /// Several strategies can be given as a tuple, such as `(a, b, c)`. They are tried in order, each starting from
+    /// where the parser failed, until one of them succeeds. Strategies that always succeed (like
+    /// [`insert_missing`](recovery::insert_missing)) should come last.
+    ///
     /// The output type of this parser is `O`, the same as the original parser.
     ///
     /// # Examples
@@ -1773,35 +2847,220 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         }
     }
 
-    /// Map the primary error of this parser to another value.
+    /// Like [`Parser::recover_with`], but any changes that this parser made to the parser state are undone before
+    /// recovery is attempted.
     ///
-    /// This function is most useful when using a custom error type, allowing you to augment errors according to
-    /// context.
+    /// Without this, a parser that fails partway through may leave the state partially updated (such as a symbol that
+    /// was registered before the rest of its declaration failed to parse), which can confuse subsequent parsing. The
+    /// state is restored by cloning it before this parser runs, so this is best used on coarse regions (such as whole
+    /// declarations or statements) rather than on small, frequently-used parsers.
+    ///
+    /// The state is also restored each time a strategy retries this parser and it fails again.
     ///
     /// The output type of this parser is `O`, the same as the original parser.
-    // TODO: Map E -> D, not E -> E
-    fn map_err<F>(self, f: F) -> MapErr<Self, F>
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, inspector::SimpleState};
+    /// type Extra<'a> = extra::Full<Simple<'a, char>, SimpleState<Vec<&'a str>>, ()>;
+    ///
+    /// // Declarations register the names they declare
+    /// let decl = text::ascii::keyword("let")
+    ///     .ignore_then(text::ascii::ident::<_, _, Extra>().padded().map_with(|name, e| {
+    ///         e.state().push(name);
+    ///         name
+    ///     }))
+    ///     .then_ignore(just('='))
+    ///     .then(text::int(10).padded())
+    ///     .then_ignore(just(';'))
+    ///     .map(Some)
+    ///     // A broken declaration doesn't declare anything
+    ///     .recover_via_state_reset(via_parser(none_of(';').repeated().then(just(';')).to(None)));
+    /// let decls = decl.padded().repeated().collect::<Vec<_>>();
+    ///
+    /// let mut names = SimpleState(Vec::new());
+    /// let res = decls.parse_with_state("let x = 1; let y = ; let z = 3;", &mut names);
+    /// assert_eq!(res.output(), Some(&vec![Some(("x", "1")), None, Some(("z", "3"))]));
+    /// assert_eq!(names.0, ["x", "z"]);
+    /// ```
+    fn recover_via_state_reset<S>(self, strategy: S) -> RecoverWith<ResetState<Self>, S>
     where
         Self: Sized,
-        F: Fn(E::Error) -> E::Error,
+        E::State: Clone,
+        S: Strategy<'a, I, O, E>,
     {
-        MapErr {
-            parser: self,
-            mapper: f,
+        RecoverWith {
+            parser: ResetState { parser: self },
+            strategy,
         }
     }
 
-    // /// Map the primary error of this parser to another value, making use of the span from the start of the attempted
-    // /// to the point at which the error was encountered.
-    // ///
-    // /// This function is useful for augmenting errors to allow them to display the span of the initial part of a
-    // /// pattern, for example to add a "while parsing" clause to your error messages.
-    // ///
-    // /// The output type of this parser is `O`, the same as the original parser.
-    // ///
-    // // TODO: Map E -> D, not E -> E
-    // fn map_err_with_span<F>(self, f: F) -> MapErrWithSpan<Self, F>
-    // where
+    /// Label this parser as a context (see [`Labelled::as_context`]) and, should it fail, recover by skipping to the
+    /// end of that context.
+    ///
+    /// The end of the context is found by skipping input until `end` (such as the `;` that terminates a statement)
+    /// successfully parses, consuming it. As with [`sync_to`](recovery::sync_to), nested `delimiters` are skipped as
+    /// a whole, so an `end` within them (such as a `;` within a block) is not mistaken for the end of this context.
+    /// Skipping also stops before a closing delimiter that belongs to an enclosing context (such as the `}` at the end
+    /// of the enclosing block), or at the end of the input, since this context can't extend past either.
+    ///
+    /// The error is reported within the context of `label`, and `fallback` is used to generate an output from the
+    /// span of the skipped input. If nothing could be skipped, recovery fails.
+    ///
+    /// This is shorthand for `self.labelled(label).as_context()` followed by
+    /// [`recover_with`](Parser::recover_with)`(`[`sync_to`](recovery::sync_to)`(end.ignored(), delimiters, fallback))`.
+    /// Chumsky doesn't keep track of the contexts that enclose a parser while parsing, so the end of the context is not
+    /// derived from `label` or from enclosing contexts: it must be given by `end`, and enclosing contexts are only
+    /// recognised by their closing `delimiters`.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// #[derive(Debug, PartialEq)]
+    /// enum Stmt<'a> {
+    ///     Error,
+    ///     Let(&'a str, &'a str),
+    /// }
+    ///
+    /// let stmt = text::ascii::keyword::<_, _, _, extra::Err<Rich<char>>>("let")
+    ///     .ignore_then(text::ascii::ident().padded())
+    ///     .then_ignore(just('='))
+    ///     .then(text::int(10).padded())
+    ///     .then_ignore(just(';'))
+    ///     .map(|(name, val)| Stmt::Let(name, val))
+    ///     .or_recover_to_end_of("statement", just(';'), [('{', '}')], |_| Stmt::Error)
+    ///     .padded();
+    ///
+    /// // The `;`s within the braces don't end the broken statement
+    /// let (stmts, errs) = stmt
+    ///     .clone()
+    ///     .repeated()
+    ///     .collect::<Vec<_>>()
+    ///     .parse("let x = 1; let y = { 2; 3 }; let z = 3;")
+    ///     .into_output_errors();
+    ///
+    /// assert_eq!(stmts, Some(vec![Stmt::Let("x", "1"), Stmt::Error, Stmt::Let("z", "3")]));
+    /// assert_eq!(errs.len(), 1);
+    /// assert_eq!(errs[0].contexts().next().map(|(l, _)| *l), Some("statement"));
+    ///
+    /// // The `}` that ends the enclosing block also ends the broken statement
+    /// let block = stmt
+    ///     .repeated()
+    ///     .collect::<Vec<_>>()
+    ///     .delimited_by(just('{'), just('}'));
+    /// let (stmts, errs) = block.parse("{ let x = 1; let y = }").into_output_errors();
+    ///
+    /// assert_eq!(stmts, Some(vec![Stmt::Let("x", "1"), Stmt::Error]));
+    /// assert_eq!(errs.len(), 1);
+    /// ```
+    #[cfg(feature = "label")]
+    fn or_recover_to_end_of<L, T, U, F, const N: usize>(
+        self,
+        label: L,
+        end: T,
+        delimiters: [(I::Token, I::Token); N],
+        fallback: F,
+    ) -> label::OrRecoverToEndOf<Self, L, T, U, I::Token, F, N>
+    where
+        Self: Sized,
+        E::Error: LabelError<'a, I, L>,
+        I::Token: PartialEq,
+        T: Parser<'a, I, U, E>,
+        F: Fn(I::Span) -> O,
+    {
+        RecoverWith {
+            parser: self.labelled(label).as_context(),
+            strategy: recovery::sync_to(end.ignored(), delimiters, fallback),
+        }
+    }
+
+    /// Map the primary error of this parser to another value.
+    ///
+    /// This function is most useful when using a custom error type, allowing you to augment errors according to
+    /// context.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    // TODO: Map E -> D, not E -> E
+    fn map_err<F>(self, f: F) -> MapErr<Self, F>
+    where
+        Self: Sized,
+        F: Fn(E::Error) -> E::Error,
+    {
+        MapErr {
+            parser: self,
+            mapper: f,
+        }
+    }
+
+    /// Turn errors produced by a [`FallibleInput`] (such as a lexer that encounters invalid text) into parser errors.
+    ///
+    /// A fallible input ends at the first error it encounters. If this parser fails at that point, or succeeds having
+    /// consumed everything up to it, the input's error is converted with the given function (which is also given the
+    /// span of the error, see [`FallibleInput::lex_error_span`]) and reported in place of the unexpected end of input.
+    /// This should usually be applied to the outermost parser, after [`end`], so that the error also takes precedence
+    /// over the input not having been fully consumed.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, input::FallibleStream};
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// enum Token { Num(u32), Plus }
+    ///
+    /// // A lexer that fails on characters it doesn't recognise
+    /// fn lex(src: &str) -> impl Iterator<Item = Result<Token, char>> + '_ {
+    ///     src.chars().filter(|c| !c.is_whitespace()).map(|c| match c {
+    ///         '+' => Ok(Token::Plus),
+    ///         c => c.to_digit(10).map(Token::Num).ok_or(c),
+    ///     })
+    /// }
+    ///
+    /// let num = select! { Token::Num(x) => x };
+    /// let sum = num
+    ///     .foldl(
+    ///         just::<_, _, extra::Err<Rich<_>>>(Token::Plus).ignore_then(num).repeated(),
+    ///         |a, b| a + b,
+    ///     )
+    ///     .then_ignore(end())
+    ///     .with_lex_errors(|c, span| Rich::custom(span, format!("unknown character '{c}'")));
+    ///
+    /// assert_eq!(sum.parse(FallibleStream::from_iter(lex("1 + 2 + 3"))).into_result(), Ok(6));
+    ///
+    /// let errs = sum.parse(FallibleStream::from_iter(lex("1 + 2 ? 3"))).into_errors();
+    /// assert_eq!(errs, vec![Rich::custom((3..3).into(), "unknown character '?'")]);
+    ///
+    /// let errs = sum.parse(FallibleStream::from_iter(lex("1 + ?"))).into_errors();
+    /// assert_eq!(errs, vec![Rich::custom((2..2).into(), "unknown character '?'")]);
+    /// ```
+    fn with_lex_errors<F>(self, f: F) -> WithLexErrors<Self, F>
+    where
+        Self: Sized,
+        I: FallibleInput<'a>,
+        F: Fn(&I::LexError, I::Span) -> E::Error,
+    {
+        WithLexErrors {
+            parser: self,
+            mapper: f,
+        }
+    }
+
+    // /// Map the primary error of this parser to another value, making use of the span from the start of the attempted
+    // /// to the point at which the error was encountered.
+    // ///
+    // /// This function is useful for augmenting errors to allow them to display the span of the initial part of a
+    // /// pattern, for example to add a "while parsing" clause to your error messages.
+    // ///
+    // /// The output type of this parser is `O`, the same as the original parser.
+    // ///
+    // // TODO: Map E -> D, not E -> E
+    // fn map_err_with_span<F>(self, f: F) -> MapErrWithSpan<Self, F>
+    // where
     //     Self: Sized,
     //     F: Fn(E::Error, I::Span) -> E::Error,
     // {
@@ -1919,6 +3178,38 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     /// As is seen in the above example, validation doesn't prevent the emission of later errors in the
     /// same parser, but still produces an error in the output.
     ///
+    /// A single validation may emit any number of errors and still transform the output. Problems that shouldn't
+    /// cause the parse to fail can be emitted as warnings with [`Emitter::warn`] instead, and are found with
+    /// [`ParseResult::warnings`]:
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let field = text::ascii::ident::<&str, _, extra::Err<Rich<char>>>().padded();
+    /// let fields = field
+    ///     .separated_by(just(','))
+    ///     .collect::<Vec<_>>()
+    ///     .validate(|fields: Vec<&str>, e, emitter| {
+    ///         let mut unique = Vec::new();
+    ///         for field in fields {
+    ///             if field.starts_with('_') {
+    ///                 emitter.warn(Rich::custom(e.span(), format!("field '{}' is unused", field)));
+    ///             } else if unique.contains(&field) {
+    ///                 emitter.emit(Rich::custom(e.span(), format!("duplicate field '{}'", field)));
+    ///             } else {
+    ///                 unique.push(field);
+    ///             }
+    ///         }
+    ///         unique.len()
+    ///     });
+    ///
+    /// let res = fields.parse("a, _b, c");
+    /// assert_eq!(res.warnings().len(), 1);
+    /// assert_eq!(res.into_result(), Ok(2));
+    ///
+    /// let res = fields.parse("a, b, a, b");
+    /// assert_eq!(res.errors().len(), 2);
+    /// assert_eq!(res.output(), Some(&2));
+    /// ```
     fn validate<U, F>(self, f: F) -> Validate<Self, O, F>
     where
         Self: Sized,
@@ -1947,6 +3238,53 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     //     }
     // }
 
+    /// Fall back on a closure if this parser fails, which may either produce an output in place of the parser or
+    /// refine its error.
+    ///
+    /// The closure is given the error that the parser failed with (from which, for error types like [`Rich`], the
+    /// expected and found tokens can be inspected) along with the span of the input from where the parser started to
+    /// where the error occurred. This is useful for overriding an awkward auto-generated diagnostic in one place
+    /// without needing a custom error type.
+    ///
+    /// If the closure returns [`Ok`], the parser succeeds with that value, without consuming any input. If it returns
+    /// [`Err`], the parser fails with that error instead, at the same location as the original error.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let value = text::int::<_, _, extra::Err<Rich<char>>>(10)
+    ///     .from_str::<u32>()
+    ///     .unwrapped()
+    ///     .or_else_with_span(|err, _| match err.found() {
+    ///         // A missing value defaults to zero
+    ///         None => Ok(0),
+    ///         Some(c) => Err(Rich::custom(*err.span(), format!("'{}' is not a number", c))),
+    ///     });
+    /// let setting = text::ascii::ident()
+    ///     .then_ignore(just('=').padded())
+    ///     .then(value);
+    ///
+    /// assert_eq!(setting.parse("width = 5").into_result(), Ok(("width", 5)));
+    /// assert_eq!(setting.parse("width =").into_result(), Ok(("width", 0)));
+    /// assert_eq!(
+    ///     setting.parse("width = x").into_errors(),
+    ///     vec![Rich::custom((8..9).into(), "'x' is not a number")],
+    /// );
+    /// ```
+    fn or_else_with_span<F>(self, f: F) -> OrElseWithSpan<Self, F>
+    where
+        Self: Sized,
+        F: Fn(E::Error, I::Span) -> Result<O, E::Error>,
+    {
+        OrElseWithSpan {
+            parser: self,
+            or_else: f,
+        }
+    }
+
     /// Attempt to convert the output of this parser into something else using Rust's [`FromStr`] trait.
     ///
     /// This is most useful when wanting to convert literal values into their corresponding Rust type, such as when
@@ -2284,6 +3622,89 @@ where
     }
 }
 
+/// An iterator that parses one item after another, with access to the parser state in between. See
+/// [`Parser::iter_with_state`].
+pub struct StateIter<'a, 'parse, P, I: Input<'a>, O, E: ParserExtra<'a, I>> {
+    parser: P,
+    own: InputOwn<'a, 'parse, I, E>,
+    stalled: bool,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<O>,
+}
+
+impl<'a, P, I: Input<'a>, O, E: ParserExtra<'a, I>> StateIter<'a, '_, P, I, O, E> {
+    /// Get the parser state, to inspect or update it before the next item is parsed.
+    pub fn state(&mut self) -> &mut E::State {
+        &mut self.own.state
+    }
+
+    /// Get the location, in the input, of the start of the next item.
+    pub fn offset(&self) -> usize {
+        I::cursor_location(&self.own.start)
+    }
+
+    /// Skip over some input with the given parser, such as the remainder of an item that failed to parse, resuming
+    /// iteration after it.
+    ///
+    /// Returns `false`, skipping nothing, if the parser fails. Errors generated by the parser are discarded.
+    pub fn skip_with<OS, S: Parser<'a, I, OS, E>>(&mut self, skipper: S) -> bool {
+        let mut inp = self.own.as_ref_start();
+        let before = inp.save();
+        let res = skipper.go::<Check>(&mut inp);
+        if res.is_err() {
+            inp.rewind(before);
+        }
+        let cursor = inp.cursor().inner;
+        self.own.errors.alt = None;
+        self.own.errors.secondary.clear();
+        if res.is_ok() {
+            self.own.start = cursor;
+            self.stalled = false;
+        }
+        res.is_ok()
+    }
+}
+
+impl<'a, P, I: Input<'a>, O, E: ParserExtra<'a, I>> Iterator for StateIter<'a, '_, P, I, O, E>
+where
+    P: Parser<'a, I, O, E>,
+{
+    type Item = ParseResult<O, E::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.stalled {
+            return None;
+        }
+        let mut inp = self.own.as_ref_start();
+        inp.peek_maybe()?;
+
+        let before = inp.save();
+        let res = self.parser.go::<Emit>(&mut inp);
+        let alt = inp.take_alt();
+        let mut errs = inp
+            .errors
+            .secondary
+            .drain(..)
+            .map(|err| err.err)
+            .collect::<Vec<_>>();
+        let out = match res {
+            Ok(out) => {
+                // An item that consumes no input would be produced forever
+                self.stalled = inp.cursor() == *before.cursor();
+                self.own.start = inp.cursor().inner;
+                Some(out)
+            }
+            Err(()) => {
+                inp.rewind(before);
+                errs.push(alt.err);
+                self.stalled = true;
+                None
+            }
+        };
+        Some(ParseResult::new(out, errs))
+    }
+}
+
 /// An iterable equivalent of [`Parser`], i.e: a parser that generates a sequence of outputs.
 pub trait IterParser<'a, I, O, E = extra::Default>: IterParserSealed<'a, I, O, E>
 where
@@ -2446,6 +3867,9 @@ where
     /// The output of the original parser must be of type `(impl IntoIterator<Item = A>, B)`. Because right-folds work
     /// backwards, the iterator must implement [`DoubleEndedIterator`] so that it can be reversed.
     ///
+    /// The fold function is also given a [`MapExtra`], which provides access to the parser's state and to the span of
+    /// the input folded so far: from the start of the item being folded in up to the end of the final value.
+    ///
     /// The output type of this parser is `B`, the right-hand component of the original parser's output.
     ///
     /// # Examples
@@ -2469,9 +3893,16 @@ where
     /// assert_eq!(signed.parse_with_state("3", &mut folds).into_result(), Ok(3));
     /// assert_eq!(signed.parse_with_state("-17", &mut folds).into_result(), Ok(-17));
     /// assert_eq!(signed.parse_with_state("--+-+-5", &mut folds).into_result(), Ok(5));
-    /// ```
-    ///
     ///
+    /// // Each fold spans from its sign to the end of the number
+    /// let spans = just::<_, _, extra::Default>('-')
+    ///     .repeated()
+    ///     .foldr_with(text::int(10).map(|_| Vec::new()), |_, mut spans: Vec<SimpleSpan>, e| {
+    ///         spans.push(e.span());
+    ///         spans
+    ///     });
+    /// assert_eq!(spans.parse("--42").into_result(), Ok(vec![(1..4).into(), (0..4).into()]));
+    /// ```
     #[cfg_attr(debug_assertions, track_caller)]
     fn foldr_with<B, F, OA>(self, other: B, f: F) -> FoldrWith<F, Self, B, O, E>
     where
@@ -2586,6 +4017,45 @@ where
     }
 }
 
+/// A parser that can only be used once, consuming itself. See [`Parser::map_once`].
+///
+/// One-shot parsers may have [`FnOnce`] closures (which, for example, move values that cannot be cloned) applied to
+/// their final output, which [`Parser::map`] cannot do, since a parser may run its mapping function any number of
+/// times.
+pub trait OneShotParser<'a, I, O, E = extra::Default>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+{
+    /// Parse a stream of tokens with the given state, consuming this parser. See [`Parser::parse_with_state`].
+    fn parse_once_with_state(self, input: I, state: &mut E::State) -> ParseResult<O, E::Error>
+    where
+        E::Context: Default;
+
+    /// Parse a stream of tokens, consuming this parser. See [`Parser::parse`].
+    fn parse_once(self, input: I) -> ParseResult<O, E::Error>
+    where
+        Self: Sized,
+        E::State: Default,
+        E::Context: Default,
+    {
+        self.parse_once_with_state(input, &mut E::State::default())
+    }
+
+    /// Map the final output of this parser to another value with a function that is called at most once. See
+    /// [`Parser::map_once`].
+    fn map_once<U, F: FnOnce(O) -> U>(self, f: F) -> MapOnce<Self, O, F>
+    where
+        Self: Sized,
+    {
+        MapOnce {
+            parser: self,
+            mapper: f,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
 /// See [`Parser::boxed`].
 ///
 /// Due to current implementation details, the inner value is not, in fact, a [`Box`], but is an [`Rc`](std::rc::Rc) to facilitate
@@ -2812,6 +4282,122 @@ macro_rules! select_ref {
     });
 }
 
+/// Build a parser from a compact, [PEG](https://en.wikipedia.org/wiki/Parsing_expression_grammar)-like notation.
+///
+/// This is useful for quickly prototyping a grammar. Each piece of notation is translated directly into the
+/// equivalent combinator, so the resulting parser is the same as one written by hand with the combinators below.
+///
+/// | Notation    | Meaning                             | Equivalent combinator                     |
+/// |-------------|-------------------------------------|-------------------------------------------|
+/// | `"abc"`     | A literal                           | [`just("abc")`](primitive::just)          |
+/// | `.`         | Any single token                    | [`any()`](primitive::any)                 |
+/// | `name`      | Another parser, by name             | `name.clone()`                            |
+/// | `{ expr }`  | Any Rust expression that's a parser | `expr`                                    |
+/// | `(a b)`     | Grouping                            |                                           |
+/// | `a b`       | Sequence                            | [`a.then(b)`](Parser::then)               |
+/// | `a / b`     | Ordered choice                      | [`a.or(b)`](Parser::or)                   |
+/// | `a*`        | Zero or more                        | [`a.repeated()`](Parser::repeated)        |
+/// | `a+`        | One or more                         | `a.repeated().at_least(1)`                |
+/// | `a?`        | Optional                            | [`a.or_not()`](Parser::or_not)            |
+/// | `&a`        | Positive lookahead                  | [`a.rewind()`](Parser::rewind)            |
+/// | `!a`        | Negative lookahead                  | [`a.not()`](Parser::not)                  |
+///
+/// Choice has the lowest precedence, followed by sequence, then lookahead, then the repetition operators. As with
+/// the combinators, repetitions produce `()`, and the outputs of the parsers in a sequence are nested into tuples.
+/// Use [`Parser::to_slice`] (or a `{ expr }` block) to produce something more useful.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let digit = one_of::<_, _, extra::Err<Simple<char>>>('0'..='9');
+/// let ident = text::ascii::ident();
+///
+/// // A number with an optional fraction, or an identifier that isn't a keyword
+/// let number = peg!(digit+ ("." digit+)?).to_slice();
+/// let name = peg!(!"let" ident).map(|((), name)| name);
+/// let atom = peg!(number / name);
+///
+/// assert_eq!(atom.parse("3.14").into_result(), Ok("3.14"));
+/// assert_eq!(atom.parse("pi").into_result(), Ok("pi"));
+/// assert!(atom.parse("let").has_errors());
+/// assert!(atom.parse("3.").has_errors());
+///
+/// // Equivalent to `just('(').then(close.not().then(any()).repeated()).then(close.clone())`
+/// let close = just::<_, _, extra::Default>(')');
+/// let parens = peg!('(' (!close .)* close).to_slice();
+/// assert_eq!(parens.parse("(a b c)").into_result(), Ok("(a b c)"));
+/// ```
+#[macro_export]
+macro_rules! peg {
+    // Choice: split the alternatives on top-level `/`s
+    (@choice [$($cur:tt)*] [$($alts:tt)*] / $($rest:tt)*) => {
+        $crate::peg!(@choice [] [$($alts)* [$($cur)*]] $($rest)*)
+    };
+    (@choice [$($cur:tt)*] [$($alts:tt)*] $t:tt $($rest:tt)*) => {
+        $crate::peg!(@choice [$($cur)* $t] [$($alts)*] $($rest)*)
+    };
+    (@choice [$($cur:tt)*] [$($alts:tt)*]) => {
+        $crate::peg!(@or $($alts)* [$($cur)*])
+    };
+    (@or [$($first:tt)*] $([$($alt:tt)*])*) => {
+        $crate::peg!(@seq [] [] $($first)*)
+            $(.or($crate::peg!(@seq [] [] $($alt)*)))*
+    };
+
+    // Sequence: gather lookahead prefixes, then an atom, then any repetition suffixes
+    (@seq [$($items:tt)*] [$($pre:tt)*] & $($rest:tt)*) => {
+        $crate::peg!(@seq [$($items)*] [$($pre)* &] $($rest)*)
+    };
+    (@seq [$($items:tt)*] [$($pre:tt)*] ! $($rest:tt)*) => {
+        $crate::peg!(@seq [$($items)*] [$($pre)* !] $($rest)*)
+    };
+    (@seq [$($items:tt)*] [$($pre:tt)*] ($($inner:tt)*) $($rest:tt)*) => {
+        $crate::peg!(@post [$($items)*] [$($pre)*] ($crate::peg!($($inner)*)) $($rest)*)
+    };
+    (@seq [$($items:tt)*] [$($pre:tt)*] {$($inner:tt)*} $($rest:tt)*) => {
+        $crate::peg!(@post [$($items)*] [$($pre)*] ({ $($inner)* }) $($rest)*)
+    };
+    (@seq [$($items:tt)*] [$($pre:tt)*] . $($rest:tt)*) => {
+        $crate::peg!(@post [$($items)*] [$($pre)*] ($crate::primitive::any()) $($rest)*)
+    };
+    (@seq [$($items:tt)*] [$($pre:tt)*] $lit:literal $($rest:tt)*) => {
+        $crate::peg!(@post [$($items)*] [$($pre)*] ($crate::primitive::just($lit)) $($rest)*)
+    };
+    (@seq [$($items:tt)*] [$($pre:tt)*] $name:ident $($rest:tt)*) => {
+        $crate::peg!(@post [$($items)*] [$($pre)*] (::core::clone::Clone::clone(&$name)) $($rest)*)
+    };
+    (@seq [$first:tt $($items:tt)*] []) => {
+        $first $(.then($items))*
+    };
+
+    (@post [$($items:tt)*] [$($pre:tt)*] $atom:tt * $($rest:tt)*) => {
+        $crate::peg!(@post [$($items)*] [$($pre)*] ($crate::Parser::repeated($atom)) $($rest)*)
+    };
+    (@post [$($items:tt)*] [$($pre:tt)*] $atom:tt + $($rest:tt)*) => {
+        $crate::peg!(@post [$($items)*] [$($pre)*] ($crate::Parser::repeated($atom).at_least(1)) $($rest)*)
+    };
+    (@post [$($items:tt)*] [$($pre:tt)*] $atom:tt ? $($rest:tt)*) => {
+        $crate::peg!(@post [$($items)*] [$($pre)*] ($crate::Parser::or_not($atom)) $($rest)*)
+    };
+    (@post [$($items:tt)*] [$($pre:tt)*] $atom:tt $($rest:tt)*) => {
+        $crate::peg!(@seq [$($items)* ($crate::peg!(@pre [$($pre)*] $atom))] [] $($rest)*)
+    };
+
+    // Lookahead prefixes apply from the inside out, so the first is outermost
+    (@pre [] $atom:tt) => { $atom };
+    (@pre [& $($pre:tt)*] $atom:tt) => {
+        $crate::Parser::rewind($crate::peg!(@pre [$($pre)*] $atom))
+    };
+    (@pre [! $($pre:tt)*] $atom:tt) => {
+        $crate::Parser::not($crate::peg!(@pre [$($pre)*] $atom))
+    };
+
+    ($($t:tt)+) => {
+        $crate::peg!(@choice [] [] $($t)+)
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
@@ -3098,6 +4684,131 @@ mod tests {
         assert_eq!(parser().parse("a+b+c").into_result().unwrap(), "abc");
     }
 
+    #[test]
+    #[cfg(feature = "memoization")]
+    fn left_recursive_seed_growing() {
+        use crate::{prelude::*, recursive::recursive_left};
+
+        let int = text::int::<_, _, extra::Err<Rich<char>>>(10)
+            .from_str::<i64>()
+            .unwrapped();
+        let expr = recursive_left::<_, i64, _, _, _>(|expr| {
+            choice((
+                expr.clone()
+                    .then_ignore(just('-'))
+                    .then(int)
+                    .map(|(a, b)| a - b),
+                expr.then_ignore(just('!')).map(|a| -a),
+                int,
+            ))
+        });
+
+        // Subtraction is left-associative
+        assert_eq!(expr.parse("10-2-3").into_result(), Ok(5));
+        assert_eq!(expr.parse("10-2!-3").into_result(), Ok(-11));
+        assert_eq!(expr.parse("7").into_result(), Ok(7));
+
+        assert!(expr.parse("10-2-").has_errors());
+    }
+
+    #[test]
+    #[cfg(feature = "memoization")]
+    fn packrat_reuse() {
+        use crate::prelude::*;
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        let attempts = AtomicUsize::new(0);
+        let parser = recursive(|expr| {
+            let atom = any::<_, extra::Err<Simple<char>>>()
+                .filter(|c: &char| c.is_alphabetic())
+                .map(|c| {
+                    attempts.fetch_add(1, Ordering::Relaxed);
+                    c.to_string()
+                })
+                .recover_with(via_parser(just('?').to("?".to_string())))
+                .packrat();
+
+            let sum = expr
+                .clone()
+                .then_ignore(just('+'))
+                .then(expr)
+                .map(|(a, b)| format!("{}{}", a, b))
+                .packrat();
+
+            sum.or(atom)
+        })
+        .then_ignore(end());
+
+        assert_eq!(parser.parse("a+b+c").into_result(), Ok("abc".to_string()));
+        assert_eq!(attempts.load(Ordering::Relaxed), 3);
+
+        // Recovered errors are reused along with outputs
+        let (out, errs) = parser.parse("a+?+c").into_output_errors();
+        assert_eq!(out, Some("a?c".to_string()));
+        assert_eq!(errs.len(), 1);
+
+        // Results are not reused between parses
+        attempts.store(0, Ordering::Relaxed);
+        assert!(parser.parse("a+").has_errors());
+        assert_eq!(parser.parse("x").into_result(), Ok("x".to_string()));
+        assert_eq!(attempts.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "memoization")]
+    fn packrat_scopes() {
+        use crate::prelude::*;
+        use alloc::sync::Arc;
+
+        // Warnings are replayed along with outputs
+        let word = text::ascii::ident::<_, _, extra::Err<Rich<char>>>()
+            .validate(|s: &str, e, emitter| {
+                if s.len() > 3 {
+                    emitter.warn(Rich::custom(e.span(), "long word"));
+                }
+                s
+            })
+            .packrat();
+        let parser = word
+            .clone()
+            .then_ignore(just('!'))
+            .or(word.then_ignore(just('?')));
+        let res = parser.parse("hello?");
+        assert_eq!(res.output(), Some(&"hello"));
+        assert_eq!(res.warnings().count(), 1);
+
+        // Results (and the outputs within them) are discarded once the parse is finished
+        let rc = Arc::new(());
+        let item = just::<_, _, extra::Err<Simple<char>>>('a')
+            .to(rc.clone())
+            .packrat();
+        let items = item.clone().then_ignore(just('b')).or(item);
+        let held = Arc::strong_count(&rc);
+        assert!(items.parse("a").into_result().is_ok());
+        assert_eq!(Arc::strong_count(&rc), held);
+
+        // Results within a nested parser don't evict those outside of it
+        let attempts = core::cell::Cell::new(0);
+        let digit = any::<_, extra::Err<Simple<char>>>()
+            .filter(char::is_ascii_digit)
+            .map(|c| {
+                attempts.set(attempts.get() + 1);
+                c
+            })
+            .packrat();
+        let group = just('(')
+            .ignore_then(digit.clone())
+            .then_ignore(just(')'))
+            .nested_in(just("(1)").to_slice());
+        let parser = digit
+            .clone()
+            .then(group.clone())
+            .then_ignore(just('!'))
+            .or(digit.then(group));
+        assert!(parser.parse("2(1)").into_result().is_ok());
+        assert_eq!(attempts.get(), 3);
+    }
+
     #[cfg(debug_assertions)]
     mod debug_asserts {
         use crate::prelude::*;
@@ -3246,10 +4957,533 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
-    fn todo_err() {
-        let expr = todo::<&str, String, extra::Default>();
-        expr.then_ignore(end()).parse("a+b+c");
+    fn recursive_group_returned() {
+        // Counts parity by alternating between two rules, each of which refers to the other
+        fn parser<'a>() -> impl Parser<'a, &'a str, bool> {
+            let group = recursive_group! {
+                even = just('a').ignore_then(odd).or(end().to(true));
+                odd = just('a').ignore_then(even).or(end().to(false));
+            };
+            group.even
+        }
+
+        assert_eq!(parser().parse("").into_result(), Ok(true));
+        assert_eq!(parser().parse("aaa").into_result(), Ok(false));
+        assert_eq!(parser().parse("aaaa").into_result(), Ok(true));
+        assert!(parser().parse("aab").has_errors());
+    }
+
+    #[test]
+    fn separated_by_with_separators() {
+        use crate::combinator::Separated::{self, *};
+
+        let items = any::<_, extra::Err<Simple<char>>>()
+            .filter(char::is_ascii_digit)
+            .separated_by(just('|'))
+            .allow_leading()
+            .with_separators();
+        let parser = items.collect::<Vec<_>>().then_ignore(just('|').or_not());
+
+        assert_eq!(
+            parser.parse("|1|2|").into_result(),
+            Ok(vec![
+                Separator('|', SimpleSpan::from(0..1)),
+                Item('1'),
+                Separator('|', SimpleSpan::from(2..3)),
+                Item('2'),
+            ]),
+        );
+        assert_eq!(parser.parse("").into_result(), Ok(Vec::new()));
+        // Separators are still parsed when no output is needed
+        assert_eq!(
+            items.count().to_slice().parse("|1|2").into_result(),
+            Ok("|1|2")
+        );
+        assert!(items
+            .collect::<Vec<Separated<_, _, _>>>()
+            .parse("|1|2|")
+            .has_errors());
+    }
+
+    #[test]
+    fn parser_fn_results() {
+        let f = crate::parser_fn!(
+            || just::<_, _, extra::Err<Rich<char>>>('a')
+                .repeated()
+                .at_least(1)
+                .count(),
+            Rich::into_owned,
+        );
+
+        assert_eq!(f("aaa"), Ok(3));
+        assert_eq!(f("ab").map_err(|errs| errs[0].span().start), Err(1));
+        // The function accepts inputs that don't outlive it
+        fn call<F: for<'a> Fn(&'a str) -> R, R>(f: &F, input: String) -> R {
+            f(&input)
+        }
+        assert_eq!(call(&f, "a".to_string()), Ok(1));
+
+        let g = crate::parser_fn!(|| text::digits::<_, _, extra::Err<Cheap>>(10).count());
+        assert_eq!(g("12"), Ok(2));
+        assert!(call(&g, "x".to_string()).is_err());
+    }
+
+    #[test]
+    fn separated_by_foldl_separators() {
+        let sum = text::int::<_, _, extra::Err<Simple<char>>>(10)
+            .from_str::<i64>()
+            .unwrapped()
+            .separated_by(one_of("+-").padded())
+            .allow_trailing()
+            .foldl_separators(|a, op, span: SimpleSpan, b| match op {
+                '+' => a + b,
+                _ => a - b * span.start as i64,
+            });
+
+        assert_eq!(sum.parse("7").into_result(), Ok(7));
+        // The span of the `-` is 5..8, including its padding
+        assert_eq!(sum.parse("1 + 2 - 3 +").into_result(), Ok(-12));
+        assert!(sum.parse("").has_errors());
+        assert!(sum.parse("+").has_errors());
+        // Separators are still parsed when no output is needed
+        assert_eq!(sum.to_slice().parse("1+2-").into_result(), Ok("1+2-"));
+    }
+
+    #[test]
+    fn separated_by_collect_pairs() {
+        let pairs = any::<_, extra::Err<Simple<char>>>()
+            .filter(char::is_ascii_digit)
+            .separated_by(just(',').padded())
+            .allow_leading()
+            .allow_trailing()
+            .at_least(1)
+            .collect_pairs();
+
+        assert_eq!(
+            pairs.parse(", 1 ,2").into_result(),
+            Ok(vec![('1', Some(SimpleSpan::from(3..5))), ('2', None)]),
+        );
+        assert_eq!(
+            pairs.parse("1,").into_result(),
+            Ok(vec![('1', Some(SimpleSpan::from(1..2)))]),
+        );
+        assert!(pairs.parse(",").has_errors());
+        // Separators are still parsed when no output is needed
+        assert_eq!(pairs.to_slice().parse("1,2,").into_result(), Ok("1,2,"));
+    }
+
+    #[test]
+    fn repeated_lazy_until() {
+        // The minimum is reached before the terminator is looked for
+        let parser = any::<_, extra::Err<Simple<char>>>()
+            .repeated()
+            .at_least(1)
+            .lazy_until(just('x'))
+            .collect::<String>()
+            .then(just('x').repeated().count());
+
+        assert_eq!(parser.parse("xxx").into_result(), Ok(("x".to_string(), 2)));
+        assert_eq!(
+            parser.parse("abxx").into_result(),
+            Ok(("ab".to_string(), 2))
+        );
+        assert_eq!(parser.parse("ab").into_result(), Ok(("ab".to_string(), 0)));
+        assert!(parser.parse("").has_errors());
+    }
+
+    #[test]
+    fn peg_notation() {
+        let a = just::<_, _, extra::Err<Simple<char>>>('a');
+        let b = just('b');
+
+        // `&` doesn't consume, and choices are ordered
+        let parser = peg!(&a (a b / b a)+ "c"?).to_slice();
+        assert_eq!(parser.parse("abbac").into_result(), Ok("abbac"));
+        assert_eq!(parser.parse("ab").into_result(), Ok("ab"));
+        assert!(parser.parse("ba").has_errors());
+
+        // Prefixes nest, with the first outermost
+        let not_ab = peg!(!&(a b) .).to_slice();
+        assert_eq!(not_ab.parse("a").into_result(), Ok("a"));
+        assert!(not_ab.parse("ab").has_errors());
+    }
+
+    #[test]
+    fn parse_with_scratch() {
+        use crate::input::ParseScratch;
+
+        let item = text::int::<_, _, extra::Err<Simple<char>>>(10)
+            .map(Some)
+            .recover_with(via_parser(any().to(None)));
+        let items = item.separated_by(just(',')).collect::<Vec<_>>();
+
+        let mut scratch = ParseScratch::new();
+        for _ in 0..3 {
+            let (out, errs) = items
+                .parse_with_scratch("1,x,3", &mut scratch)
+                .into_output_errors();
+            assert_eq!(out, Some(vec![Some("1"), None, Some("3")]));
+            assert_eq!(errs.len(), 1);
+
+            // Backtracking over errors leaves their space behind for the next parse to reuse
+            let either = just::<_, _, extra::Err<Rich<char>>>('a')
+                .validate(|c, e, emitter| {
+                    emitter.emit(Rich::custom(e.span(), "no"));
+                    c
+                })
+                .then(just('!'))
+                .or(just('a').then(just('?')));
+            let src = "a?".to_string();
+            assert!(either
+                .parse_with_scratch(src.as_str(), &mut scratch)
+                .into_result()
+                .is_ok());
+
+            // Errors don't carry over into the next parse
+            let res = items.parse_with_scratch("4,5", &mut scratch);
+            assert_eq!(res.into_result(), Ok(vec![Some("4"), Some("5")]));
+        }
+    }
+
+    #[test]
+    fn recover_with_strategy_tuple() {
+        // Replace a junk operand, or else skip ahead to the next number, or else pretend it was there
+        let num = text::int::<_, _, extra::Err<Simple<char>>>(10)
+            .recover_with((
+                via_parser(none_of("+0123456789").repeated().at_least(1).to("?")),
+                skip_then_retry_until(any().ignored(), end()),
+                insert_missing("0"),
+            ))
+            .padded();
+        let sum = num
+            .separated_by(just('+'))
+            .collect::<Vec<_>>()
+            .then_ignore(end());
+
+        let (out, errs) = sum.parse("1 + (a b) + +3 +").into_output_errors();
+        assert_eq!(out, Some(vec!["1", "?", "3", "0"]));
+        assert_eq!(errs.len(), 3);
+    }
+
+    #[test]
+    fn recoveries_recorded() {
+        let num = text::int::<_, _, extra::Err<Simple<char>>>(10)
+            .recover_with((
+                via_parser(none_of("+0123456789").repeated().at_least(1).to("?")),
+                skip_then_retry_until(any().ignored(), end()),
+            ))
+            .padded();
+        let sum = num.separated_by(just('+')).collect::<Vec<_>>();
+
+        let res = sum.parse("1+ñé ++3");
+        assert_eq!(res.output(), Some(&vec!["1", "?", "3"]));
+        let recoveries = res.recoveries().cloned().collect::<Vec<_>>();
+        assert_eq!(
+            recoveries,
+            vec![
+                crate::recovery::RecoveryEvent {
+                    strategy: "via_parser",
+                    skipped: 2..7,
+                    tokens: 3,
+                },
+                crate::recovery::RecoveryEvent {
+                    strategy: "skip_then_retry_until",
+                    skipped: 8..10,
+                    tokens: 2,
+                },
+            ],
+        );
+
+        // Recoveries within an alternative that is backtracked out of are not recorded
+        let parser = num.then_ignore(just(';')).or(just("x!").to("x"));
+        let res = parser.parse("x!");
+        assert_eq!(res.output(), Some(&"x"));
+        assert_eq!(res.recoveries().len(), 0);
+    }
+
+    #[test]
+    fn recover_insert_missing() {
+        let call = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+            .then(
+                text::int(10)
+                    .separated_by(just(','))
+                    .collect::<Vec<_>>()
+                    .delimited_by(just('('), just(')').recover_with(insert_missing(')'))),
+            )
+            .padded();
+        let calls = call.repeated().collect::<Vec<_>>();
+
+        let (out, errs) = calls.parse("f(1,2) g(3 h()").into_output_errors();
+        assert_eq!(
+            out,
+            Some(vec![("f", vec!["1", "2"]), ("g", vec!["3"]), ("h", vec![])]),
+        );
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(10..11));
+    }
+
+    #[test]
+    fn recover_sync_to() {
+        let stmt = just::<_, _, extra::Err<Simple<char>>>("ok;")
+            .map(|_| None)
+            .recover_with(sync_to(
+                one_of(";,").ignored(),
+                [('(', ')'), ('{', '}')],
+                |span: SimpleSpan| Some(span.into_range()),
+            ));
+        let stmts = stmt.repeated().collect::<Vec<_>>();
+
+        assert_eq!(
+            stmts.parse("ok;x(;{,});ok;y,ok;").into_output(),
+            Some(vec![None, Some(3..11), None, Some(14..16), None]),
+        );
+        // Skipping stops at the end of the input
+        assert_eq!(
+            stmts.parse("ok;x(;").into_output(),
+            Some(vec![None, Some(3..6)])
+        );
+        // Nothing can be skipped before a closing delimiter of the enclosing level
+        let block = stmts.delimited_by(just('('), just(')'));
+        assert_eq!(block.parse("(ok;})").into_output(), None);
+        assert_eq!(
+            block.parse("(ok;x)").into_output(),
+            Some(vec![None, Some(4..5)]),
+        );
+    }
+
+    #[test]
+    fn recover_as_error_node_span() {
+        use crate::recovery::HasErrorNode;
+
+        #[derive(Debug, PartialEq)]
+        enum Expr<'a> {
+            Var(&'a str),
+            Error(SimpleSpan),
+        }
+
+        impl HasErrorNode for Expr<'_> {
+            fn is_error_node(&self) -> bool {
+                matches!(self, Expr::Error(_))
+            }
+        }
+
+        let expr = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+            .map(Expr::Var)
+            .recover_with(recover_as_error_node(
+                none_of(";").repeated().at_least(1),
+                Expr::Error,
+            ));
+        let stmt = expr.then_ignore(just(';'));
+        let stmts = stmt.padded().repeated().collect::<Vec<_>>();
+
+        let (out, errs) = stmts.parse("a; 1 + 2; b;").into_output_errors();
+        assert_eq!(
+            out,
+            Some(vec![
+                Expr::Var("a"),
+                Expr::Error(SimpleSpan::from(3..8)),
+                Expr::Var("b"),
+            ]),
+        );
+        assert_eq!(errs.len(), 1);
+        assert!(out.unwrap()[1].is_error_node());
+
+        // Recovery fails if nothing can be skipped
+        assert_eq!(stmts.parse("a; ;").into_output(), None);
+    }
+
+    #[test]
+    fn recover_skip_balanced() {
+        let block = just::<_, _, extra::Err<Simple<char>>>("{}")
+            .to(true)
+            .recover_with(skip_balanced([('{', '}'), ('(', ')'), ('[', ']')], |_| {
+                false
+            }));
+        let blocks = block.repeated().collect::<Vec<_>>();
+
+        // Mismatched delimiters within the block don't stop it from being closed
+        let (out, errs) = blocks.parse("{}{([)x}{}").into_output_errors();
+        assert_eq!(out, Some(vec![true, false, true]));
+        assert_eq!(errs.len(), 1);
+
+        // An unclosed block extends to the end of the input
+        let (out, errs) = blocks.parse("{}{(x)").into_output_errors();
+        assert_eq!(out, Some(vec![true, false]));
+        assert_eq!(errs.len(), 1);
+
+        // Nothing can be skipped at a closing delimiter
+        assert!(block.parse(")").has_errors());
+        assert!(block.parse(")").output().is_none());
+    }
+
+    #[test]
+    fn recover_via_custom_with_ctx() {
+        use crate::input::InputRef;
+
+        type Extra<'a> = extra::Full<Simple<'a, char>, (), char>;
+
+        // Recovery skips to the terminator given by the context
+        let item = text::int::<_, _, Extra>(10)
+            .then_ignore(just(';').or(just(',')))
+            .map(Some)
+            .recover_with(via_custom(|inp: &mut InputRef<&str, Extra>, _| {
+                let term = *inp.ctx();
+                inp.peek()?;
+                while matches!(inp.next(), Some(c) if c != term) {}
+                Some(None)
+            }));
+        let items = empty::<_, extra::Err<Simple<char>>>()
+            .ignore_then(item.repeated().collect::<Vec<_>>().with_ctx(','));
+
+        let (out, errs) = items.parse("1,x;2,3,").into_output_errors();
+        assert_eq!(out, Some(vec![Some("1"), None, Some("3")]));
+        assert_eq!(errs.len(), 1);
+    }
+
+    #[test]
+    fn or_else_with_span() {
+        let parser = just::<_, _, extra::Err<Rich<char>>>("ab")
+            .then(just('c'))
+            .to_slice()
+            .or_else_with_span(|err, span| {
+                if err.found() == Some(&'x') {
+                    Ok("x")
+                } else {
+                    Err(Rich::custom(span, "expected 'abc'"))
+                }
+            })
+            .then(any().repeated().to_slice());
+
+        assert_eq!(parser.parse("abcd").into_result(), Ok(("abc", "d")));
+        // Replacement outputs don't consume any input
+        assert_eq!(parser.parse("abx").into_result(), Ok(("x", "abx")));
+        // The span covers the input from the start of the parser to the error
+        assert_eq!(
+            parser.parse("aby").into_errors(),
+            vec![Rich::custom((0..2).into(), "expected 'abc'")],
+        );
+    }
+
+    #[test]
+    fn nested_in_region() {
+        // A doc comment whose body may contain `code` spans
+        let code = none_of::<_, _, extra::Err<Rich<char>>>('`')
+            .repeated()
+            .to_slice()
+            .map_with(|code, e| (code, e.span()))
+            .nested_in_region(none_of('`').repeated())
+            .delimited_by(just('`'), just('`'));
+        let body = code
+            .map(Some)
+            .or(none_of('`').to(None))
+            .repeated()
+            .collect::<Vec<_>>()
+            .map(|parts| parts.into_iter().flatten().collect::<Vec<_>>());
+        let doc = body
+            .nested_in_region(any().and_is(just('\n').not()).repeated())
+            .delimited_by(just("/// "), just('\n'));
+        let docs = doc.repeated().collect::<Vec<_>>();
+
+        assert_eq!(
+            docs.parse("/// Call `f` twice\n/// Or `g`\n").into_result(),
+            Ok(vec![
+                vec![("f", SimpleSpan::from(10..11))],
+                vec![("g", SimpleSpan::from(27..28))],
+            ]),
+        );
+
+        // Lookahead within a region doesn't see beyond its end
+        let not_followed = just::<_, _, extra::Err<Simple<char>>>('a')
+            .then_ignore(just('b').not())
+            .nested_in_region(just('a'))
+            .then(just('b'));
+        assert_eq!(not_followed.parse("ab").into_result(), Ok(('a', 'b')));
+        let errs = docs.parse("/// Unclosed `f\n").into_errors();
+        assert_eq!(errs[0].span(), &SimpleSpan::from(15..15));
+    }
+
+    #[test]
+    fn normalized_input() {
+        use crate::input::Input;
+
+        let kw = |kw: &'static str| {
+            text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+                .and_is(just(kw))
+                .padded()
+        };
+        let parser = kw("select")
+            .ignore_then(
+                text::ascii::ident()
+                    .then_ignore(just('x').lookbehind(1).not())
+                    .padded(),
+            )
+            .then_ignore(kw("from"))
+            .then(text::ascii::ident().padded());
+
+        let normalize = |c| text::fold_width(text::fold_case(c));
+        assert_eq!(
+            parser
+                .parse("SELECT Name FROM ｕｓｅｒｓ".normalize(normalize))
+                .into_result(),
+            Ok(("Name", "ｕｓｅｒｓ")),
+        );
+        // Lookbehind sees the normalized tokens too
+        assert!(parser
+            .parse("select AX from users".normalize(normalize))
+            .has_errors());
+
+        // Keywords are compared against the normalized tokens, not the slice of the input
+        let select = text::ascii::keyword::<_, _, _, extra::Err<Simple<char>>>("select");
+        assert_eq!(
+            select
+                .parse("SELECT".normalize(text::fold_case))
+                .into_result(),
+            Ok("SELECT"),
+        );
+        assert!(select
+            .parse("SELECTED".normalize(text::fold_case))
+            .has_errors());
+        assert_eq!(
+            text::unicode::keyword::<_, _, _, extra::Err<Simple<char>>>("select")
+                .parse("ｓｅｌｅｃｔ".normalize(text::fold_width))
+                .into_result(),
+            Ok("ｓｅｌｅｃｔ"),
+        );
+        assert_eq!(
+            text::ascii::keyword_ignore_case::<_, _, _, extra::Err<Simple<char>>>("select")
+                .parse("ＳｅＬＥＣＴ".normalize(text::fold_width))
+                .into_result(),
+            Ok("ＳｅＬＥＣＴ"),
+        );
+    }
+
+    #[test]
+    fn validate_warnings() {
+        let warn_on = |c: char| {
+            just::<_, _, extra::Err<Simple<char>>>(c).validate(|c, e, emitter| {
+                emitter.warn(crate::Error::<&str>::expected_found([], None, e.span()));
+                c
+            })
+        };
+
+        let parser = warn_on('a')
+            .then(just('b'))
+            .or(warn_on('a').then(just('c')));
+
+        // Warnings from a branch that was rewound are discarded
+        let res = parser.parse("ac");
+        assert_eq!(res.warnings().len(), 1);
+        assert_eq!(res.into_result(), Ok(('a', 'c')));
+
+        assert_eq!(parser.check("ab").into_warnings().len(), 1);
+        assert_eq!(parser.parse("ad").warnings().len(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn todo_err() {
+        let expr = todo::<&str, String, extra::Default>();
+        expr.then_ignore(end()).parse("a+b+c");
     }
 
     #[test]
@@ -3482,6 +5716,43 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "unstable")]
+    fn cached_rule_fragments() {
+        use core::cell::Cell;
+
+        let runs = Cell::new(0);
+        let cache = crate::cache::RuleCache::<String, Vec<char>>::new();
+        let item = any::<_, extra::Default>()
+            .filter(|c: &char| c.is_ascii_alphanumeric())
+            .repeated()
+            .at_least(1)
+            .collect::<Vec<_>>()
+            .map(|cs| {
+                runs.set(runs.get() + 1);
+                cs
+            })
+            .cached_rule(&cache);
+        let items = item.separated_by(just(',')).collect::<Vec<_>>();
+
+        // Each distinct fragment is parsed once, wherever it appears
+        let out = items.parse("ab,abc,ab,abc,ab").into_result().unwrap();
+        assert_eq!(out.len(), 5);
+        assert_eq!(out[3], vec!['a', 'b', 'c']);
+        assert_eq!(runs.get(), 2);
+        assert_eq!(cache.len(), 2);
+
+        assert_eq!(
+            items.parse("abc").into_result(),
+            Ok(vec![vec!['a', 'b', 'c']])
+        );
+        assert_eq!(runs.get(), 2);
+
+        // Failed parses aren't cached
+        assert!(items.parse("ab,").has_errors());
+        assert_eq!(cache.len(), 2);
+    }
+
     #[test]
     #[allow(dead_code)]
     fn map_with_compiles() {
@@ -3536,4 +5807,1290 @@ mod tests {
         <Rich<_, _, _> as LabelError<&str, _>>::label_with(&mut err, "greeting");
         assert_eq!(parser2().parse("goodbye").into_errors(), vec![err]);
     }
+
+    #[cfg(feature = "label")]
+    #[test]
+    fn keyword_labelled() {
+        use crate::label::LabelError;
+
+        // The error for a different identifier is at its start, so the label replaces what was expected
+        let expected = |span: SimpleSpan| {
+            let mut err = <Rich<_> as crate::Error<&str>>::expected_found(None, None, span);
+            <Rich<_, _, _> as LabelError<&str, _>>::label_with(&mut err, "keyword");
+            vec![err]
+        };
+        let keywords: [Boxed<&str, &str, extra::Err<Rich<char>>>; 3] = [
+            text::ascii::keyword("let").boxed(),
+            text::ascii::keyword_ignore_case("let").boxed(),
+            text::unicode::keyword("let").boxed(),
+        ];
+        for keyword in keywords {
+            let parser = keyword.labelled("keyword").padded();
+            assert_eq!(
+                parser.parse("  lettuce").into_errors(),
+                expected((2..9).into())
+            );
+            assert_eq!(parser.parse("fn").into_errors(), expected((0..2).into()));
+        }
+    }
+
+    #[cfg(feature = "clap")]
+    #[test]
+    fn clap_value_parser_error() {
+        use ::clap::{Arg, Command, ErrorKind};
+
+        let cmd = Command::new("tool").arg(Arg::new("pair").takes_value(true).value_parser(
+            crate::clap::value_parser(|arg| {
+                text::ident::<_, _, extra::Err<Rich<char>>>()
+                    .then_ignore(just('='))
+                    .then(text::int(10))
+                    .map(|(k, v): (&str, &str)| (k.to_string(), v.to_string()))
+                    .parse(arg)
+            }),
+        ));
+
+        let matches = cmd.clone().try_get_matches_from(["tool", "né=3"]).unwrap();
+        assert_eq!(
+            matches.get_one::<(String, String)>("pair"),
+            Some(&("né".to_string(), "3".to_string())),
+        );
+
+        let err = cmd.try_get_matches_from(["tool", "né=x"]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        // The marker is placed by character, not by byte
+        assert!(err.to_string().contains("\n    né=x\n       ^\n"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn read_input_window() {
+        use crate::input::ReadInput;
+
+        let src = vec![b'a'; 100_000];
+        let count = any::<_, extra::Default>().repeated().count();
+        assert_eq!(
+            count
+                .parse(ReadInput::new(&src[..]).with_window(16))
+                .into_result(),
+            Ok(100_000)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn read_input_backtrack_too_far() {
+        use crate::input::ReadInput;
+
+        let src = vec![b'a'; 100_000];
+        let parser = any::<_, extra::Err<Rich<u8>>>()
+            .repeated()
+            .rewind()
+            .ignore_then(any().repeated())
+            .then_ignore(end())
+            .with_lex_errors(|err, span| Rich::custom(span, err));
+        let errs = parser
+            .parse(ReadInput::new(&src[..]).with_window(16))
+            .into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(0, 0));
+        assert!(matches!(
+            errs[0].reason(),
+            crate::error::RichReason::Custom(msg) if msg.contains("retained window"),
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn read_input_io_error() {
+        use crate::input::ReadInput;
+        use std::io::{self, Read};
+
+        // Produces a few bytes, then fails
+        struct Failing(&'static [u8]);
+
+        impl Read for Failing {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                if self.0.is_empty() {
+                    Err(io::Error::other("disk on fire"))
+                } else {
+                    self.0.read(buf)
+                }
+            }
+        }
+
+        let parser = any::<_, extra::Err<Rich<u8>>>()
+            .repeated()
+            .collect::<Vec<_>>()
+            .then_ignore(end())
+            .with_lex_errors(|err, span| Rich::custom(span, err));
+
+        let (out, errs) = parser
+            .parse(ReadInput::new(Failing(b"abc")))
+            .into_output_errors();
+        assert_eq!(out, Some(b"abc".to_vec()));
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(3, 3));
+        assert!(matches!(
+            errs[0].reason(),
+            crate::error::RichReason::Custom(msg) if msg.contains("disk on fire"),
+        ));
+    }
+
+    #[test]
+    fn bounded_stream_backtrack_past_commit() {
+        use crate::extra::Halt;
+        use crate::input::BoundedStream;
+
+        let line = text::digits::<_, _, extra::Err<Rich<char>>>(10)
+            .then_ignore(just('\n'))
+            .commit();
+
+        // Backtracking to a point that was committed to is fine
+        let lines = line.repeated().count().then_ignore(end());
+        assert_eq!(
+            lines
+                .parse(BoundedStream::from_iter("1\n22\n".chars()))
+                .into_result(),
+            Ok(2)
+        );
+
+        // Backtracking to before it is not, even when a later alternative would succeed
+        let lines = line
+            .repeated()
+            .count()
+            .then_ignore(just('!'))
+            .or(any().repeated().count());
+        let res = lines.parse(BoundedStream::from_iter("1\n22\n?".chars()));
+        assert_eq!(res.halted(), Some(Halt::Discarded));
+        assert_eq!(res.output(), None);
+        let errs = res.into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(0, 0));
+    }
+
+    #[test]
+    fn group_of_eoi_span() {
+        use crate::input::{TokenTree, TreeGroup};
+        use crate::primitive::group_of;
+
+        #[derive(Clone, PartialEq, Debug)]
+        enum Token {
+            Num(u64),
+            Parens(Vec<(Token, SimpleSpan)>, SimpleSpan),
+        }
+
+        impl TokenTree<SimpleSpan> for Token {
+            type Delimiter = ();
+
+            fn as_group(&self) -> Option<TreeGroup<'_, Self, SimpleSpan>> {
+                match self {
+                    Token::Parens(tokens, close) => Some(TreeGroup {
+                        delimiter: &(),
+                        tokens,
+                        close: *close,
+                    }),
+                    _ => None,
+                }
+            }
+        }
+
+        // (1 )
+        let tokens = [(
+            Token::Parens(vec![(Token::Num(1), (1..2).into())], (3..4).into()),
+            SimpleSpan::from(0..4),
+        )];
+
+        let pair = select_ref! { Token::Num(x) => *x }
+            .then(select_ref! { Token::Num(x) => *x })
+            .nested_in(group_of::<_, _, extra::Err<Rich<_>>>(()));
+
+        let errs = pair
+            .parse(tokens.as_slice().spanned((4..4).into()))
+            .into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(3..4));
+    }
+
+    #[test]
+    fn spanned_empty_spans() {
+        // a  b
+        let tokens = [('a', SimpleSpan::from(0..1)), ('b', SimpleSpan::from(3..4))];
+        let eoi = SimpleSpan::from(4..4);
+        let empty = just::<_, _, extra::Err<Simple<char>>>('a')
+            .ignore_then(empty().map_with(|_, e| e.span()))
+            .then_ignore(any().repeated());
+
+        // Between two tokens, an empty span starts at the next token rather than ending at the previous one
+        assert_eq!(
+            empty.parse(tokens.as_slice().spanned(eoi)).into_result(),
+            Ok(SimpleSpan::from(3..3))
+        );
+        // At the end of the input, an empty span is the EoI span
+        assert_eq!(empty.parse(tokens[..1].spanned(eoi)).into_result(), Ok(eoi));
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic = "end before they start")]
+    fn reversed_span_union() {
+        use crate::span::Span;
+
+        // Clamped in release builds, but reported in debug builds
+        let reversed = SimpleSpan::new(9, 6);
+        assert_eq!(
+            reversed.checked_union(reversed),
+            Some(SimpleSpan::from(9..9))
+        );
+        assert_eq!(reversed.union(reversed), SimpleSpan::from(9..9));
+    }
+
+    #[test]
+    fn peek_back() {
+        let parser = just::<_, _, extra::Default>("aé")
+            .ignore_then(custom(|inp| {
+                Ok((inp.peek_back(1), inp.peek_back(2), inp.peek_back(3)))
+            }))
+            .map(|(a, b, c)| (a.as_deref().copied(), b.as_deref().copied(), c));
+        assert_eq!(
+            parser.parse("aé").into_result(),
+            Ok((Some('é'), Some('a'), None))
+        );
+
+        let tokens = [('a', SimpleSpan::from(0..1)), ('b', SimpleSpan::from(2..3))];
+        let parser = just::<_, _, extra::Default>('a')
+            .then(just('b'))
+            .then_ignore(just('a').then(just('b')).lookbehind(2))
+            .map_with(|_, e| e.span());
+        assert_eq!(
+            parser
+                .parse(tokens.as_slice().spanned((3..3).into()))
+                .into_result(),
+            Ok(SimpleSpan::from(0..3))
+        );
+    }
+
+    #[test]
+    fn concat_section_spans() {
+        use crate::{input::Concat, span::Span};
+
+        let span = |ctx, range| <SimpleSpan<usize, &str> as Span>::new(ctx, range);
+        let src = Concat::new([("a", "xy"), ("b", ""), ("c", "z")]);
+        let spans = any::<_, extra::Default>()
+            .map_with(|c, e| (c, e.span()))
+            .repeated()
+            .collect::<Vec<_>>()
+            .map_with(|toks, e| (toks, e.span()))
+            .parse(src)
+            .into_result();
+        assert_eq!(
+            spans,
+            Ok((
+                vec![
+                    ('x', span("a", 0..1)),
+                    ('y', span("a", 1..2)),
+                    ('z', span("c", 0..1)),
+                ],
+                span("a", 0..2),
+            ))
+        );
+    }
+
+    #[test]
+    fn highlights_backtrack() {
+        use crate::inspector::Highlights;
+
+        let a = just::<_, _, extra::State<Highlights<SimpleSpan, &str, u8>>>('a');
+        let parser = a
+            .inspect_rule("one")
+            .then(just('b'))
+            .or(a.inspect_rule("two").then(just('c')))
+            .inspect_rule("ignored");
+
+        let mut highlights = Highlights::new(|name: &&str| match *name {
+            "one" => Some(1),
+            "two" => Some(2),
+            _ => None,
+        });
+        assert!(!parser.parse_with_state("ac", &mut highlights).has_errors());
+        assert_eq!(highlights.into_vec(), vec![((0..1).into(), 2)]);
+    }
+
+    #[cfg(feature = "label")]
+    #[test]
+    fn highlights_from_labels() {
+        use crate::inspector::Highlights;
+
+        #[derive(Clone, Debug, PartialEq)]
+        enum Label {
+            Keyword,
+            Expr,
+        }
+
+        type Extra = extra::Full<
+            Rich<'static, char, SimpleSpan, Label>,
+            Highlights<SimpleSpan, Label, u8>,
+            (),
+        >;
+        let kw = text::ascii::keyword::<_, _, _, Extra>("if").labelled(Label::Keyword);
+        let expr = text::int(10).labelled(Label::Expr);
+        // Rule names aren't labels of type `Label`, so the mapping doesn't see them
+        let parser = kw.then(expr.padded()).inspect_rule("if");
+
+        let mut highlights = Highlights::new(|label: &Label| match label {
+            Label::Keyword => Some(0),
+            Label::Expr => None,
+        });
+        assert!(!parser
+            .parse_with_state("if 42", &mut highlights)
+            .has_errors());
+        assert_eq!(highlights.into_vec(), vec![((0..2).into(), 0)]);
+    }
+
+    #[test]
+    fn dyn_input_backtrack() {
+        use crate::input::DynInput;
+
+        let parser = just::<_, DynInput<char, SimpleSpan>, extra::Err<Simple<char>>>('a')
+            .then(just('b'))
+            .or(just('a').then(just('c')))
+            .map_with(|out, e| (out, e.span()));
+
+        let tokens = [('a', SimpleSpan::from(2..3)), ('c', SimpleSpan::from(5..6))];
+        let input = DynInput::new(tokens.as_slice().spanned((6..6).into()));
+        assert_eq!(
+            parser.parse(input).into_result(),
+            Ok((('a', 'c'), (2..6).into()))
+        );
+
+        let errs = parser.parse(DynInput::new("ad")).into_errors();
+        assert_eq!(errs[0].span(), &SimpleSpan::from(1..2));
+    }
+
+    #[test]
+    fn lenient_warnings_backtrack() {
+        use crate::inspector::Leniency;
+
+        let a = just::<_, _, extra::Full<Rich<char>, Leniency, ()>>('a');
+        let parser = a
+            .lenient("legacy")
+            .then(just('x'))
+            .ignored()
+            .or(a.then(just('y')).ignored());
+
+        let res = parser.parse_with_state("ay", &mut Leniency::lenient());
+        assert!(!res.has_errors());
+        assert_eq!(res.warnings().count(), 0);
+
+        let res = parser.parse_with_state("ax", &mut Leniency::lenient());
+        assert!(!res.has_errors());
+        assert_eq!(
+            res.warnings()
+                .map(|w| (*w.span(), w.to_string()))
+                .collect::<Vec<_>>(),
+            vec![((0..1).into(), "legacy".to_string())]
+        );
+
+        let res = parser.parse_with_state("ax", &mut Leniency::strict());
+        assert!(res.has_errors());
+        assert_eq!(res.warnings().count(), 0);
+    }
+
+    #[test]
+    fn found_slices_runs() {
+        let parser = text::int::<_, _, extra::Err<Rich<char>>>(10)
+            .separated_by(just('+'))
+            .collect::<Vec<_>>()
+            .found_slices(3);
+
+        let found = |s| {
+            parser.parse(s).into_errors()[0]
+                .found_slice()
+                .map(str::to_string)
+        };
+        assert_eq!(found("1+=>2"), Some("=>".to_string()));
+        assert_eq!(found("1+abcdef"), Some("abc".to_string()));
+        assert_eq!(found("1+ 2"), Some(" ".to_string()));
+        assert_eq!(found("1+"), None);
+
+        let errs = parser.parse("1+=>2").into_errors();
+        assert_eq!(errs[0].to_report().found.as_deref(), Some("=>"));
+    }
+
+    #[test]
+    fn binary_byte_order() {
+        use crate::binary;
+
+        let bytes: &[u8] = &[0x01, 0x02, 0x03, 0x04];
+        let le = binary::u32_le::<_, extra::Err<Simple<u8>>>();
+        let be = binary::u32_be::<_, extra::Err<Simple<u8>>>();
+        assert_eq!(le.parse(bytes).into_result(), Ok(0x04030201));
+        assert_eq!(be.parse(bytes).into_result(), Ok(0x01020304));
+
+        let pi = std::f64::consts::PI.to_le_bytes();
+        let float = binary::f64_le::<_, extra::Err<Simple<u8>>>();
+        assert_eq!(float.parse(&pi[..]).into_result(), Ok(std::f64::consts::PI));
+        assert!(float.parse(&pi[..7]).has_errors());
+    }
+
+    #[test]
+    fn iter_with_state_resume() {
+        let item = text::int::<_, _, extra::Full<Simple<char>, (), ()>>(10).then_ignore(just(','));
+        let mut state = ();
+        let mut items = item.iter_with_state("1,2,x,3,", &mut state);
+
+        assert_eq!(items.next().unwrap().into_output(), Some("1"));
+        assert_eq!(items.next().unwrap().into_output(), Some("2"));
+        let res = items.next().unwrap();
+        assert!(res.has_errors() && !res.has_output());
+        // A failed item leaves the iterator at its start, and ends iteration until it is skipped
+        assert_eq!(items.offset(), 4);
+        assert!(items.next().is_none());
+        assert!(!items.skip_with(just('y')));
+        assert!(items.skip_with(just("x,")));
+        assert_eq!(items.next().unwrap().into_output(), Some("3"));
+        assert!(items.next().is_none());
+    }
+
+    #[test]
+    fn token_tree_skip() {
+        use crate::primitive::token_tree;
+
+        let tt = token_tree::<_, extra::Err<Rich<char>>, 2>([('(', ')'), ('"', '"')]);
+        let tts = tt.clone().padded().repeated().collect::<Vec<_>>();
+        assert_eq!(
+            tts.parse(r#"a ("b (" c) "x)" d"#).into_result(),
+            Ok(vec!["a", r#"("b (" c)"#, r#""x)""#, "d"]),
+        );
+
+        // An unclosed group expects its closing delimiter at the end of input
+        let errs = tt.parse("((a)").into_errors();
+        assert_eq!(errs[0].span(), &SimpleSpan::from(4..4));
+        assert_eq!(
+            errs[0].expected().collect::<Vec<_>>(),
+            vec![&crate::error::RichPattern::Token(crate::MaybeRef::Val(')'))]
+        );
+    }
+
+    #[test]
+    fn choice_longest_ties() {
+        use crate::primitive::{choice_longest, Just};
+
+        let word = text::ascii::ident::<_, _, extra::Err<Simple<char>>>();
+        let token = choice_longest((
+            text::ascii::keyword("if").to("keyword"),
+            word.to("ident"),
+            word.then(just('!')).to("macro"),
+        ));
+        // The keyword and the identifier are the same length, so the first wins
+        assert_eq!(token.parse("if").into_result(), Ok("keyword"));
+        assert_eq!(token.parse("iffy").into_result(), Ok("ident"));
+        assert_eq!(token.parse("if!").into_result(), Ok("macro"));
+        assert!(token.parse("!").has_errors());
+
+        let digits = choice_longest(vec![
+            just::<_, _, extra::Default>("1"),
+            just("12"),
+            just("123"),
+        ]);
+        assert_eq!(digits.lazy().parse("1234").into_result(), Ok("123"));
+        let none = choice_longest(Vec::<Just<&str, &str, extra::Default>>::new());
+        assert!(none.parse("").has_errors());
+    }
+
+    #[test]
+    fn cut_prevents_backtracking() {
+        let ab = just::<_, _, extra::Err<Simple<char>>>('a').then(just('b').cut());
+        let ac = just('a').then(just('c'));
+        let pair = ab.or(ac);
+        assert_eq!(pair.parse("ab").into_result(), Ok(('a', 'b')));
+        assert!(pair.parse("ac").has_errors());
+        assert!(choice(vec![ab.boxed(), ac.boxed()])
+            .parse("ac")
+            .has_errors());
+        assert!(ab.or_not().then(ac).parse("ac").has_errors());
+
+        // Recovering from a cut (or negating a cut) doesn't stop later parsers from backtracking
+        let item = pair
+            .to('p')
+            .recover_with(via_parser(just('a').then(any()).to('!')))
+            .or(just('x'));
+        let items = item.repeated().collect::<String>();
+        assert_eq!(items.parse("abacx").into_output(), Some("p!x".to_string()));
+        let not_ab = ab.not().ignore_then(any()).or(just('y'));
+        assert_eq!(
+            not_ab
+                .then(just('a').or(just('z')))
+                .parse("az")
+                .into_result(),
+            Ok(('a', 'z'))
+        );
+
+        // A cut within a probe that was backtracked from doesn't apply to later failures
+        let comment = any::<_, extra::Err<Simple<char>>>()
+            .repeated()
+            .lazy_until(just('*').then(just('/').cut()))
+            .to(1);
+        let item = comment.then_ignore(just("*/;")).or(just("a*b*/!").to(2));
+        assert_eq!(item.parse("a*b*/;").into_result(), Ok(1));
+        assert_eq!(item.parse("a*b*/!").into_result(), Ok(2));
+    }
+
+    #[test]
+    fn map_once_moves() {
+        struct Resource(String);
+
+        let resource = Resource("res".to_string());
+        let parser = just::<_, _, extra::Err<Simple<char>>>('a')
+            .repeated()
+            .count()
+            .then_ignore(end())
+            .map_once(move |n| resource.0.repeat(n));
+        assert_eq!(
+            parser.parse_once("aa").into_result(),
+            Ok("resres".to_string())
+        );
+
+        // The function is not called if parsing fails
+        let parser = just::<_, _, extra::Err<Simple<char>>>('a')
+            .map_once(|_| -> () { panic!("mapped a failed parse") });
+        assert!(parser.parse_once("b").has_errors());
+
+        // Recovered errors are kept alongside the output
+        let parser = just::<_, _, extra::Err<Simple<char>>>('a')
+            .recover_with(via_parser(any()))
+            .map_once(|c| c.to_ascii_uppercase());
+        let (out, errs) = parser.parse_once("b").into_output_errors();
+        assert_eq!(out, Some('B'));
+        assert_eq!(errs.len(), 1);
+
+        // As are warnings and recoveries
+        let res = just::<_, _, extra::Err<Rich<char>>>('a')
+            .recover_with(via_parser(any()))
+            .validate(|c, e, emitter| {
+                emitter.warn(Rich::custom(e.span(), "odd"));
+                c
+            })
+            .map_once(|c| c.to_ascii_uppercase())
+            .parse_once("b");
+        assert_eq!(res.warnings().count(), 1);
+        assert_eq!(res.recoveries().len(), 1);
+        assert_eq!(res.into_output_errors().0, Some('B'));
+    }
+
+    #[test]
+    fn with_scope_balanced() {
+        use crate::extra::SimpleState;
+
+        type Depth = SimpleState<(i32, i32)>;
+        // The current and maximum depth
+        let enter = |s: &mut Depth| {
+            let (depth, max) = &mut s.0;
+            *depth += 1;
+            *max = (*max).max(*depth);
+        };
+        let exit = |s: &mut Depth| s.0 .0 -= 1;
+
+        let scoped = just::<_, _, extra::Full<Simple<char>, Depth, ()>>('(')
+            .then(just('a'))
+            .then(just(')'))
+            .with_scope(enter, exit);
+        // The first alternative fails part of the way through, so is exited before the second is tried
+        let parser = scoped
+            .or(just('(').then(just('b')).then(just(')')))
+            .with_scope(enter, exit)
+            .recover_with(via_parser(any().repeated().to((('?', '?'), '?'))));
+
+        let mut depth = SimpleState((0, 0));
+        assert!(!parser.parse_with_state("(a)", &mut depth).has_errors());
+        assert_eq!(depth.0, (0, 2));
+        assert!(!parser.check_with_state("(b)", &mut depth).has_errors());
+        assert!(parser.parse_with_state("(c)", &mut depth).has_errors());
+        assert_eq!(depth.0, (0, 2));
+    }
+
+    #[test]
+    fn with_fast_path_reparses() {
+        type Fast = extra::Full<EmptyErr, (), ()>;
+        // The fast path is recognisable by its output
+        let fast = just::<_, _, Fast>('a')
+            .to(1)
+            .recover_with(via_parser(just('b').to(2)));
+        let parser = just::<_, _, extra::Err<Rich<char>>>('a')
+            .or(just('b'))
+            .to(0)
+            .with_fast_path(fast)
+            .then_ignore(end());
+
+        assert_eq!(parser.parse("a").into_result(), Ok(1));
+        // Recovery in the fast path causes the input to be parsed again
+        assert_eq!(parser.parse("b").into_result(), Ok(0));
+        let errs = parser.parse("c").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(0..1));
+        assert_eq!(errs[0].expected().count(), 2);
+    }
+
+    #[test]
+    fn rollback_state() {
+        use crate::inspector::Rollback;
+
+        type Extra = extra::Full<EmptyErr, Rollback<Vec<char>>, ()>;
+        let push = |c: char| {
+            just::<_, _, Extra>(c).validate(|c, e, _| {
+                e.state().push(c);
+                c
+            })
+        };
+        // Every alternative but the last pushes before failing, and the first fails after a nested rollback
+        let parser = push('a')
+            .then(push('b').then(push('x')).or(push('b').then(push('y'))))
+            .then(push('z'))
+            .ignored()
+            .or(push('a').then(push('b')).ignored())
+            .or(push('a').ignored())
+            .repeated();
+
+        let mut state = Rollback::new(Vec::new());
+        assert!(!parser.parse_with_state("abyzaba", &mut state).has_errors());
+        assert_eq!(*state, ['a', 'b', 'y', 'z', 'a', 'b', 'a']);
+        // Only `ab` succeeds, leaving `yc` unparsed
+        assert!(parser.parse_with_state("abyc", &mut state).has_errors());
+        assert_eq!(state.into_inner()[7..], ['a', 'b']);
+    }
+
+    #[test]
+    fn tuple_state_slots() {
+        use crate::inspector::{Has, Rollback, SimpleState};
+
+        type State = (Rollback<Vec<char>>, SimpleState<u32>);
+        let item = any::<_, extra::State<State>>().validate(|c, e, _| {
+            let pushed: &mut Rollback<Vec<char>> = e.state().get_mut();
+            pushed.push(c);
+            let count: &mut SimpleState<u32> = e.state().get_mut();
+            **count += 1;
+            c
+        });
+        // The first alternative fails after its first item, which must be undone in the slot that supports it
+        let parser = item.then(just('!')).ignored().or(item.repeated());
+
+        let mut state = (Rollback::default(), SimpleState(0));
+        assert!(!parser.parse_with_state("ab", &mut state).has_errors());
+        assert_eq!(*state.0, ['a', 'b']);
+        // Slots that don't roll back still see every change
+        assert_eq!(*state.1, 3);
+    }
+
+    #[test]
+    fn filter_output_backtracks() {
+        let num = text::int::<_, _, extra::Err<Rich<char>>>(10)
+            .from_str::<u8>()
+            .unwrapped()
+            .filter(|n| *n < 10);
+        // A rejected output is backtracked over, so the next alternative sees the same input
+        let parser = num.map(Some).or(any().repeated().to(None));
+        assert_eq!(parser.parse("7").into_result(), Ok(Some(7)));
+        assert_eq!(parser.parse("42").into_result(), Ok(None));
+
+        let errs = num.parse("42").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(0..2));
+        assert_eq!(errs[0].found(), Some(&'4'));
+    }
+
+    #[test]
+    fn repeated_follow_set() {
+        use crate::error::RichPattern;
+
+        let path = text::ascii::ident::<_, _, extra::Err<Rich<char>>>()
+            .then(just('.').then(text::ascii::ident()).repeated());
+        let call = path.then(just('(')).then(just(')'));
+
+        // The path could have continued with `.` where the call expected `(`
+        let errs = call.parse("a.b c").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(3..4));
+        let mut expected = errs[0].expected().cloned().collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(
+            expected,
+            vec![
+                RichPattern::Token('('.into()),
+                RichPattern::Token('.'.into())
+            ]
+        );
+
+        // Once something has followed the repetition, it's no longer relevant
+        let errs = call.parse("a.b(c").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(
+            errs[0].expected().collect::<Vec<_>>(),
+            vec![&RichPattern::Token(')'.into())]
+        );
+    }
+
+    #[test]
+    fn bounded_follow_set() {
+        use crate::error::RichPattern;
+
+        fn expected(errs: Vec<Rich<char>>) -> Vec<RichPattern<char>> {
+            assert_eq!(errs.len(), 1);
+            let mut expected = errs[0].expected().cloned().collect::<Vec<_>>();
+            expected.sort();
+            expected
+        }
+
+        let path = text::ascii::ident::<_, _, extra::Err<Rich<char>>>()
+            .then(just('.').then(text::ascii::ident()).repeated().at_least(1));
+        let call = path.then(just('(')).then(just(')'));
+        assert_eq!(
+            expected(call.parse("a.b c").into_errors()),
+            vec![
+                RichPattern::Token('('.into()),
+                RichPattern::Token('.'.into())
+            ]
+        );
+
+        let args = text::ascii::ident::<_, _, extra::Err<Rich<char>>>()
+            .separated_by(just(','))
+            .collect::<Vec<_>>();
+        let call = args.then(just(')'));
+        assert_eq!(
+            expected(call.parse("a,b c").into_errors()),
+            vec![
+                RichPattern::Token(')'.into()),
+                RichPattern::Token(','.into())
+            ]
+        );
+
+        let bang = text::ascii::ident::<_, _, extra::Err<Rich<char>>>().then(just('!').or_not());
+        let call = bang.then(just(')'));
+        assert_eq!(
+            expected(call.parse("a c").into_errors()),
+            vec![
+                RichPattern::Token('!'.into()),
+                RichPattern::Token(')'.into())
+            ]
+        );
+
+        // A follow error from input that was backtracked over doesn't apply to later failures at the same position
+        let peek = just::<_, _, extra::Err<Rich<char>>>('a')
+            .then(just('b').repeated())
+            .rewind();
+        let pair = peek.ignore_then(just('a').then(just('c')));
+        assert_eq!(
+            expected(pair.parse("ad").into_errors()),
+            vec![RichPattern::Token('c'.into())]
+        );
+    }
+
+    #[test]
+    fn map_extra_side_channels() {
+        let word = text::ascii::ident::<_, _, extra::Err<Rich<char>>>().map_with(|w: &str, e| {
+            let span = e.span();
+            if w.len() > 3 {
+                e.emit_warning(Rich::custom(span, "long word"));
+            }
+            e.emit_note(Rich::custom(span, "word"));
+            w
+        });
+        // The first alternative is backtracked over, taking its diagnostics with it
+        let parser = word
+            .then_ignore(just(';'))
+            .or(word.then_ignore(just('.')))
+            .padded()
+            .repeated()
+            .collect::<Vec<_>>();
+
+        let res = parser.parse("long. a;");
+        assert_eq!(res.output(), Some(&vec!["long", "a"]));
+        assert_eq!(
+            res.warnings().map(|w| *w.span()).collect::<Vec<_>>(),
+            vec![(0..4).into()]
+        );
+        assert_eq!(
+            res.into_notes()
+                .into_iter()
+                .map(|n| *n.span())
+                .collect::<Vec<_>>(),
+            vec![(0..4).into(), (6..7).into()]
+        );
+    }
+
+    #[test]
+    fn rich_reasons_flattened() {
+        use crate::error::RichReason;
+
+        let parser = just::<_, _, extra::Err<Rich<char>>>('a').or(any()
+            .try_map(|_, span| Err(Rich::custom(span, "bad")))
+            .or(any().try_map(|_, span| Err(Rich::custom(span, "worse")))));
+        let errs = parser.parse("b").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert!(matches!(errs[0].reason(), RichReason::Many(_)));
+
+        let reasons = errs[0].reason().reasons().collect::<Vec<_>>();
+        assert_eq!(reasons.len(), 3);
+        assert!(matches!(reasons[0], RichReason::ExpectedFound { .. }));
+        assert_eq!(reasons[1], &RichReason::Custom("bad".to_string()));
+        assert_eq!(reasons[2], &RichReason::Custom("worse".to_string()));
+    }
+
+    #[test]
+    fn debug_err_profiles() {
+        type E<'a, const DETAILED: bool> =
+            <extra::DebugErr<Rich<'a, char>, DETAILED> as extra::ParserExtra<'a, &'a str>>::Error;
+        // Errors are only built when asked for
+        assert_ne!(core::mem::size_of::<E<true>>(), 0);
+        assert_eq!(core::mem::size_of::<E<false>>(), 0);
+
+        let parser = just::<_, _, extra::DebugErr<Rich<char>, { cfg!(debug_assertions) }>>("ab")
+            .repeated()
+            .at_least(1);
+        assert!(parser.parse("abab").into_result().is_ok());
+        assert_eq!(parser.parse("abac").into_errors().len(), 1);
+
+        // A detailed error type can be relied on regardless of the build profile
+        let parser = just::<_, _, extra::DebugErr<Rich<char>, true>>("ab")
+            .try_map(|_, span| Err::<(), _>(Rich::custom(span, "no")));
+        assert_eq!(parser.parse("ab").into_errors()[0].to_string(), "no");
+    }
+
+    #[test]
+    fn recursion_limit_error() {
+        use crate::extra::{Halt, ParseOptions};
+
+        let src = format!("{}x{}", "(".repeat(50_000), ")".repeat(50_000));
+        let parens = recursive(|parens| {
+            parens
+                .delimited_by(just('('), just(')'))
+                .or(just::<_, _, extra::Err<Rich<char>>>('x').to(()))
+        });
+        let options = ParseOptions::new().recursion_limit(256);
+        let res = parens.parse_with_options(src.as_str(), &mut (), &options);
+        assert_eq!(res.halted(), Some(Halt::RecursionLimit));
+        let errs = res.into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(256..256));
+        assert_eq!(errs[0].to_string(), "nesting too deep");
+    }
+
+    #[test]
+    fn halt_is_not_end_of_input() {
+        use crate::extra::{Halt, ParseOptions};
+        use core::cell::Cell;
+
+        // Running out of budget mustn't look like running out of input to the parsers that were cut short
+        let seen = Cell::new(None);
+        let count = just::<_, _, extra::Err<Simple<char>>>('a')
+            .repeated()
+            .count()
+            .map(|n| seen.set(Some(n)))
+            .or(empty());
+        let options = ParseOptions::new().token_budget(2);
+        let res = count.parse_with_options("aaaa", &mut (), &options);
+        assert_eq!(res.halted(), Some(Halt::TokenBudget));
+        assert!(res.has_errors());
+        assert_eq!(seen.get(), None);
+
+        assert!(!count.parse("aaaa").has_errors());
+        assert_eq!(seen.get(), Some(4));
+    }
+
+    #[test]
+    fn parse_options_limits() {
+        use crate::extra::{Cancellation, Halt, ParseOptions};
+        use crate::inspector::Leniency;
+
+        // Each unexpected character is skipped, with an error
+        let items = just::<_, _, extra::Err<Simple<char>>>('a')
+            .recover_with(via_parser(any().to('?')))
+            .repeated()
+            .collect::<String>();
+        assert_eq!(items.parse("axxxa").into_errors().len(), 3);
+        let options = ParseOptions::new().max_errors(2);
+        let res = items.parse_with_options("axxxa", &mut (), &options);
+        assert_eq!(res.halted(), Some(Halt::MaxErrors));
+        assert_eq!(res.into_output_errors().1.len(), 2);
+        assert_eq!(
+            items
+                .parse_with_options("axxa", &mut (), &ParseOptions::new().max_errors(3))
+                .into_output(),
+            Some("a??a".to_string())
+        );
+
+        let cancel = Cancellation::new();
+        let options = ParseOptions::new().cancellation(cancel.clone());
+        assert!(items
+            .check_with_options("aaa", &mut (), &options)
+            .halted()
+            .is_none());
+        cancel.cancel();
+        let res = items.check_with_options("aaa", &mut (), &options);
+        assert_eq!(res.halted(), Some(Halt::Cancelled));
+        assert!(res.has_errors());
+
+        // Options decide strictness over the state
+        let semi = just::<_, _, extra::Full<EmptyErr, Leniency, ()>>('a')
+            .then(just(';').strict("missing `;`"));
+        let mut lenient = Leniency::lenient();
+        let options = ParseOptions::new().strict(true);
+        assert!(semi
+            .parse_with_options("a", &mut lenient, &options)
+            .has_errors());
+        assert!(!semi.parse_with_state("a", &mut lenient).has_errors());
+    }
+
+    #[test]
+    fn choice_vec_runtime() {
+        use crate::primitive::Choice;
+
+        let mut digits = Choice::default();
+        assert!(digits.is_empty());
+        assert!(digits.parse("1").has_errors());
+
+        let digit = |n| {
+            just::<_, _, extra::Err<Simple<char>>>(char::from_digit(n, 10).unwrap())
+                .to(n)
+                .boxed()
+        };
+        digits.extend((0..5).map(digit));
+        let mut more = (5..9).map(digit).collect::<Choice<_>>();
+        more.push(digit(9));
+        assert_eq!((digits.len(), more.len()), (5, 5));
+        assert_eq!(
+            choice((digits, more))
+                .repeated()
+                .collect()
+                .parse("907")
+                .into_result(),
+            Ok(vec![9, 0, 7])
+        );
+    }
+
+    #[test]
+    fn permutation_missing() {
+        let parser = permutation((
+            just::<_, _, extra::Err<Rich<char>>>('a'),
+            just('b'),
+            just('c'),
+        ))
+        .then_ignore(end());
+
+        assert_eq!(parser.parse("cab").into_result(), Ok(('a', 'b', 'c')));
+        assert_eq!(parser.parse("bca").into_result(), Ok(('a', 'b', 'c')));
+
+        // Only the element that is left is expected
+        let errs = parser.parse("ca").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(2..2));
+        assert_eq!(
+            errs[0].expected().collect::<Vec<_>>(),
+            vec![&crate::error::RichPattern::Token(crate::MaybeRef::Val('b'))]
+        );
+
+        let errs = parser.parse("cac").into_errors();
+        assert_eq!(errs[0].found(), Some(&'c'));
+    }
+
+    #[test]
+    fn rich_display_named() {
+        let parser = just::<_, _, extra::Err<Rich<char>>>('a')
+            .or(just('b'))
+            .then(end());
+        let errs = parser.parse("c").into_errors();
+        assert_eq!(errs[0].to_string(), "found c expected 'a', or 'b'");
+        assert_eq!(
+            errs[0].display_named().to_string(),
+            "found 'c' expected 'a', or 'b'"
+        );
+
+        let errs = parser.parse("").into_errors();
+        assert_eq!(
+            errs[0].display_named().to_string(),
+            "found end of input expected 'a', or 'b'"
+        );
+    }
+
+    #[test]
+    fn inspect_rule_hooks() {
+        use crate::{
+            input::{Checkpoint, Cursor},
+            inspector::Inspector,
+        };
+
+        #[derive(Default)]
+        struct Coverage {
+            events: Vec<(&'static str, usize, Option<bool>)>,
+        }
+
+        impl<'src> Inspector<'src, &'src str> for Coverage {
+            type Checkpoint = ();
+            fn on_token(&mut self, _: &char) {}
+            fn on_save<'parse>(&self, _: &Cursor<'src, 'parse, &'src str>) {}
+            fn on_rewind<'parse>(&mut self, _: &Checkpoint<'src, 'parse, &'src str, ()>) {}
+            fn on_enter_rule<'parse>(
+                &mut self,
+                name: &'static str,
+                cursor: &Cursor<'src, 'parse, &'src str>,
+            ) {
+                self.events.push((name, *cursor.inner(), None));
+            }
+            fn on_exit_rule<'parse>(
+                &mut self,
+                name: &'static str,
+                cursor: &Cursor<'src, 'parse, &'src str>,
+                succeeded: bool,
+            ) {
+                self.events.push((name, *cursor.inner(), Some(succeeded)));
+            }
+        }
+
+        let digits = text::digits::<_, _, extra::State<Coverage>>(10)
+            .to_slice()
+            .inspect_rule("digits");
+        let parser = digits
+            .separated_by(just(','))
+            .collect::<Vec<_>>()
+            .inspect_rule("list");
+
+        let mut coverage = Coverage::default();
+        assert_eq!(
+            parser.parse_with_state("1,23", &mut coverage).into_result(),
+            Ok(vec!["1", "23"])
+        );
+        assert_eq!(
+            coverage.events,
+            vec![
+                ("list", 0, None),
+                ("digits", 0, None),
+                ("digits", 1, Some(true)),
+                ("digits", 2, None),
+                ("digits", 4, Some(true)),
+                ("list", 4, Some(true)),
+            ]
+        );
+
+        // The default state ignores the hooks
+        let parser = just::<_, _, extra::Default>('a').inspect_rule("a");
+        assert!(parser.parse("b").has_errors());
+    }
+
+    #[test]
+    fn exactly_one_of_conflicts() {
+        let parser = exactly_one_of(
+            [
+                just::<_, _, extra::Err<Rich<char>>>('a'),
+                just('b'),
+                just('c'),
+            ],
+            |(a, a_span), (b, b_span)| {
+                Rich::custom(b_span, format!("{b} after {a}")).with_secondary(a_span, "here")
+            },
+        )
+        .then_ignore(just(';'));
+
+        assert_eq!(parser.parse("b;").into_result(), Ok('b'));
+        assert!(parser.parse(";").has_errors());
+
+        // Every later match conflicts with the first, but parsing carries on
+        let res = parser.parse("bac;");
+        assert_eq!(res.output(), Some(&'b'));
+        let errs = res.errors().collect::<Vec<_>>();
+        assert_eq!(errs.len(), 2);
+        assert_eq!(errs[0].to_string(), "a after b; here at 0..1");
+        assert_eq!(errs[0].span(), &SimpleSpan::from(1..2));
+        assert_eq!(errs[1].span(), &SimpleSpan::from(2..3));
+
+        let optional = at_most_one_of(
+            (just::<_, _, extra::Err<Rich<char>>>('a'),),
+            |_, (_, span)| Rich::custom(span, "duplicate"),
+        )
+        .then(just(';'));
+        assert_eq!(optional.parse(";").into_result(), Ok((None, ';')));
+        assert_eq!(optional.parse("aa;").into_errors().len(), 1);
+    }
+
+    #[test]
+    fn exactly_one_of_empty_alternatives() {
+        // Alternatives that can match nothing must not be retried forever
+        let parser = at_most_one_of(
+            (
+                just::<_, _, extra::Err<Rich<char>>>('a').or_not(),
+                just('b').or_not(),
+            ),
+            |_, (_, span)| Rich::custom(span, "dup"),
+        )
+        .then_ignore(just(';'));
+
+        assert_eq!(parser.parse("a;").into_result(), Ok(Some(Some('a'))));
+        assert_eq!(parser.parse(";").into_result(), Ok(Some(None)));
+        let res = parser.parse("aa;");
+        assert_eq!(res.output(), Some(&Some(Some('a'))));
+        assert_eq!(res.errors().count(), 1);
+    }
+
+    #[test]
+    fn expanded_span_origin() {
+        use crate::span::ExpandedSpan;
+
+        let origin = SimpleSpan::from(1..5);
+        let tokens = [
+            ('a', ExpandedSpan::real((0..1).into())),
+            ('b', ExpandedSpan::synthetic((50..51).into(), origin)),
+            ('c', ExpandedSpan::real((5..6).into())),
+        ];
+        let eoi = ExpandedSpan::real((6..6).into());
+        let pair = any::<_, extra::Err<Simple<char, ExpandedSpan<SimpleSpan>>>>()
+            .then(any())
+            .map_with(|_, e| e.span());
+
+        // A span that ends in synthetic code takes the origin of its end
+        let span = pair.parse(tokens[..2].spanned(eoi)).into_result().unwrap();
+        assert_eq!(span.span(), &SimpleSpan::from(0..51));
+        assert_eq!(span.location(), &origin);
+
+        let span = pair.parse(tokens[1..].spanned(eoi)).into_result().unwrap();
+        assert_eq!(span.location(), &origin);
+
+        let errs = pair.parse(tokens[..1].spanned(eoi)).into_errors();
+        assert!(!errs[0].span().is_synthetic());
+        assert_eq!(errs[0].span().location(), &SimpleSpan::from(6..6));
+    }
+
+    #[test]
+    fn grid_rows() {
+        use crate::grid::{self, Grid, Pos};
+
+        let cell =
+            any::<_, extra::Err<Simple<u8, SimpleSpan<Pos>>>>().map_with(|c, e| (c, e.span()));
+        let cells = cell.repeated().collect::<Vec<_>>();
+        // Empty rows are skipped over, and spans start in the row of the token
+        let out = cells.parse(Grid::lines("ab\n\nc")).into_result().unwrap();
+        assert_eq!(
+            out[1].1,
+            SimpleSpan::from(Pos { row: 0, col: 1 }..Pos { row: 0, col: 2 })
+        );
+        assert_eq!(
+            out[2].1,
+            SimpleSpan::from(Pos { row: 2, col: 0 }..Pos { row: 2, col: 1 })
+        );
+
+        // Skipping the rest of each row
+        let first = any::<_, extra::Err<Simple<u8, SimpleSpan<Pos>>>>()
+            .then(grid::position())
+            .then_ignore(grid::next_row().or(end()))
+            .repeated()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            first.parse(Grid::lines("xyz\nw\nu")).into_result(),
+            Ok(vec![
+                (b'x', Pos { row: 0, col: 1 }),
+                (b'w', Pos { row: 1, col: 1 }),
+                (b'u', Pos { row: 2, col: 1 }),
+            ]),
+        );
+        assert!(grid::next_row::<u8, extra::Default>()
+            .parse(Grid::lines("abc"))
+            .has_errors());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_value() {
+        use crate::de::{from_value, Value};
+        use serde::Deserialize;
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        enum Shape<'a> {
+            Dot,
+            Circle(f64),
+            Rect { w: i64, h: i64 },
+            Named(&'a str, Option<i64>),
+        }
+
+        let value = recursive(|value| {
+            let str = none_of::<_, _, extra::Err<Simple<char>>>('"')
+                .repeated()
+                .to_slice()
+                .map(Value::from)
+                .delimited_by(just('"'), just('"'));
+            let num = text::int(10).from_str().unwrapped().map(Value::Int);
+            let seq = value
+                .clone()
+                .separated_by(just(','))
+                .collect()
+                .delimited_by(just('['), just(']'))
+                .map(Value::Seq);
+            let map = str
+                .then_ignore(just(':'))
+                .then(value)
+                .separated_by(just(','))
+                .collect()
+                .delimited_by(just('{'), just('}'))
+                .map(Value::Map);
+            choice((str, num, seq, map, just("null").to(Value::Null)))
+        });
+        let shapes = |src| from_value::<Vec<Shape>>(value.parse(src).into_output().unwrap());
+
+        assert_eq!(
+            shapes(r#"["Dot",{"Rect":{"w":1,"h":2}},{"Named":["x",null]},{"Named":["y",3]}]"#),
+            Ok(vec![
+                Shape::Dot,
+                Shape::Rect { w: 1, h: 2 },
+                Shape::Named("x", None),
+                Shape::Named("y", Some(3)),
+            ]),
+        );
+        assert!(shapes(r#"[{"Circle":"big"}]"#).is_err());
+        assert!(shapes(r#"[{"Dot":1}]"#).is_err());
+        assert!(shapes(r#"[{"Rect":{"w":1}}]"#).is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "label"))]
+    fn rich_report_json() {
+        let errs = just::<_, _, extra::Err<Rich<char>>>('a')
+            .or(just('b').labelled("bee"))
+            .parse("x")
+            .into_errors();
+        let json = serde_json::to_value(errs[0].to_report()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "message": "found x expected 'a', or bee",
+                "span": { "start": 0, "end": 1, "context": null },
+                "expected": [
+                    { "kind": "token", "value": "a" },
+                    { "kind": "label", "value": "bee" },
+                ],
+                "found": "x",
+                "custom": [],
+                "contexts": [],
+                "notes": [],
+            })
+        );
+    }
+
+    #[test]
+    fn ignore_with_ctx_iter() {
+        fn parser<'a>() -> impl Parser<'a, &'a str, Vec<char>> {
+            // The leading digit only determines how many letters follow
+            any()
+                .filter(|c: &char| c.is_ascii_digit())
+                .map(|c| c.to_digit(10).unwrap() as usize)
+                .ignore_with_ctx(
+                    any()
+                        .filter(|c: &char| c.is_ascii_alphabetic())
+                        .repeated()
+                        .configure(|cfg, ctx: &usize| cfg.exactly(*ctx)),
+                )
+                .collect()
+        }
+
+        assert_eq!(
+            parser().parse("3abc").into_result(),
+            Ok(vec!['a', 'b', 'c'])
+        );
+        assert_eq!(parser().parse("0").into_result(), Ok(vec![]));
+        assert!(parser().parse("2abc").has_errors());
+        assert!(parser().parse("3ab").has_errors());
+    }
 }
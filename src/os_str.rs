@@ -0,0 +1,190 @@
+//! Parsing of platform strings ([`OsStr`]) and [`Path`]s without lossy conversion to UTF-8.
+//!
+//! *"Time is an illusion. Lunchtime doubly so."*
+//!
+//! File names and command-line arguments are not guaranteed to be valid UTF-8, so converting them to `str` before
+//! parsing them either fails or (with [`OsStr::to_string_lossy`]) replaces parts of them. This module instead gives
+//! access to a platform string as bytes, which can be parsed like any other `&[u8]` input:
+//!
+//! - On Unix-like platforms, the bytes are the platform string itself, without any copying.
+//! - On Windows, platform strings are sequences of 16-bit code units that may contain unpaired surrogates. These are
+//!   converted to [WTF-8](https://simonsapin.github.io/wtf-8/), an extension of UTF-8 that can represent unpaired
+//!   surrogates. Any part of the string that is valid UTF-16 becomes the same bytes that it would in UTF-8.
+//!
+//! Slices of the bytes (such as those produced by [`Parser::to_slice`]) can be converted back into platform strings
+//! with [`from_bytes`].
+//!
+//! # Examples
+//!
+//! ```
+//! # use chumsky::{prelude::*, os_str};
+//! # use std::{ffi::OsStr, path::Path};
+//! // Parse the stem and numeric suffix of a file name like `frame-0042.png`
+//! fn frame<'a>() -> impl Parser<'a, &'a [u8], (&'a [u8], u32)> {
+//!     let stem = any().and_is(just(b'-').not()).repeated().at_least(1).to_slice();
+//!     let num = text::digits(10).to_slice().map(|d: &[u8]| {
+//!         d.iter().fold(0, |n, d: &u8| n * 10 + (d - b'0') as u32)
+//!     });
+//!     stem.then_ignore(just(b'-')).then(num).then_ignore(just(b".png"))
+//! }
+//!
+//! let path = Path::new("renders/frame-0042.png");
+//! let bytes = os_str::to_bytes(path.file_name().unwrap());
+//! let (stem, num) = frame().parse(&*bytes).into_result().unwrap();
+//!
+//! assert_eq!(os_str::from_bytes(stem), OsStr::new("frame"));
+//! assert_eq!(num, 42);
+//! ```
+
+use super::*;
+use alloc::borrow::Cow;
+use std::ffi::OsStr;
+#[cfg(not(unix))]
+use std::ffi::OsString;
+#[allow(unused)] // for intra-doc links
+use std::path::Path;
+
+/// Get the bytes of a platform string, for parsing.
+///
+/// On Unix-like platforms, this borrows the string. On Windows, the string is converted to WTF-8. On other platforms,
+/// strings that are not valid UTF-8 are converted lossily. See [the module documentation](self) for more information.
+///
+/// To parse a [`Path`], use [`Path::as_os_str`].
+pub fn to_bytes(s: &OsStr) -> Cow<'_, [u8]> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        Cow::Borrowed(s.as_bytes())
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStrExt;
+        Cow::Owned(encode_wtf8(s.encode_wide()))
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        match s.to_string_lossy() {
+            Cow::Borrowed(s) => Cow::Borrowed(s.as_bytes()),
+            Cow::Owned(s) => Cow::Owned(s.into_bytes()),
+        }
+    }
+}
+
+/// Convert bytes produced by [`to_bytes`] (or a slice of them) back into a platform string.
+///
+/// On Unix-like platforms, this borrows the bytes. On Windows, the bytes are decoded from WTF-8. If a slice splits a
+/// character in two, the partial character is replaced with `U+FFFD REPLACEMENT CHARACTER` on platforms other than
+/// Unix.
+pub fn from_bytes(bytes: &[u8]) -> Cow<'_, OsStr> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        Cow::Borrowed(OsStr::from_bytes(bytes))
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStringExt;
+        Cow::Owned(OsString::from_wide(&decode_wtf8(bytes)))
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        match String::from_utf8_lossy(bytes) {
+            Cow::Borrowed(s) => Cow::Borrowed(OsStr::new(s)),
+            Cow::Owned(s) => Cow::Owned(OsString::from(s)),
+        }
+    }
+}
+
+// Encode UTF-16 code units (which may contain unpaired surrogates) as WTF-8
+#[cfg_attr(not(any(windows, test)), allow(dead_code))]
+fn encode_wtf8(units: impl Iterator<Item = u16>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for c in char::decode_utf16(units) {
+        match c {
+            Ok(c) => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+            // Unpaired surrogates are encoded as if they were characters
+            Err(err) => {
+                let s = err.unpaired_surrogate();
+                bytes.extend_from_slice(&[
+                    0xE0 | (s >> 12) as u8,
+                    0x80 | ((s >> 6) & 0x3F) as u8,
+                    0x80 | (s & 0x3F) as u8,
+                ]);
+            }
+        }
+    }
+    bytes
+}
+
+// Decode WTF-8 into UTF-16 code units, replacing invalid sequences with U+FFFD
+#[cfg_attr(not(any(windows, test)), allow(dead_code))]
+fn decode_wtf8(mut bytes: &[u8]) -> Vec<u16> {
+    let mut units = Vec::with_capacity(bytes.len());
+    while !bytes.is_empty() {
+        match core::str::from_utf8(bytes) {
+            Ok(s) => {
+                units.extend(s.encode_utf16());
+                break;
+            }
+            Err(err) => {
+                let (valid, rest) = bytes.split_at(err.valid_up_to());
+                // SAFETY: `valid_up_to` is the length of the valid prefix
+                units.extend(unsafe { core::str::from_utf8_unchecked(valid) }.encode_utf16());
+                // Surrogates are encoded like three-byte characters in the range U+D800..=U+DFFF
+                match rest {
+                    [a @ 0xED, b @ 0xA0..=0xBF, c @ 0x80..=0xBF, ..] => {
+                        units.push(
+                            ((*a as u16 & 0x0F) << 12)
+                                | ((*b as u16 & 0x3F) << 6)
+                                | (*c as u16 & 0x3F),
+                        );
+                        bytes = &rest[3..];
+                    }
+                    _ => {
+                        units.push(char::REPLACEMENT_CHARACTER as u16);
+                        bytes = &rest[err.error_len().unwrap_or(rest.len())..];
+                    }
+                }
+            }
+        }
+    }
+    units
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wtf8_round_trip() {
+        // `a`, an unpaired high surrogate, `é`, a surrogate pair for U+1F600, and an unpaired low surrogate
+        let units = [0x61, 0xD800, 0xE9, 0xD83D, 0xDE00, 0xDC00];
+        let bytes = encode_wtf8(units.iter().copied());
+        assert_eq!(
+            bytes,
+            b"a\xED\xA0\x80\xC3\xA9\xF0\x9F\x98\x80\xED\xB0\x80".to_vec()
+        );
+        assert_eq!(decode_wtf8(&bytes), units.to_vec());
+
+        // Partial characters are replaced
+        assert_eq!(decode_wtf8(b"a\xC3"), vec![0x61, 0xFFFD]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_unix() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let name = OsStr::from_bytes(b"\xFFname.txt");
+        let bytes = to_bytes(name);
+        assert!(matches!(bytes, Cow::Borrowed(_)));
+
+        let stem = any::<_, extra::Default>()
+            .and_is(just(b'.').not())
+            .repeated()
+            .to_slice()
+            .then_ignore(just(b".txt"));
+        let stem = stem.parse(&*bytes).into_result().unwrap();
+        assert_eq!(from_bytes(stem), OsStr::from_bytes(b"\xFFname"));
+    }
+}
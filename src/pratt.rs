@@ -23,6 +23,124 @@
 //! combines its operands together into a syntax tree. These functions are given as the last arguments of [`infix`],
 //! [`prefix`], and [`postfix`].
 //!
+//! # Mixfix operators
+//!
+//! Some operators have more than one part, with operands in between, such as the conditional operator `c ? a : b` in
+//! C-like languages, or `if c then a else b` in ML-like languages. These can be defined with [`ternary`] (for operators
+//! that begin with an operand) and [`prefix_ternary`] (for those that begin with part of the operator).
+//!
+//! Operands that sit between two parts of an operator are delimited by them, so they are parsed with the lowest
+//! binding power: they may be any expression, just as if they were in parentheses. Only the operand at the end of the
+//! operator (which is not delimited) is parsed with the operator's binding power, so `c ? a : b + 1` is parsed as
+//! `c ? a : (b + 1)` if `+` binds more tightly than `?:`.
+//!
+//! ```
+//! # use chumsky::{prelude::*, pratt::*};
+//! let atom = text::ascii::ident::<_, _, extra::Err<Simple<char>>>().map(str::to_string).padded();
+//! let kw = |s| text::ascii::keyword(s).padded();
+//!
+//! let expr = atom.pratt((
+//!     infix(left(2), just('+').padded(), |l, _, r, _| format!("({l} + {r})")),
+//!     ternary(right(1), just('?').padded(), just(':').padded(), |c, a, b, _| {
+//!         format!("({c} ? {a} : {b})")
+//!     }),
+//!     prefix_ternary(0, (kw("if"), kw("then"), kw("else")), |c, a, b, _| {
+//!         format!("(if {c} then {a} else {b})")
+//!     }),
+//! ));
+//!
+//! assert_eq!(
+//!     expr.parse("a ? b + c : d ? e : f + g").into_result(),
+//!     Ok("(a ? (b + c) : (d ? e : (f + g)))".to_string()),
+//! );
+//! assert_eq!(
+//!     expr.parse("if a then if b then c else d else e + f").into_result(),
+//!     Ok("(if a then (if b then c else d) else (e + f))".to_string()),
+//! );
+//! ```
+//!
+//! Postfix operators that take a list of arguments that are themselves expressions, such as function calls `f(a, b)`
+//! and indexing `x[i]`, can be defined with [`postfix_args`].
+//!
+//! # Runtime operator tables
+//!
+//! Operators are usually given as a tuple, but a `Vec` of operators can be used too, allowing the operator table to be
+//! built at runtime (such as from a configuration file, or from the operators that a language's prelude defines).
+//! Because the operators in a `Vec` must all have the same type, they must first be [boxed](Operator::boxed):
+//!
+//! ```
+//! # use chumsky::{prelude::*, pratt::*};
+//! // Operator symbols, with their associativity and binding power
+//! let table = [("+", left(1)), ("-", left(1)), ("*", left(2)), ("^", right(3))];
+//!
+//! let ops = table
+//!     .into_iter()
+//!     .map(|(sym, assoc)| {
+//!         infix(assoc, just(sym).padded(), |l, op, r, _| format!("({l} {op} {r})")).boxed()
+//!     })
+//!     .collect::<Vec<_>>();
+//!
+//! let atom = text::int::<_, _, extra::Err<Simple<char>>>(10).map(str::to_string).padded();
+//! let expr = atom.pratt(ops);
+//!
+//! assert_eq!(
+//!     expr.parse("1 + 2 * 3 ^ 4 ^ 5 - 6").into_result(),
+//!     Ok("((1 + (2 * (3 ^ (4 ^ 5)))) - 6)".to_string()),
+//! );
+//! ```
+//!
+//! When the operators aren't known until parsing is underway, such as in languages with user-defined operators and
+//! fixity declarations, use [`dynamic_infix`] to look up the fixity of each operator as it is parsed.
+//!
+//! # Error recovery
+//!
+//! An expression with a malformed operand or operator would normally fail as a whole. Instead, operands that fail to
+//! parse can be replaced with error nodes with [`Pratt::recover_operands_with`], and input that should have been an
+//! operator can be skipped over with [`Pratt::skip_invalid_operators`]. In both cases, an error is emitted and parsing
+//! continues with the operators that follow.
+//!
+//! ```
+//! # use chumsky::{prelude::*, pratt::*, recovery::HasErrorNode};
+//! #[derive(Debug, PartialEq)]
+//! enum Expr {
+//!     Num(u64),
+//!     Add(Box<Self>, Box<Self>),
+//!     Mul(Box<Self>, Box<Self>),
+//!     Error,
+//! }
+//!
+//! impl HasErrorNode for Expr {
+//!     fn is_error_node(&self) -> bool {
+//!         matches!(self, Expr::Error)
+//!     }
+//! }
+//!
+//! let atom = text::int::<_, _, extra::Err<Rich<char>>>(10)
+//!     .from_str()
+//!     .unwrapped()
+//!     .map(Expr::Num)
+//!     .padded();
+//! let expr = atom
+//!     .pratt((
+//!         infix(left(1), just('+').padded(), |l, _, r, _| Expr::Add(Box::new(l), Box::new(r))),
+//!         infix(left(2), just('*').padded(), |l, _, r, _| Expr::Mul(Box::new(l), Box::new(r))),
+//!     ))
+//!     .recover_operands_with(recover_as_error_node(empty(), |_| Expr::Error))
+//!     .skip_invalid_operators(none_of("+*").repeated().at_least(1));
+//!
+//! // The operand missing from `1 + * 2` becomes an error node, and `$` is skipped
+//! let (out, errs) = expr.parse("1 + * 2 $ + 3").into_output_errors();
+//! let num = |n| Box::new(Expr::Num(n));
+//! assert_eq!(
+//!     out,
+//!     Some(Expr::Add(
+//!         Box::new(Expr::Add(num(1), Box::new(Expr::Mul(Box::new(Expr::Error), num(2))))),
+//!         num(3),
+//!     )),
+//! );
+//! assert_eq!(errs.len(), 2);
+//! ```
+//!
 //! # Examples
 //!
 //! ```
@@ -444,6 +562,8 @@ pub enum Associativity {
     Left(u16),
     /// Specifies that the operator should be right-associative, with the given binding power (see [`right`]).
     Right(u16),
+    /// Specifies that the operator should be non-associative, with the given binding power (see [`non_assoc`]).
+    NonAssoc(u16),
 }
 
 /// Specifies a left [`Associativity`] with the given binding power.
@@ -462,20 +582,95 @@ pub fn right(binding_power: u16) -> Associativity {
     Associativity::Right(binding_power)
 }
 
+/// Specifies a non-associative [`Associativity`] with the given binding power.
+///
+/// Non-associative operators cannot be chained together with other non-associative operators of the same binding
+/// power. For example, comparison operators are non-associative in many languages, so `a < b < c` is an error rather
+/// than being evaluated as `(a < b) < c`. When a chain is found, an error is produced by
+/// [`Error::not_associative`] at the site of the second operator, and parsing
+/// continues as if the operator were right-associative.
+///
+/// Non-associative operators with different binding powers may be mixed freely, as may a non-associative operator and
+/// operators that have a higher binding power (such as in `a + b < c + d`).
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, pratt::*};
+/// let atom = text::ascii::ident::<_, _, extra::Err<Rich<char>>>().padded();
+/// let expr = atom.map(str::to_string).pratt((
+///     infix(non_assoc(1), just('<'), |l, _, r, _| format!("({l} < {r})")),
+///     infix(non_assoc(1), just('>'), |l, _, r, _| format!("({l} > {r})")),
+///     infix(left(2), just('+'), |l, _, r, _| format!("({l} + {r})")),
+/// ));
+///
+/// assert_eq!(expr.parse("a + b < c").into_result(), Ok("((a + b) < c)".to_string()));
+///
+/// let errs = expr.parse("a < b > c").into_errors();
+/// assert_eq!(errs.len(), 1);
+/// assert_eq!(errs[0].span(), &SimpleSpan::from(6..7));
+/// assert_eq!(errs[0].to_string(), "operator is not associative");
+/// ```
+pub fn non_assoc(binding_power: u16) -> Associativity {
+    Associativity::NonAssoc(binding_power)
+}
+
+// Set on the minimum binding power of the right operand of a non-associative operator, so that an operator of the
+// same binding power that follows the operand (forming a chain like `a < b < c`) can tell that it is chained.
+const NON_ASSOC: u32 = 1 << 31;
+
+// Remove any non-associative marker from a minimum binding power
+fn min_power_of(min_power: u32) -> u32 {
+    min_power & !NON_ASSOC
+}
+
 impl Associativity {
     fn left_power(&self) -> u32 {
         match self {
-            Self::Left(x) => *x as u32 * 2,
+            Self::Left(x) | Self::NonAssoc(x) => *x as u32 * 2,
             Self::Right(x) => *x as u32 * 2 + 1,
         }
     }
 
     fn right_power(&self) -> u32 {
         match self {
-            Self::Left(x) => *x as u32 * 2 + 1,
+            Self::Left(x) | Self::NonAssoc(x) => *x as u32 * 2 + 1,
             Self::Right(x) => *x as u32 * 2,
         }
     }
+
+    // The minimum binding power with which to parse the right operand
+    fn right_operand_power(&self) -> u32 {
+        match self {
+            Self::NonAssoc(_) => self.right_power() | NON_ASSOC,
+            _ => self.right_power(),
+        }
+    }
+
+    // Whether an operator with this associativity, following an operand parsed with the given minimum binding power,
+    // would be chained onto a non-associative operator of the same binding power
+    fn is_chained(&self, min_power: u32) -> bool {
+        matches!(self, Self::NonAssoc(_)) && min_power == self.right_power() | NON_ASSOC
+    }
+}
+
+// Parse the operator of a non-associative chain, emitting an error for it
+fn parse_chained_op<'src, 'parse, I, E, A, Op, M>(
+    op_parser: &A,
+    inp: &mut InputRef<'src, 'parse, I, E>,
+) -> PResult<M, Op>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, Op, E>,
+    M: Mode,
+{
+    let before = inp.cursor();
+    let found = inp.peek_maybe();
+    let op = op_parser.go::<M>(inp)?;
+    let span = inp.span_since(&before);
+    inp.emit(E::Error::not_associative(found, span));
+    Ok(op)
 }
 
 /// See [`infix`].
@@ -549,28 +744,29 @@ where
     where
         Self: Sized,
     {
-        if self.associativity.left_power() >= min_power {
-            match self.op_parser.go::<M>(inp) {
-                Ok(op) => match f(inp, self.associativity.right_power()) {
-                    Ok(rhs) => Ok(M::combine(
-                        M::combine(lhs, rhs, |lhs, rhs| (lhs, rhs)),
-                        op,
-                        |(lhs, rhs), op| {
-                            (self.fold)(lhs, op, rhs, &mut MapExtra::new(pre_expr, inp))
-                        },
-                    )),
-                    Err(()) => {
-                        inp.rewind(pre_op.clone());
-                        Err(lhs)
-                    }
-                },
+        let op = if self.associativity.is_chained(min_power) {
+            parse_chained_op::<_, _, _, _, M>(&self.op_parser, inp)
+        } else if self.associativity.left_power() >= min_power_of(min_power) {
+            self.op_parser.go::<M>(inp)
+        } else {
+            return Err(lhs);
+        };
+        match op {
+            Ok(op) => match f(inp, self.associativity.right_operand_power()) {
+                Ok(rhs) => Ok(M::combine(
+                    M::combine(lhs, rhs, |lhs, rhs| (lhs, rhs)),
+                    op,
+                    |(lhs, rhs), op| (self.fold)(lhs, op, rhs, &mut MapExtra::new(pre_expr, inp)),
+                )),
                 Err(()) => {
                     inp.rewind(pre_op.clone());
                     Err(lhs)
                 }
+            },
+            Err(()) => {
+                inp.rewind(pre_op.clone());
+                Err(lhs)
             }
-        } else {
-            Err(lhs)
         }
     }
 
@@ -729,7 +925,7 @@ where
     where
         Self: Sized,
     {
-        if Associativity::Left(self.binding_power).right_power() >= min_power {
+        if Associativity::Left(self.binding_power).right_power() >= min_power_of(min_power) {
             match self.op_parser.go::<M>(inp) {
                 Ok(op) => Ok(M::combine(lhs, op, |lhs, op| {
                     (self.fold)(lhs, op, &mut MapExtra::new(pre_expr, inp))
@@ -747,174 +943,948 @@ where
     op_check_and_emit!();
 }
 
-/// See [`Parser::pratt`].
-#[derive(Copy, Clone)]
-pub struct Pratt<Atom, Ops> {
-    pub(crate) atom: Atom,
-    pub(crate) ops: Ops,
+/// See [`juxtaposition`].
+pub struct Juxtaposition<'src, F, Atom, I, E> {
+    fold: F,
+    associativity: Associativity,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<&'src (Atom, I, E)>,
 }
 
-macro_rules! impl_operator_for_tuple {
-    () => {};
-    ($head:ident $($X:ident)*) => {
-        impl_operator_for_tuple!($($X)*);
-        impl_operator_for_tuple!(~ $head $($X)*);
-    };
-    (~ $($X:ident)+) => {
-        #[allow(unused_variables, non_snake_case)]
-        impl<'src, I, O, E, $($X),*> Operator<'src, I, O, E> for ($($X,)*)
-            where
-                I: Input<'src>,
-                E: ParserExtra<'src, I>,
-                $($X: Operator<'src, I, O, E>),*
-        {
-            #[inline]
-            fn do_parse_prefix<'parse, M: Mode>(
-                &self,
-                inp: &mut InputRef<'src, 'parse, I, E>,
-                pre_expr: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
-                f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
-            ) -> PResult<M, O>
-            where
-                Self: Sized,
-            {
-                let ($($X,)*) = self;
-                $(
-                    match $X.do_parse_prefix::<M>(inp, pre_expr, f) {
-                        Ok(out) => return Ok(out),
-                        Err(()) => {},
-                    }
-                )*
-                Err(())
-            }
-
-            #[inline]
-            fn do_parse_postfix<'parse, M: Mode>(
-                &self,
-                inp: &mut InputRef<'src, 'parse, I, E>,
-                pre_expr: &input::Cursor<'src, 'parse, I>,
-                pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
-                mut lhs: M::Output<O>,
-                min_power: u32,
-            ) -> Result<M::Output<O>, M::Output<O>>
-            where
-                Self: Sized,
-            {
-                let ($($X,)*) = self;
-                $(
-                    match $X.do_parse_postfix::<M>(inp, pre_expr, pre_op, lhs, min_power) {
-                        Ok(out) => return Ok(out),
-                        Err(out) => lhs = out,
-                    }
-                )*
-                Err(lhs)
-            }
-
-            #[inline]
-            fn do_parse_infix<'parse, M: Mode>(
-                &self,
-                inp: &mut InputRef<'src, 'parse, I, E>,
-                pre_expr: &input::Cursor<'src, 'parse, I>,
-                pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
-                mut lhs: M::Output<O>,
-                min_power: u32,
-                f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
-            ) -> Result<M::Output<O>, M::Output<O>>
-            where
-                Self: Sized,
-            {
-                let ($($X,)*) = self;
-                $(
-                    match $X.do_parse_infix::<M>(inp, pre_expr, pre_op, lhs, min_power, f) {
-                        Ok(out) => return Ok(out),
-                        Err(out) => lhs = out,
-                    }
-                )*
-                Err(lhs)
-            }
-
-            op_check_and_emit!();
+impl<F: Copy, Atom, I, E> Copy for Juxtaposition<'_, F, Atom, I, E> {}
+impl<F: Clone, Atom, I, E> Clone for Juxtaposition<'_, F, Atom, I, E> {
+    fn clone(&self) -> Self {
+        Self {
+            fold: self.fold.clone(),
+            associativity: self.associativity,
+            phantom: EmptyPhantom::new(),
         }
-    };
+    }
 }
 
-impl_operator_for_tuple!(A_ B_ C_ D_ E_ F_ G_ H_ I_ J_ K_ L_ M_ N_ O_ P_ Q_ R_ S_ T_ U_ V_ W_ X_ Y_ Z_);
+/// Specify an 'empty' binary infix operator for a pratt parser, written by placing its operands next to one another,
+/// with the given associativity, binding power, and [fold function](crate::pratt#fold-functions).
+///
+/// Function application in ML-like languages (`f x y`) and implicit multiplication in mathematical notation (`2x`) are
+/// operators of this kind.
+///
+/// Because there is nothing to mark the operator, it applies whenever an operand can be parsed after another, so it
+/// should usually be the last of the operators: otherwise, in `a - b`, the `-` could be taken as a prefix operator on
+/// the right operand of a juxtaposition.
+///
+/// The fold function (the last argument) tells the parser how to combine the operands into a new expression. It must
+/// have the following signature:
+///
+/// ```ignore
+/// impl Fn(Atom, Atom, &mut MapExtra<'a, '_, I, E>) -> O
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, pratt::*};
+/// let atom = text::ascii::ident::<_, _, extra::Err<Simple<char>>>().map(str::to_string).padded();
+///
+/// let expr = atom.pratt((
+///     infix(left(1), just('+').padded(), |l, _, r, _| format!("({l} + {r})")),
+///     prefix(2, just('-').padded(), |_, x, _| format!("(-{x})")),
+///     juxtaposition(left(3), |f, x, _| format!("({f} {x})")),
+/// ));
+///
+/// assert_eq!(
+///     expr.parse("f x y + g -z").into_result(),
+///     Ok("(((f x) y) + (g (-z)))".to_string()),
+/// );
+/// ```
+pub const fn juxtaposition<'src, F, Atom, I, E>(
+    associativity: Associativity,
+    fold: F,
+) -> Juxtaposition<'src, F, Atom, I, E>
+where
+    F: Fn(Atom, Atom, &mut MapExtra<'src, '_, I, E>) -> Atom,
+{
+    Juxtaposition {
+        fold,
+        associativity,
+        phantom: EmptyPhantom::new(),
+    }
+}
 
-#[allow(unused_variables, non_snake_case)]
-impl<'src, I, O, E, Op> Operator<'src, I, O, E> for Vec<Op>
+impl<'src, I, O, E, F> Operator<'src, I, O, E> for Juxtaposition<'src, F, O, I, E>
 where
     I: Input<'src>,
     E: ParserExtra<'src, I>,
-    Op: Operator<'src, I, O, E>,
+    F: Fn(O, O, &mut MapExtra<'src, '_, I, E>) -> O,
 {
-    #[inline]
-    fn do_parse_prefix<'parse, M: Mode>(
-        &self,
-        inp: &mut InputRef<'src, 'parse, I, E>,
-        pre_expr: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
-        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
-    ) -> PResult<M, O>
-    where
-        Self: Sized,
-    {
-        for op in self {
-            if let Ok(out) = op.do_parse_prefix::<M>(inp, pre_expr, f) {
-                return Ok(out);
-            }
-        }
-        Err(())
-    }
-
-    #[inline]
-    fn do_parse_postfix<'parse, M: Mode>(
-        &self,
-        inp: &mut InputRef<'src, 'parse, I, E>,
-        pre_expr: &input::Cursor<'src, 'parse, I>,
-        pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
-        mut lhs: M::Output<O>,
-        min_power: u32,
-    ) -> Result<M::Output<O>, M::Output<O>>
-    where
-        Self: Sized,
-    {
-        for op in self {
-            match op.do_parse_postfix::<M>(inp, pre_expr, pre_op, lhs, min_power) {
-                Ok(out) => return Ok(out),
-                Err(out) => lhs = out,
-            }
-        }
-        Err(lhs)
-    }
-
     #[inline]
     fn do_parse_infix<'parse, M: Mode>(
         &self,
         inp: &mut InputRef<'src, 'parse, I, E>,
         pre_expr: &input::Cursor<'src, 'parse, I>,
         pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
-        mut lhs: M::Output<O>,
+        lhs: M::Output<O>,
         min_power: u32,
         f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
     ) -> Result<M::Output<O>, M::Output<O>>
     where
         Self: Sized,
     {
-        for op in self {
-            match op.do_parse_infix::<M>(inp, pre_expr, pre_op, lhs, min_power, f) {
-                Ok(out) => return Ok(out),
-                Err(out) => lhs = out,
+        let chained = self.associativity.is_chained(min_power);
+        if !chained && self.associativity.left_power() < min_power_of(min_power) {
+            return Err(lhs);
+        }
+        let found = inp.peek_maybe();
+        match f(inp, self.associativity.right_operand_power()) {
+            // An operand that consumed nothing (such as one produced by recovery) could be juxtaposed forever
+            Ok(_) if inp.cursor() == *pre_op.cursor() => {
+                inp.rewind(pre_op.clone());
+                Err(lhs)
+            }
+            Ok(rhs) => {
+                // With no operator to point to, the error for a chain points to the operand that forms it
+                if chained {
+                    let span = inp.span_since(pre_op.cursor());
+                    inp.emit(E::Error::not_associative(found, span));
+                }
+                Ok(M::combine(lhs, rhs, |lhs, rhs| {
+                    (self.fold)(lhs, rhs, &mut MapExtra::new(pre_expr, inp))
+                }))
+            }
+            Err(()) => {
+                inp.rewind(pre_op.clone());
+                Err(lhs)
             }
         }
-        Err(lhs)
     }
 
     op_check_and_emit!();
 }
 
-#[allow(unused_variables, non_snake_case)]
-impl<'a, Atom, Ops> Pratt<Atom, Ops> {
-    #[inline]
-    fn pratt_go<M: Mode, I, O, E>(
+/// See [`ternary`].
+pub struct Ternary<'src, A, B, F, Atom, OpA, OpB, I, E> {
+    first_op: A,
+    second_op: B,
+    fold: F,
+    associativity: Associativity,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<&'src (Atom, OpA, OpB, I, E)>,
+}
+
+impl<A: Copy, B: Copy, F: Copy, Atom, OpA, OpB, I, E> Copy
+    for Ternary<'_, A, B, F, Atom, OpA, OpB, I, E>
+{
+}
+impl<A: Clone, B: Clone, F: Clone, Atom, OpA, OpB, I, E> Clone
+    for Ternary<'_, A, B, F, Atom, OpA, OpB, I, E>
+{
+    fn clone(&self) -> Self {
+        Self {
+            first_op: self.first_op.clone(),
+            second_op: self.second_op.clone(),
+            fold: self.fold.clone(),
+            associativity: self.associativity,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// Specify a ternary infix operator for a pratt parser, such as `cond ? a : b`, with the given associativity, binding
+/// power, and [fold function](crate::pratt#fold-functions).
+///
+/// The operator is made up of two parts, `first_op` and `second_op`, with an operand between them. Because this
+/// operand is delimited on both sides, it may be any expression (including one that uses operators with a lower
+/// binding power than this one). The operand after `second_op` is parsed according to the given associativity, as
+/// for [`infix`]. See [the module documentation](crate::pratt#mixfix-operators) for more information.
+///
+/// The outputs of `first_op` and `second_op` are discarded. The fold function is given the three operands, and must
+/// have the following signature:
+///
+/// ```ignore
+/// impl Fn(Atom, Atom, Atom, &mut MapExtra<'a, '_, I, E>) -> O
+/// ```
+pub const fn ternary<'src, A, B, F, Atom, OpA, OpB, I, E>(
+    associativity: Associativity,
+    first_op: A,
+    second_op: B,
+    fold: F,
+) -> Ternary<'src, A, B, F, Atom, OpA, OpB, I, E>
+where
+    F: Fn(Atom, Atom, Atom, &mut MapExtra<'src, '_, I, E>) -> Atom,
+{
+    Ternary {
+        first_op,
+        second_op,
+        fold,
+        associativity,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'src, I, O, E, A, B, F, OpA, OpB> Operator<'src, I, O, E>
+    for Ternary<'src, A, B, F, O, OpA, OpB, I, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, OpA, E>,
+    B: Parser<'src, I, OpB, E>,
+    F: Fn(O, O, O, &mut MapExtra<'src, '_, I, E>) -> O,
+{
+    #[inline]
+    fn do_parse_infix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Cursor<'src, 'parse, I>,
+        pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        lhs: M::Output<O>,
+        min_power: u32,
+        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+    ) -> Result<M::Output<O>, M::Output<O>>
+    where
+        Self: Sized,
+    {
+        if self.associativity.left_power() < min_power_of(min_power) {
+            return Err(lhs);
+        }
+        let operands = self
+            .first_op
+            .go::<Check>(inp)
+            .and_then(|()| f(inp, 0))
+            .and_then(|mid| self.second_op.go::<Check>(inp).map(|()| mid))
+            .and_then(|mid| f(inp, self.associativity.right_power()).map(|rhs| (mid, rhs)));
+        match operands {
+            Ok((mid, rhs)) => Ok(M::combine(
+                M::combine(lhs, mid, |lhs, mid| (lhs, mid)),
+                rhs,
+                |(lhs, mid), rhs| (self.fold)(lhs, mid, rhs, &mut MapExtra::new(pre_expr, inp)),
+            )),
+            Err(()) => {
+                inp.rewind(pre_op.clone());
+                Err(lhs)
+            }
+        }
+    }
+
+    op_check_and_emit!();
+}
+
+/// See [`prefix_ternary`].
+pub struct PrefixTernary<'src, A, B, C, F, Atom, OpA, OpB, OpC, I, E> {
+    ops: (A, B, C),
+    fold: F,
+    binding_power: u16,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<&'src (Atom, OpA, OpB, OpC, I, E)>,
+}
+
+impl<A: Copy, B: Copy, C: Copy, F: Copy, Atom, OpA, OpB, OpC, I, E> Copy
+    for PrefixTernary<'_, A, B, C, F, Atom, OpA, OpB, OpC, I, E>
+{
+}
+impl<A: Clone, B: Clone, C: Clone, F: Clone, Atom, OpA, OpB, OpC, I, E> Clone
+    for PrefixTernary<'_, A, B, C, F, Atom, OpA, OpB, OpC, I, E>
+{
+    fn clone(&self) -> Self {
+        Self {
+            ops: self.ops.clone(),
+            fold: self.fold.clone(),
+            binding_power: self.binding_power,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// Specify a ternary prefix operator for a pratt parser, such as `if a then b else c`, with the given binding power
+/// and [fold function](crate::pratt#fold-functions).
+///
+/// The operator is made up of three parts, given as a tuple of parsers, each of which is followed by an operand. The
+/// first two operands are delimited on both sides, so they may be any expression. The last operand is parsed with the
+/// given binding power, as for [`prefix`]. See [the module documentation](crate::pratt#mixfix-operators) for more
+/// information.
+///
+/// The outputs of the operator's parts are discarded. The fold function is given the three operands, and must have
+/// the following signature:
+///
+/// ```ignore
+/// impl Fn(Atom, Atom, Atom, &mut MapExtra<'a, '_, I, E>) -> O
+/// ```
+pub const fn prefix_ternary<'src, A, B, C, F, Atom, OpA, OpB, OpC, I, E>(
+    binding_power: u16,
+    ops: (A, B, C),
+    fold: F,
+) -> PrefixTernary<'src, A, B, C, F, Atom, OpA, OpB, OpC, I, E>
+where
+    F: Fn(Atom, Atom, Atom, &mut MapExtra<'src, '_, I, E>) -> Atom,
+{
+    PrefixTernary {
+        ops,
+        fold,
+        binding_power,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'src, I, O, E, A, B, C, F, OpA, OpB, OpC> Operator<'src, I, O, E>
+    for PrefixTernary<'src, A, B, C, F, O, OpA, OpB, OpC, I, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, OpA, E>,
+    B: Parser<'src, I, OpB, E>,
+    C: Parser<'src, I, OpC, E>,
+    F: Fn(O, O, O, &mut MapExtra<'src, '_, I, E>) -> O,
+{
+    #[inline]
+    fn do_parse_prefix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+    ) -> PResult<M, O>
+    where
+        Self: Sized,
+    {
+        let (a, b, c) = &self.ops;
+        let operands = a
+            .go::<Check>(inp)
+            .and_then(|()| f(inp, 0))
+            .and_then(|x| b.go::<Check>(inp).map(|()| x))
+            .and_then(|x| f(inp, 0).map(|y| (x, y)))
+            .and_then(|xy| c.go::<Check>(inp).map(|()| xy))
+            .and_then(|(x, y)| {
+                f(inp, Associativity::Left(self.binding_power).left_power()).map(|z| (x, y, z))
+            });
+        match operands {
+            Ok((x, y, z)) => Ok(M::combine(
+                M::combine(x, y, |x, y| (x, y)),
+                z,
+                |(x, y), z| (self.fold)(x, y, z, &mut MapExtra::new(pre_expr.cursor(), inp)),
+            )),
+            Err(()) => {
+                inp.rewind(pre_expr.clone());
+                Err(())
+            }
+        }
+    }
+
+    op_check_and_emit!();
+}
+
+/// See [`postfix_args`].
+pub struct PostfixArgs<'src, A, S, B, F, Atom, OpA, OpS, OpB, I, E> {
+    ops: (A, S, B),
+    fold: F,
+    binding_power: u16,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<&'src (Atom, OpA, OpS, OpB, I, E)>,
+}
+
+impl<A: Copy, S: Copy, B: Copy, F: Copy, Atom, OpA, OpS, OpB, I, E> Copy
+    for PostfixArgs<'_, A, S, B, F, Atom, OpA, OpS, OpB, I, E>
+{
+}
+impl<A: Clone, S: Clone, B: Clone, F: Clone, Atom, OpA, OpS, OpB, I, E> Clone
+    for PostfixArgs<'_, A, S, B, F, Atom, OpA, OpS, OpB, I, E>
+{
+    fn clone(&self) -> Self {
+        Self {
+            ops: self.ops.clone(),
+            fold: self.fold.clone(),
+            binding_power: self.binding_power,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// Specify a postfix operator that takes a list of arguments, such as a function call `f(a, b)` or an index `x[i]`,
+/// with the given binding power and [fold function](crate::pratt#fold-functions).
+///
+/// The arguments are delimited by `open` and `close`, and separated by `separator`. Like the operands in the middle of
+/// a [mixfix operator](crate::pratt#mixfix-operators), each argument may be any expression. There may be no
+/// arguments at all, as in `f()`.
+///
+/// The outputs of `open`, `separator`, and `close` are discarded. The fold function is given the operand and the
+/// arguments, and must have the following signature:
+///
+/// ```ignore
+/// impl Fn(Atom, Vec<Atom>, &mut MapExtra<'a, '_, I, E>) -> O
+/// ```
+///
+/// The span given by the [`MapExtra`] covers the whole expression, from the start of the operand to `close`.
+///
+/// Postfix operators whose arguments are not expressions (such as a field access `.field`, perhaps with generic
+/// arguments) don't need to be parsed by the pratt parser, and can use [`postfix`] with a parser for the whole
+/// operator.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, pratt::*};
+/// let atom = text::ascii::ident::<_, _, extra::Err<Simple<char>>>().map(str::to_string).padded();
+/// let op = |c| just(c).padded();
+///
+/// let expr = atom.pratt((
+///     infix(left(1), op('+'), |l, _, r, _| format!("({l} + {r})")),
+///     postfix_args(2, (op('('), op(','), op(')')), |f, args, _| format!("{f}({})", args.join(", "))),
+///     postfix_args(2, (op('['), op(','), op(']')), |x, args, _| format!("{x}[{}]", args.join(", "))),
+///     postfix(2, op('.').ignore_then(text::ascii::ident()), |x, field, _| format!("{x}.{field}")),
+/// ));
+///
+/// assert_eq!(
+///     expr.parse("f(a + b, g())[i].x + y").into_result(),
+///     Ok("(f((a + b), g())[i].x + y)".to_string()),
+/// );
+/// ```
+pub const fn postfix_args<'src, A, S, B, F, Atom, OpA, OpS, OpB, I, E>(
+    binding_power: u16,
+    ops: (A, S, B),
+    fold: F,
+) -> PostfixArgs<'src, A, S, B, F, Atom, OpA, OpS, OpB, I, E>
+where
+    F: Fn(Atom, Vec<Atom>, &mut MapExtra<'src, '_, I, E>) -> Atom,
+{
+    PostfixArgs {
+        ops,
+        fold,
+        binding_power,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'src, A, S, B, F, OpA, OpS, OpB, I, O, E> PostfixArgs<'src, A, S, B, F, O, OpA, OpS, OpB, I, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, OpA, E>,
+    S: Parser<'src, I, OpS, E>,
+    B: Parser<'src, I, OpB, E>,
+{
+    // Parse the delimited arguments
+    fn parse_args<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+    ) -> PResult<M, Vec<O>> {
+        let (open, separator, close) = &self.ops;
+        open.go::<Check>(inp)?;
+        let mut args = M::bind(Vec::new);
+        let before_close = inp.save();
+        if close.go::<Check>(inp).is_ok() {
+            return Ok(args);
+        }
+        inp.rewind(before_close);
+        loop {
+            let arg = f(inp, 0)?;
+            args = M::combine(args, arg, |mut args, arg| {
+                args.push(arg);
+                args
+            });
+            let before_separator = inp.save();
+            if separator.go::<Check>(inp).is_err() {
+                inp.rewind(before_separator);
+                break;
+            }
+        }
+        close.go::<Check>(inp)?;
+        Ok(args)
+    }
+}
+
+impl<'src, I, O, E, A, S, B, F, OpA, OpS, OpB> Operator<'src, I, O, E>
+    for PostfixArgs<'src, A, S, B, F, O, OpA, OpS, OpB, I, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, OpA, E>,
+    S: Parser<'src, I, OpS, E>,
+    B: Parser<'src, I, OpB, E>,
+    F: Fn(O, Vec<O>, &mut MapExtra<'src, '_, I, E>) -> O,
+{
+    // Parsed in the position of an infix operator, since that gives access to the parser for the arguments
+    #[inline]
+    fn do_parse_infix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Cursor<'src, 'parse, I>,
+        pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        lhs: M::Output<O>,
+        min_power: u32,
+        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+    ) -> Result<M::Output<O>, M::Output<O>>
+    where
+        Self: Sized,
+    {
+        if Associativity::Left(self.binding_power).right_power() < min_power_of(min_power) {
+            return Err(lhs);
+        }
+        match self.parse_args::<M>(inp, f) {
+            Ok(args) => Ok(M::combine(lhs, args, |lhs, args| {
+                (self.fold)(lhs, args, &mut MapExtra::new(pre_expr, inp))
+            })),
+            Err(()) => {
+                inp.rewind(pre_op.clone());
+                Err(lhs)
+            }
+        }
+    }
+
+    op_check_and_emit!();
+}
+
+/// See [`dynamic_infix`].
+pub struct DynamicInfix<'src, A, L, F, Atom, Op, I, E> {
+    op_parser: A,
+    fixity: L,
+    fold: F,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<&'src (Atom, Op, I, E)>,
+}
+
+impl<A: Copy, L: Copy, F: Copy, Atom, Op, I, E> Copy for DynamicInfix<'_, A, L, F, Atom, Op, I, E> {}
+impl<A: Clone, L: Clone, F: Clone, Atom, Op, I, E> Clone
+    for DynamicInfix<'_, A, L, F, Atom, Op, I, E>
+{
+    fn clone(&self) -> Self {
+        Self {
+            op_parser: self.op_parser.clone(),
+            fixity: self.fixity.clone(),
+            fold: self.fold.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// Specify a family of binary infix operators for a pratt parser, with associativities and binding powers that are
+/// looked up as they are parsed.
+///
+/// This is useful for languages with user-defined operators, such as Haskell, where fixity declarations like
+/// `infixl 6 <+>` give operators their associativity and binding power. `op_parser` parses any operator, and `fixity`
+/// is given the operator along with a [`MapExtra`] (spanning the operator, and giving access to the parser state and
+/// context, where fixity declarations might be kept). It returns the [`Associativity`] of the operator, or `None` if
+/// the operator is not known, in which case it is not parsed. The fold function is as for [`infix`].
+///
+/// Because the fixity of an operator depends on the operator itself, `op_parser` is run (and its output produced) at
+/// every point where an operator could appear.
+///
+/// For operator tables that are known before parsing starts but are only built at runtime, a `Vec` of
+/// [boxed](Operator::boxed) operators can also be used. See [the module documentation](crate::pratt#runtime-operator-tables).
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, pratt::*, extra::SimpleState, input::MapExtra};
+/// use std::collections::HashMap;
+///
+/// // Fixity declarations, as collected from earlier in the source
+/// type Fixities = SimpleState<HashMap<&'static str, Associativity>>;
+///
+/// let atom = text::ascii::ident::<_, _, extra::State<Fixities>>().map(str::to_string).padded();
+/// let expr = atom.pratt(dynamic_infix(
+///     one_of("+-*/<>|&").repeated().at_least(1).to_slice().padded(),
+///     |op: &&str, e: &mut MapExtra<'_, '_, &str, extra::State<Fixities>>| e.state().get(op).copied(),
+///     |l, op, r, _| format!("({l} {op} {r})"),
+/// ));
+///
+/// let mut fixities = SimpleState(HashMap::from([("<+>", left(6)), ("<*>", left(7)), ("||", right(2))]));
+/// assert_eq!(
+///     expr.parse_with_state("a <+> b <*> c || d || e", &mut fixities).into_result(),
+///     Ok("((a <+> (b <*> c)) || (d || e))".to_string()),
+/// );
+/// // Unknown operators aren't parsed
+/// assert!(expr.parse_with_state("a <-> b", &mut fixities).has_errors());
+/// ```
+pub const fn dynamic_infix<'src, A, L, F, Atom, Op, I, E>(
+    op_parser: A,
+    fixity: L,
+    fold: F,
+) -> DynamicInfix<'src, A, L, F, Atom, Op, I, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    L: Fn(&Op, &mut MapExtra<'src, '_, I, E>) -> Option<Associativity>,
+    F: Fn(Atom, Op, Atom, &mut MapExtra<'src, '_, I, E>) -> Atom,
+{
+    DynamicInfix {
+        op_parser,
+        fixity,
+        fold,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'src, I, O, E, A, L, F, Op> Operator<'src, I, O, E>
+    for DynamicInfix<'src, A, L, F, O, Op, I, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, Op, E>,
+    L: Fn(&Op, &mut MapExtra<'src, '_, I, E>) -> Option<Associativity>,
+    F: Fn(O, Op, O, &mut MapExtra<'src, '_, I, E>) -> O,
+{
+    #[inline]
+    fn do_parse_infix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Cursor<'src, 'parse, I>,
+        pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        lhs: M::Output<O>,
+        min_power: u32,
+        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+    ) -> Result<M::Output<O>, M::Output<O>>
+    where
+        Self: Sized,
+    {
+        // The operator is always needed to find its fixity, even if the output is not
+        let found = inp.peek_maybe();
+        let Ok(op) = self.op_parser.go::<Emit>(inp) else {
+            inp.rewind(pre_op.clone());
+            return Err(lhs);
+        };
+        let after_op = inp.cursor();
+        let associativity = (self.fixity)(&op, &mut MapExtra::new(pre_op.cursor(), inp));
+        let associativity = match associativity {
+            Some(associativity) if associativity.is_chained(min_power) => {
+                let span = inp.span_since(pre_op.cursor());
+                inp.emit(E::Error::not_associative(found, span));
+                associativity
+            }
+            Some(associativity) if associativity.left_power() >= min_power_of(min_power) => {
+                associativity
+            }
+            _ => {
+                inp.rewind(pre_op.clone());
+                return Err(lhs);
+            }
+        };
+        debug_assert!(inp.cursor() == after_op);
+        match f(inp, associativity.right_operand_power()) {
+            Ok(rhs) => Ok(M::combine(lhs, rhs, |lhs, rhs| {
+                (self.fold)(lhs, op, rhs, &mut MapExtra::new(pre_expr, inp))
+            })),
+            Err(()) => {
+                inp.rewind(pre_op.clone());
+                Err(lhs)
+            }
+        }
+    }
+
+    op_check_and_emit!();
+}
+
+/// See [`Pratt::skip_invalid_operators`].
+#[derive(Copy, Clone)]
+pub struct SkipInvalid<Ops, S> {
+    ops: Ops,
+    skip: S,
+}
+
+impl<'src, I, O, E, Ops, S> Operator<'src, I, O, E> for SkipInvalid<Ops, S>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    Ops: Operator<'src, I, O, E>,
+    S: Parser<'src, I, (), E>,
+{
+    #[inline]
+    fn do_parse_prefix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+    ) -> PResult<M, O>
+    where
+        Self: Sized,
+    {
+        self.ops.do_parse_prefix::<M>(inp, pre_expr, f)
+    }
+
+    #[inline]
+    fn do_parse_postfix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Cursor<'src, 'parse, I>,
+        pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        lhs: M::Output<O>,
+        min_power: u32,
+    ) -> Result<M::Output<O>, M::Output<O>>
+    where
+        Self: Sized,
+    {
+        self.ops
+            .do_parse_postfix::<M>(inp, pre_expr, pre_op, lhs, min_power)
+    }
+
+    #[inline]
+    fn do_parse_infix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Cursor<'src, 'parse, I>,
+        pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        lhs: M::Output<O>,
+        min_power: u32,
+        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+    ) -> Result<M::Output<O>, M::Output<O>>
+    where
+        Self: Sized,
+    {
+        let lhs = match self
+            .ops
+            .do_parse_infix::<M>(inp, pre_expr, pre_op, lhs, min_power, f)
+        {
+            Ok(out) => return Ok(out),
+            Err(lhs) => lhs,
+        };
+
+        // No operator follows the operand, so skip over the input that should have been one and try again
+        let alt = inp.errors.alt.take();
+        let found = inp.peek_maybe();
+        if self.skip.go::<Check>(inp).is_err() || inp.cursor() == *pre_op.cursor() {
+            inp.rewind(pre_op.clone());
+            inp.errors.alt = alt;
+            return Err(lhs);
+        }
+        let skipped = inp.span_since(pre_op.cursor());
+        inp.record_recovery("skip_invalid_operators", &pre_op.cursor().inner);
+        let pre_op_again = inp.save();
+        match self
+            .ops
+            .do_parse_infix::<M>(inp, pre_expr, &pre_op_again, lhs, min_power, f)
+        {
+            Ok(out) => {
+                // If no operator could be tried before skipping (because none bind tightly enough), there is no error
+                // to report for the skipped input yet
+                let alt = alt.unwrap_or_else(|| {
+                    Located::at(
+                        pre_op.cursor().inner.clone(),
+                        E::Error::expected_found([], found, skipped),
+                    )
+                });
+                // The error is only known to be needed once the operator has parsed, but it belongs before any errors
+                // emitted while parsing the operator and its operand
+                if inp.limits.halt.is_none() {
+                    inp.errors.secondary.insert(pre_op_again.err_count, alt);
+                }
+                Ok(out)
+            }
+            Err(lhs) => {
+                inp.rewind(pre_op.clone());
+                inp.errors.alt = alt;
+                Err(lhs)
+            }
+        }
+    }
+
+    op_check_and_emit!();
+}
+
+/// See [`Parser::pratt`].
+#[derive(Copy, Clone)]
+pub struct Pratt<Atom, Ops> {
+    pub(crate) atom: Atom,
+    pub(crate) ops: Ops,
+}
+
+impl<Atom, Ops> Pratt<Atom, Ops> {
+    /// Recover from operands that fail to parse with the given [recovery strategy](crate::recovery::Strategy).
+    ///
+    /// This applies to every operand of the expression, including those that follow operators, so a malformed operand
+    /// (such as the one missing from `1 + * 2`) does not cause the whole expression to fail. Instead, the strategy
+    /// produces an output in its place (usually an error node, with
+    /// [`recover_as_error_node`](crate::recovery::recover_as_error_node)) and parsing continues with the operators that
+    /// follow.
+    ///
+    /// This is much like calling [`Parser::recover_with`] on the atom, but makes it clear that recovery is intended
+    /// for every operand position. See the [module documentation](crate::pratt#error-recovery) for an example.
+    pub fn recover_operands_with<S>(self, strategy: S) -> Pratt<RecoverWith<Atom, S>, Ops> {
+        Pratt {
+            atom: RecoverWith {
+                parser: self.atom,
+                strategy,
+            },
+            ops: self.ops,
+        }
+    }
+
+    /// Recover from invalid operators by skipping over them with the given parser.
+    ///
+    /// When an operand is not followed by any operator, `skip` is used to skip over the input that follows it. If
+    /// `skip` consumes some input and an infix operator follows, an error is emitted for the skipped input and parsing
+    /// continues as if it were not there, so `1 $ + 2` is parsed as `1 + 2`. Otherwise, the expression ends after the
+    /// operand, as usual.
+    ///
+    /// Because the expression might legitimately end after any operand, `skip` should only consume input that cannot
+    /// follow an expression (so, for example, it should not skip closing delimiters).
+    pub fn skip_invalid_operators<S>(self, skip: S) -> Pratt<Atom, SkipInvalid<Ops, S>> {
+        Pratt {
+            atom: self.atom,
+            ops: SkipInvalid {
+                ops: self.ops,
+                skip,
+            },
+        }
+    }
+}
+
+macro_rules! impl_operator_for_tuple {
+    () => {};
+    ($head:ident $($X:ident)*) => {
+        impl_operator_for_tuple!($($X)*);
+        impl_operator_for_tuple!(~ $head $($X)*);
+    };
+    (~ $($X:ident)+) => {
+        #[allow(unused_variables, non_snake_case)]
+        impl<'src, I, O, E, $($X),*> Operator<'src, I, O, E> for ($($X,)*)
+            where
+                I: Input<'src>,
+                E: ParserExtra<'src, I>,
+                $($X: Operator<'src, I, O, E>),*
+        {
+            #[inline]
+            fn do_parse_prefix<'parse, M: Mode>(
+                &self,
+                inp: &mut InputRef<'src, 'parse, I, E>,
+                pre_expr: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+                f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+            ) -> PResult<M, O>
+            where
+                Self: Sized,
+            {
+                let ($($X,)*) = self;
+                $(
+                    match $X.do_parse_prefix::<M>(inp, pre_expr, f) {
+                        Ok(out) => return Ok(out),
+                        Err(()) => {},
+                    }
+                )*
+                Err(())
+            }
+
+            #[inline]
+            fn do_parse_postfix<'parse, M: Mode>(
+                &self,
+                inp: &mut InputRef<'src, 'parse, I, E>,
+                pre_expr: &input::Cursor<'src, 'parse, I>,
+                pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+                mut lhs: M::Output<O>,
+                min_power: u32,
+            ) -> Result<M::Output<O>, M::Output<O>>
+            where
+                Self: Sized,
+            {
+                let ($($X,)*) = self;
+                $(
+                    match $X.do_parse_postfix::<M>(inp, pre_expr, pre_op, lhs, min_power) {
+                        Ok(out) => return Ok(out),
+                        Err(out) => lhs = out,
+                    }
+                )*
+                Err(lhs)
+            }
+
+            #[inline]
+            fn do_parse_infix<'parse, M: Mode>(
+                &self,
+                inp: &mut InputRef<'src, 'parse, I, E>,
+                pre_expr: &input::Cursor<'src, 'parse, I>,
+                pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+                mut lhs: M::Output<O>,
+                min_power: u32,
+                f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+            ) -> Result<M::Output<O>, M::Output<O>>
+            where
+                Self: Sized,
+            {
+                let ($($X,)*) = self;
+                $(
+                    match $X.do_parse_infix::<M>(inp, pre_expr, pre_op, lhs, min_power, f) {
+                        Ok(out) => return Ok(out),
+                        Err(out) => lhs = out,
+                    }
+                )*
+                Err(lhs)
+            }
+
+            op_check_and_emit!();
+        }
+    };
+}
+
+impl_operator_for_tuple!(A_ B_ C_ D_ E_ F_ G_ H_ I_ J_ K_ L_ M_ N_ O_ P_ Q_ R_ S_ T_ U_ V_ W_ X_ Y_ Z_);
+
+#[allow(unused_variables, non_snake_case)]
+impl<'src, I, O, E, Op> Operator<'src, I, O, E> for Vec<Op>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    Op: Operator<'src, I, O, E>,
+{
+    #[inline]
+    fn do_parse_prefix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+    ) -> PResult<M, O>
+    where
+        Self: Sized,
+    {
+        for op in self {
+            if let Ok(out) = op.do_parse_prefix::<M>(inp, pre_expr, f) {
+                return Ok(out);
+            }
+        }
+        Err(())
+    }
+
+    #[inline]
+    fn do_parse_postfix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Cursor<'src, 'parse, I>,
+        pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        mut lhs: M::Output<O>,
+        min_power: u32,
+    ) -> Result<M::Output<O>, M::Output<O>>
+    where
+        Self: Sized,
+    {
+        for op in self {
+            match op.do_parse_postfix::<M>(inp, pre_expr, pre_op, lhs, min_power) {
+                Ok(out) => return Ok(out),
+                Err(out) => lhs = out,
+            }
+        }
+        Err(lhs)
+    }
+
+    #[inline]
+    fn do_parse_infix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Cursor<'src, 'parse, I>,
+        pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        mut lhs: M::Output<O>,
+        min_power: u32,
+        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+    ) -> Result<M::Output<O>, M::Output<O>>
+    where
+        Self: Sized,
+    {
+        for op in self {
+            match op.do_parse_infix::<M>(inp, pre_expr, pre_op, lhs, min_power, f) {
+                Ok(out) => return Ok(out),
+                Err(out) => lhs = out,
+            }
+        }
+        Err(lhs)
+    }
+
+    op_check_and_emit!();
+}
+
+#[allow(unused_variables, non_snake_case)]
+impl<'a, Atom, Ops> Pratt<Atom, Ops> {
+    #[inline]
+    fn pratt_go<M: Mode, I, O, E>(
         &self,
         inp: &mut InputRef<'a, '_, I, E>,
         min_power: u32,
@@ -1032,19 +2002,279 @@ mod tests {
         assert_eq!(parser().parse("-2 + 2").into_result(), Ok(0));
     }
 
-    // TODO: Make this work
-    // fn parser_dynamic<'a>() -> impl Parser<'a, &'a str, i64> {
-    //     let atom = text::int(10).padded().from_str::<i64>().unwrapped();
+    fn parser_dynamic<'a>() -> impl Parser<'a, &'a str, i64> {
+        let atom = text::int(10).padded().from_str::<i64>().unwrapped();
+
+        atom.pratt(vec![
+            prefix(2, just('-'), |_, x: i64, _| -x).boxed(),
+            postfix(2, just('!'), |x, _, _| factorial(x)).boxed(),
+            infix(left(0), just('+'), |l, _, r, _| l + r).boxed(),
+            infix(left(0), just('-'), |l, _, r, _| l - r).boxed(),
+            infix(left(1), just('*'), |l, _, r, _| l * r).boxed(),
+            infix(left(1), just('/'), |l, _, r, _| l / r).boxed(),
+        ])
+    }
+
+    #[test]
+    fn dynamic() {
+        assert_eq!(parser_dynamic().parse("2 + 3 * 4").into_result(), Ok(14));
+        assert_eq!(parser_dynamic().parse("-2 * 3 - 4!").into_result(), Ok(-30));
+    }
+
+    #[test]
+    fn dynamic_infix_fixities() {
+        // Fixities as they might be declared by `infixl`/`infixr` declarations
+        let fixities = [('+', left(1)), ('*', left(2)), ('^', right(3))];
+
+        let atom = text::int::<_, _, Err<Simple<char>>>(10)
+            .from_str::<i64>()
+            .unwrapped()
+            .padded();
+        let expr = atom.pratt(dynamic_infix(
+            any().filter(|c: &char| c.is_ascii_punctuation()).padded(),
+            |op: &char, _: &mut MapExtra<'_, '_, &str, Err<Simple<char>>>| {
+                fixities
+                    .iter()
+                    .find(|(sym, _)| sym == op)
+                    .map(|(_, assoc)| *assoc)
+            },
+            |l: i64, op, r, _| match op {
+                '+' => l + r,
+                '*' => l * r,
+                _ => l.pow(r as u32),
+            },
+        ));
+
+        assert_eq!(expr.parse("1 + 2 * 3 ^ 2 ^ 1").into_result(), Ok(19));
+        assert_eq!(expr.parse("2 ^ 3 ^ 2").into_result(), Ok(512));
+        // Operators without a fixity aren't parsed
+        assert!(expr.parse("1 % 2").has_errors());
+        assert_eq!(expr.lazy().parse("1 + 2 % 3").into_result(), Ok(3));
+    }
+
+    #[test]
+    fn non_associative() {
+        let atom = text::int::<_, _, Err<Rich<char>>>(10)
+            .from_str::<i64>()
+            .unwrapped()
+            .padded();
+        let expr = atom.pratt((
+            infix(non_assoc(0), just("=="), |l, _, r, _| (l == r) as i64),
+            infix(non_assoc(1), just('<'), |l, _, r, _| (l < r) as i64),
+            infix(left(2), just('+'), |l, _, r, _| l + r),
+        ));
+
+        assert_eq!(expr.parse("1 + 1 < 3").into_result(), Ok(1));
+        // Non-associative operators of different precedence may be mixed
+        assert_eq!(expr.parse("1 < 2 == 2 < 3").into_result(), Ok(1));
+        assert_eq!(expr.to_slice().parse("1 < 2").into_result(), Ok("1 < 2"));
+
+        // Every chained operator is reported, but parsing continues as if they were right-associative
+        let res = expr.parse("1 < 2 < 3 < 4");
+        assert_eq!(res.output(), Some(&0));
+        assert_eq!(
+            res.errors().map(|e| *e.span()).collect::<Vec<_>>(),
+            vec![SimpleSpan::from(6..7), SimpleSpan::from(10..11)],
+        );
+        assert_eq!(
+            expr.to_slice().parse("1 == 1 == 1").into_errors(),
+            vec![Rich::custom((7..9).into(), "operator is not associative")],
+        );
+    }
+
+    #[test]
+    fn mixfix() {
+        let atom = text::int::<_, _, Err<Simple<char>>>(10)
+            .from_str::<i64>()
+            .unwrapped()
+            .padded();
+        let expr = atom.pratt((
+            infix(left(1), just('+'), |l, _, r, _| l + r),
+            ternary(
+                right(0),
+                just('?'),
+                just(':'),
+                |c, a, b, _| {
+                    if c != 0 {
+                        a
+                    } else {
+                        b
+                    }
+                },
+            ),
+            prefix_ternary(0, (just('['), just('|'), just(']')), |a, b, c, _| a * b * c),
+        ));
+        let complete = expr.then_ignore(end());
+
+        assert_eq!(complete.parse("1 ? 2 : 3 + 4").into_result(), Ok(2));
+        assert_eq!(complete.parse("0 ? 2 : 3 + 4").into_result(), Ok(7));
+        assert_eq!(complete.parse("0 ? 1 : 0 ? 2 : 3").into_result(), Ok(3));
+        assert_eq!(complete.parse("1 + 1 ? 0 ? 5 : 6 : 7").into_result(), Ok(6));
+        assert_eq!(complete.parse("[2 + 1 | 2] 1 + 1").into_result(), Ok(12));
+        assert_eq!(
+            complete.to_slice().parse("[1|2]3").into_result(),
+            Ok("[1|2]3")
+        );
+        // An incomplete operator is not parsed at all
+        assert_eq!(expr.lazy().parse("1 + 1 ? 2").into_result(), Ok(2));
+        assert!(complete.parse("1 ? 2").has_errors());
+        assert!(complete.parse("[1 | 2 3").has_errors());
+    }
+
+    #[test]
+    fn postfix_arguments() {
+        let atom = text::ascii::ident::<_, _, Err<Simple<char>>>()
+            .map(str::to_string)
+            .padded();
+        let expr = atom
+            .pratt((
+                infix(left(1), just('+'), |l, _, r, _| format!("({l} + {r})")),
+                prefix(2, just('-'), |_, x, _| format!("(-{x})")),
+                postfix_args(3, (just('('), just(','), just(')')), |f, args, e| {
+                    let span: SimpleSpan = e.span();
+                    format!("{f}({})@{}", args.join(", "), span.end)
+                }),
+            ))
+            .then_ignore(end());
+
+        assert_eq!(expr.parse("f()").into_result(), Ok("f()@3".to_string()));
+        assert_eq!(
+            expr.parse("-f(a, b + c)(d)").into_result(),
+            Ok("(-f(a, (b + c))@12(d)@15)".to_string()),
+        );
+        assert_eq!(
+            expr.parse("f(g(x),-y)").into_result(),
+            Ok("f(g(x)@6, (-y))@10".to_string()),
+        );
+        assert!(expr.parse("f(a,)").has_errors());
+        assert!(expr.parse("f(a b)").has_errors());
+        // Arguments are still parsed when no output is needed
+        assert_eq!(
+            expr.to_slice().parse("f(a)(b)").into_result(),
+            Ok("f(a)(b)")
+        );
+    }
+
+    #[test]
+    fn juxtapose() {
+        let atom = choice((
+            text::int::<_, _, Err<Simple<char>>>(10).map(str::to_string),
+            one_of("xyz").map(|c: char| c.to_string()),
+        ))
+        .padded();
+        let expr = atom
+            .pratt((
+                infix(left(1), just('+'), |l, _, r, _| format!("({l} + {r})")),
+                infix(right(3), just('^'), |l, _, r, _| format!("({l} ^ {r})")),
+                postfix(4, just('!'), |x, _, _| format!("({x}!)")),
+                juxtaposition(left(2), |l, r, _| format!("({l}{r})")),
+            ))
+            .then_ignore(end());
+
+        assert_eq!(expr.parse("2x").into_result(), Ok("(2x)".to_string()));
+        assert_eq!(
+            expr.parse("2x^2 + 3 x y!").into_result(),
+            Ok("((2(x ^ 2)) + ((3x)(y!)))".to_string()),
+        );
+        assert_eq!(
+            expr.parse("x^2y").into_result(),
+            Ok("((x ^ 2)y)".to_string())
+        );
+        assert!(expr.parse("2x +").has_errors());
+
+        let chain = atom
+            .pratt(juxtaposition(non_assoc(1), |l, r, _| format!("({l}{r})")))
+            .then_ignore(end());
+        assert_eq!(chain.parse("x y").into_result(), Ok("(xy)".to_string()));
+        assert_eq!(chain.parse("x y z").into_errors().len(), 1);
+    }
+
+    #[test]
+    fn cut_in_operator() {
+        // A cut in an operator that is backtracked from doesn't stop alternatives of the whole expression
+        let atom = text::int::<_, _, Err<Simple<char>>>(10).to(());
+        let expr = atom
+            .pratt((infix(
+                left(1),
+                just('+').then(just('+').cut()),
+                |_, _, _, _| (),
+            ),))
+            .then(just(';'))
+            .to(1)
+            .or(just("1+x").to(2));
+        assert_eq!(expr.parse("1++1;").into_result(), Ok(1));
+        assert_eq!(expr.parse("1+x").into_result(), Ok(2));
+    }
+
+    #[test]
+    fn juxtapose_with_operand_recovery() {
+        // A recovered operand that consumes nothing must not be juxtaposed onto the expression
+        let expr = text::ascii::ident::<_, _, Err<Simple<char>>>()
+            .map(str::to_string)
+            .padded()
+            .pratt(juxtaposition(left(1), |f, x, _| format!("({f} {x})")))
+            .recover_operands_with(via_parser(empty().to("?".to_string())))
+            .then_ignore(end());
+
+        assert_eq!(expr.parse("f x").into_result(), Ok("(f x)".to_string()));
+        assert_eq!(
+            expr.parse("f x y").into_result(),
+            Ok("((f x) y)".to_string())
+        );
+    }
 
-    //     atom.pratt(vec![
-    //         prefix(2, just('-'), |x: i64| -x).into(),
-    //         postfix(2, just('!'), factorial).into(),
-    //         infix(left(0), just('+'), |l, r| l + r).into(),
-    //         infix(left(0), just('-'), |l, r| l - r).into(),
-    //         infix(left(1), just('*'), |l, r| l * r).into(),
-    //         infix(left(1), just('/'), |l, _, r| l / r).into(),
-    //     ])
-    // }
+    #[test]
+    fn recovery() {
+        let atom = text::int::<_, _, Err<Simple<char>>>(10)
+            .map(str::to_string)
+            .padded();
+        let expr = atom
+            .pratt((
+                infix(left(1), just('+').padded(), |l, _, r, _| {
+                    format!("({l} + {r})")
+                }),
+                infix(left(2), just('*').padded(), |l, _, r, _| {
+                    format!("({l} * {r})")
+                }),
+            ))
+            .recover_operands_with(via_parser(empty().to("?".to_string())))
+            .skip_invalid_operators(one_of("$@").padded().repeated().at_least(1))
+            .then_ignore(end());
+
+        let parse = |s| {
+            let (out, errs) = expr.parse(s).into_output_errors();
+            let spans = errs.iter().map(|e| *e.span()).collect::<Vec<_>>();
+            (out, spans)
+        };
+        assert_eq!(parse("1 + 2"), (Some("(1 + 2)".to_string()), vec![]));
+        assert_eq!(
+            parse("1 + * 2"),
+            (
+                Some("(1 + (? * 2))".to_string()),
+                vec![SimpleSpan::from(4..5)]
+            )
+        );
+        assert_eq!(
+            parse("1 +"),
+            (Some("(1 + ?)".to_string()), vec![SimpleSpan::from(3..3)])
+        );
+        assert_eq!(
+            parse("1 * 2 $ + 3 @@ * 4"),
+            (
+                Some("((1 * 2) + (3 * 4))".to_string()),
+                vec![SimpleSpan::from(6..7), SimpleSpan::from(12..13)]
+            ),
+        );
+        // Skipped input that isn't followed by an operator is left for the parser that follows
+        assert_eq!(parse("1 + 2 $").1, vec![SimpleSpan::from(6..7)]);
+        assert_eq!(
+            expr.parse("1 $ + 2")
+                .recoveries()
+                .map(|r| r.skipped.clone())
+                .collect::<Vec<_>>(),
+            vec![2..4],
+        );
+    }
 
     enum Expr {
         Literal(i64),
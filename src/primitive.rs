@@ -16,6 +16,7 @@
 //! - [`end`]: parses the end of input (i.e: if there any more inputs, this parse fails)
 
 use super::*;
+use crate::input::{TokenTree, TokenTreeInput};
 
 /// See [`end`].
 pub struct End<I, E>(EmptyPhantom<(E, I)>);
@@ -623,6 +624,10 @@ where
 ///
 /// This function is the borrowing equivalent of [any]. Where possible, it's recommended to use [any] instead.
 ///
+/// Along with [`just_ref`], [`one_of_ref`], [`none_of_ref`] and [`select_ref!`], this allows parsing tokens that are not
+/// [`Clone`] with outputs that borrow from the input. To accept only tokens that satisfy a predicate, use
+/// `any_ref().filter(|tok| ...)`.
+///
 /// # Examples
 ///
 /// ```
@@ -641,6 +646,448 @@ pub const fn any_ref<'a, I: BorrowInput<'a>, E: ParserExtra<'a, I>>() -> AnyRef<
     }
 }
 
+/// See [`just_ref`].
+pub struct JustRef<'a, T, I, E> {
+    tok: &'a T,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(E, I)>,
+}
+
+impl<T, I, E> Copy for JustRef<'_, T, I, E> {}
+impl<T, I, E> Clone for JustRef<'_, T, I, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// A parser that accepts only the given token, producing a reference to the token in the input.
+///
+/// The output type of this parser is `&'a I::Token`, the input that was found.
+///
+/// This function is the borrowing equivalent of [`just`] for a single token: neither the token nor the output needs to
+/// be [`Clone`], which is useful when tokens own data (such as a [`String`]).
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, error::Simple};
+/// #[derive(Debug, PartialEq)]
+/// enum Token { Word(String), Comma }
+///
+/// let comma = just_ref::<_, extra::Err<Simple<Token>>>(&Token::Comma);
+/// let word = select_ref! { Token::Word(w) => w.as_str() };
+/// let words = word.separated_by(comma).collect::<Vec<_>>();
+///
+/// let tokens = [Token::Word("hello".to_string()), Token::Comma, Token::Word("world".to_string())];
+/// assert_eq!(words.parse(&tokens[..]).into_result(), Ok(vec!["hello", "world"]));
+/// assert!(comma.parse(&tokens[..1]).has_errors());
+/// ```
+pub const fn just_ref<'a, I, E>(tok: &'a I::Token) -> JustRef<'a, I::Token, I, E>
+where
+    I: BorrowInput<'a>,
+    E: ParserExtra<'a, I>,
+    I::Token: PartialEq,
+{
+    JustRef {
+        tok,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'a, I, E> ParserSealed<'a, I, &'a I::Token, E> for JustRef<'a, I::Token, I, E>
+where
+    I: BorrowInput<'a>,
+    E: ParserExtra<'a, I>,
+    I::Token: PartialEq,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, &'a I::Token> {
+        let before = inp.save();
+        match inp.next_ref_inner() {
+            Some(tok) if tok == self.tok => Ok(M::bind(|| tok)),
+            found => {
+                let err_span = inp.span_since(before.cursor());
+                inp.rewind(before);
+                inp.add_alt(
+                    Some(Some(MaybeRef::Ref(self.tok))),
+                    found.map(|f| f.into()),
+                    err_span,
+                );
+                Err(())
+            }
+        }
+    }
+
+    go_extra!(&'a I::Token);
+}
+
+/// See [`one_of_ref`].
+pub struct OneOfRef<T, I, E> {
+    seq: T,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(E, I)>,
+}
+
+impl<T: Copy, I, E> Copy for OneOfRef<T, I, E> {}
+impl<T: Clone, I, E> Clone for OneOfRef<T, I, E> {
+    fn clone(&self) -> Self {
+        Self {
+            seq: self.seq.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// A parser that accepts one of a sequence of specific inputs, producing a reference to the token in the input.
+///
+/// The output type of this parser is `&'a I::Token`, the input that was found.
+///
+/// This function is the borrowing equivalent of [`one_of`]. To avoid needing tokens to be [`Clone`], provide the
+/// sequence by reference (for example, as a `&[T]`).
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, error::Simple};
+/// #[derive(Debug, PartialEq)]
+/// enum Token { Add, Sub, Mul, Num(String) }
+///
+/// let additive = [Token::Add, Token::Sub];
+/// let op = one_of_ref::<_, _, extra::Err<Simple<Token>>>(&additive[..]);
+///
+/// assert_eq!(op.parse(&[Token::Sub]).into_result(), Ok(&Token::Sub));
+/// assert!(op.parse(&[Token::Mul]).has_errors());
+/// ```
+pub const fn one_of_ref<'a, T, I, E>(seq: T) -> OneOfRef<T, I, E>
+where
+    I: BorrowInput<'a>,
+    E: ParserExtra<'a, I>,
+    I::Token: PartialEq,
+    T: Seq<'a, I::Token>,
+{
+    OneOfRef {
+        seq,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'a, I, E, T> ParserSealed<'a, I, &'a I::Token, E> for OneOfRef<T, I, E>
+where
+    I: BorrowInput<'a>,
+    E: ParserExtra<'a, I>,
+    I::Token: PartialEq,
+    T: Seq<'a, I::Token>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, &'a I::Token> {
+        let before = inp.cursor();
+        match inp.next_ref_inner() {
+            Some(tok) if self.seq.contains(tok) => Ok(M::bind(|| tok)),
+            found => {
+                let err_span = inp.span_since(&before);
+                inp.add_alt(
+                    self.seq.seq_iter().map(|e| Some(T::to_maybe_ref(e))),
+                    found.map(|f| f.into()),
+                    err_span,
+                );
+                Err(())
+            }
+        }
+    }
+
+    go_extra!(&'a I::Token);
+}
+
+/// See [`none_of_ref`].
+pub struct NoneOfRef<T, I, E> {
+    seq: T,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(E, I)>,
+}
+
+impl<T: Copy, I, E> Copy for NoneOfRef<T, I, E> {}
+impl<T: Clone, I, E> Clone for NoneOfRef<T, I, E> {
+    fn clone(&self) -> Self {
+        Self {
+            seq: self.seq.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// A parser that accepts any input that is *not* in a sequence of specific inputs, producing a reference to the token
+/// in the input.
+///
+/// The output type of this parser is `&'a I::Token`, the input that was found.
+///
+/// This function is the borrowing equivalent of [`none_of`].
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, error::Simple};
+/// #[derive(Debug, PartialEq)]
+/// enum Token { Open, Close, Word(String) }
+///
+/// let close = [Token::Close];
+/// let body = none_of_ref::<_, _, extra::Err<Simple<Token>>>(&close[..])
+///     .repeated()
+///     .collect::<Vec<_>>()
+///     .delimited_by(just_ref(&Token::Open), just_ref(&Token::Close));
+///
+/// let tokens = [Token::Open, Token::Word("x".to_string()), Token::Close];
+/// assert_eq!(body.parse(&tokens).into_result(), Ok(vec![&Token::Word("x".to_string())]));
+/// ```
+pub const fn none_of_ref<'a, T, I, E>(seq: T) -> NoneOfRef<T, I, E>
+where
+    I: BorrowInput<'a>,
+    E: ParserExtra<'a, I>,
+    I::Token: PartialEq,
+    T: Seq<'a, I::Token>,
+{
+    NoneOfRef {
+        seq,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'a, I, E, T> ParserSealed<'a, I, &'a I::Token, E> for NoneOfRef<T, I, E>
+where
+    I: BorrowInput<'a>,
+    E: ParserExtra<'a, I>,
+    I::Token: PartialEq,
+    T: Seq<'a, I::Token>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, &'a I::Token> {
+        let before = inp.cursor();
+        match inp.next_ref_inner() {
+            Some(tok) if !self.seq.contains(tok) => Ok(M::bind(|| tok)),
+            found => {
+                let err_span = inp.span_since(&before);
+                inp.add_alt(None, found.map(|f| f.into()), err_span);
+                Err(())
+            }
+        }
+    }
+
+    go_extra!(&'a I::Token);
+}
+
+/// See [`group_of`].
+pub struct GroupOf<D, I, E> {
+    delimiter: D,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(I, E)>,
+}
+
+impl<D: Copy, I, E> Copy for GroupOf<D, I, E> {}
+impl<D: Clone, I, E> Clone for GroupOf<D, I, E> {
+    fn clone(&self) -> Self {
+        Self {
+            delimiter: self.delimiter.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<'a, T, S, E> ParserSealed<'a, TokenTreeInput<'a, T, S>, TokenTreeInput<'a, T, S>, E>
+    for GroupOf<T::Delimiter, TokenTreeInput<'a, T, S>, E>
+where
+    T: TokenTree<S> + 'a,
+    S: Span + Clone + 'a,
+    E: ParserExtra<'a, TokenTreeInput<'a, T, S>>,
+{
+    #[inline]
+    fn go<M: Mode>(
+        &self,
+        inp: &mut InputRef<'a, '_, TokenTreeInput<'a, T, S>, E>,
+    ) -> PResult<M, TokenTreeInput<'a, T, S>> {
+        let before = inp.cursor();
+        match inp.next_ref_inner() {
+            Some(tok) => match tok.as_group() {
+                Some(group) if *group.delimiter == self.delimiter => {
+                    Ok(M::bind(|| group.tokens.spanned(group.close)))
+                }
+                _ => {
+                    let err_span = inp.span_since(&before);
+                    inp.add_alt(None, Some(tok.into()), err_span);
+                    Err(())
+                }
+            },
+            None => {
+                let err_span = inp.span_since(&before);
+                inp.add_alt(None, None, err_span);
+                Err(())
+            }
+        }
+    }
+
+    // `go_extra!` can't be used here, since the input type isn't a type parameter
+    #[inline(always)]
+    fn go_emit(
+        &self,
+        inp: &mut InputRef<'a, '_, TokenTreeInput<'a, T, S>, E>,
+    ) -> PResult<Emit, TokenTreeInput<'a, T, S>> {
+        self.go::<Emit>(inp)
+    }
+    #[inline(always)]
+    fn go_check(
+        &self,
+        inp: &mut InputRef<'a, '_, TokenTreeInput<'a, T, S>, E>,
+    ) -> PResult<Check, TokenTreeInput<'a, T, S>> {
+        self.go::<Check>(inp)
+    }
+}
+
+/// A parser that accepts a group token with the given delimiter, producing an input over the group's contents.
+///
+/// This parser is intended to be used with [`Parser::nested_in`] to parse the contents of a group in a
+/// [`TokenTreeInput`]. Errors at the end of the group's contents are given the span of its closing delimiter.
+///
+/// The output type of this parser is [`TokenTreeInput`].
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, input::{TokenTree, TreeGroup}, primitive::group_of};
+/// #[derive(Copy, Clone, PartialEq, Debug)]
+/// enum Delim { Paren, Bracket }
+///
+/// #[derive(Clone, PartialEq, Debug)]
+/// enum Token {
+///     Num(u64),
+///     Group(Delim, Vec<(Token, SimpleSpan)>, SimpleSpan),
+/// }
+///
+/// impl TokenTree<SimpleSpan> for Token {
+///     type Delimiter = Delim;
+///
+///     fn as_group(&self) -> Option<TreeGroup<'_, Self, SimpleSpan>> {
+///         match self {
+///             Token::Group(delimiter, tokens, close) => Some(TreeGroup { delimiter, tokens, close: *close }),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// // (1 2 (3 4))
+/// let parens = [(
+///     Token::Group(Delim::Paren, vec![
+///         (Token::Num(1), (1..2).into()),
+///         (Token::Num(2), (3..4).into()),
+///         (Token::Group(Delim::Paren, vec![
+///             (Token::Num(3), (6..7).into()),
+///             (Token::Num(4), (8..9).into()),
+///         ], (9..10).into()), (5..10).into()),
+///     ], (10..11).into()),
+///     (0..11).into(),
+/// )];
+/// // [1]
+/// let brackets = [(
+///     Token::Group(Delim::Bracket, vec![(Token::Num(1), (1..2).into())], (2..3).into()),
+///     (0..3).into(),
+/// )];
+///
+/// // Sums of numbers within parentheses
+/// let sum = recursive(|sum| {
+///     select_ref! { Token::Num(x) => *x }
+///         .or(sum)
+///         .repeated()
+///         .collect::<Vec<u64>>()
+///         .map(|xs| xs.iter().sum())
+///         .nested_in(group_of::<_, _, extra::Err<Rich<_>>>(Delim::Paren))
+/// });
+///
+/// assert_eq!(sum.parse(parens.as_slice().spanned((11..11).into())).into_result(), Ok(10));
+/// assert!(sum.parse(brackets.as_slice().spanned((3..3).into())).has_errors());
+/// ```
+pub const fn group_of<'a, T, S, E>(
+    delimiter: T::Delimiter,
+) -> GroupOf<T::Delimiter, TokenTreeInput<'a, T, S>, E>
+where
+    T: TokenTree<S> + 'a,
+    S: Span + Clone + 'a,
+    E: ParserExtra<'a, TokenTreeInput<'a, T, S>>,
+{
+    GroupOf {
+        delimiter,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+/// A parser that accepts a single *token tree* without interpreting it: either a single token that is not a delimiter,
+/// or a balanced group of tokens that starts and ends with one of the given pairs of delimiters.
+///
+/// The output type of this parser is the slice of the input that the token tree covers.
+///
+/// This is useful for skipping quickly over the bodies of constructs that don't need to be parsed (or that will be
+/// parsed later), such as the arguments of a macro. Pairs may use the same token to open and close a group, such as a
+/// quote: other delimiters within such a group are ignored. Closing delimiters that don't match the innermost open
+/// group produce an error.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, primitive::token_tree};
+/// let tt = token_tree::<_, extra::Err<Simple<char>>, 3>([('(', ')'), ('[', ']'), ('{', '}')]);
+/// let macro_call = text::ascii::ident().then_ignore(just('!')).then(tt.clone());
+///
+/// assert_eq!(macro_call.parse("vec![(1, 2), {3}]").into_result(), Ok(("vec", "[(1, 2), {3}]")));
+/// assert_eq!(tt.clone().lazy().parse("x + 1").into_result(), Ok("x"));
+/// // Mismatched and unclosed delimiters are errors
+/// assert!(tt.parse("(1]").has_errors());
+/// assert!(tt.parse("[1, (2]").has_errors());
+/// assert!(tt.parse(")").has_errors());
+/// ```
+pub fn token_tree<'a, I, E, const N: usize>(
+    pairs: [(I::Token, I::Token); N],
+) -> impl Parser<'a, I, I::Slice, E> + Clone
+where
+    I: ValueInput<'a> + SliceInput<'a>,
+    I::Token: PartialEq + Clone,
+    E: ParserExtra<'a, I>,
+{
+    custom(move |inp| {
+        let before = inp.cursor();
+        // The indices of the pairs of the groups that are open, innermost last
+        let mut open = Vec::new();
+        loop {
+            let tok_before = inp.cursor();
+            let Some(tok) = inp.next_inner() else {
+                let span = inp.span_since(&tok_before);
+                let expected = open
+                    .last()
+                    .map(|&i: &usize| Some(MaybeRef::Val(pairs[i].1.clone())));
+                return Err(Error::expected_found(expected, None, span));
+            };
+
+            let innermost = open.last().map(|&i| &pairs[i]);
+            if matches!(innermost, Some((_, end)) if *end == tok) {
+                open.pop();
+            } else if matches!(innermost, Some((start, end)) if start == end) {
+                // Delimiters within quote-like groups have no meaning
+            } else if let Some(i) = pairs.iter().position(|(start, _)| *start == tok) {
+                open.push(i);
+            } else if pairs.iter().any(|(_, end)| *end == tok) {
+                let span = inp.span_since(&tok_before);
+                let expected = open
+                    .last()
+                    .map(|&i| Some(MaybeRef::Val(pairs[i].1.clone())));
+                return Err(Error::expected_found(
+                    expected,
+                    Some(MaybeRef::Val(tok)),
+                    span,
+                ));
+            }
+
+            if open.is_empty() {
+                return Ok(inp.slice_since(&before..));
+            }
+        }
+    })
+}
+
 /// See [`map_ctx`].
 pub struct MapCtx<A, AE, F, E> {
     pub(crate) parser: A,
@@ -887,14 +1334,18 @@ macro_rules! impl_choice_for_tuple {
 
                 let Choice { parsers: ($Head, $($X,)*), .. } = self;
 
+                inp.errors.cut = false;
                 match $Head.go::<M>(inp) {
                     Ok(out) => return Ok(out),
+                    Err(()) if inp.errors.cut => return Err(()),
                     Err(()) => inp.rewind(before.clone()),
                 }
 
                 $(
+                    inp.errors.cut = false;
                     match $X.go::<M>(inp) {
                         Ok(out) => return Ok(out),
+                        Err(()) if inp.errors.cut => return Err(()),
                         Err(()) => inp.rewind(before.clone()),
                     }
                 )*
@@ -941,8 +1392,11 @@ where
             let before = inp.save();
             for parser in self.parsers.iter() {
                 inp.rewind(before.clone());
-                if let Ok(out) = parser.go::<M>(inp) {
-                    return Ok(out);
+                inp.errors.cut = false;
+                match parser.go::<M>(inp) {
+                    Ok(out) => return Ok(out),
+                    Err(()) if inp.errors.cut => return Err(()),
+                    Err(()) => {}
                 }
             }
             Err(())
@@ -952,6 +1406,66 @@ where
     go_extra!(O);
 }
 
+impl<A> Choice<Vec<A>> {
+    /// Add a parser to the end of this choice, to be tried after all of the parsers that came before it.
+    ///
+    /// This allows grammars to be assembled at runtime, such as statements defined by plugins or operators chosen by
+    /// the user. Since the parsers must all be of the same type, they will usually be [`Boxed`](crate::Boxed).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// type Extra<'a> = extra::Err<Rich<'a, char>>;
+    ///
+    /// let mut stmt = choice(Vec::new());
+    /// stmt.push(just::<_, _, Extra>("print").ignore_then(text::int(10).padded()).boxed());
+    /// // Plugins add their own statements
+    /// for name in ["sleep", "beep"] {
+    ///     stmt.push(just(name).to("0").boxed());
+    /// }
+    /// assert_eq!(stmt.len(), 3);
+    ///
+    /// assert_eq!(stmt.parse("print 42").into_result(), Ok("42"));
+    /// assert_eq!(stmt.parse("beep").into_result(), Ok("0"));
+    ///
+    /// // Errors from every parser are merged
+    /// let errs = stmt.parse("jump").into_errors();
+    /// assert_eq!(errs[0].expected().count(), 3);
+    /// ```
+    pub fn push(&mut self, parser: A) {
+        self.parsers.push(parser);
+    }
+
+    /// The number of parsers in this choice.
+    pub fn len(&self) -> usize {
+        self.parsers.len()
+    }
+
+    /// Returns `true` if this choice has no parsers, in which case it always fails.
+    pub fn is_empty(&self) -> bool {
+        self.parsers.is_empty()
+    }
+}
+
+impl<A> Default for Choice<Vec<A>> {
+    fn default() -> Self {
+        choice(Vec::new())
+    }
+}
+
+impl<A> FromIterator<A> for Choice<Vec<A>> {
+    fn from_iter<T: IntoIterator<Item = A>>(iter: T) -> Self {
+        choice(iter.into_iter().collect())
+    }
+}
+
+impl<A> Extend<A> for Choice<Vec<A>> {
+    fn extend<T: IntoIterator<Item = A>>(&mut self, iter: T) {
+        self.parsers.extend(iter);
+    }
+}
+
 impl<'a, A, I, O, E> ParserSealed<'a, I, O, E> for Choice<Vec<A>>
 where
     A: Parser<'a, I, O, E>,
@@ -978,6 +1492,168 @@ where
     go_extra!(O);
 }
 
+/// See [`choice_longest`].
+#[derive(Copy, Clone)]
+pub struct ChoiceLongest<T> {
+    parsers: T,
+}
+
+/// Parse using a tuple of many parsers, producing the output of the one that consumes the most input.
+///
+/// Unlike [`choice`], which commits to the first parser that succeeds, every parser is tried. Ties are broken in favour
+/// of the parser that comes first. This frees grammars from needing to order alternatives carefully so that longer
+/// matches come before their prefixes, as with keywords and identifiers, or operators like `=` and `==`.
+///
+/// Each parser is tried in turn without generating outputs, and then the longest is run again to generate its output,
+/// so this is slower than [`choice`].
+///
+/// The output type of this parser is the output type of the inner parsers.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, primitive::choice_longest};
+/// let op = choice_longest((
+///     just::<_, _, extra::Err<Simple<char>>>("=").to("assign"),
+///     just("<").to("less"),
+///     just("==").to("equal"),
+///     just("<=").to("less or equal"),
+/// ));
+///
+/// assert_eq!(op.parse("=").into_result(), Ok("assign"));
+/// assert_eq!(op.parse("==").into_result(), Ok("equal"));
+/// assert_eq!(op.parse("<=").into_result(), Ok("less or equal"));
+/// ```
+pub const fn choice_longest<T>(parsers: T) -> ChoiceLongest<T> {
+    ChoiceLongest { parsers }
+}
+
+macro_rules! impl_choice_longest_for_tuple {
+    () => {};
+    ($head:ident $($X:ident)*) => {
+        impl_choice_longest_for_tuple!($($X)*);
+        impl_choice_longest_for_tuple!(~ $head $($X)*);
+    };
+    (~ $($X:ident)+) => {
+        #[allow(unused_variables, unused_assignments, non_snake_case)]
+        impl<'a, I, E, $($X),*, O> ParserSealed<'a, I, O, E> for ChoiceLongest<($($X,)*)>
+        where
+            I: Input<'a>,
+            E: ParserExtra<'a, I>,
+            $($X: Parser<'a, I, O, E>),*
+        {
+            #[inline]
+            fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+                let before = inp.save();
+
+                let ChoiceLongest { parsers: ($($X,)*), .. } = self;
+
+                // The index of the longest parser so far, and where it ended
+                let mut longest: Option<(usize, usize)> = None;
+                let mut i = 0;
+                $(
+                    inp.rewind(before.clone());
+                    inp.errors.cut = false;
+                    match $X.go::<Check>(inp) {
+                        Ok(()) => {
+                            let end = I::cursor_location(&inp.cursor().inner);
+                            match longest {
+                                Some((_, longest_end)) if longest_end >= end => {}
+                                _ => longest = Some((i, end)),
+                            }
+                        }
+                        Err(()) if inp.errors.cut => return Err(()),
+                        Err(()) => {}
+                    }
+                    i += 1;
+                )*
+
+                let (longest, _) = longest.ok_or(())?;
+                inp.rewind(before);
+                let mut i = 0;
+                $(
+                    if i == longest {
+                        return $X.go::<M>(inp);
+                    }
+                    i += 1;
+                )*
+                unreachable!()
+            }
+
+            go_extra!(O);
+        }
+    };
+}
+
+impl_choice_longest_for_tuple!(A_ B_ C_ D_ E_ F_ G_ H_ I_ J_ K_ L_ M_ N_ O_ P_ Q_ R_ S_ T_ U_ V_ W_ X_ Y_ Z_);
+
+impl<'a, A, I, O, E> ParserSealed<'a, I, O, E> for ChoiceLongest<&[A]>
+where
+    A: Parser<'a, I, O, E>,
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        if self.parsers.is_empty() {
+            let offs = inp.cursor();
+            let err_span = inp.span_since(&offs);
+            inp.add_alt(None, None, err_span);
+            return Err(());
+        }
+
+        let before = inp.save();
+        let mut longest: Option<(&A, usize)> = None;
+        for parser in self.parsers.iter() {
+            inp.rewind(before.clone());
+            inp.errors.cut = false;
+            match parser.go::<Check>(inp) {
+                Ok(()) => {
+                    let end = I::cursor_location(&inp.cursor().inner);
+                    match longest {
+                        Some((_, longest_end)) if longest_end >= end => {}
+                        _ => longest = Some((parser, end)),
+                    }
+                }
+                Err(()) if inp.errors.cut => return Err(()),
+                Err(()) => {}
+            }
+        }
+
+        let (longest, _) = longest.ok_or(())?;
+        inp.rewind(before);
+        longest.go::<M>(inp)
+    }
+
+    go_extra!(O);
+}
+
+impl<'a, A, I, O, E> ParserSealed<'a, I, O, E> for ChoiceLongest<Vec<A>>
+where
+    A: Parser<'a, I, O, E>,
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        choice_longest(&self.parsers[..]).go::<M>(inp)
+    }
+    go_extra!(O);
+}
+
+impl<'a, A, I, O, E, const N: usize> ParserSealed<'a, I, O, E> for ChoiceLongest<[A; N]>
+where
+    A: Parser<'a, I, O, E>,
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        choice_longest(&self.parsers[..]).go::<M>(inp)
+    }
+    go_extra!(O);
+}
+
 /// See [`group`].
 #[derive(Copy, Clone)]
 pub struct Group<T> {
@@ -1103,3 +1779,332 @@ impl_group_for_tuple! {
     Y_ OY
     Z_ OZ
 }
+
+/// See [`permutation`].
+#[derive(Copy, Clone)]
+pub struct Permutation<T> {
+    parsers: T,
+}
+
+/// Parse using a tuple of many parsers in any order, each exactly once, producing a tuple of their outputs in the
+/// order that the parsers were given.
+///
+/// This is useful for syntax in which the order of elements does not matter, such as attribute lists, struct
+/// initialisers, or command-line options.
+///
+/// If the input runs out, or an element doesn't match any of the parsers that are left, the error will be produced
+/// by those parsers that are left, so it will describe what is missing.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let attr = |name| {
+///     text::ascii::keyword::<_, _, _, extra::Err<Simple<char>>>(name)
+///         .ignore_then(just('=').padded())
+///         .ignore_then(text::int(10))
+///         .padded()
+/// };
+/// let size = permutation((attr("width"), attr("height")));
+///
+/// assert_eq!(size.parse("width = 4 height = 3").into_result(), Ok(("4", "3")));
+/// assert_eq!(size.parse("height = 3 width = 4").into_result(), Ok(("4", "3")));
+/// // Each attribute must appear exactly once
+/// assert!(size.parse("width = 4").has_errors());
+/// assert!(size.parse("width = 4 width = 5").has_errors());
+/// ```
+pub const fn permutation<T>(parsers: T) -> Permutation<T> {
+    Permutation { parsers }
+}
+
+macro_rules! impl_permutation_for_tuple {
+    () => {};
+    ($head:ident $ohead:ident $($X:ident $O:ident)*) => {
+        impl_permutation_for_tuple!($($X $O)*);
+        impl_permutation_for_tuple!(~ $head $ohead $($X $O)*);
+    };
+    (~ $($X:ident $O:ident)*) => {
+        #[allow(unused_variables, non_snake_case)]
+        impl<'a, I, E, $($X),*, $($O),*> ParserSealed<'a, I, ($($O,)*), E> for Permutation<($($X,)*)>
+        where
+            I: Input<'a>,
+            E: ParserExtra<'a, I>,
+            $($X: Parser<'a, I, $O, E>),*
+        {
+            #[inline]
+            fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, ($($O,)*)> {
+                let Permutation { parsers: ($($X,)*) } = self;
+
+                $(
+                    let mut $O = None::<M::Output<$O>>;
+                )*
+
+                // Each time around, one more of the parsers that are left must match
+                while $($O.is_none())||* {
+                    let before = inp.save();
+                    $(
+                        if $O.is_none() {
+                            inp.errors.cut = false;
+                            match $X.go::<M>(inp) {
+                                Ok(out) => {
+                                    $O = Some(out);
+                                    continue;
+                                }
+                                Err(()) if inp.errors.cut => return Err(()),
+                                Err(()) => inp.rewind(before.clone()),
+                            }
+                        }
+                    )*
+                    return Err(());
+                }
+
+                $(
+                    let $O = $O.unwrap();
+                )*
+
+                Ok(flatten_map!(<M> $($O)*))
+            }
+
+            go_extra!(($($O,)*));
+        }
+    };
+}
+
+impl_permutation_for_tuple! {
+    A_ OA
+    B_ OB
+    C_ OC
+    D_ OD
+    E_ OE
+    F_ OF
+    G_ OG
+    H_ OH
+    I_ OI
+    J_ OJ
+    K_ OK
+    L_ OL
+    M_ OM
+    N_ ON
+    O_ OO
+    P_ OP
+    Q_ OQ
+    R_ OR
+    S_ OS
+    T_ OT
+    U_ OU
+    V_ OV
+    W_ OW
+    X_ OX
+    Y_ OY
+    Z_ OZ
+}
+
+/// See [`exactly_one_of`].
+pub struct ExactlyOneOf<T, F, O> {
+    choice: Choice<T>,
+    conflict: F,
+    #[cfg(debug_assertions)]
+    location: Location<'static>,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<O>,
+}
+
+impl<T: Copy, F: Copy, O> Copy for ExactlyOneOf<T, F, O> {}
+impl<T: Clone, F: Clone, O> Clone for ExactlyOneOf<T, F, O> {
+    fn clone(&self) -> Self {
+        Self {
+            choice: self.choice.clone(),
+            conflict: self.conflict.clone(),
+            #[cfg(debug_assertions)]
+            location: self.location,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// Parse any number of mutually exclusive alternatives, requiring that exactly one of them appears.
+///
+/// This is useful for modifiers and attributes, such as visibility or mutability markers, where only one of a set of
+/// constructs may be given, but where accidentally giving more than one (like `pub priv`, or `mut mut`) is a common
+/// mistake that deserves a better error than "unexpected input".
+///
+/// The alternatives are given in the same way as for [`choice`] (a tuple, array, or `Vec` of parsers that produce the
+/// same output) and are parsed repeatedly until none of them match. If more than one match, the `conflict` function is
+/// given the output and span of the first match followed by those of the later match, and produces an error naming
+/// both (perhaps using [`Rich::with_secondary`] to point at the first). This error is emitted without
+/// causing parsing to fail, and the output of the first match is kept. If none of the alternatives match, parsing
+/// fails as [`choice`] would.
+///
+/// See [`at_most_one_of`] for when the alternatives are optional.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// #[derive(Copy, Clone, Debug, PartialEq)]
+/// enum Vis { Pub, Priv }
+///
+/// let vis = exactly_one_of(
+///     (
+///         text::ascii::keyword::<_, _, _, extra::Err<Rich<char>>>("pub").to(Vis::Pub).padded(),
+///         text::ascii::keyword("priv").to(Vis::Priv).padded(),
+///     ),
+///     |(a, a_span): (&Vis, SimpleSpan), (b, b_span): (&Vis, SimpleSpan)| {
+///         Rich::custom(b_span, format!("{b:?} conflicts with {a:?}"))
+///             .with_secondary(a_span, format!("{a:?} given here"))
+///     },
+/// );
+/// let item = vis.then_ignore(text::ascii::keyword("fn"));
+///
+/// assert_eq!(item.parse("pub fn").into_result(), Ok(Vis::Pub));
+/// assert!(item.parse("fn").has_errors());
+///
+/// let res = item.parse("priv pub fn");
+/// assert_eq!(res.output(), Some(&Vis::Priv));
+/// assert_eq!(res.errors().map(|e| e.to_string()).collect::<Vec<_>>(), vec![
+///     "Pub conflicts with Priv; Priv given here at 0..5".to_string(),
+/// ]);
+/// ```
+#[track_caller]
+pub const fn exactly_one_of<'a, T, F, I, O, E>(parsers: T, conflict: F) -> ExactlyOneOf<T, F, O>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    Choice<T>: Parser<'a, I, O, E>,
+    F: Fn((&O, I::Span), (&O, I::Span)) -> E::Error,
+{
+    ExactlyOneOf {
+        choice: choice(parsers),
+        conflict,
+        #[cfg(debug_assertions)]
+        location: *Location::caller(),
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<T, F, O> ExactlyOneOf<T, F, O> {
+    // Parse alternatives until none match, reporting any after the first as conflicts
+    fn go_one_of<'a, I, E>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<Emit, Option<O>>
+    where
+        I: Input<'a>,
+        E: ParserExtra<'a, I>,
+        Choice<T>: Parser<'a, I, O, E>,
+        F: Fn((&O, I::Span), (&O, I::Span)) -> E::Error,
+    {
+        let mut first = None;
+        loop {
+            let before = inp.save();
+            match self.choice.go::<Emit>(inp) {
+                // An alternative that matched nothing can't conflict with anything, and trying again would match
+                // nothing forever
+                Ok(out) if *before.cursor() == inp.cursor() => {
+                    break Ok(Some(first.map_or(out, |(out, _, _)| out)));
+                }
+                Ok(out) => match &first {
+                    None => first = Some((out, before.cursor().clone(), inp.cursor())),
+                    Some((first_out, first_start, first_end)) => {
+                        let first_span = inp.span_between(first_start..first_end);
+                        let span = inp.span_since(before.cursor());
+                        let err = (self.conflict)((first_out, first_span), (&out, span));
+                        inp.emit(err);
+                    }
+                },
+                Err(()) if inp.errors.cut => break Err(()),
+                Err(()) => {
+                    inp.rewind(before);
+                    break Ok(first.map(|(out, _, _)| out));
+                }
+            }
+            #[cfg(debug_assertions)]
+            debug_assert!(
+                *before.cursor() != inp.cursor(),
+                "found ExactlyOneOf combinator making no progress at {}",
+                self.location,
+            );
+        }
+    }
+}
+
+impl<'a, T, F, I, O, E> ParserSealed<'a, I, O, E> for ExactlyOneOf<T, F, O>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    Choice<T>: Parser<'a, I, O, E>,
+    F: Fn((&O, I::Span), (&O, I::Span)) -> E::Error,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        // The output of every match is needed to report conflicts, so alternatives are always parsed in emit mode
+        match self.go_one_of(inp)? {
+            Some(out) => Ok(M::bind(|| out)),
+            None => Err(()),
+        }
+    }
+
+    go_extra!(O);
+}
+
+/// See [`at_most_one_of`].
+pub struct AtMostOneOf<T, F, O>(ExactlyOneOf<T, F, O>);
+
+impl<T: Copy, F: Copy, O> Copy for AtMostOneOf<T, F, O> {}
+impl<T: Clone, F: Clone, O> Clone for AtMostOneOf<T, F, O> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+/// Parse any number of mutually exclusive alternatives, requiring that at most one of them appears.
+///
+/// This behaves like [`exactly_one_of`], except that it produces `None` (without consuming any input) if none of the
+/// alternatives match, rather than failing.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let mutability = at_most_one_of(
+///     (
+///         text::ascii::keyword::<_, _, _, extra::Err<Rich<char>>>("mut").padded(),
+///         text::ascii::keyword("const").padded(),
+///     ),
+///     |(a, a_span): (&&str, SimpleSpan), (b, b_span): (&&str, SimpleSpan)| {
+///         Rich::custom(b_span, format!("`{b}` cannot be used with `{a}`"))
+///             .with_secondary(a_span, format!("`{a}` given here"))
+///     },
+/// );
+/// let binding = mutability.then(text::ascii::ident());
+///
+/// assert_eq!(binding.parse("x").into_result(), Ok((None, "x")));
+/// assert_eq!(binding.parse("mut x").into_result(), Ok((Some("mut"), "x")));
+///
+/// let errs = binding.parse("mut mut x").into_errors();
+/// assert_eq!(errs[0].to_string(), "`mut` cannot be used with `mut`; `mut` given here at 0..4");
+/// assert_eq!(errs[0].span(), &SimpleSpan::from(4..8));
+/// ```
+#[track_caller]
+pub const fn at_most_one_of<'a, T, F, I, O, E>(parsers: T, conflict: F) -> AtMostOneOf<T, F, O>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    Choice<T>: Parser<'a, I, O, E>,
+    F: Fn((&O, I::Span), (&O, I::Span)) -> E::Error,
+{
+    AtMostOneOf(exactly_one_of(parsers, conflict))
+}
+
+impl<'a, T, F, I, O, E> ParserSealed<'a, I, Option<O>, E> for AtMostOneOf<T, F, O>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    Choice<T>: Parser<'a, I, O, E>,
+    F: Fn((&O, I::Span), (&O, I::Span)) -> E::Error,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, Option<O>> {
+        let out = self.0.go_one_of(inp)?;
+        Ok(M::bind(|| out))
+    }
+
+    go_extra!(Option<O>);
+}
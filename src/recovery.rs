@@ -5,8 +5,8 @@ use super::*;
 /// A trait implemented by error recovery strategies. See [`Parser::recover_with`].
 ///
 /// This trait is sealed and so cannot be implemented by other crates because it has an unstable API. This may
-/// eventually change. For now, if you wish to implement a new strategy, consider using [`via_parser`] or
-/// [opening an issue/PR](https://github.com/zesterer/chumsky/issues/new).
+/// eventually change. For now, if you wish to implement a new strategy, consider using [`via_parser`] or [`via_custom`],
+/// or [opening an issue/PR](https://github.com/zesterer/chumsky/issues/new).
 pub trait Strategy<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>: Sealed {
     // Attempt to recover from a parsing failure.
     // The strategy should properly handle the alt error but is not required to handle rewinding.
@@ -16,6 +16,63 @@ pub trait Strategy<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         inp: &mut InputRef<'a, '_, I, E>,
         parser: &P,
     ) -> PResult<M, O>;
+
+    // The name of the strategy, as reported by the `RecoveryEvent`s that it produces.
+    #[doc(hidden)]
+    fn name(&self) -> &'static str;
+
+    // Attempt to recover, as with `recover`, also producing the name of the strategy that recovered.
+    #[doc(hidden)]
+    fn recover_named<M: Mode, P: Parser<'a, I, O, E>>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        parser: &P,
+    ) -> Result<(M::Output<O>, &'static str), ()> {
+        self.recover::<M, P>(inp, parser)
+            .map(|out| (out, self.name()))
+    }
+}
+
+/// A record of a parser being recovered by a [`Strategy`]. See [`ParseResult::recoveries`].
+///
+/// These are useful for auditing and tuning the recovery of a parser: for example, a strategy that skips far more
+/// input than expected is a common cause of cascading errors.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecoveryEvent {
+    /// The name of the strategy that recovered, such as `"skip_until"`.
+    ///
+    /// When a tuple of strategies is used, this is the name of the strategy within it that succeeded.
+    pub strategy: &'static str,
+    /// The input consumed by the strategy, as a range of [`Input::cursor_location`]s (byte offsets, for `&str`).
+    ///
+    /// The range starts where the parser that failed started.
+    pub skipped: Range<usize>,
+    /// The number of tokens consumed by the strategy.
+    pub tokens: usize,
+}
+
+// Events are ordered by where they occurred in the input
+impl PartialOrd for RecoveryEvent {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RecoveryEvent {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (
+            self.skipped.start,
+            self.skipped.end,
+            self.strategy,
+            self.tokens,
+        )
+            .cmp(&(
+                other.skipped.start,
+                other.skipped.end,
+                other.strategy,
+                other.tokens,
+            ))
+    }
 }
 
 /// See [`via_parser`].
@@ -50,8 +107,174 @@ where
         inp.emit(alt.err);
         Ok(out)
     }
+
+    fn name(&self) -> &'static str {
+        "via_parser"
+    }
+}
+
+/// See [`via_custom`].
+pub struct ViaCustom<F, I, O, E> {
+    f: F,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(E, O, I)>,
+}
+
+impl<F: Copy, I, O, E> Copy for ViaCustom<F, I, O, E> {}
+impl<F: Clone, I, O, E> Clone for ViaCustom<F, I, O, E> {
+    fn clone(&self) -> Self {
+        Self {
+            f: self.f.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// Recover via a function that has direct access to the input, much like [`custom`].
+///
+/// The function is given the input, positioned where the failed parser started, along with the error that it failed
+/// with. Through the input, it can see the parser state (with [`InputRef::state`]) and context (with
+/// [`InputRef::ctx`]), which allows for recovery that depends on what's been parsed so far, such as skipping to the
+/// next line at the current indentation level. The function should consume input as it sees fit and return a fallback
+/// output, or `None` if recovery isn't possible here.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, extra::SimpleState, input::InputRef};
+/// // The indentation of the block being parsed is kept in the state
+/// type Extra<'a> = extra::Full<Rich<'a, char>, SimpleState<usize>, ()>;
+///
+/// let stmt = just(' ')
+///     .repeated()
+///     .ignore_then(text::ascii::ident())
+///     .then_ignore(just(";\n"))
+///     .map(Some)
+///     .recover_with(via_custom(|inp: &mut InputRef<&str, Extra>, _| {
+///         // Skip the rest of the line, along with any lines indented further than the block
+///         let indent = **inp.state();
+///         // There's nothing to skip at the end of the input
+///         inp.peek()?;
+///         while let Some(c) = inp.next() {
+///             if c == '\n' {
+///                 let line = inp.save();
+///                 let mut spaces = 0;
+///                 while inp.next() == Some(' ') {
+///                     spaces += 1;
+///                 }
+///                 inp.rewind(line);
+///                 if spaces <= indent {
+///                     break;
+///                 }
+///             }
+///         }
+///         Some(None)
+///     }));
+///
+/// let block = just::<_, _, Extra>(' ')
+///     .repeated()
+///     .count()
+///     .map_with(|indent, e| **e.state() = indent)
+///     .rewind()
+///     .then(stmt.repeated().collect::<Vec<_>>())
+///     .map(|((), stmts)| stmts);
+///
+/// let src = "  a;\n  b +\n      c;\n  d;\n";
+/// let (stmts, errs) = block.parse_with_state(src, &mut SimpleState(0)).into_output_errors();
+/// assert_eq!(stmts, Some(vec![Some("a"), None, Some("d")]));
+/// assert_eq!(errs.len(), 1);
+/// ```
+pub const fn via_custom<'a, F, I, O, E>(f: F) -> ViaCustom<F, I, O, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    F: Fn(&mut InputRef<'a, '_, I, E>, &E::Error) -> Option<O>,
+{
+    ViaCustom {
+        f,
+        phantom: EmptyPhantom::new(),
+    }
 }
 
+impl<F, I, O, E> Sealed for ViaCustom<F, I, O, E> {}
+impl<'a, I, O, E, F> Strategy<'a, I, O, E> for ViaCustom<F, I, O, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    F: Fn(&mut InputRef<'a, '_, I, E>, &E::Error) -> Option<O>,
+{
+    fn recover<M: Mode, P: Parser<'a, I, O, E>>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        _parser: &P,
+    ) -> PResult<M, O> {
+        let alt = inp.take_alt();
+        match (self.f)(inp, &alt.err) {
+            Some(out) => {
+                inp.emit(alt.err);
+                Ok(M::bind(|| out))
+            }
+            None => {
+                inp.errors.alt = Some(alt);
+                Err(())
+            }
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "via_custom"
+    }
+}
+
+macro_rules! impl_strategy_for_tuple {
+    () => {};
+    ($head:ident $($X:ident)*) => {
+        impl_strategy_for_tuple!($($X)*);
+        impl_strategy_for_tuple!(~ $head $($X)*);
+    };
+    (~ $($X:ident)+) => {
+        impl<$($X),+> Sealed for ($($X,)+) {}
+        #[allow(non_snake_case)]
+        impl<'a, I, O, E, $($X),+> Strategy<'a, I, O, E> for ($($X,)+)
+        where
+            I: Input<'a>,
+            E: ParserExtra<'a, I>,
+            $($X: Strategy<'a, I, O, E>),+
+        {
+            fn recover<M: Mode, P: Parser<'a, I, O, E>>(
+                &self,
+                inp: &mut InputRef<'a, '_, I, E>,
+                parser: &P,
+            ) -> PResult<M, O> {
+                self.recover_named::<M, P>(inp, parser).map(|(out, _)| out)
+            }
+
+            fn name(&self) -> &'static str {
+                "tuple"
+            }
+
+            fn recover_named<M: Mode, P: Parser<'a, I, O, E>>(
+                &self,
+                inp: &mut InputRef<'a, '_, I, E>,
+                parser: &P,
+            ) -> Result<(M::Output<O>, &'static str), ()> {
+                let ($($X,)+) = self;
+                let before = inp.save();
+                $(
+                    match $X.recover_named::<M, P>(inp, parser) {
+                        Ok(out) => return Ok(out),
+                        // Each strategy starts from where the parser failed
+                        Err(()) => inp.rewind(before.clone()),
+                    }
+                )+
+                Err(())
+            }
+        }
+    };
+}
+
+impl_strategy_for_tuple!(A_ B_ C_ D_ E_ F_ G_ H_);
+
 /// See [`Parser::recover_with`].
 #[derive(Copy, Clone)]
 pub struct RecoverWith<A, S> {
@@ -71,12 +294,19 @@ where
         match self.parser.go::<M>(inp) {
             Ok(out) => Ok(out),
             Err(()) => {
+                // Recovery is permitted even after a cut, but the cut still applies if recovery fails
+                let cut = inp.errors.cut;
                 inp.rewind(before.clone());
-                match self.strategy.recover::<M, _>(inp, &self.parser) {
-                    Ok(out) => Ok(out),
+                match self.strategy.recover_named::<M, _>(inp, &self.parser) {
+                    Ok((out, strategy)) => {
+                        inp.errors.cut = false;
+                        inp.record_recovery(strategy, &before.cursor().inner);
+                        Ok(out)
+                    }
                     Err(()) => {
                         // Reset to before fallback attempt
                         inp.rewind(before);
+                        inp.errors.cut = cut;
                         Err(())
                     }
                 }
@@ -87,6 +317,31 @@ where
     go_extra!(O);
 }
 
+/// See [`Parser::recover_via_state_reset`].
+#[derive(Copy, Clone)]
+pub struct ResetState<A> {
+    pub(crate) parser: A,
+}
+
+impl<'a, I, O, E, A> ParserSealed<'a, I, O, E> for ResetState<A>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    E::State: Clone,
+    A: Parser<'a, I, O, E>,
+{
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let state = inp.state().clone();
+        let res = self.parser.go::<M>(inp);
+        if res.is_err() {
+            *inp.state() = state;
+        }
+        res
+    }
+
+    go_extra!(O);
+}
+
 /// See [`skip_then_retry_until`].
 #[must_use]
 #[derive(Copy, Clone)]
@@ -138,6 +393,10 @@ where
             }
         }
     }
+
+    fn name(&self) -> &'static str {
+        "skip_then_retry_until"
+    }
 }
 
 /// TODO
@@ -183,6 +442,10 @@ where
             }
         }
     }
+
+    fn name(&self) -> &'static str {
+        "skip_until"
+    }
 }
 
 /// A recovery parser that skips input until one of several inputs is found.
@@ -197,6 +460,576 @@ pub fn skip_until<S, U, F>(skip: S, until: U, fallback: F) -> SkipUntil<S, U, F>
     }
 }
 
+/// See [`skip_unterminated`].
+#[must_use]
+#[derive(Copy, Clone)]
+pub struct SkipUnterminated<S, U, F, G> {
+    start: S,
+    until: U,
+    error: F,
+    fallback: G,
+}
+
+impl<S, U, F, G> Sealed for SkipUnterminated<S, U, F, G> {}
+impl<'a, I, O, E, S, U, F, G> Strategy<'a, I, O, E> for SkipUnterminated<S, U, F, G>
+where
+    I: Input<'a>,
+    S: Parser<'a, I, (), E>,
+    U: Parser<'a, I, (), E>,
+    F: Fn(I::Span) -> E::Error,
+    G: Fn(I::Span) -> O,
+    E: ParserExtra<'a, I>,
+{
+    fn recover<M: Mode, P: Parser<'a, I, O, E>>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        _parser: &P,
+    ) -> PResult<M, O> {
+        let alt = inp.take_alt();
+        let start = inp.cursor();
+        if let Err(()) = self.start.go::<Check>(inp) {
+            inp.errors.alt = Some(alt);
+            return Err(());
+        }
+
+        loop {
+            let before = inp.save();
+            let found_end = self.until.go::<Check>(inp).is_ok();
+            inp.rewind(before);
+            if found_end || inp.next_maybe_inner().is_none() {
+                break;
+            }
+        }
+
+        // The terminator (or end of input) was found, so the original error is superseded by a more specific one
+        inp.errors.alt.take();
+        let span = inp.span_since(&start);
+        inp.emit((self.error)(span));
+        let span = inp.span_since(&start);
+        Ok(M::bind(|| (self.fallback)(span)))
+    }
+
+    fn name(&self) -> &'static str {
+        "skip_unterminated"
+    }
+}
+
+/// A recovery strategy for unterminated delimited constructs such as string literals.
+///
+/// If the input at the point of failure begins with `start` (such as an opening quote), input is skipped until
+/// `until` would match (such as a newline) or the end of input is reached. The terminator itself is not consumed.
+/// Rather than letting the missing closing delimiter swallow the rest of the input and cause an avalanche of
+/// unrelated errors, the error produced by `error` is emitted for the skipped span and parsing continues with the
+/// output produced by `fallback`.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// #[derive(Debug, PartialEq)]
+/// enum Expr<'a> {
+///     Str(&'a str),
+///     Error,
+/// }
+///
+/// let string = none_of::<_, _, extra::Err<Rich<char>>>("\"\n")
+///     .repeated()
+///     .to_slice()
+///     .delimited_by(just('"'), just('"'))
+///     .map(Expr::Str)
+///     .recover_with(skip_unterminated(
+///         just('"').ignored(),
+///         text::newline(),
+///         |span| Rich::custom(span, "unterminated string literal"),
+///         |_| Expr::Error,
+///     ));
+///
+/// let strings = string
+///     .separated_by(text::whitespace())
+///     .collect::<Vec<_>>();
+///
+/// let (out, errs) = strings.parse("\"a\" \"bc\n\"d\"").into_output_errors();
+/// assert_eq!(out, Some(vec![Expr::Str("a"), Expr::Error, Expr::Str("d")]));
+/// assert_eq!(errs.len(), 1);
+/// assert_eq!(errs[0].to_string(), "unterminated string literal");
+/// assert_eq!(errs[0].span(), &SimpleSpan::from(4..7));
+/// ```
+pub fn skip_unterminated<S, U, F, G>(
+    start: S,
+    until: U,
+    error: F,
+    fallback: G,
+) -> SkipUnterminated<S, U, F, G> {
+    SkipUnterminated {
+        start,
+        until,
+        error,
+        fallback,
+    }
+}
+
+/// See [`insert_missing`].
+#[must_use]
+#[derive(Copy, Clone)]
+pub struct InsertMissing<O> {
+    output: O,
+}
+
+impl<O> Sealed for InsertMissing<O> {}
+impl<'a, I, O, E> Strategy<'a, I, O, E> for InsertMissing<O>
+where
+    I: Input<'a>,
+    O: Clone,
+    E: ParserExtra<'a, I>,
+{
+    fn recover<M: Mode, P: Parser<'a, I, O, E>>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        _parser: &P,
+    ) -> PResult<M, O> {
+        let alt = inp.take_alt();
+        inp.emit(alt.err);
+        Ok(M::bind(|| self.output.clone()))
+    }
+
+    fn name(&self) -> &'static str {
+        "insert_missing"
+    }
+}
+
+/// A recovery strategy that pretends the input that the parser expected was present, without consuming anything.
+///
+/// This is useful for small, unambiguous pieces of syntax that are easy to forget, such as a `;` at the end of a
+/// statement or the `)` that closes a call: the error is emitted and parsing continues as if the missing input had
+/// been written, producing the given output. Where [`skip_until`] and friends repair input by deleting it, this
+/// repairs it by insertion.
+///
+/// Because it consumes no input, this strategy should only be used on parsers that are always preceded by something
+/// that does, or else a repeated parser might never make progress.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let stmt = text::ascii::keyword::<_, _, _, extra::Err<Rich<char>>>("let")
+///     .ignore_then(text::ascii::ident().padded())
+///     .then_ignore(just('=').padded())
+///     .then(text::int(10).padded())
+///     .then_ignore(just(';').recover_with(insert_missing(';')).padded());
+/// let stmts = stmt.repeated().collect::<Vec<_>>();
+///
+/// let (out, errs) = stmts.parse("let a = 1; let b = 2 let c = 3;").into_output_errors();
+/// assert_eq!(out, Some(vec![("a", "1"), ("b", "2"), ("c", "3")]));
+/// assert_eq!(errs.len(), 1);
+/// assert_eq!(errs[0].span(), &SimpleSpan::from(21..22));
+/// ```
+pub fn insert_missing<O>(output: O) -> InsertMissing<O> {
+    InsertMissing { output }
+}
+
+/// A trait for outputs, such as AST nodes, that can be error nodes produced during recovery.
+///
+/// Implementing this trait lets generic tooling (such as a pass that reports or skips over the broken parts of a
+/// syntax tree) detect error nodes without knowing about the specific AST. Outputs recovered with
+/// [`recover_as_error_node`] must implement it.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, recovery::HasErrorNode};
+/// enum Expr {
+///     Num(u64),
+///     Error(SimpleSpan),
+/// }
+///
+/// impl HasErrorNode for Expr {
+///     fn is_error_node(&self) -> bool {
+///         matches!(self, Expr::Error(_))
+///     }
+/// }
+///
+/// assert!(Expr::Error(SimpleSpan::from(0..1)).is_error_node());
+/// assert!(!Box::new(Expr::Num(42)).is_error_node());
+/// ```
+pub trait HasErrorNode {
+    /// Returns `true` if this is an error node.
+    fn is_error_node(&self) -> bool;
+}
+
+impl<T: HasErrorNode + ?Sized> HasErrorNode for Box<T> {
+    fn is_error_node(&self) -> bool {
+        (**self).is_error_node()
+    }
+}
+
+impl<T: HasErrorNode + ?Sized> HasErrorNode for &T {
+    fn is_error_node(&self) -> bool {
+        (**self).is_error_node()
+    }
+}
+
+/// See [`recover_as_error_node`].
+#[must_use]
+#[derive(Copy, Clone)]
+pub struct RecoverAsErrorNode<S, F> {
+    skip: S,
+    node: F,
+}
+
+impl<S, F> Sealed for RecoverAsErrorNode<S, F> {}
+impl<'a, I, O, E, S, F> Strategy<'a, I, O, E> for RecoverAsErrorNode<S, F>
+where
+    I: Input<'a>,
+    O: HasErrorNode,
+    S: Parser<'a, I, (), E>,
+    F: Fn(I::Span) -> O,
+    E: ParserExtra<'a, I>,
+{
+    fn recover<M: Mode, P: Parser<'a, I, O, E>>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        _parser: &P,
+    ) -> PResult<M, O> {
+        let alt = inp.take_alt();
+        let start = inp.cursor();
+        if let Err(()) = self.skip.go::<Check>(inp) {
+            inp.errors.alt = Some(alt);
+            return Err(());
+        }
+        inp.emit(alt.err);
+        let span = inp.span_since(&start);
+        Ok(M::bind(|| {
+            let node = (self.node)(span);
+            debug_assert!(
+                node.is_error_node(),
+                "recover_as_error_node produced an output that is not an error node",
+            );
+            node
+        }))
+    }
+
+    fn name(&self) -> &'static str {
+        "recover_as_error_node"
+    }
+}
+
+/// A recovery strategy that skips input with the given parser and produces an error node in place of the output.
+///
+/// This standardises a very common pattern: when a piece of syntax (such as an expression) is broken, parse over the
+/// broken input and put a sentinel node, like `Expr::Error(span)`, in the syntax tree. The span given to `node` covers
+/// exactly the input consumed by `skip` (which may be empty, for syntax that is missing entirely), and the output
+/// must implement [`HasErrorNode`] so that error nodes can be found again later.
+///
+/// If `skip` fails, recovery fails.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, recovery::HasErrorNode};
+/// #[derive(Debug, PartialEq)]
+/// enum Expr {
+///     Num(u64),
+///     Error(SimpleSpan),
+/// }
+///
+/// impl HasErrorNode for Expr {
+///     fn is_error_node(&self) -> bool {
+///         matches!(self, Expr::Error(_))
+///     }
+/// }
+///
+/// let expr = text::int::<_, _, extra::Err<Rich<char>>>(10)
+///     .from_str()
+///     .unwrapped()
+///     .map(Expr::Num)
+///     .recover_with(recover_as_error_node(
+///         none_of(",]").repeated(),
+///         Expr::Error,
+///     ))
+///     .padded();
+/// let list = expr
+///     .separated_by(just(','))
+///     .collect::<Vec<_>>()
+///     .delimited_by(just('['), just(']'));
+///
+/// let (out, errs) = list.parse("[1, x + y, 3]").into_output_errors();
+/// assert_eq!(
+///     out,
+///     Some(vec![Expr::Num(1), Expr::Error(SimpleSpan::from(4..9)), Expr::Num(3)]),
+/// );
+/// assert_eq!(errs.len(), 1);
+/// assert_eq!(out.unwrap().iter().filter(|e| e.is_error_node()).count(), 1);
+/// ```
+pub fn recover_as_error_node<S, F>(skip: S, node: F) -> RecoverAsErrorNode<S, F> {
+    RecoverAsErrorNode { skip, node }
+}
+
+// How a token skipped during recovery affects the nesting of delimiters
+enum Nesting {
+    // The token opened a delimiter
+    Opened,
+    // The token closed a delimiter that was opened while skipping
+    Closed,
+    // The token closed the nesting level that the parser started in, so should be left for the enclosing parser
+    ClosedOuter,
+    // The token is not a delimiter
+    Other,
+}
+
+// Update the stack of delimiters that have been opened (and not yet closed) while skipping, given the next token
+fn track_nesting<T: PartialEq>(delimiters: &[(T, T)], stack: &mut Vec<usize>, tok: &T) -> Nesting {
+    if let Some(i) = delimiters.iter().position(|(open, _)| open == tok) {
+        stack.push(i);
+        Nesting::Opened
+    } else if let Some(depth) = stack.iter().rposition(|i| &delimiters[*i].1 == tok) {
+        // Any delimiters opened since the one being closed are left unclosed
+        stack.truncate(depth);
+        Nesting::Closed
+    } else if delimiters.iter().any(|(_, close)| close == tok) {
+        Nesting::ClosedOuter
+    } else {
+        Nesting::Other
+    }
+}
+
+/// See [`skip_balanced`].
+#[must_use]
+#[derive(Copy, Clone)]
+pub struct SkipBalanced<T, F, const N: usize> {
+    delimiters: [(T, T); N],
+    fallback: F,
+}
+
+impl<T, F, const N: usize> Sealed for SkipBalanced<T, F, N> {}
+impl<'a, I, O, E, F, const N: usize> Strategy<'a, I, O, E> for SkipBalanced<I::Token, F, N>
+where
+    I: Input<'a>,
+    I::Token: PartialEq,
+    F: Fn(I::Span) -> O,
+    E: ParserExtra<'a, I>,
+{
+    fn recover<M: Mode, P: Parser<'a, I, O, E>>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        _parser: &P,
+    ) -> PResult<M, O> {
+        let alt = inp.take_alt();
+        let start = inp.cursor();
+        // The delimiters that have been opened (and not yet closed) while skipping
+        let mut stack = Vec::new();
+        let mut starts_open = None;
+        loop {
+            let before = inp.save();
+            let Some(tok) = inp.next_maybe_inner() else {
+                break;
+            };
+            match track_nesting(&self.delimiters, &mut stack, tok.borrow()) {
+                Nesting::Opened => {
+                    starts_open.get_or_insert(true);
+                }
+                Nesting::Closed if stack.is_empty() && starts_open == Some(true) => break,
+                Nesting::ClosedOuter => {
+                    inp.rewind(before);
+                    break;
+                }
+                Nesting::Closed | Nesting::Other => {}
+            }
+            starts_open.get_or_insert(false);
+        }
+
+        let span = inp.span_since(&start);
+        if starts_open.is_none() {
+            // Nothing could be skipped, so recovery isn't possible here
+            inp.errors.alt = Some(alt);
+            return Err(());
+        }
+        inp.emit(alt.err);
+        Ok(M::bind(|| (self.fallback)(span)))
+    }
+
+    fn name(&self) -> &'static str {
+        "skip_balanced"
+    }
+}
+
+/// A recovery strategy that skips over input while keeping track of nested delimiters, such as `()`, `[]` and `{}`.
+///
+/// If the input at the point of failure starts with an opening delimiter, input is skipped until the matching closing
+/// delimiter, which is also consumed. Otherwise, input is skipped until the closing delimiter of the nesting level that
+/// the parser started in (which is left for the enclosing parser to consume) or the end of input. Either way,
+/// delimiters that are opened while skipping must be closed before skipping can stop.
+///
+/// Unbalanced input is handled gracefully: a closing delimiter that doesn't match the innermost opening delimiter, but
+/// does match one further out, closes that one (leaving those within it unclosed). Unlike [`nested_delimiters`], this
+/// means that a missing or mismatched closing delimiter doesn't cause recovery to fail or to skip the rest of the input.
+///
+/// The output of the recovered parser is produced by `fallback`, which is given the span of the skipped input. If
+/// nothing could be skipped (for example, because the parser failed at a closing delimiter), recovery fails.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// #[derive(Debug, PartialEq)]
+/// enum Expr {
+///     Num(u64),
+///     List(Vec<Expr>),
+///     Error,
+/// }
+///
+/// let expr = recursive(|expr| {
+///     let list = expr
+///         .separated_by(just(','))
+///         .collect()
+///         .delimited_by(just('['), just(']'))
+///         .map(Expr::List)
+///         .recover_with(skip_balanced([('[', ']'), ('(', ')')], |_| Expr::Error));
+///
+///     text::int::<_, _, extra::Err<Rich<char>>>(10)
+///         .from_str()
+///         .unwrapped()
+///         .map(Expr::Num)
+///         .or(list)
+///         .padded()
+/// });
+///
+/// // The unclosed `(` is skipped, but the `]` after it still closes the list
+/// let (out, errs) = expr.repeated().collect::<Vec<_>>().parse("[1, 2] [3, (4 ] [5]").into_output_errors();
+/// assert_eq!(
+///     out,
+///     Some(vec![
+///         Expr::List(vec![Expr::Num(1), Expr::Num(2)]),
+///         Expr::List(vec![Expr::Num(3), Expr::Error]),
+///         Expr::List(vec![Expr::Num(5)]),
+///     ]),
+/// );
+/// assert_eq!(errs.len(), 1);
+/// ```
+pub fn skip_balanced<T, F, const N: usize>(
+    delimiters: [(T, T); N],
+    fallback: F,
+) -> SkipBalanced<T, F, N> {
+    SkipBalanced {
+        delimiters,
+        fallback,
+    }
+}
+
+/// See [`sync_to`].
+#[must_use]
+#[derive(Copy, Clone)]
+pub struct SyncTo<S, T, F, const N: usize> {
+    sync: S,
+    delimiters: [(T, T); N],
+    fallback: F,
+}
+
+impl<S, T, F, const N: usize> Sealed for SyncTo<S, T, F, N> {}
+impl<'a, I, O, E, S, F, const N: usize> Strategy<'a, I, O, E> for SyncTo<S, I::Token, F, N>
+where
+    I: Input<'a>,
+    I::Token: PartialEq,
+    S: Parser<'a, I, (), E>,
+    F: Fn(I::Span) -> O,
+    E: ParserExtra<'a, I>,
+{
+    fn recover<M: Mode, P: Parser<'a, I, O, E>>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        _parser: &P,
+    ) -> PResult<M, O> {
+        let alt = inp.take_alt();
+        let start = inp.cursor();
+        // The delimiters that have been opened (and not yet closed) while skipping
+        let mut stack = Vec::new();
+        loop {
+            let before = inp.save();
+            // Synchronisation points within nested delimiters are ignored
+            if stack.is_empty() {
+                if let Ok(()) = self.sync.go::<Check>(inp) {
+                    break;
+                }
+                inp.rewind(before.clone());
+            }
+            let Some(tok) = inp.next_maybe_inner() else {
+                break;
+            };
+            if let Nesting::ClosedOuter = track_nesting(&self.delimiters, &mut stack, tok.borrow())
+            {
+                inp.rewind(before);
+                break;
+            }
+        }
+
+        if inp.cursor() == start {
+            // Nothing could be skipped, so recovery isn't possible here
+            inp.errors.alt = Some(alt);
+            return Err(());
+        }
+        let span = inp.span_since(&start);
+        inp.emit(alt.err);
+        Ok(M::bind(|| (self.fallback)(span)))
+    }
+
+    fn name(&self) -> &'static str {
+        "sync_to"
+    }
+}
+
+/// A recovery strategy for classic 'panic mode' recovery: input is skipped up to and including the next
+/// synchronisation point (such as the `;` at the end of a statement), while respecting nested delimiters.
+///
+/// Skipping stops after the first input that `sync` accepts, outside of any `delimiters` opened while skipping, or
+/// before a closing delimiter of the nesting level that the parser started in (such as the `}` at the end of the
+/// enclosing block), which is left for the enclosing parser. Nested delimiters are handled as in [`skip_balanced`], so
+/// a `;` within parentheses is not mistaken for the end of the statement. Skipping also stops at the end of the input.
+///
+/// The output of the recovered parser is produced by `fallback`, which is given the span of the skipped input. If
+/// nothing could be skipped, recovery fails.
+///
+/// This packages up a pattern that would otherwise be assembled by hand from [`skip_until`] or [`via_parser`] in many
+/// parsers for statement-based languages.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let stmt = text::ascii::ident::<_, _, extra::Err<Rich<char>>>()
+///     .then_ignore(just('=').padded())
+///     .then(text::int(10))
+///     .then_ignore(just(';'))
+///     .map(Some)
+///     .recover_with(sync_to(
+///         just(';').ignored(),
+///         [('(', ')'), ('[', ']'), ('{', '}')],
+///         |_| None,
+///     ))
+///     .padded();
+/// let block = stmt
+///     .repeated()
+///     .collect::<Vec<_>>()
+///     .delimited_by(just('{'), just('}'));
+///
+/// // The `;` within the parentheses doesn't end the broken statement, and the `}` is left to close the block
+/// let (out, errs) = block.parse("{ a = 1; b = (2; 3); c = 4; d = }").into_output_errors();
+/// assert_eq!(
+///     out,
+///     Some(vec![Some(("a", "1")), None, Some(("c", "4")), None]),
+/// );
+/// assert_eq!(errs.len(), 2);
+/// ```
+pub fn sync_to<S, T, F, const N: usize>(
+    sync: S,
+    delimiters: [(T, T); N],
+    fallback: F,
+) -> SyncTo<S, T, F, N> {
+    SyncTo {
+        sync,
+        delimiters,
+        fallback,
+    }
+}
+
 /// A recovery parser that searches for a start and end delimiter, respecting nesting.
 ///
 /// It is possible to specify additional delimiter pairs that are valid in the pattern's context for better errors. For
@@ -6,7 +6,9 @@
 //!
 //! The [`recursive()`] function covers most cases, but sometimes it's necessary to manually control the declaration and
 //! definition of parsers more carefully, particularly for mutually-recursive parsers. In such cases, the functions on
-//! [`Recursive`] allow for this.
+//! [`Recursive`] allow for this, and [`recursive_group!`](crate::recursive_group) declares and defines a whole group of
+//! mutually-recursive rules at once. For rules that are left-recursive, see `recursive_left` (which requires the
+//! `memoization` feature).
 
 use super::*;
 
@@ -186,15 +188,17 @@ where
 {
     #[inline]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
-        recurse(move || {
-            M::invoke(
-                self.parser()
-                    .inner
-                    .get()
-                    .expect("Recursive parser used before being defined")
-                    .as_ref(),
-                inp,
-            )
+        inp.recurse(|inp| {
+            recurse(move || {
+                M::invoke(
+                    self.parser()
+                        .inner
+                        .get()
+                        .expect("Recursive parser used before being defined")
+                        .as_ref(),
+                    inp,
+                )
+            })
         })
     }
 
@@ -208,7 +212,7 @@ where
 {
     #[inline]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
-        recurse(move || M::invoke(&*self.parser(), inp))
+        inp.recurse(|inp| recurse(move || M::invoke(&*self.parser(), inp)))
     }
 
     go_extra!(O);
@@ -284,3 +288,249 @@ where
         inner: RecursiveInner::Owned(rc),
     }
 }
+
+/// Declare and define a group of mutually recursive parsers all at once.
+///
+/// Each rule is written as `name = parser;`. All of the rules are [declared](Recursive::declare) up front, and each
+/// rule's parser may then refer to any rule in the group (including itself) by name. The result is a struct with one
+/// field per rule, each being a [`Recursive`] parser.
+///
+/// Within each rule, the names refer to fresh clones of the group's parsers, so a rule that uses another rule only once
+/// can use it by value without calling `.clone()`.
+///
+/// This is a wrapper around [`Recursive::declare`] and [`Recursive::define`], and is useful for grammars with many
+/// rules that refer to one another.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// #[derive(Debug, PartialEq)]
+/// enum Expr {
+///     Num(u64),
+///     Block(Vec<Stmt>),
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Stmt {
+///     Expr(Expr),
+///     Print(Expr),
+/// }
+///
+/// let grammar = recursive_group! {
+///     expr = text::int::<_, _, extra::Err<Simple<char>>>(10)
+///         .from_str()
+///         .unwrapped()
+///         .map(Expr::Num)
+///         .or(stmt
+///             .repeated()
+///             .collect()
+///             .delimited_by(just('{'), just('}'))
+///             .map(Expr::Block))
+///         .padded();
+///     stmt = text::keyword("print")
+///         .ignore_then(expr.clone())
+///         .map(Stmt::Print)
+///         .or(expr.map(Stmt::Expr))
+///         .then_ignore(just(';'))
+///         .padded();
+/// };
+///
+/// assert_eq!(
+///     grammar.stmt.parse("print { 1; print 2; };").into_result(),
+///     Ok(Stmt::Print(Expr::Block(vec![
+///         Stmt::Expr(Expr::Num(1)),
+///         Stmt::Print(Expr::Num(2)),
+///     ]))),
+/// );
+/// assert!(grammar.expr.parse("{ print; }").has_errors());
+/// ```
+#[macro_export]
+macro_rules! recursive_group {
+    ($($name:ident = $parser:expr);+ $(;)?) => ({
+        $(let mut $name = $crate::recursive::Recursive::declare();)+
+        $crate::recursive_group!(@define [$($name)+] $($name = $parser;)+);
+
+        // Not every rule is necessarily used from outside the group
+        #[allow(non_camel_case_types, dead_code)]
+        struct RecursiveGroup<$($name),+> {
+            $($name: $name),+
+        }
+
+        RecursiveGroup { $($name),+ }
+    });
+    (@define [$($all:ident)+]) => {};
+    (@define [$($all:ident)+] $name:ident = $parser:expr; $($rest:tt)*) => {
+        $name.define({
+            $(#[allow(unused_variables)] let $all = $all.clone();)+
+            $parser
+        });
+        $crate::recursive_group!(@define [$($all)+] $($rest)*);
+    };
+}
+
+#[cfg(feature = "memoization")]
+#[derive(Clone)]
+struct Seed<C, O, Err> {
+    end: C,
+    out: O,
+    errors: Vec<Located<C, Err>>,
+}
+
+// Grows the result of a left-recursive rule from a seed. See `recursive_left`.
+#[cfg(feature = "memoization")]
+struct GrowSeed<'a, A, I: Input<'a>, O, E: ParserExtra<'a, I>> {
+    parser: A,
+    // Keyed by memoization scope and position. `None` means that no seed has been found yet.
+    seeds: combinator::MemoCell<HashMap<(usize, usize), Option<Seed<I::Cursor, O, E::Error>>>>,
+}
+
+#[cfg(feature = "memoization")]
+impl<'a, A, I, O, E> GrowSeed<'a, A, I, O, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    E::Error: Clone,
+    O: Clone,
+{
+    fn accept<M: Mode>(
+        inp: &mut InputRef<'a, '_, I, E>,
+        seed: Seed<I::Cursor, O, E::Error>,
+    ) -> PResult<M, O> {
+        inp.errors.secondary.extend(seed.errors);
+        // SAFETY: the cursor was produced by advancing from the current position in this input
+        unsafe { inp.set_cursor(seed.end) };
+        Ok(M::bind(|| seed.out))
+    }
+}
+
+#[cfg(feature = "memoization")]
+impl<'a, A, I, O, E> ParserSealed<'a, I, O, E> for GrowSeed<'a, A, I, O, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    E::Error: Clone,
+    A: Parser<'a, I, O, E>,
+    O: Clone,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let before = inp.save();
+        let key = (inp.memo_scope, I::cursor_location(&before.cursor().inner));
+
+        // A left-recursive call: use the seed that has been grown so far
+        let seed = combinator::lock_memo(&self.seeds).get(&key).cloned();
+        match seed {
+            Some(Some(seed)) => return Self::accept::<M>(inp, seed),
+            Some(None) => {
+                let err_span = inp.span_since(before.cursor());
+                inp.add_alt(None, None, err_span);
+                return Err(());
+            }
+            None => {}
+        }
+
+        combinator::lock_memo(&self.seeds).insert(key, None);
+        // Parse again with each new seed, until the parse no longer gets any further
+        let mut best: Option<Seed<_, _, _>> = None;
+        let res = loop {
+            inp.rewind(before.clone());
+            inp.errors.cut = false;
+            match self.parser.go::<Emit>(inp) {
+                Ok(out) => {
+                    let end = inp.cursor().inner;
+                    if matches!(&best, Some(best) if I::cursor_location(&end) <= I::cursor_location(&best.end))
+                    {
+                        break Ok(());
+                    }
+                    let seed = Seed {
+                        end,
+                        out,
+                        errors: inp.errors.secondary_errors_since(before.err_count).to_vec(),
+                    };
+                    combinator::lock_memo(&self.seeds).insert(key, Some(seed.clone()));
+                    best = Some(seed);
+                }
+                Err(()) if inp.errors.cut => break Err(()),
+                Err(()) => break Ok(()),
+            }
+        };
+        combinator::lock_memo(&self.seeds).remove(&key);
+
+        res?;
+        inp.rewind(before);
+        match best {
+            Some(seed) => Self::accept::<M>(inp, seed),
+            None => Err(()),
+        }
+    }
+
+    go_extra!(O);
+}
+
+/// Construct a recursive parser that may be directly left-recursive, such as `expr = expr '+' term | term`.
+///
+/// Parsers created with [`recursive()`] loop forever if they refer to themselves before consuming any input. This
+/// function instead uses the 'seed-growing' technique of Warth et al.: the parser is first run with its left-recursive
+/// references failing, to find a *seed* (such as a single `term`). It is then run again and again, with the
+/// left-recursive references producing the previous result, for as long as each run gets further through the input.
+/// This makes left-recursive rules left-associative, as in grammars written for yacc or ANTLR.
+///
+/// Outputs are cloned each time that they are reused, so this works best for outputs that are cheap to clone. Since
+/// each growth of the seed is parsed from scratch, changes made to the parser state are only kept for the final
+/// parse. Only direct left recursion is supported: rules that are left-recursive through other rules may not find
+/// the longest parse.
+///
+/// The output type of this parser is `O`, the same as the inner parser.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, recursive::recursive_left};
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum Type<'a> {
+///     Named(&'a str),
+///     Array(Box<Type<'a>>),
+///     Pointer(Box<Type<'a>>),
+/// }
+///
+/// // Postfix type syntax, such as `int[]*`
+/// let ty = recursive_left::<_, _, extra::Err<Simple<char>>, _, _>(|ty| choice((
+///     ty.clone().then_ignore(just("[]")).map(|ty| Type::Array(Box::new(ty))),
+///     ty.then_ignore(just('*')).map(|ty| Type::Pointer(Box::new(ty))),
+///     text::ascii::ident().map(Type::Named),
+/// )));
+///
+/// assert_eq!(
+///     ty.parse("int[]*").into_result(),
+///     Ok(Type::Pointer(Box::new(Type::Array(Box::new(Type::Named("int")))))),
+/// );
+/// ```
+#[cfg(feature = "memoization")]
+pub fn recursive_left<'a, 'b, I, O, E, A, F>(f: F) -> Recursive<Direct<'a, 'b, I, O, E>>
+where
+    'a: 'b,
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    E::Error: Clone + MaybeSync,
+    A: Parser<'a, I, O, E> + MaybeSync + 'b,
+    F: FnOnce(Recursive<Direct<'a, 'b, I, O, E>>) -> A,
+    O: Clone + MaybeSync + 'b,
+    I::Cursor: MaybeSync,
+{
+    let rc = RefC::new_cyclic(|rc| {
+        let rc: RefW<DynParser<'a, 'b, I, O, E>> = rc.clone() as _;
+        let parser = Recursive {
+            inner: RecursiveInner::Unowned(rc.clone()),
+        };
+
+        GrowSeed {
+            parser: f(parser),
+            seeds: combinator::MemoCell::new(HashMap::default()),
+        }
+    });
+
+    Recursive {
+        inner: RecursiveInner::Owned(rc),
+    }
+}
@@ -61,12 +61,18 @@ pub trait Span {
     ///
     /// For example, spans like `3..5` and `7..8` will result in a unioned span of `3..8`.
     ///
-    /// The spans may overlap one-another, but the start offset must come before the end offset for each span (i.e:
-    /// each span must be 'well-formed'). If this is not the case, the result is unspecified.
+    /// The spans may overlap one-another, and should each be 'well-formed' (i.e: the start offset of each span should
+    /// come before its end offset). In release builds, spans that are not are clamped: if the result would end before
+    /// it starts, its end is clamped to its start. Since such a span usually points to a bug in whatever produced it,
+    /// debug builds panic instead. Use [`Span::checked_union`] to combine spans that may legitimately be reversed (such
+    /// as those made from the positions of recovered or synthesized tokens).
     ///
     /// # Panics
     ///
-    /// Panics if the [`Self::Context`]s of both spans are not equal.
+    /// Panics if the [`Self::Context`]s of both spans are not equal. See [`Span::checked_union`] for a version that
+    /// does not panic.
+    ///
+    /// In debug builds, also panics if the result would end before it starts.
     fn union(&self, other: Self) -> Self
     where
         Self::Context: PartialEq + fmt::Debug,
@@ -78,10 +84,43 @@ pub trait Span {
             other.context(),
             "tried to union two spans with different contexts"
         );
-        Self::new(
-            self.context(),
-            self.start().min(other.start())..self.end().max(other.end()),
-        )
+        let start = self.start().min(other.start());
+        let end = self.end().max(other.end());
+        debug_assert!(
+            start <= end,
+            "tried to union two spans that together end before they start"
+        );
+        let end = end.max(start.clone());
+        Self::new(self.context(), start..end)
+    }
+
+    /// Like [`Span::union`], but never panics: it produces `None` if the [`Self::Context`]s of the spans are not
+    /// equal, and clamps a result that would end before it starts in all builds.
+    ///
+    /// ```
+    /// # use chumsky::span::{Span, SimpleSpan};
+    /// let a = <SimpleSpan<usize, &str> as Span>::new("a.txt", 3..5);
+    /// let b = <SimpleSpan<usize, &str> as Span>::new("a.txt", 7..8);
+    /// let c = <SimpleSpan<usize, &str> as Span>::new("b.txt", 0..1);
+    ///
+    /// assert_eq!(a.checked_union(b).map(|s| s.start..s.end), Some(3..8));
+    /// assert!(a.checked_union(c).is_none());
+    /// // Reversed spans produce an empty span, rather than another reversed span
+    /// let d = <SimpleSpan<usize, &str> as Span>::new("a.txt", 9..6);
+    /// assert_eq!(d.checked_union(d).map(|s| s.start..s.end), Some(9..9));
+    /// ```
+    fn checked_union(&self, other: Self) -> Option<Self>
+    where
+        Self::Context: PartialEq + fmt::Debug,
+        Self::Offset: Ord,
+        Self: Sized,
+    {
+        if self.context() != other.context() {
+            return None;
+        }
+        let start = self.start().min(other.start());
+        let end = self.end().max(other.end()).max(start.clone());
+        Some(Self::new(self.context(), start..end))
     }
 }
 
@@ -225,3 +264,114 @@ impl<T: Clone> Span for Range<T> {
         self.end.clone()
     }
 }
+
+/// A span that may belong to a *synthetic* token, such as one produced by macro expansion, along with the span of the
+/// source code that the token originated from.
+///
+/// When generated tokens are fed into a parser (usually with [`Input::spanned`]), real tokens can be given spans
+/// created with [`ExpandedSpan::real`] and synthetic tokens spans created with [`ExpandedSpan::synthetic`]. The origin
+/// is carried along through the spans that chumsky builds out of these, so that [`ExpandedSpan::location`] can point
+/// errors at code that the user actually wrote.
+///
+/// A span that starts at a synthetic token takes that token's origin. Failing that, a span that ends at a synthetic
+/// token takes that token's origin instead.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, span::ExpandedSpan};
+/// // The tokens of `twice!(x)`, expanded to `x + x` at offsets 100 onwards
+/// let call = SimpleSpan::from(0..9);
+/// let tokens = [
+///     ('x', ExpandedSpan::synthetic((100..101).into(), call)),
+///     ('+', ExpandedSpan::synthetic((101..102).into(), call)),
+///     ('x', ExpandedSpan::synthetic((102..103).into(), call)),
+///     (';', ExpandedSpan::real((9..10).into())),
+/// ];
+///
+/// let parser = just::<_, _, extra::Err<Rich<char, ExpandedSpan<SimpleSpan>>>>('x').then(just('-'));
+/// let eoi = ExpandedSpan::real((10..10).into());
+/// let errs = parser.parse(tokens.spanned(eoi)).into_errors();
+///
+/// // The error is reported at the macro call, rather than somewhere in code the user never saw
+/// assert!(errs[0].span().is_synthetic());
+/// assert_eq!(errs[0].span().span(), &SimpleSpan::from(101..102));
+/// assert_eq!(errs[0].span().location(), &call);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ExpandedSpan<S> {
+    span: S,
+    origin: Option<S>,
+}
+
+impl<S> ExpandedSpan<S> {
+    /// Create a span for a token that appears in the source code.
+    pub fn real(span: S) -> Self {
+        Self { span, origin: None }
+    }
+
+    /// Create a span for a synthetic token, such as one produced by macro expansion, that originated from `origin`.
+    pub fn synthetic(span: S, origin: S) -> Self {
+        Self {
+            span,
+            origin: Some(origin),
+        }
+    }
+
+    /// Get the span itself, which may refer to generated code.
+    pub fn span(&self) -> &S {
+        &self.span
+    }
+
+    /// Get the span of the source code that this span originated from, if it is synthetic.
+    pub fn origin(&self) -> Option<&S> {
+        self.origin.as_ref()
+    }
+
+    /// Returns `true` if this span is synthetic.
+    pub fn is_synthetic(&self) -> bool {
+        self.origin.is_some()
+    }
+
+    /// Get the span that should be reported to the user: the origin if this span is synthetic, or the span itself if
+    /// not.
+    pub fn location(&self) -> &S {
+        self.origin.as_ref().unwrap_or(&self.span)
+    }
+}
+
+impl<S> From<S> for ExpandedSpan<S> {
+    fn from(span: S) -> Self {
+        Self::real(span)
+    }
+}
+
+impl<S: fmt::Display> fmt::Display for ExpandedSpan<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.location().fmt(f)
+    }
+}
+
+impl<S: Span + Clone> Span for ExpandedSpan<S> {
+    type Context = S::Context;
+    /// An offset within the span, along with the origin of the token that it belongs to.
+    type Offset = (S::Offset, Option<S>);
+
+    fn new(context: Self::Context, range: Range<Self::Offset>) -> Self {
+        let ((start, start_origin), (end, end_origin)) = (range.start, range.end);
+        Self {
+            span: S::new(context, start..end),
+            origin: start_origin.or(end_origin),
+        }
+    }
+    fn context(&self) -> Self::Context {
+        self.span.context()
+    }
+    fn start(&self) -> Self::Offset {
+        (self.span.start(), self.origin.clone())
+    }
+    fn end(&self) -> Self::Offset {
+        (self.span.end(), self.origin.clone())
+    }
+}
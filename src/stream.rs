@@ -1,4 +1,6 @@
 use super::*;
+use crate::input::{CommitInput, FallibleInput};
+use alloc::collections::VecDeque;
 
 /// An input that dynamically pulls tokens from an [`Iterator`].
 ///
@@ -126,6 +128,224 @@ where
     }
 }
 
+/// An input that dynamically pulls tokens from an [`Iterator`] of [`Result`]s, such as a fallible lexer.
+///
+/// Tokens are pulled lazily, so the lexer does not need to be run to completion (and validated) before parsing
+/// starts. When the iterator produces an error, the stream ends at that point and the error is retained: use
+/// [`Parser::with_lex_errors`] to turn it into a parser error.
+pub struct FallibleStream<I, T, L> {
+    tokens: Vec<T>,
+    iter: I,
+    error: Option<L>,
+}
+
+impl<I: Iterator<Item = Result<T, L>>, T, L> FallibleStream<I, T, L> {
+    /// Create a new fallible stream from an [`Iterator`] of [`Result`]s.
+    pub fn from_iter<J: IntoIterator<IntoIter = I>>(iter: J) -> Self {
+        Self {
+            tokens: Vec::new(),
+            iter: iter.into_iter(),
+            error: None,
+        }
+    }
+}
+
+impl<'a, I, T, L> Input<'a> for FallibleStream<I, T, L>
+where
+    I: Iterator<Item = Result<T, L>> + 'a,
+    T: Clone + 'a,
+    L: 'a,
+{
+    type Span = SimpleSpan<usize>;
+
+    type Token = T;
+    type MaybeToken = T;
+
+    type Cursor = usize;
+
+    type Cache = Self;
+
+    #[inline(always)]
+    fn begin(self) -> (Self::Cursor, Self::Cache) {
+        (0, self)
+    }
+
+    #[inline]
+    fn cursor_location(cursor: &Self::Cursor) -> usize {
+        *cursor
+    }
+
+    #[inline(always)]
+    unsafe fn next_maybe(
+        this: &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<Self::MaybeToken> {
+        Self::next(this, cursor)
+    }
+
+    #[inline(always)]
+    unsafe fn span(_this: &mut Self::Cache, range: Range<&Self::Cursor>) -> Self::Span {
+        (*range.start..*range.end).into()
+    }
+}
+
+impl<'a, I, T, L> ValueInput<'a> for FallibleStream<I, T, L>
+where
+    I: Iterator<Item = Result<T, L>> + 'a,
+    T: Clone + 'a,
+    L: 'a,
+{
+    #[inline]
+    unsafe fn next(this: &mut Self::Cache, cursor: &mut Self::Cursor) -> Option<Self::Token> {
+        // Pull new items into the vector if we need them, stopping for good at the first error
+        if this.tokens.len() <= *cursor && this.error.is_none() {
+            for item in (&mut this.iter).take(512) {
+                match item {
+                    Ok(tok) => this.tokens.push(tok),
+                    Err(err) => {
+                        this.error = Some(err);
+                        break;
+                    }
+                }
+            }
+        }
+
+        // Get the token at the given cursor
+        this.tokens.get(*cursor).map(|tok| {
+            *cursor += 1;
+            tok.clone()
+        })
+    }
+}
+
+impl<'a, I, T, L> FallibleInput<'a> for FallibleStream<I, T, L>
+where
+    I: Iterator<Item = Result<T, L>> + 'a,
+    T: Clone + 'a,
+    L: 'a,
+{
+    type LexError = L;
+
+    #[inline]
+    fn lex_error(this: &Self::Cache) -> Option<(Self::Cursor, &Self::LexError)> {
+        this.error.as_ref().map(|err| (this.tokens.len(), err))
+    }
+}
+
+/// An input that pulls tokens from an [`Iterator`], retaining only those that might still be needed.
+///
+/// Tokens are kept from the most recent commit point (see [`Parser::commit`]) up to the furthest token read, so a
+/// grammar that commits regularly (for example, after each line of NDJSON or each record of a log file) can parse an
+/// unbounded stream in bounded memory.
+///
+/// Backtracking to before the commit point is not possible, since those tokens have been discarded. If a parser
+/// attempts it, parsing halts with an error (see [`Error::input_discarded`] and
+/// [`Halt::Discarded`](crate::extra::Halt::Discarded)).
+pub struct BoundedStream<I: Iterator> {
+    tokens: VecDeque<I::Item>,
+    iter: I,
+    // The cursor of the first retained token
+    base: usize,
+}
+
+impl<I: Iterator> BoundedStream<I> {
+    /// Create a new bounded stream from an [`Iterator`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, input::BoundedStream};
+    /// // A stream of lines that would be far too large to keep in memory
+    /// let lines = (0..1_000_000).flat_map(|_| "42\n".chars());
+    ///
+    /// let line = text::digits::<_, _, extra::Err<Simple<_>>>(10)
+    ///     .then_ignore(just('\n'))
+    ///     .commit();
+    ///
+    /// assert_eq!(line.repeated().count().parse(BoundedStream::from_iter(lines)).into_result(), Ok(1_000_000));
+    /// ```
+    pub fn from_iter<J: IntoIterator<IntoIter = I>>(iter: J) -> Self {
+        Self {
+            tokens: VecDeque::new(),
+            iter: iter.into_iter(),
+            base: 0,
+        }
+    }
+}
+
+impl<'a, I: Iterator + 'a> Input<'a> for BoundedStream<I>
+where
+    I::Item: Clone,
+{
+    type Span = SimpleSpan<usize>;
+
+    type Token = I::Item;
+    type MaybeToken = I::Item;
+
+    type Cursor = usize;
+
+    type Cache = Self;
+
+    #[inline(always)]
+    fn begin(self) -> (Self::Cursor, Self::Cache) {
+        (0, self)
+    }
+
+    #[inline]
+    fn cursor_location(cursor: &Self::Cursor) -> usize {
+        *cursor
+    }
+
+    #[inline(always)]
+    unsafe fn next_maybe(
+        this: &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<Self::MaybeToken> {
+        Self::next(this, cursor)
+    }
+
+    #[inline(always)]
+    unsafe fn span(_this: &mut Self::Cache, range: Range<&Self::Cursor>) -> Self::Span {
+        (*range.start..*range.end).into()
+    }
+}
+
+impl<'a, I: Iterator + 'a> ValueInput<'a> for BoundedStream<I>
+where
+    I::Item: Clone,
+{
+    #[inline]
+    unsafe fn next(this: &mut Self::Cache, cursor: &mut Self::Cursor) -> Option<Self::Token> {
+        // The parse has already halted if this happens, see `InputRef::rewind`
+        if *cursor < this.base {
+            return None;
+        }
+
+        let idx = *cursor - this.base;
+        if idx >= this.tokens.len() {
+            this.tokens
+                .extend((&mut this.iter).take(idx + 1 - this.tokens.len()));
+        }
+
+        this.tokens.get(idx).map(|tok| {
+            *cursor += 1;
+            tok.clone()
+        })
+    }
+}
+
+impl<'a, I: Iterator + 'a> CommitInput<'a> for BoundedStream<I>
+where
+    I::Item: Clone,
+{
+    #[inline]
+    fn commit(this: &mut Self::Cache, cursor: &Self::Cursor) {
+        let n = cursor.saturating_sub(this.base).min(this.tokens.len());
+        this.tokens.drain(..n);
+        this.base += n;
+    }
+}
+
 /// An input type that uses an iterator to generate tokens.
 ///
 /// This input type supports backtracking by duplicating the iterator. It is recommended that your iterator is very
@@ -0,0 +1,306 @@
+//! Conformance tests for custom implementations of [`Input`], [`Span`], and [`Error`].
+//!
+//! *"Curiouser and curiouser!"*
+//!
+//! Chumsky's parsers rely on these traits behaving in certain ways that the type system can't enforce: cursors must
+//! move forwards, spans must not end before they start, and errors must merge consistently. An implementation that
+//! breaks these rules usually won't fail to compile, but will instead produce confusing errors or spans much later on.
+//!
+//! Each function in this module checks one of these contracts, and panics with a description of the problem if it is
+//! broken. They are intended to be called from your own tests with a few representative values.
+//!
+//! # Examples
+//!
+//! ```
+//! # use chumsky::{prelude::*, test_suite};
+//! // A token stream where each token carries its own span
+//! let tokens = [('a', SimpleSpan::new(0, 1)), ('b', SimpleSpan::new(2, 4))];
+//! let input = tokens.as_slice().spanned(SimpleSpan::new(4, 4));
+//!
+//! test_suite::check_input(input);
+//! test_suite::check_span(SimpleSpan::new(2, 4));
+//! test_suite::check_error::<&str, Rich<char>>(&'a', &'b', SimpleSpan::new(2, 4));
+//! ```
+
+use super::*;
+
+/// Check that an [`Input`] upholds the contract that chumsky's parsers rely on.
+///
+/// The whole input is read, token by token, and the following are checked:
+///
+/// - Reading a token moves the cursor forward (its [`Input::cursor_location`] increases).
+/// - Once the end of the input is reached, no more tokens are produced and the cursor stays where it is.
+/// - Reading the input again from a cursor that was saved earlier (as happens when a parser backtracks) produces the
+///   same cursor locations.
+/// - The span of each token, and of the whole input, ends at or after its start.
+///
+/// # Panics
+///
+/// Panics if any of the above do not hold.
+pub fn check_input<'src, I>(input: I)
+where
+    I: Input<'src>,
+    <I::Span as Span>::Offset: Ord + fmt::Debug,
+{
+    let (start, mut cache) = input.begin();
+
+    // SAFETY: Every cursor used below was produced by `begin` or by reading from the same input
+    let next = |cache: &mut I::Cache, cursor: &mut I::Cursor| unsafe {
+        I::next_maybe(cache, cursor).is_some()
+    };
+    // SAFETY: As above
+    let span = |cache: &mut I::Cache, range: Range<&I::Cursor>| unsafe { I::span(cache, range) };
+
+    let mut cursors = vec![start.clone()];
+    let mut cursor = start.clone();
+    while next(&mut cache, &mut cursor) {
+        let prev = cursors.last().unwrap();
+        assert!(
+            I::cursor_location(&cursor) > I::cursor_location(prev),
+            "reading a token did not move the cursor forward (cursor location {} after token {})",
+            I::cursor_location(&cursor),
+            cursors.len() - 1,
+        );
+        check_span_order(&span(&mut cache, prev..&cursor));
+        cursors.push(cursor.clone());
+    }
+
+    let end = cursors.last().unwrap();
+    for _ in 0..2 {
+        let mut after = end.clone();
+        assert!(
+            !next(&mut cache, &mut after),
+            "a token was produced after the end of the input",
+        );
+        assert_eq!(
+            I::cursor_location(&after),
+            I::cursor_location(end),
+            "reading past the end of the input moved the cursor",
+        );
+    }
+    check_span_order(&span(&mut cache, &start..end));
+
+    // Backtracking to any earlier cursor must replay the input in the same way
+    for (i, saved) in cursors.iter().enumerate() {
+        let mut cursor = saved.clone();
+        for expected in &cursors[i + 1..] {
+            assert!(
+                next(&mut cache, &mut cursor),
+                "reading again from a saved cursor ended the input early",
+            );
+            assert_eq!(
+                I::cursor_location(&cursor),
+                I::cursor_location(expected),
+                "reading again from a saved cursor produced a different cursor location",
+            );
+        }
+    }
+}
+
+fn check_span_order<S: Span>(span: &S)
+where
+    S::Offset: Ord + fmt::Debug,
+{
+    assert!(
+        span.start() <= span.end(),
+        "span ends before it starts ({:?}..{:?})",
+        span.start(),
+        span.end(),
+    );
+}
+
+/// Check that a [`Span`] upholds the contract that chumsky's parsers rely on.
+///
+/// The following are checked:
+///
+/// - The span ends at or after its start.
+/// - Creating a span with [`Span::new`] from the context, start, and end of the span produces a span with the same
+///   context, start, and end.
+/// - [`Span::to_end`] produces an empty span at the end of the span.
+/// - [`Span::union`] of the span with itself, with its end, and with a later span covers exactly the expected range,
+///   whichever way around the spans are given.
+///
+/// # Panics
+///
+/// Panics if any of the above do not hold.
+pub fn check_span<S>(span: S)
+where
+    S: Span + Clone,
+    S::Context: PartialEq + fmt::Debug,
+    S::Offset: Ord + fmt::Debug,
+{
+    check_span_order(&span);
+
+    let rebuilt = S::new(span.context(), span.start()..span.end());
+    assert_eq!(
+        rebuilt.context(),
+        span.context(),
+        "`Span::new` did not keep the context"
+    );
+    assert_eq!(
+        rebuilt.start()..rebuilt.end(),
+        span.start()..span.end(),
+        "`Span::new` did not keep the range",
+    );
+
+    let end = span.to_end();
+    assert_eq!(
+        end.context(),
+        span.context(),
+        "`Span::to_end` did not keep the context"
+    );
+    assert_eq!(
+        end.start()..end.end(),
+        span.end()..span.end(),
+        "`Span::to_end` did not produce an empty span at the end",
+    );
+
+    let union = |a: &S, b: &S| {
+        let ab = a.union(b.clone());
+        let ba = b.union(a.clone());
+        assert_eq!(
+            ab.start()..ab.end(),
+            ba.start()..ba.end(),
+            "`Span::union` depends on the order of the spans",
+        );
+        ab.start()..ab.end()
+    };
+    assert_eq!(
+        union(&span, &span),
+        span.start()..span.end(),
+        "`Span::union` of a span with itself changed its range",
+    );
+    assert_eq!(
+        union(&span, &end),
+        span.start()..span.end(),
+        "`Span::union` of a span with its end changed its range",
+    );
+    let later = S::new(span.context(), span.end()..span.end());
+    assert_eq!(
+        union(&rebuilt, &later),
+        span.start()..span.end(),
+        "`Span::union` with a later span did not cover both spans",
+    );
+}
+
+/// Check that an [`Error`] upholds the contract that chumsky's parsers rely on, using two different tokens and a span.
+///
+/// The following are checked:
+///
+/// - Merging an error with itself produces the same error (chumsky may encounter the same error more than once, such
+///   as when a parser backtracks).
+/// - [`Error::merge_expected_found`] behaves like merging with an error made by [`Error::expected_found`].
+/// - [`Error::replace_expected_found`] behaves like making a new error with [`Error::expected_found`], whatever the
+///   error being replaced was.
+///
+/// # Panics
+///
+/// Panics if any of the above do not hold.
+pub fn check_error<'a, I, E>(a: &'a I::Token, b: &'a I::Token, span: I::Span)
+where
+    I: Input<'a>,
+    I::Span: Clone,
+    E: Error<'a, I> + Clone + PartialEq + fmt::Debug,
+{
+    let tok = |t: &'a I::Token| Some(MaybeRef::Ref(t));
+    let err = |expected: Option<&'a I::Token>, found: Option<&'a I::Token>| {
+        E::expected_found(
+            [expected.map(MaybeRef::Ref)],
+            found.map(MaybeRef::Ref),
+            span.clone(),
+        )
+    };
+
+    for e in [
+        err(Some(a), Some(b)),
+        err(None, Some(a)),
+        err(Some(b), None),
+    ] {
+        assert_eq!(
+            e.clone().merge(e.clone()),
+            e,
+            "merging an error with itself changed it"
+        );
+    }
+
+    let merged = err(Some(a), Some(b)).merge(E::expected_found([tok(b)], tok(b), span.clone()));
+    assert_eq!(
+        err(Some(a), Some(b)).merge_expected_found([tok(b)], tok(b), span.clone()),
+        merged,
+        "`Error::merge_expected_found` differs from `Error::merge`",
+    );
+    assert_eq!(
+        err(Some(a), Some(b)).merge_expected_found([None], None, span.clone()),
+        err(Some(a), Some(b)).merge(err(None, None)),
+        "`Error::merge_expected_found` differs from `Error::merge`",
+    );
+
+    for old in [err(Some(b), None), merged] {
+        assert_eq!(
+            old.replace_expected_found([tok(a), None], tok(b), span.clone()),
+            E::expected_found([tok(a), None], tok(b), span.clone()),
+            "`Error::replace_expected_found` differs from `Error::expected_found`",
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::Stream;
+
+    #[test]
+    fn builtin_impls_conform() {
+        check_input("");
+        check_input("héllo, wörld");
+        check_input(b"bytes".as_slice());
+        check_input(&[1, 2, 3]);
+        check_input(Stream::from_iter("abc".chars()));
+        let tokens = [('a', SimpleSpan::new(0, 1)), ('b', SimpleSpan::new(3, 5))];
+        check_input(tokens.as_slice().spanned(SimpleSpan::new(5, 5)));
+
+        check_span(SimpleSpan::new(0, 0));
+        check_span(SimpleSpan::new(3, 7));
+        check_span(("a.txt", 3..7));
+        check_span(2..9);
+
+        let span = SimpleSpan::new(1, 2);
+        check_error::<&str, EmptyErr>(&'a', &'b', span);
+        check_error::<&str, Cheap>(&'a', &'b', span);
+        check_error::<&str, Simple<char>>(&'a', &'b', span);
+        check_error::<&str, Rich<char>>(&'a', &'b', span);
+    }
+
+    #[test]
+    #[should_panic = "reading a token did not move the cursor forward"]
+    fn stuck_cursor() {
+        // An input that never runs out and never moves
+        struct Stuck;
+
+        impl<'src> Input<'src> for Stuck {
+            type Cursor = usize;
+            type Span = SimpleSpan;
+            type Token = char;
+            type MaybeToken = char;
+            type Cache = ();
+
+            fn begin(self) -> (usize, ()) {
+                (0, ())
+            }
+
+            fn cursor_location(cursor: &usize) -> usize {
+                *cursor
+            }
+
+            unsafe fn next_maybe(_: &mut (), _: &mut usize) -> Option<char> {
+                Some('a')
+            }
+
+            unsafe fn span(_: &mut (), range: Range<&usize>) -> SimpleSpan {
+                SimpleSpan::new(*range.start, *range.end)
+            }
+        }
+
+        check_input(Stuck);
+    }
+}
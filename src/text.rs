@@ -256,6 +256,557 @@ where
         .ignored()
 }
 
+/// A parser that accepts the contents of a line: everything up to (but not including) the next line ending, or the end
+/// of the input. Any of the line endings accepted by [`newline`] end a line. To consume the line ending too, use
+/// [`rest_of_line`].
+///
+/// This is most useful with [`Parser::nested_in_region`], which allows a grammar for a single line to be run over each
+/// line in turn, while spans still refer to the whole input.
+///
+/// The output type of this parser is `&C::Str`, the contents of the line.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// // An INI-style `key = value` setting, which must fit on a single line
+/// let setting = text::ascii::ident::<_, _, extra::Err<Rich<char>>>()
+///     .then_ignore(just('=').padded())
+///     .then(any().repeated().to_slice())
+///     .nested_in_region(text::line());
+/// let settings = setting
+///     .separated_by(text::newline())
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(
+///     settings.parse("name = chumsky\r\nkind = parser").into_result(),
+///     Ok(vec![("name", "chumsky"), ("kind", "parser")]),
+/// );
+///
+/// // Errors within a line are reported relative to the whole input
+/// let errs = settings.parse("name = chumsky\n= parser").into_errors();
+/// assert_eq!(errs[0].span(), &SimpleSpan::from(15..16));
+/// ```
+#[must_use]
+pub fn line<'a, C: Char, I: StrInput<'a, C>, E: ParserExtra<'a, I>>(
+) -> impl Parser<'a, I, &'a C::Str, E> + Copy {
+    any().and_is(newline().not()).repeated().to_slice()
+}
+
+/// A parser that accepts the rest of the current line, including its line ending (if there is one), producing the
+/// contents of the line without the line ending.
+///
+/// This is useful for syntax that runs to the end of a line, such as line comments, preprocessor directives, or the
+/// message of a log entry. Unlike [`line()`], which leaves the line ending to be parsed by whatever follows, this parser
+/// consumes it. The last line of the input need not have a line ending.
+///
+/// The output type of this parser is `&C::Str`, the contents of the line.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// // A `#define NAME value` directive
+/// let define = just::<_, _, extra::Err<Simple<char>>>("#define")
+///     .ignore_then(text::ascii::ident().padded_by(text::inline_whitespace()))
+///     .then(text::rest_of_line());
+/// let defines = define.repeated().collect::<Vec<_>>();
+///
+/// assert_eq!(
+///     defines.parse("#define A 1 + 2\r\n#define B\n#define C x").into_result(),
+///     Ok(vec![("A", "1 + 2"), ("B", ""), ("C", "x")]),
+/// );
+/// ```
+#[must_use]
+pub fn rest_of_line<'a, C: Char, I: StrInput<'a, C>, E: ParserExtra<'a, I>>(
+) -> impl Parser<'a, I, &'a C::Str, E> + Copy {
+    line().then_ignore(newline().or(end()))
+}
+
+/// An iterable parser that accepts the lines of the input, one after another, producing the contents of each line
+/// along with its span.
+///
+/// As with [`str::lines`], each line is ended by a line ending (any of those accepted by [`newline`]) or the end of the
+/// input, and the line endings are not included in the output. An empty input has no lines, and a line ending at the
+/// very end of the input does not start another line.
+///
+/// The output type of this iterable parser is `(&C::Str, I::Span)`.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let lines = text::lines::<_, _, extra::Err<Simple<char>>>().collect::<Vec<_>>();
+///
+/// assert_eq!(
+///     lines.parse("foo\r\n\nbar\n").into_result(),
+///     Ok(vec![("foo", (0..3).into()), ("", (5..5).into()), ("bar", (6..9).into())]),
+/// );
+/// assert_eq!(lines.parse("").into_result(), Ok(vec![]));
+///
+/// // Lines can be counted without building a collection
+/// let count = text::lines::<_, _, extra::Err<Simple<char>>>().count();
+/// assert_eq!(count.parse("a\n\nb\n\n").into_result(), Ok(4));
+/// ```
+#[must_use]
+pub fn lines<'a, C: Char, I: StrInput<'a, C>, E: ParserExtra<'a, I>>(
+) -> impl IterParser<'a, I, (&'a C::Str, I::Span), E> + Copy {
+    // There are no more lines at the end of the input
+    any()
+        .rewind()
+        .ignore_then(line().map_with(|line, e| (line, e.span())))
+        .then_ignore(newline().or(end()))
+        .repeated()
+}
+
+/// How tab characters are counted when measuring whitespace. See [`whitespace_measured`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Tabs {
+    /// Each tab is this many columns wide.
+    Width(usize),
+    /// Each tab moves to the next column that is a multiple of this many columns, as in most text editors.
+    Stop(usize),
+}
+
+impl Tabs {
+    fn advance(self, col: usize) -> usize {
+        match self {
+            Tabs::Width(width) => col + width,
+            Tabs::Stop(stop) => (col / stop.max(1) + 1) * stop.max(1),
+        }
+    }
+}
+
+/// Whitespace that was measured by [`whitespace_measured`] or [`inline_whitespace_measured`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Whitespace<S> {
+    /// The span of the whitespace.
+    pub span: S,
+    /// The number of whitespace characters.
+    pub count: usize,
+    /// The width of the whitespace in columns, with tabs expanded according to the [`Tabs`] policy.
+    ///
+    /// Only the whitespace after the last newline is counted, so this is the indentation of whatever follows.
+    pub width: usize,
+}
+
+fn measured_whitespace<'a, I, E>(
+    tabs: Tabs,
+    inline: bool,
+) -> impl Parser<'a, I, Whitespace<I::Span>, E> + Copy
+where
+    I: ValueInput<'a>,
+    I::Token: Char,
+    E: ParserExtra<'a, I>,
+{
+    custom(move |inp: &mut InputRef<'a, '_, I, E>| {
+        let before = inp.cursor();
+        let (mut count, mut width) = (0, 0);
+        while let Some(c) = inp.peek() {
+            let is_inline = c.is_inline_whitespace();
+            if !(is_inline || !inline && c.is_whitespace()) {
+                break;
+            }
+            inp.skip();
+            count += 1;
+            width = match c.to_char() {
+                '\t' => tabs.advance(width),
+                _ if is_inline => width + 1,
+                // Newlines start a new line of indentation
+                _ => 0,
+            };
+        }
+        Ok(Whitespace {
+            span: inp.span_since(&before),
+            count,
+            width,
+        })
+    })
+}
+
+/// A parser that accepts any number of whitespace characters, like [`whitespace`], measuring them as it goes.
+///
+/// This is useful for indentation-sensitive grammars, which can compare the width of indentation without
+/// re-scanning the input. Tabs are expanded according to the given [`Tabs`] policy: tab stops are measured from
+/// the start of the whitespace (or of the last line within it), so this is best used for whitespace at the start of
+/// a line.
+///
+/// The output type of this parser is [`Whitespace`].
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, text::Tabs};
+/// let whitespace = text::whitespace_measured::<_, extra::Err<Simple<char>>>(Tabs::Stop(4));
+///
+/// let ws = whitespace.parse("\n\n  \t ").into_result().unwrap();
+/// assert_eq!(ws.span, (0..6).into());
+/// assert_eq!(ws.count, 6);
+/// // Blank lines don't count towards the width
+/// assert_eq!(ws.width, 5);
+/// ```
+pub fn whitespace_measured<'a, I, E>(
+    tabs: Tabs,
+) -> impl Parser<'a, I, Whitespace<I::Span>, E> + Copy
+where
+    I: ValueInput<'a>,
+    I::Token: Char,
+    E: ParserExtra<'a, I>,
+{
+    measured_whitespace(tabs, false)
+}
+
+/// A parser that accepts any number of inline whitespace characters, like [`inline_whitespace`], measuring them as it
+/// goes. See [`whitespace_measured`].
+///
+/// The output type of this parser is [`Whitespace`].
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, text::Tabs};
+/// // A line made up of an indented word
+/// let line = text::inline_whitespace_measured::<_, extra::Err<Simple<char>>>(Tabs::Width(8))
+///     .map(|ws| ws.width)
+///     .then(text::ascii::ident());
+///
+/// assert_eq!(line.parse("  \tfoo").into_result(), Ok((10, "foo")));
+/// assert_eq!(line.parse("bar").into_result(), Ok((0, "bar")));
+/// ```
+pub fn inline_whitespace_measured<'a, I, E>(
+    tabs: Tabs,
+) -> impl Parser<'a, I, Whitespace<I::Span>, E> + Copy
+where
+    I: ValueInput<'a>,
+    I::Token: Char,
+    E: ParserExtra<'a, I>,
+{
+    measured_whitespace(tabs, true)
+}
+
+/// A configurable parser for *trivia*: whitespace, line comments and block comments. See [`trivia`].
+pub struct Trivia<'p, C: Char> {
+    line: Option<&'p C::Str>,
+    block: Option<(&'p C::Str, &'p C::Str)>,
+    nested: bool,
+    doc: Option<&'p C::Str>,
+}
+
+impl<'p, C: Char> Copy for Trivia<'p, C> {}
+impl<'p, C: Char> Clone for Trivia<'p, C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'p, C: Char> Trivia<'p, C> {
+    /// Treat everything from `prefix` up to (but not including) the next newline as a comment.
+    pub fn line_comment(self, prefix: &'p C::Str) -> Self {
+        Self {
+            line: Some(prefix),
+            ..self
+        }
+    }
+
+    /// Treat everything between `open` and `close` (inclusive) as a comment.
+    ///
+    /// Reaching the end of the input before `close` is found is an error.
+    pub fn block_comment(self, open: &'p C::Str, close: &'p C::Str) -> Self {
+        Self {
+            block: Some((open, close)),
+            ..self
+        }
+    }
+
+    /// Allow block comments to nest, as in Rust (`/* a /* b */ c */`). By default, the first `close` ends the
+    /// comment, as in C.
+    pub fn nested(self) -> Self {
+        Self {
+            nested: true,
+            ..self
+        }
+    }
+
+    /// Capture the contents of line comments that begin with `prefix` (such as `///`), producing them as the output
+    /// of the parser instead of discarding them.
+    ///
+    /// The prefix is checked before the line comment prefix, so it may share a prefix with it.
+    pub fn doc_comments(self, prefix: &'p C::Str) -> Self {
+        Self {
+            doc: Some(prefix),
+            ..self
+        }
+    }
+}
+
+// Attempt to consume the given string, rewinding if it is not found.
+fn eat_str<'a, C: Char, I: StrInput<'a, C>, E: ParserExtra<'a, I>>(
+    inp: &mut InputRef<'a, '_, I, E>,
+    s: &C::Str,
+) -> bool {
+    let before = inp.save();
+    for c in C::str_to_chars(s) {
+        if inp.next_inner() != Some(c) {
+            inp.rewind(before);
+            return false;
+        }
+    }
+    true
+}
+
+// Skip the rest of a line comment, stopping before the line ending (`\n` or `\r\n`) so that it isn't part of the
+// comment. A lone `\r` doesn't end the comment.
+fn skip_line<'a, C: Char, I: StrInput<'a, C>, E: ParserExtra<'a, I>>(
+    inp: &mut InputRef<'a, '_, I, E>,
+) {
+    loop {
+        inp.skip_while(|c| !matches!(c.to_char(), '\n' | '\r'));
+        let before = inp.save();
+        if inp.next_inner().map(|c| c.to_char()) != Some('\r') {
+            inp.rewind(before);
+            break;
+        }
+        let after_cr = inp.save();
+        if inp.next_inner().map(|c| c.to_char()) == Some('\n') {
+            inp.rewind(before);
+            break;
+        }
+        inp.rewind(after_cr);
+    }
+}
+
+impl<'p, C: Char> Trivia<'p, C> {
+    /// Capture all trivia, producing each piece (whitespace or comment) along with its kind and span as the output of
+    /// the parser, rather than only doc comments.
+    ///
+    /// This is useful for tools that need to preserve trivia, such as formatters, or when comments need to be attached
+    /// to the items that follow them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, text::{TriviaKind, TriviaPiece}};
+    /// let trivia = text::trivia::<char>().line_comment("//").collected();
+    ///
+    /// // Attach the trivia preceding each identifier to it
+    /// let ident = trivia.then(text::ascii::ident::<_, _, extra::Err<Simple<char>>>());
+    /// let idents = ident.repeated().collect::<Vec<_>>().then_ignore(trivia);
+    ///
+    /// let out = idents.parse("// first\nfoo bar").into_result().unwrap();
+    /// assert_eq!(out[0].0, vec![
+    ///     TriviaPiece { kind: TriviaKind::LineComment, text: "// first", span: (0..8).into() },
+    ///     TriviaPiece { kind: TriviaKind::Whitespace, text: "\n", span: (8..9).into() },
+    /// ]);
+    /// assert_eq!(out[1].0, vec![
+    ///     TriviaPiece { kind: TriviaKind::Whitespace, text: " ", span: (12..13).into() },
+    /// ]);
+    /// ```
+    pub fn collected(self) -> CollectedTrivia<'p, C> {
+        CollectedTrivia { trivia: self }
+    }
+
+    // Skip any trivia, calling `f` with the kind of each piece, the cursor at its start and the cursor at the start of
+    // its body (the same as the start for anything other than doc comments).
+    fn skip<'a, 'parse, I, E, F>(
+        &self,
+        inp: &mut InputRef<'a, 'parse, I, E>,
+        mut f: F,
+    ) -> Result<(), ()>
+    where
+        I: StrInput<'a, C>,
+        E: ParserExtra<'a, I>,
+        F: FnMut(
+            &mut InputRef<'a, 'parse, I, E>,
+            TriviaKind,
+            &input::Cursor<'a, 'parse, I>,
+            &input::Cursor<'a, 'parse, I>,
+        ),
+    {
+        loop {
+            let start = inp.cursor();
+            inp.skip_while(|c| c.is_whitespace());
+            if start.inner() != inp.cursor().inner() {
+                f(inp, TriviaKind::Whitespace, &start, &start);
+                continue;
+            }
+
+            if let Some(doc) = self.doc {
+                if eat_str(inp, doc) {
+                    let body = inp.cursor();
+                    skip_line(inp);
+                    f(inp, TriviaKind::DocComment, &start, &body);
+                    continue;
+                }
+            }
+
+            if let Some(line) = self.line {
+                if eat_str(inp, line) {
+                    skip_line(inp);
+                    f(inp, TriviaKind::LineComment, &start, &start);
+                    continue;
+                }
+            }
+
+            if let Some((open, close)) = self.block {
+                if eat_str(inp, open) {
+                    let mut depth = 1usize;
+                    while depth > 0 {
+                        if eat_str(inp, close) {
+                            depth -= 1;
+                        } else if self.nested && eat_str(inp, open) {
+                            depth += 1;
+                        } else if inp.next_inner().is_none() {
+                            let span = inp.span_since(&start);
+                            inp.add_alt(
+                                C::str_to_chars(close)
+                                    .next()
+                                    .map(|c| Some(MaybeRef::Val(c))),
+                                None,
+                                span,
+                            );
+                            return Err(());
+                        }
+                    }
+                    f(inp, TriviaKind::BlockComment, &start, &start);
+                    continue;
+                }
+            }
+
+            break Ok(());
+        }
+    }
+}
+
+impl<'a, 'p, C, I, E> ParserSealed<'a, I, Vec<&'a C::Str>, E> for Trivia<'p, C>
+where
+    C: Char,
+    I: StrInput<'a, C>,
+    E: ParserExtra<'a, I>,
+{
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, Vec<&'a C::Str>> {
+        let mut docs = M::bind(Vec::new);
+        self.skip(inp, |inp, kind, _, body| {
+            if let TriviaKind::DocComment = kind {
+                let body = M::bind(|| inp.slice_since(body..));
+                M::combine_mut(&mut docs, body, |docs, body| docs.push(body));
+            }
+        })?;
+        Ok(docs)
+    }
+
+    go_extra!(Vec<&'a C::Str>);
+}
+
+/// The kind of a [`TriviaPiece`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TriviaKind {
+    /// A run of whitespace.
+    Whitespace,
+    /// A line comment. See [`Trivia::line_comment`].
+    LineComment,
+    /// A block comment. See [`Trivia::block_comment`].
+    BlockComment,
+    /// A doc comment. See [`Trivia::doc_comments`].
+    DocComment,
+}
+
+/// A piece of trivia captured by [`Trivia::collected`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TriviaPiece<'a, Str: ?Sized, S> {
+    /// The kind of trivia.
+    pub kind: TriviaKind,
+    /// The text of the trivia, including any comment delimiters.
+    pub text: &'a Str,
+    /// The span of the trivia.
+    pub span: S,
+}
+
+/// See [`Trivia::collected`].
+pub struct CollectedTrivia<'p, C: Char> {
+    trivia: Trivia<'p, C>,
+}
+
+impl<'p, C: Char> Copy for CollectedTrivia<'p, C> {}
+impl<'p, C: Char> Clone for CollectedTrivia<'p, C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, 'p, C, I, E> ParserSealed<'a, I, Vec<TriviaPiece<'a, C::Str, I::Span>>, E>
+    for CollectedTrivia<'p, C>
+where
+    C: Char,
+    I: StrInput<'a, C>,
+    E: ParserExtra<'a, I>,
+{
+    fn go<M: Mode>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+    ) -> PResult<M, Vec<TriviaPiece<'a, C::Str, I::Span>>> {
+        let mut pieces = M::bind(Vec::new);
+        self.trivia.skip(inp, |inp, kind, start, _| {
+            let piece = M::bind(|| TriviaPiece {
+                kind,
+                text: inp.slice_since(start..),
+                span: inp.span_since(start),
+            });
+            M::combine_mut(&mut pieces, piece, |pieces, piece| pieces.push(piece));
+        })?;
+        Ok(pieces)
+    }
+
+    go_extra!(Vec<TriviaPiece<'a, C::Str, I::Span>>);
+}
+
+/// A parser that accepts (and ignores) any amount of whitespace and comments, collectively known as *trivia*.
+///
+/// By default, this parser is equivalent to [`whitespace`]. Comment syntaxes can be added with
+/// [`Trivia::line_comment`] and [`Trivia::block_comment`]. Together with [`Parser::padded_by`], this replaces the
+/// hand-written 'whitespace or comment' parser that most languages otherwise need.
+///
+/// The output type of this parser is `Vec<&C::Str>`, the bodies of any doc comments that were encountered (see
+/// [`Trivia::doc_comments`]). If doc comments are not enabled, this is always empty and does not allocate.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let trivia = text::trivia::<char>()
+///     .line_comment("//")
+///     .block_comment("/*", "*/")
+///     .nested();
+///
+/// let ident = text::ascii::ident::<_, _, extra::Err<Simple<char>>>().padded_by(trivia);
+/// let idents = ident.repeated().collect::<Vec<_>>();
+///
+/// assert_eq!(
+///     idents.parse("foo // comment\n /* a /* nested */ comment */ bar").into_result(),
+///     Ok(vec!["foo", "bar"]),
+/// );
+/// // Block comments must be terminated
+/// assert!(idents.parse("foo /* bar").has_errors());
+///
+/// // Doc comments can be captured rather than ignored
+/// let documented = text::trivia::<char>()
+///     .line_comment("//")
+///     .doc_comments("///")
+///     .then(text::ascii::ident::<_, _, extra::Err<Simple<char>>>());
+///
+/// assert_eq!(
+///     documented.parse("/// Hello\n// Ignored\n/// world\nfoo").into_result(),
+///     Ok((vec![" Hello", " world"], "foo")),
+/// );
+/// ```
+pub const fn trivia<'p, C: Char>() -> Trivia<'p, C> {
+    Trivia {
+        line: None,
+        block: None,
+        nested: false,
+        doc: None,
+    }
+}
+
 /// A parser that accepts one or more ASCII digits.
 ///
 /// The output type of this parser is `I::Slice` (i.e: [`&str`] when `I` is [`&str`], and [`&[u8]`]
@@ -347,6 +898,76 @@ pub fn int<'a, I: StrInput<'a, C>, C: Char, E: ParserExtra<'a, I>>(
         .to_slice()
 }
 
+// Parse an identifier, then check that its tokens are the characters of `keyword`, according to `eq`. On a mismatch,
+// the error is placed at the start of the identifier (as it is for any other token that isn't expected) so that
+// `labelled` applies to it.
+fn keyword_of<'a, I, C, Str, E>(
+    ident: impl Parser<'a, I, &'a C::Str, E> + Clone + 'a,
+    keyword: Str,
+    eq: impl Fn(C, C) -> bool + Clone + 'a,
+) -> impl Parser<'a, I, &'a C::Str, E> + Clone + 'a
+where
+    I: StrInput<'a, C>,
+    C: Char + 'a,
+    Str: AsRef<C::Str> + Clone + 'a,
+    E: ParserExtra<'a, I> + 'a,
+{
+    custom(move |inp: &mut InputRef<'a, '_, I, E>| {
+        let before = inp.cursor();
+        inp.check(&ident)?;
+        if inp.tokens_eq_since(&before, keyword.as_ref(), &eq) {
+            Ok(inp.slice_since(&before..))
+        } else {
+            Err(Error::expected_found(None, None, inp.span_since(&before)))
+        }
+    })
+}
+
+/// Fold the case of a character, for use with [`Input::normalize`].
+///
+/// Characters are mapped to their lowercase form. Characters whose lowercase form is made up of several characters
+/// are left as they are.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// assert_eq!(text::fold_case('A'), 'a');
+/// assert_eq!(text::fold_case('Σ'), 'σ');
+/// assert_eq!(text::fold_case('1'), '1');
+/// ```
+#[must_use]
+pub fn fold_case(c: char) -> char {
+    let mut lower = c.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(l), None) => l,
+        _ => c,
+    }
+}
+
+/// Fold full-width characters into their half-width (ASCII) forms, for use with [`Input::normalize`].
+///
+/// The full-width forms of printable ASCII characters (`U+FF01` to `U+FF5E`) and the ideographic space (`U+3000`) are
+/// mapped to their ASCII equivalents. All other characters are left as they are.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// assert_eq!(text::fold_width('Ａ'), 'A');
+/// assert_eq!(text::fold_width('１'), '1');
+/// assert_eq!(text::fold_width('\u{3000}'), ' ');
+/// assert_eq!(text::fold_width('あ'), 'あ');
+/// ```
+#[must_use]
+pub fn fold_width(c: char) -> char {
+    match c {
+        '\u{3000}' => ' ',
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFF01 + 0x21).unwrap_or(c),
+        _ => c,
+    }
+}
+
 /// Parsers and utilities for working with ASCII inputs.
 pub mod ascii {
     use super::*;
@@ -421,15 +1042,123 @@ pub mod ascii {
                 assert!(c.to_char().is_ascii_alphanumeric() || c.to_char() == '_', "Trailing characters of a keyword must be ASCII alphanumeric or an underscore, not {:?}", c);
             }
         }
-        ident()
-            .try_map(move |s: &C::Str, span| {
-                if s == keyword.as_ref() {
-                    Ok(())
-                } else {
-                    Err(Error::expected_found(None, None, span))
+        keyword_of(ident(), keyword, |a: C, b| a == b)
+    }
+
+    /// Like [`keyword`], but matches ASCII letters case-insensitively, as is common in languages such as SQL and BASIC.
+    ///
+    /// The output type of this parser is `I::Slice`, the identifier as it appears in the input, so the original casing
+    /// is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let from = text::ascii::keyword_ignore_case::<_, _, _, extra::Err<Simple<char>>>("from");
+    ///
+    /// assert_eq!(from.parse("FROM").into_result(), Ok("FROM"));
+    /// assert_eq!(from.parse("From").into_result(), Ok("From"));
+    /// // 'from' was found, but only as part of a larger identifier, so this fails to parse
+    /// assert!(from.parse("FROMAGE").has_errors());
+    /// ```
+    #[track_caller]
+    pub fn keyword_ignore_case<
+        'a,
+        I: ValueInput<'a> + StrInput<'a, C>,
+        C: Char + 'a,
+        Str: AsRef<C::Str> + 'a + Clone,
+        E: ParserExtra<'a, I> + 'a,
+    >(
+        keyword: Str,
+    ) -> impl Parser<'a, I, &'a C::Str, E> + Clone + 'a {
+        #[cfg(debug_assertions)]
+        {
+            let mut cs = C::str_to_chars(keyword.as_ref());
+            if let Some(c) = cs.next() {
+                assert!(c.to_char().is_ascii_alphabetic() || c.to_char() == '_', "The first character of a keyword must be ASCII alphabetic or an underscore, not {:?}", c);
+            } else {
+                panic!("Keyword must have at least one character");
+            }
+            for c in cs {
+                assert!(c.to_char().is_ascii_alphanumeric() || c.to_char() == '_', "Trailing characters of a keyword must be ASCII alphanumeric or an underscore, not {:?}", c);
+            }
+        }
+        // Non-ASCII characters are left untouched
+        keyword_of(ident(), keyword, |a: C, b| {
+            a.to_char().eq_ignore_ascii_case(&b.to_char())
+        })
+    }
+
+    /// See [`just_ignore_case`].
+    pub struct JustIgnoreCase<Str, C, I, E> {
+        seq: Str,
+        #[allow(dead_code)]
+        phantom: EmptyPhantom<(C, I, E)>,
+    }
+
+    impl<Str: Copy, C, I, E> Copy for JustIgnoreCase<Str, C, I, E> {}
+    impl<Str: Clone, C, I, E> Clone for JustIgnoreCase<Str, C, I, E> {
+        fn clone(&self) -> Self {
+            Self {
+                seq: self.seq.clone(),
+                phantom: EmptyPhantom::new(),
+            }
+        }
+    }
+
+    impl<'a, I, C, Str, E> ParserSealed<'a, I, &'a C::Str, E> for JustIgnoreCase<Str, C, I, E>
+    where
+        I: StrInput<'a, C>,
+        C: Char,
+        Str: AsRef<C::Str>,
+        E: ParserExtra<'a, I>,
+    {
+        fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, &'a C::Str> {
+            let start = inp.cursor();
+            for c in C::str_to_chars(self.seq.as_ref()) {
+                let before = inp.save();
+                match inp.next_inner() {
+                    Some(found) if found.to_char().eq_ignore_ascii_case(&c.to_char()) => {}
+                    found => {
+                        let span = inp.span_since(before.cursor());
+                        inp.rewind(before);
+                        inp.add_alt(Some(Some(MaybeRef::Val(c))), found.map(MaybeRef::Val), span);
+                        return Err(());
+                    }
                 }
-            })
-            .to_slice()
+            }
+            Ok(M::bind(|| inp.slice_since(&start..)))
+        }
+
+        go_extra!(&'a C::Str);
+    }
+
+    /// Like [`just`], but matches ASCII letters case-insensitively.
+    ///
+    /// The output type of this parser is `I::Slice`, the text as it appears in the input (rather than the given
+    /// sequence), so the original casing is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let select = text::ascii::just_ignore_case::<_, _, _, extra::Err<Simple<char>>>("select");
+    ///
+    /// assert_eq!(select.parse("select").into_result(), Ok("select"));
+    /// assert_eq!(select.parse("SeLeCt").into_result(), Ok("SeLeCt"));
+    /// assert!(select.parse("selekt").has_errors());
+    /// ```
+    pub const fn just_ignore_case<'a, I, C, Str, E>(seq: Str) -> JustIgnoreCase<Str, C, I, E>
+    where
+        I: StrInput<'a, C>,
+        C: Char,
+        Str: AsRef<C::Str>,
+        E: ParserExtra<'a, I>,
+    {
+        JustIgnoreCase {
+            seq,
+            phantom: EmptyPhantom::new(),
+        }
     }
 }
 
@@ -440,6 +1169,9 @@ pub use unicode::*;
 pub mod unicode {
     use super::*;
 
+    #[cfg(feature = "unicode-normalization")]
+    use alloc::borrow::Cow;
+
     /// A parser that accepts an identifier.
     ///
     /// The output type of this parser is [`Char::Str`] (i.e: [`&str`] when `C` is [`char`], and [`&[u8]`] when `C` is
@@ -449,19 +1181,156 @@ pub mod unicode {
     #[must_use]
     pub fn ident<'a, I: ValueInput<'a> + StrInput<'a, C>, C: Char, E: ParserExtra<'a, I>>(
     ) -> impl Parser<'a, I, &'a C::Str, E> + Copy {
+        ident_with(C::is_ident_start, C::is_ident_continue)
+    }
+
+    /// Like [`ident`], but with custom sets of characters that may start and continue an identifier.
+    ///
+    /// The output type of this parser is [`Char::Str`] (i.e: [`&str`] when `C` is [`char`], and [`&[u8]`] when `C` is
+    /// [`u8`]).
+    ///
+    /// This is useful for languages that extend the default identifier syntax (such as allowing `$` or `-`), or that
+    /// restrict it further. With the `unicode-normalization` feature, the identifier can be normalized to NFC by
+    /// mapping it with [`nfc`].
+    ///
+    /// Chumsky does not include the data needed to reject mixed-script or confusable identifiers as described by
+    /// [UTS #39](https://www.unicode.org/reports/tr39/). Checks like these can be layered on top of this parser with
+    /// [`Parser::try_map`], using a crate that provides the data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// use chumsky::text::Char;
+    ///
+    /// // Identifiers that may also contain (but not start with) a hyphen, as in Lisp or CSS
+    /// let ident = text::unicode::ident_with::<_, _, extra::Err<Simple<char>>, _, _>(
+    ///     |c: &char| c.is_ident_start(),
+    ///     |c: &char| c.is_ident_continue() || *c == '-',
+    /// );
+    ///
+    /// assert_eq!(ident.parse("foo-bar").into_result(), Ok("foo-bar"));
+    /// assert_eq!(ident.parse("größe").into_result(), Ok("größe"));
+    /// assert!(ident.parse("-foo").has_errors());
+    /// ```
+    #[must_use]
+    pub fn ident_with<'a, I, C, E, S, T>(
+        is_start: S,
+        is_continue: T,
+    ) -> impl Parser<'a, I, &'a C::Str, E> + Copy
+    where
+        I: ValueInput<'a> + StrInput<'a, C>,
+        C: Char,
+        E: ParserExtra<'a, I>,
+        S: Fn(&C) -> bool + Copy,
+        T: Fn(&C) -> bool + Copy,
+    {
         any()
             // Use try_map over filter to get a better error on failure
-            .try_map(|c: C, span| {
-                if c.is_ident_start() {
+            .try_map(move |c: C, span| {
+                if is_start(&c) {
                     Ok(c)
                 } else {
                     Err(Error::expected_found([], Some(MaybeRef::Val(c)), span))
                 }
             })
-            .then(select! { c if (c as C).is_ident_continue() => () }.repeated())
+            .then(select! { c if is_continue(&c) => () }.repeated())
             .to_slice()
     }
 
+    /// Normalize an identifier to [Unicode Normalization Form C](https://www.unicode.org/reports/tr15/) (NFC), as
+    /// recommended for identifiers by [UAX #31](https://www.unicode.org/reports/tr31/).
+    ///
+    /// Identifiers that are already in NFC, as most are, are returned as they are without allocating. This is intended
+    /// to be used with [`Parser::map`], such as after [`ident_with`]. See [`ident_nfc`] for a shorthand when using the
+    /// default identifier syntax.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// // 'é' written as 'e' followed by a combining acute accent
+    /// assert_eq!(text::unicode::nfc("cafe\u{301}"), "caf\u{e9}");
+    /// ```
+    #[cfg(feature = "unicode-normalization")]
+    #[must_use]
+    pub fn nfc(ident: &str) -> Cow<'_, str> {
+        use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization};
+
+        match is_nfc_quick(ident.chars()) {
+            IsNormalized::Yes => Cow::Borrowed(ident),
+            _ => Cow::Owned(ident.nfc().collect()),
+        }
+    }
+
+    /// Like [`ident`], but outputs the identifier normalized to NFC (see [`nfc`]), so that identifiers that are
+    /// written differently but are canonically equivalent compare as equal.
+    ///
+    /// The output type of this parser is [`Cow<str>`], which borrows from the input unless normalizing the identifier
+    /// changed it. Spans still refer to the identifier as it was written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let ident = text::unicode::ident_nfc::<_, extra::Err<Simple<char>>>();
+    ///
+    /// // Both are 'café', but the second is written with a combining acute accent
+    /// let a = ident.parse("caf\u{e9}").into_result().unwrap();
+    /// let b = ident.parse("cafe\u{301}").into_result().unwrap();
+    /// assert_eq!(a, b);
+    /// ```
+    #[cfg(feature = "unicode-normalization")]
+    #[must_use]
+    pub fn ident_nfc<'a, I, E>() -> impl Parser<'a, I, Cow<'a, str>, E> + Copy
+    where
+        I: ValueInput<'a> + StrInput<'a, char>,
+        E: ParserExtra<'a, I>,
+    {
+        ident().map(nfc)
+    }
+
+    /// Like [`ident`], but interns the identifier with a [`lasso::Rodeo`] held by the parser state, outputting its key.
+    ///
+    /// Identifiers that have been seen before are looked up rather than allocated again, so the output is a cheap,
+    /// `Copy` key (such as a [`lasso::Spur`]) that can be compared without comparing strings. The key can be turned
+    /// back into a string with [`Rodeo::resolve`](lasso::Rodeo::resolve).
+    ///
+    /// The state may be the interner itself or, via [`Has`](crate::inspector::Has), a tuple of state values that
+    /// contains it, so this parser can be combined with others that need state of their own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// use lasso::{Rodeo, Spur};
+    ///
+    /// let idents = text::ident_interned::<_, extra::State<Rodeo>, _, _, _>()
+    ///     .padded()
+    ///     .repeated()
+    ///     .collect::<Vec<Spur>>();
+    ///
+    /// let mut rodeo = Rodeo::new();
+    /// let syms = idents.parse_with_state("foo bar foo", &mut rodeo).into_result().unwrap();
+    /// assert_eq!(syms[0], syms[2]);
+    /// assert_eq!(rodeo.resolve(&syms[1]), "bar");
+    /// assert_eq!(rodeo.len(), 2);
+    /// ```
+    #[cfg(feature = "lasso")]
+    #[must_use]
+    pub fn ident_interned<'a, I, E, K, H, N>() -> impl Parser<'a, I, K, E> + Copy
+    where
+        I: ValueInput<'a> + StrInput<'a, char>,
+        E: ParserExtra<'a, I>,
+        E::State: crate::inspector::Has<lasso::Rodeo<K, H>, N>,
+        K: lasso::Key,
+        H: core::hash::BuildHasher + Clone,
+    {
+        ident().map_with(|name: &'a str, e: &mut MapExtra<'a, '_, I, E>| {
+            crate::inspector::Has::get_mut(e.state()).get_or_intern(name)
+        })
+    }
+
     /// Like [`ident`], but only accepts a specific identifier while rejecting trailing identifier characters.
     ///
     /// The output type of this parser is `I::Slice` (i.e: [`&str`] when `I` is [`&str`], and [`&[u8]`]
@@ -510,15 +1379,7 @@ pub mod unicode {
                 assert!(c.is_ident_continue(), "Trailing characters of a keyword must be valid as unicode XID_CONTINUE, not {:?}", c);
             }
         }
-        ident()
-            .try_map(move |s: &C::Str, span| {
-                if s == keyword.as_ref() {
-                    Ok(())
-                } else {
-                    Err(Error::expected_found(None, None, span))
-                }
-            })
-            .to_slice()
+        keyword_of(ident(), keyword, |a: C, b| a == b)
     }
 }
 
@@ -547,22 +1408,13 @@ mod tests {
     }
 
     fn test_ok<'a, P: Parser<'a, &'a str, &'a str>>(parser: P, input: &'a str) {
-        assert_eq!(
-            parser.parse(input),
-            ParseResult {
-                output: Some(input),
-                errs: vec![]
-            }
-        );
+        assert_eq!(parser.parse(input), ParseResult::new(Some(input), vec![]));
     }
 
     fn test_err<'a, P: Parser<'a, &'a str, &'a str>>(parser: P, input: &'a str) {
         assert_eq!(
             parser.parse(input),
-            ParseResult {
-                output: None,
-                errs: vec![EmptyErr::default()]
-            }
+            ParseResult::new(None, vec![EmptyErr::default()])
         );
     }
 
@@ -614,4 +1466,141 @@ mod tests {
     fn keyword_unicode_in_ascii() {
         make_ascii_kw_parser::<char, &str>("שלום");
     }
+
+    #[test]
+    fn whitespace_measured_tabs() {
+        use text::Tabs;
+
+        let measure = |tabs, s| {
+            text::inline_whitespace_measured::<_, extra::Default>(tabs)
+                .map(|ws| (ws.count, ws.width))
+                .lazy()
+                .parse(s)
+                .into_result()
+        };
+        // Tab stops depend on where the tab is, but fixed widths do not
+        assert_eq!(measure(Tabs::Stop(4), " \t \t"), Ok((4, 8)));
+        assert_eq!(measure(Tabs::Width(4), " \t \t"), Ok((4, 10)));
+        assert_eq!(measure(Tabs::Stop(0), "\t\t"), Ok((2, 2)));
+        // Inline whitespace stops at newlines
+        assert_eq!(measure(Tabs::Stop(4), "  \n    "), Ok((2, 2)));
+    }
+
+    #[test]
+    fn lines_line_endings() {
+        let lines = text::lines::<_, _, extra::Default>()
+            .collect::<Vec<_>>()
+            .map(|lines| lines.into_iter().map(|(line, _)| line).collect::<Vec<_>>());
+        assert_eq!(
+            lines.parse("a\rb\r\nc\u{2028}d\u{0085}").into_result(),
+            Ok(vec!["a", "b", "c", "d"]),
+        );
+        assert_eq!(lines.parse("\n").into_result(), Ok(vec![""]));
+
+        // A per-line grammar, with spans into the whole input
+        let word = text::ascii::ident::<_, _, extra::Default>()
+            .map_with(|w, e| (w, e.span()))
+            .padded_by(text::inline_whitespace());
+        let line_words = word
+            .repeated()
+            .collect::<Vec<_>>()
+            .nested_in_region(text::line())
+            .separated_by(text::newline())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            line_words.parse("a b\n c").into_result(),
+            Ok(vec![
+                vec![("a", (0..1).into()), ("b", (2..3).into())],
+                vec![("c", (5..6).into())],
+            ]),
+        );
+    }
+
+    #[test]
+    fn rest_of_line_comments() {
+        let comment =
+            just::<_, _, extra::Default>(b"//" as &[u8]).ignore_then(text::rest_of_line());
+        let item = comment
+            .map(Some)
+            .or(text::ascii::ident().then_ignore(text::newline()).to(None));
+        let comments = item.repeated().collect::<Vec<_>>();
+        assert_eq!(
+            comments
+                .parse(b"// a\r\nx\n//\x0C//b" as &[u8])
+                .into_result(),
+            Ok(vec![Some(b" a" as &[u8]), None, Some(b""), Some(b"b")]),
+        );
+        // An empty line at the end of the input is still a line
+        assert_eq!(
+            text::rest_of_line::<_, _, extra::Default>()
+                .parse("")
+                .into_result(),
+            Ok("")
+        );
+    }
+
+    #[test]
+    fn trivia_crlf_line_comments() {
+        use text::{TriviaKind, TriviaPiece};
+
+        let trivia = text::trivia::<char>()
+            .line_comment("//")
+            .doc_comments("///");
+        let items = trivia
+            .then(text::ascii::ident::<_, _, extra::Default>())
+            .repeated()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            items.parse("/// doc\r\na // x\ry\r\nb").into_result(),
+            Ok(vec![(vec![" doc"], "a"), (vec![], "b")]),
+        );
+
+        let collected = text::trivia::<char>().line_comment("//").collected();
+        let pieces = collected.then_ignore(text::ascii::ident::<_, _, extra::Default>());
+        assert_eq!(
+            pieces.parse("// a\r\nb").into_result(),
+            Ok(vec![
+                TriviaPiece {
+                    kind: TriviaKind::LineComment,
+                    text: "// a",
+                    span: (0..4).into()
+                },
+                TriviaPiece {
+                    kind: TriviaKind::Whitespace,
+                    text: "\r\n",
+                    span: (4..6).into()
+                },
+            ]),
+        );
+    }
+
+    #[cfg(feature = "lasso")]
+    #[test]
+    fn ident_interned_tuple_state() {
+        use crate::inspector::SimpleState;
+        use lasso::{Rodeo, Spur};
+
+        // The interner shares the state with a count of `let` bindings
+        type State = (Rodeo, SimpleState<usize>);
+        let binding = text::keyword::<_, _, _, extra::State<State>>("let")
+            .padded()
+            .ignore_then(text::ident_interned())
+            .map_with(|name: Spur, e| {
+                e.state().1 .0 += 1;
+                name
+            })
+            .then_ignore(just(';').padded());
+        let bindings = binding.repeated().collect::<Vec<_>>();
+
+        let mut state = (Rodeo::new(), SimpleState(0));
+        let names = bindings
+            .parse_with_state("let x; let größe; let x;", &mut state)
+            .into_result()
+            .unwrap();
+        assert_eq!(names[0], names[2]);
+        assert_ne!(names[0], names[1]);
+        assert_eq!(state.0.resolve(&names[1]), "größe");
+        assert_eq!(state.0.len(), 2);
+        assert_eq!(*state.1, 3);
+    }
 }
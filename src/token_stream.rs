@@ -0,0 +1,182 @@
+//! Parsing of [`proc_macro2::TokenStream`]s, for writing procedural macros with chumsky.
+//!
+//! A token stream is first converted into a tree of [`Token`]s using [`tokens`], which can then be parsed like any
+//! other token input. Groups (such as `(...)` or `{...}`) are kept as single tokens and their contents can be parsed
+//! with [`group_of`](crate::primitive::group_of). Spans can be converted back into [`proc_macro2::Span`]s, so errors
+//! can be reported at the right location in the user's code (for example, with `syn::Error`).
+//!
+//! ```
+//! # use chumsky::{prelude::*, primitive::group_of, token_stream::{tokens, Token, TokenSpan}};
+//! use proc_macro2::{Delimiter, TokenStream};
+//!
+//! let ident = select_ref! { Token::Ident(x) => x.to_string() };
+//! let comma = select_ref! { Token::Punct(p) if p.as_char() == ',' => () };
+//! let args = ident
+//!     .separated_by(comma)
+//!     .collect::<Vec<_>>()
+//!     .nested_in(group_of::<_, _, extra::Err<Rich<_, _>>>(Delimiter::Parenthesis));
+//! let call = ident.then(args);
+//!
+//! let stream: TokenStream = "foo(a, b)".parse().unwrap();
+//! let tokens = tokens(stream);
+//! assert_eq!(
+//!     call.parse(tokens.as_slice().spanned(TokenSpan::call_site())).into_output(),
+//!     Some(("foo".to_string(), vec!["a".to_string(), "b".to_string()])),
+//! );
+//! ```
+
+use super::*;
+use crate::input::{TokenTree, TreeGroup};
+use proc_macro2::{Delimiter, Ident, Literal, Punct};
+
+/// A token from a [`proc_macro2::TokenStream`]. See [`tokens`].
+#[derive(Clone, Debug)]
+pub enum Token {
+    /// An identifier or keyword, such as `foo` or `fn`.
+    Ident(Ident),
+    /// A single punctuation character, such as `+` or `,`.
+    Punct(Punct),
+    /// A literal, such as `42` or `"hello"`.
+    Literal(Literal),
+    /// A delimited group of tokens, along with the span of its closing delimiter.
+    Group(Delimiter, Vec<(Token, TokenSpan)>, TokenSpan),
+}
+
+/// Tokens are compared by their kind and text (and, for punctuation, their spacing), ignoring spans.
+impl PartialEq for Token {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Ident(a), Self::Ident(b)) => a == b,
+            (Self::Punct(a), Self::Punct(b)) => {
+                a.as_char() == b.as_char() && a.spacing() == b.spacing()
+            }
+            (Self::Literal(a), Self::Literal(b)) => a.to_string() == b.to_string(),
+            (Self::Group(a, a_toks, _), Self::Group(b, b_toks, _)) => {
+                a == b
+                    && a_toks.len() == b_toks.len()
+                    && a_toks.iter().zip(b_toks).all(|((a, _), (b, _))| a == b)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Ident(x) => write!(f, "{}", x),
+            Self::Punct(x) => write!(f, "{}", x),
+            Self::Literal(x) => write!(f, "{}", x),
+            Self::Group(delimiter, _, _) => match delimiter {
+                Delimiter::Parenthesis => write!(f, "(...)"),
+                Delimiter::Brace => write!(f, "{{...}}"),
+                Delimiter::Bracket => write!(f, "[...]"),
+                Delimiter::None => write!(f, "..."),
+            },
+        }
+    }
+}
+
+impl TokenTree<TokenSpan> for Token {
+    type Delimiter = Delimiter;
+
+    fn as_group(&self) -> Option<TreeGroup<'_, Self, TokenSpan>> {
+        match self {
+            Self::Group(delimiter, tokens, close) => Some(TreeGroup {
+                delimiter,
+                tokens,
+                close: *close,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// A span within a [`proc_macro2::TokenStream`], made up of the spans of its first and last tokens.
+///
+/// Use [`TokenSpan::span`] (or [`From`]) to turn this into a [`proc_macro2::Span`] for reporting errors.
+#[derive(Copy, Clone, Debug)]
+pub struct TokenSpan {
+    start: proc_macro2::Span,
+    end: proc_macro2::Span,
+}
+
+impl TokenSpan {
+    /// A span that resolves at the macro call site, useful as the end of input span when parsing a token stream.
+    pub fn call_site() -> Self {
+        proc_macro2::Span::call_site().into()
+    }
+
+    /// Get a [`proc_macro2::Span`] that covers this span.
+    ///
+    /// Joining spans is only supported by the compiler on nightly: elsewhere, the span of the first token is used.
+    pub fn span(&self) -> proc_macro2::Span {
+        self.start.join(self.end).unwrap_or(self.start)
+    }
+}
+
+impl From<proc_macro2::Span> for TokenSpan {
+    fn from(span: proc_macro2::Span) -> Self {
+        Self {
+            start: span,
+            end: span,
+        }
+    }
+}
+
+impl From<TokenSpan> for proc_macro2::Span {
+    fn from(span: TokenSpan) -> Self {
+        span.span()
+    }
+}
+
+impl Span for TokenSpan {
+    type Context = ();
+    type Offset = proc_macro2::Span;
+
+    fn new((): Self::Context, range: Range<Self::Offset>) -> Self {
+        Self {
+            start: range.start,
+            end: range.end,
+        }
+    }
+    fn context(&self) -> Self::Context {}
+    fn start(&self) -> Self::Offset {
+        self.start
+    }
+    fn end(&self) -> Self::Offset {
+        self.end
+    }
+}
+
+/// Convert a [`proc_macro2::TokenStream`] into a sequence of [`Token`]s and their spans, ready to be parsed.
+///
+/// The result can be turned into an input with [`Input::spanned`], using something like [`TokenSpan::call_site`] as the
+/// end of input span.
+pub fn tokens(stream: proc_macro2::TokenStream) -> Vec<(Token, TokenSpan)> {
+    stream
+        .into_iter()
+        .map(|tree| match tree {
+            proc_macro2::TokenTree::Ident(x) => {
+                let span = x.span().into();
+                (Token::Ident(x), span)
+            }
+            proc_macro2::TokenTree::Punct(x) => {
+                let span = x.span().into();
+                (Token::Punct(x), span)
+            }
+            proc_macro2::TokenTree::Literal(x) => {
+                let span = x.span().into();
+                (Token::Literal(x), span)
+            }
+            proc_macro2::TokenTree::Group(x) => {
+                let span = TokenSpan {
+                    start: x.span_open(),
+                    end: x.span_close(),
+                };
+                let close = x.span_close().into();
+                (Token::Group(x.delimiter(), tokens(x.stream()), close), span)
+            }
+        })
+        .collect()
+}
@@ -224,3 +224,62 @@ impl<'src, T: 'src> IntoMaybe<'src, T> for T {
         g(self)
     }
 }
+
+/// Turn a parser factory into a plain function that parses a string and produces either its output or its errors.
+///
+/// This is an escape hatch for integrating with code that expects a function-shaped parser, such as a value parser
+/// for a command-line argument library (like
+/// [clap's `value_parser`](https://docs.rs/clap/latest/clap/macro.value_parser.html)), a deserializer, or a template
+/// engine. The function behaves like [`Parser::parse`] followed by [`ParseResult::into_result`], using the default
+/// state and context.
+///
+/// A parser only accepts inputs with the lifetime that it was created for, so this takes a factory (a function or
+/// closure that creates the parser) rather than a parser: the factory is called for each input. This allows the
+/// function to accept strings of any lifetime (`impl for<'a> Fn(&'a str) -> Result<O, Vec<_>>`), including ones that
+/// don't outlive it.
+///
+/// Since the function outlives the strings that it parses, its errors can't borrow from them. If the parser's error
+/// type does (such as [`Rich`]), give a second argument to convert each error into one that doesn't, such as
+/// `Rich::into_owned` or `|e| e.to_string()`.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// fn parse_all<F>(f: F, args: &[String]) -> Vec<u16>
+/// where
+///     F: for<'a> Fn(&'a str) -> Result<u16, Vec<String>>,
+/// {
+///     args.iter().filter_map(|arg| f(arg).ok()).collect()
+/// }
+///
+/// fn port<'a>() -> impl Parser<'a, &'a str, u16, extra::Err<Rich<'a, char>>> {
+///     text::int(10).from_str().unwrapped()
+/// }
+///
+/// let args = ["80", "http", "8080"].map(String::from);
+/// assert_eq!(parse_all(chumsky::parser_fn!(port, |e| e.to_string()), &args), vec![80, 8080]);
+///
+/// // A closure works too, and errors that don't borrow from the input need no conversion
+/// let digits = chumsky::parser_fn!(|| text::digits::<_, _, extra::Err<Cheap>>(10).count());
+/// assert_eq!(digits("123"), Ok(3));
+/// ```
+#[macro_export]
+macro_rules! parser_fn {
+    ($factory:expr $(,)?) => {
+        move |input: &str| $crate::Parser::parse(&($factory)(), input).into_result()
+    };
+    ($factory:expr, $map_err:expr $(,)?) => {
+        move |input: &str| {
+            $crate::util::map_errors(
+                $crate::Parser::parse(&($factory)(), input).into_result(),
+                $map_err,
+            )
+        }
+    };
+}
+
+#[doc(hidden)]
+pub fn map_errors<O, E, F>(res: Result<O, Vec<E>>, f: impl FnMut(E) -> F) -> Result<O, Vec<F>> {
+    res.map_err(|errs| errs.into_iter().map(f).collect())
+}